@@ -23,7 +23,12 @@ use std::fmt;
 pub enum HibouCoreError {
     UnknownLifeline(usize),
     UnknownMessage(usize),
-    UnknownGate(usize)
+    UnknownMessageParameter(usize,usize),
+    UnknownGate(usize),
+    UnknownGroup(usize),
+    UnknownLifelineName(String),
+    UnknownMessageName(String),
+    ForbiddenTransmission(String,String,String)
 }
 
 impl fmt::Display for HibouCoreError {
@@ -35,8 +40,23 @@ impl fmt::Display for HibouCoreError {
             HibouCoreError::UnknownMessage( ms_id ) => {
                 return write!(f, "{}", format!("context error ; unknown message : {:}", ms_id));
             },
+            HibouCoreError::UnknownMessageParameter( ms_id, param_id ) => {
+                return write!(f, "{}", format!("context error ; unknown parameter value {:} of message : {:}", param_id, ms_id));
+            },
             HibouCoreError::UnknownGate( gt_id ) => {
                 return write!(f, "{}", format!("context error ; unknown gate : {:}", gt_id));
+            },
+            HibouCoreError::UnknownGroup( group_id ) => {
+                return write!(f, "{}", format!("context error ; unknown group : {:}", group_id));
+            },
+            HibouCoreError::UnknownLifelineName( lf_name ) => {
+                return write!(f, "{}", format!("context error ; unknown lifeline : '{:}'", lf_name));
+            },
+            HibouCoreError::UnknownMessageName( ms_name ) => {
+                return write!(f, "{}", format!("context error ; unknown message : '{:}'", ms_name));
+            },
+            HibouCoreError::ForbiddenTransmission( ms_name, orig_lf_name, tar_lf_name ) => {
+                return write!(f, "{}", format!("context error ; message '{:}' may not be transmitted from '{:}' to '{:}'", ms_name, orig_lf_name, tar_lf_name));
             }
         }
     }
@@ -0,0 +1,233 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::semantics::execute::execute_interaction;
+use crate::core::execution::semantics::frontier::{FrontierElement, global_frontier};
+use crate::core::execution::semantics::membership::match_frontier_element;
+use crate::core::execution::trace::multitrace::MultiTrace;
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::position::position::Position;
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// Nodes of an interaction term that are covered by an accepted multi-trace.
+/// Positions are those of the term drawing (the innermost position being the root)
+/// so that they can be highlighted on it.
+pub struct MultiTraceCoverage {
+    pub covered_positions : HashSet<Position>,
+    pub num_covered_actions : usize,
+    pub num_actions : usize
+}
+
+
+/// Replaces the message of each action by a fresh tag, which survives the execution of the action
+/// (e.g. from an emission to the remaining receptions) and the unfolding of loops.
+/// For each tag, the original message and the position of the action are recorded.
+fn tag_actions(interaction : &Interaction,
+               current_pos : Position,
+               tags : &mut Vec<(usize,Position)>) -> Result<Interaction,String> {
+    match interaction {
+        Interaction::Empty => {
+            Ok(Interaction::Empty)
+        },
+        Interaction::Emission(em_act) => {
+            let mut tagged = em_act.clone();
+            tagged.ms_id = tags.len();
+            tags.push((em_act.ms_id,current_pos));
+            Ok(Interaction::Emission(tagged))
+        },
+        Interaction::Reception(rc_act) => {
+            let mut tagged = rc_act.clone();
+            tagged.ms_id = tags.len();
+            tags.push((rc_act.ms_id,current_pos));
+            Ok(Interaction::Reception(tagged))
+        },
+        Interaction::Strict(i1,i2) => {
+            let (t1,t2) = tag_binary(i1,i2,current_pos,tags)?;
+            Ok(Interaction::Strict(t1,t2))
+        },
+        Interaction::Seq(i1,i2) => {
+            let (t1,t2) = tag_binary(i1,i2,current_pos,tags)?;
+            Ok(Interaction::Seq(t1,t2))
+        },
+        Interaction::CoReg(cr,i1,i2) => {
+            let (t1,t2) = tag_binary(i1,i2,current_pos,tags)?;
+            Ok(Interaction::CoReg(cr.clone(),t1,t2))
+        },
+        Interaction::Par(i1,i2) => {
+            let (t1,t2) = tag_binary(i1,i2,current_pos,tags)?;
+            Ok(Interaction::Par(t1,t2))
+        },
+        Interaction::Alt(i1,i2) => {
+            let (t1,t2) = tag_binary(i1,i2,current_pos,tags)?;
+            Ok(Interaction::Alt(t1,t2))
+        },
+        Interaction::Loop(lk,i1) => {
+            let t1 = tag_actions(i1,Position::Left(Box::new(current_pos)),tags)?;
+            Ok(Interaction::Loop(lk.clone(),Box::new(t1)))
+        },
+        Interaction::Sync(_,_,_) | Interaction::And(_,_) => {
+            // synchronizations are resolved by comparing messages, which tags would break
+            Err("coverage is not supported for interactions with synchronization operators".to_string())
+        },
+        Interaction::Not(_) => {
+            // forbidden actions are also resolved by comparing messages
+            Err("coverage is not supported for interactions with forbidden actions".to_string())
+        }
+    }
+}
+
+fn tag_binary(i1 : &Interaction,
+              i2 : &Interaction,
+              current_pos : Position,
+              tags : &mut Vec<(usize,Position)>) -> Result<(Box<Interaction>,Box<Interaction>),String> {
+    let t1 = tag_actions(i1,Position::Left(Box::new(current_pos.clone())),tags)?;
+    let t2 = tag_actions(i2,Position::Right(Box::new(current_pos)),tags)?;
+    Ok((Box::new(t1),Box::new(t2)))
+}
+
+fn untag_frontier_element(frt_elt : &FrontierElement, tags : &Vec<(usize,Position)>) -> FrontierElement {
+    let target_actions : BTreeSet<TraceAction> = frt_elt.target_actions.iter()
+        .map(|act| {
+            let mut untagged = act.clone();
+            untagged.ms_id = tags[act.ms_id].0;
+            untagged
+        }).collect();
+    FrontierElement::new(frt_elt.position.clone(),
+                         frt_elt.target_lf_ids.clone(),
+                         target_actions,
+                         frt_elt.max_loop_depth)
+}
+
+fn insert_with_ancestors(position : &Position, positions : &mut HashSet<Position>) {
+    positions.insert(position.clone());
+    match position {
+        Position::Left(parent) | Position::Right(parent) => {
+            insert_with_ancestors(parent,positions);
+        },
+        _ => {}
+    }
+}
+
+/// Returns the nodes of the interaction that are fired on at least one of the accepting paths of the multi-trace,
+/// or None if the multi-trace is not accepted.
+/// Operator nodes are covered whenever one of the actions below them is.
+pub fn get_multi_trace_coverage(interaction : &Interaction,
+                                co_localizations : &CoLocalizations,
+                                multi_trace : &MultiTrace) -> Result<Option<MultiTraceCoverage>,String> {
+    let mut tags : Vec<(usize,Position)> = vec![];
+    let tagged = tag_actions(interaction,Position::Epsilon(None),&mut tags)?;
+    // ***
+    let mut accepted = false;
+    let mut covered_tags : BTreeSet<usize> = BTreeSet::new();
+    let mut visited : HashSet<(Interaction,Vec<usize>,BTreeSet<usize>)> = HashSet::new();
+    let mut to_visit = vec![(tagged,vec![0; multi_trace.len()],BTreeSet::new())];
+    while let Some(state) = to_visit.pop() {
+        if visited.contains(&state) {
+            continue;
+        }
+        let (current,consumed,fired) = &state;
+        let fully_consumed = consumed.iter().zip(multi_trace.iter()).all(|(c,trace)| *c == trace.len());
+        if fully_consumed && current.express_empty() {
+            accepted = true;
+            covered_tags.extend(fired.iter().cloned());
+        }
+        // the alternatives are not delayed so that each fired action comes from a single branch
+        for frt_elt in global_frontier(current,false) {
+            let untagged = untag_frontier_element(&frt_elt,&tags);
+            if let Some(new_consumed) = match_frontier_element(co_localizations,multi_trace,consumed,&untagged) {
+                let next = execute_interaction(current,
+                                               &frt_elt.position,
+                                               &frt_elt.target_lf_ids,
+                                               false).interaction;
+                let mut new_fired = fired.clone();
+                new_fired.extend(frt_elt.target_actions.iter().map(|act| act.ms_id));
+                to_visit.push((next,new_consumed,new_fired));
+            }
+        }
+        visited.insert(state);
+    }
+    if !accepted {
+        return Ok(None);
+    }
+    // ***
+    let mut covered_positions = HashSet::new();
+    for tag in &covered_tags {
+        insert_with_ancestors(&tags[*tag].1,&mut covered_positions);
+    }
+    Ok(Some(MultiTraceCoverage{covered_positions,
+        num_covered_actions:covered_tags.len(),
+        num_actions:tags.len()}))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::execution::trace::trace::TraceActionKind;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    fn emission(lf_id : usize, ms_id : usize) -> Interaction {
+        Interaction::Emission(
+            EmissionAction::new(lf_id,ms_id,CommunicationSynchronicity::Asynchronous,vec![])
+        )
+    }
+
+    fn emission_act(lf_id : usize, ms_id : usize) -> BTreeSet<TraceAction> {
+        btreeset!{TraceAction::new(lf_id,TraceActionKind::Emission,ms_id)}
+    }
+
+    fn left(pos : Position) -> Position {
+        Position::Left(Box::new(pos))
+    }
+
+    fn right(pos : Position) -> Position {
+        Position::Right(Box::new(pos))
+    }
+
+    #[test]
+    fn coverage_test() {
+        // seq(alt(l0!m0, l0!m0), loopS(l1!m1))
+        let i = Interaction::Seq(
+            Box::new(Interaction::Alt(Box::new(emission(0,0)),Box::new(emission(0,0)))),
+            Box::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(emission(1,1))))
+        );
+        let root = Position::Epsilon(None);
+        let global = CoLocalizations::get_trivial_partition(2);
+        // both branches of the alternative are covered on distinct accepting paths
+        let mt = vec![vec![emission_act(0,0)]];
+        let coverage = get_multi_trace_coverage(&i,&global,&mt).unwrap().unwrap();
+        assert_eq!(coverage.num_actions, 3);
+        assert_eq!(coverage.num_covered_actions, 2);
+        assert_eq!(coverage.covered_positions,
+                   hashset!{root.clone(),left(root.clone()),left(left(root.clone())),right(left(root.clone()))});
+        // the loop body is covered by its unfoldings
+        let mt = vec![vec![emission_act(0,0),emission_act(1,1),emission_act(1,1)]];
+        let coverage = get_multi_trace_coverage(&i,&global,&mt).unwrap().unwrap();
+        assert_eq!(coverage.num_covered_actions, 3);
+        assert!(coverage.covered_positions.contains(&left(right(root.clone()))));
+        // rejected multi-trace
+        let mt = vec![vec![emission_act(1,1)]];
+        assert!(get_multi_trace_coverage(&i,&global,&mt).unwrap().is_none());
+    }
+
+}
@@ -42,7 +42,7 @@ fn execute_emission(em_act : &EmissionAction) -> Interaction {
                 }
             }
             if target_lf_ids.len() > 0 {
-                let rc_act = ReceptionAction::new(None,em_act.ms_id,CommunicationSynchronicity::Asynchronous,target_lf_ids);
+                let rc_act = ReceptionAction::new(None,em_act.ms_id,CommunicationSynchronicity::Asynchronous,target_lf_ids).with_param(em_act.ms_param);
                 return Interaction::Reception(rc_act);
             } else {
                 return Interaction::Empty;
@@ -65,7 +65,7 @@ fn execute_reception(rc_act : &ReceptionAction, sub_pos : &Option<usize>) -> Int
                     let mut target_lf_ids = rc_act.recipients.clone();
                     target_lf_ids.remove(*sub_pos_idx);
                     if target_lf_ids.len() > 0 {
-                        let new_rc_act = ReceptionAction::new(rc_act.origin_gt_id,rc_act.ms_id,CommunicationSynchronicity::Asynchronous,target_lf_ids);
+                        let new_rc_act = ReceptionAction::new(rc_act.origin_gt_id,rc_act.ms_id,CommunicationSynchronicity::Asynchronous,target_lf_ids).with_param(rc_act.ms_param);
                         return Interaction::Reception(new_rc_act);
                     } else {
                         return Interaction::Empty;
@@ -49,7 +49,7 @@ impl FrontierElement {
 
 
 pub fn global_frontier(interaction : &Interaction, delayed_alt : bool) -> Vec<FrontierElement> {
-    global_frontier_rec(delayed_alt, interaction, 0)
+    global_frontier_rec(delayed_alt, interaction, 0).0
 }
 
 
@@ -97,38 +97,68 @@ fn frontier_on_reception(rc_act : &ReceptionAction, loop_depth : u32) -> Vec<Fro
     }
 }
 
-fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_depth : u32) -> Vec<FrontierElement> {
+/// Actions forbidden by a 'not' that is reached from the left operand of a weak sequencing,
+/// i.e. which lifeline is not necessarily occupied by the left operand.
+fn forbidden_reaching_right(i1 : &Interaction,
+                            forbidden2 : BTreeSet<TraceAction>,
+                            cr : &Vec<usize>) -> BTreeSet<TraceAction> {
+    forbidden2.into_iter()
+        .filter(|act| cr.contains(&act.lf_id) || i1.avoids_all_of(&btreeset!{act.lf_id}))
+        .collect()
+}
+
+fn remove_forbidden(frontier : Vec<FrontierElement>, forbidden : &BTreeSet<TraceAction>) -> Vec<FrontierElement> {
+    if forbidden.is_empty() {
+        return frontier;
+    }
+    frontier.into_iter().filter(|frt_elt| frt_elt.target_actions.is_disjoint(forbidden)).collect()
+}
+
+/// Returns the frontier together with the actions forbidden at that point by the 'not' sub-interactions
+/// which can be reached without executing any action.
+fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_depth : u32) -> (Vec<FrontierElement>,BTreeSet<TraceAction>) {
     match interaction {
         Interaction::Empty => {
-            return vec![];
+            return (vec![],btreeset!{});
         },
         Interaction::Emission( em_act) => {
-            return frontier_on_emission(em_act, loop_depth);
+            return (frontier_on_emission(em_act, loop_depth),btreeset!{});
         },
         Interaction::Reception( rc_act) => {
-            return frontier_on_reception(rc_act, loop_depth);
+            return (frontier_on_reception(rc_act, loop_depth),btreeset!{});
+        },
+        Interaction::Not(ref act) => {
+            return (vec![],btreeset!{act.clone()});
         },
         Interaction::Strict(ref i1, ref i2) => {
-            let mut front = push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth) );
+            let (mut frt1,mut forbidden) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let mut front = push_frontier_left( &mut frt1 );
             if i1.express_empty() {
-                front.append( &mut push_frontier_right( &mut global_frontier_rec(delayed_alt,i2,loop_depth)) );
+                let (frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+                front.append( &mut push_frontier_right( &mut remove_forbidden(frt2,&forbidden)) );
+                forbidden.extend(forbidden2);
             }
-            return front;
+            return (front,forbidden);
         },
         Interaction::Seq(ref i1, ref i2) => {
-            let mut front = push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth) );
+            let (mut frt1,mut forbidden) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let mut front = push_frontier_left( &mut frt1 );
             // ***
-            for frt_elt2 in push_frontier_right( &mut global_frontier_rec(delayed_alt,i2,loop_depth)) {
+            let (frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+            for frt_elt2 in push_frontier_right( &mut remove_forbidden(frt2,&forbidden)) {
                 if i1.avoids_all_of(&frt_elt2.target_lf_ids) {
                     front.push(frt_elt2);
                 }
             }
-            return front;
+            forbidden.extend(forbidden_reaching_right(i1,forbidden2,&vec![]));
+            return (front,forbidden);
         },
         Interaction::CoReg(ref cr, ref i1, ref i2) => {
-            let mut front = push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth) );
+            let (mut frt1,mut forbidden) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let mut front = push_frontier_left( &mut frt1 );
             // ***
-            for frt_elt2 in push_frontier_right( &mut global_frontier_rec(delayed_alt,i2,loop_depth)) {
+            let (frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+            for frt_elt2 in push_frontier_right( &mut remove_forbidden(frt2,&forbidden)) {
                 let mut reqs_lf_ids = frt_elt2.target_lf_ids.clone();
                 for cr_lf_id in cr {
                     reqs_lf_ids.remove(cr_lf_id);
@@ -137,18 +167,21 @@ fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_dept
                     front.push(frt_elt2);
                 }
             }
-            return front;
+            forbidden.extend(forbidden_reaching_right(i1,forbidden2,cr));
+            return (front,forbidden);
         },
         Interaction::Alt(ref i1, ref i2) => {
+            let (mut frt1,forbidden1) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let (mut frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+            // an action forbidden in either branch stays forbidden until the alternative is resolved
+            // so that passing it with another action never goes through that branch
+            let forbidden : BTreeSet<TraceAction> = forbidden1.union(&forbidden2).cloned().collect();
             if delayed_alt {
                 // BELOW with delayed alt
                 let mut match_indices : Vec<(usize,usize)> = vec![];
                 let mut frt1_matched : HashSet<usize> = hashset![];
                 let mut frt2_matched : HashSet<usize> = hashset![];
                 // ***
-                let frt1 = global_frontier_rec(delayed_alt,i1,loop_depth);
-                let frt2 = global_frontier_rec(delayed_alt,i2,loop_depth);
-                // ***
                 for (frt1_idx,frt1_elt) in frt1.iter().enumerate() {
                     for (frt2_idx,frt2_elt) in frt2.iter().enumerate() {
                         if frt1_elt.target_actions == frt2_elt.target_actions {
@@ -195,18 +228,21 @@ fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_dept
                     }
                 }
                 // ***
-                return new_front;
+                return (new_front,forbidden);
             } else {
                 // BELOW non-delayed ALT
-                let mut front = push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth) );
-                front.append( &mut push_frontier_right( &mut global_frontier_rec(delayed_alt,i2,loop_depth)) );
-                return front;
+                let mut front = push_frontier_left( &mut frt1 );
+                front.append( &mut push_frontier_right( &mut frt2) );
+                return (front,forbidden);
             }
         },
         Interaction::Par(ref i1, ref i2) => {
-            let mut front = push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth) );
-            front.append( &mut push_frontier_right( &mut global_frontier_rec(delayed_alt,i2,loop_depth)) );
-            return front;
+            let (frt1,mut forbidden) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let (frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+            let mut front = push_frontier_left( &mut remove_forbidden(frt1,&forbidden2) );
+            front.append( &mut push_frontier_right( &mut remove_forbidden(frt2,&forbidden)) );
+            forbidden.extend(forbidden2);
+            return (front,forbidden);
         },
         Interaction::Sync(ref sync_acts,ref i1, ref i2) => {
             let sync_acts_as_set : BTreeSet<TraceAction> = BTreeSet::from_iter(sync_acts.iter().cloned());
@@ -215,7 +251,10 @@ fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_dept
             let mut rem_frt1 = vec![];
             let mut rem_frt2 = vec![];
             // ***
-            for frt1_elt in global_frontier_rec(delayed_alt,i1,loop_depth) {
+            let (frt1,mut forbidden) = global_frontier_rec(delayed_alt,i1,loop_depth);
+            let (frt2,forbidden2) = global_frontier_rec(delayed_alt,i2,loop_depth);
+            // ***
+            for frt1_elt in remove_forbidden(frt1,&forbidden2) {
                 let intersect : BTreeSet<TraceAction> = frt1_elt.target_actions.intersection(&sync_acts_as_set).cloned().collect();
                 if intersect.is_empty() {
                     let shifted_pos = Position::Left(Box::new(frt1_elt.position));
@@ -228,7 +267,7 @@ fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_dept
                 }
             }
             // ***
-            for frt2_elt in global_frontier_rec(delayed_alt,i2,loop_depth) {
+            for frt2_elt in remove_forbidden(frt2,&forbidden) {
                 let intersect : BTreeSet<TraceAction> = frt2_elt.target_actions.intersection(&sync_acts_as_set).cloned().collect();
                 if intersect.is_empty() {
                     let shifted_pos = Position::Right(Box::new(frt2_elt.position));
@@ -258,10 +297,13 @@ fn global_frontier_rec(delayed_alt : bool, interaction : &Interaction, loop_dept
                 }
             }
             // ***
-            new_front
+            forbidden.extend(forbidden2);
+            (new_front,forbidden)
         },
         Interaction::Loop(_, ref i1) => {
-            return push_frontier_left( &mut global_frontier_rec(delayed_alt,i1,loop_depth+1) );
+            // a loop can always be skipped so that the 'not' it contains do not forbid anything outside of it
+            let (mut frt1,_) = global_frontier_rec(delayed_alt,i1,loop_depth+1);
+            return (push_frontier_left( &mut frt1 ),btreeset!{});
         },
         _ => {
             panic!("non-conform interaction");
@@ -283,4 +325,43 @@ fn push_frontier_right(frontier : &mut Vec<FrontierElement>) -> Vec<FrontierElem
                                                                  frt_elt.target_lf_ids,
                                                                  frt_elt.target_actions,
                                                                  frt_elt.max_loop_depth) ).collect();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::count::{count_nfa_traces, TraceLanguageCardinality};
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn forbidden_action_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let count = |as_str : &str| {
+            let i = parse_hif_string(&gen_ctx,as_str.to_string()).unwrap();
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+            count_nfa_traces(&nfa)
+        };
+        // only 'b?n' remains
+        assert_eq!(count("seq(not(b?m), alt(m -> b, n -> b))"), TraceLanguageCardinality::Finite(1));
+        assert_eq!(count("seq(alt(m -> b, n -> b), not(b?m))"), TraceLanguageCardinality::Finite(2));
+        // an action on another lifeline does not pass the forbidden action, contrary to one on the same lifeline
+        assert_eq!(count("seq(not(b?m), a -- n ->|, m -> b)"), TraceLanguageCardinality::Finite(0));
+        assert_eq!(count("seq(not(b?m), b -- n ->|, m -> b)"), TraceLanguageCardinality::Finite(1));
+        assert_eq!(count("strict(not(b?m), a -- n ->|, m -> b)"), TraceLanguageCardinality::Finite(1));
+        assert_eq!(count("par(not(b?m), m -> b)"), TraceLanguageCardinality::Finite(0));
+        assert_eq!(count("coreg(b)(not(b?m), m -> b)"), TraceLanguageCardinality::Finite(0));
+        // the forbidden action applies until the alternative is resolved and does not apply outside of a loop
+        assert_eq!(count("seq(alt(not(b?m), o), m -> b)"), TraceLanguageCardinality::Finite(0));
+        assert_eq!(count("seq(alt(not(b?m), a -- n ->|), m -> b)"), TraceLanguageCardinality::Finite(1));
+        assert_eq!(count("seq(loopS(not(b?m)), m -> b)"), TraceLanguageCardinality::Finite(1));
+        // the frontier does not contain the forbidden action
+        let i = parse_hif_string(&gen_ctx,"seq(not(b?m), par(m -> b, a -- m ->|))".to_string()).unwrap();
+        let frontier = global_frontier(&i,false);
+        assert_eq!(frontier.len(), 1);
+        assert_eq!(frontier[0].target_lf_ids, btreeset!{0});
+    }
+}
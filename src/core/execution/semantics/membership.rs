@@ -0,0 +1,154 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::semantics::execute::execute_interaction;
+use crate::core::execution::semantics::frontier::{FrontierElement, global_frontier};
+use crate::core::execution::trace::multitrace::MultiTrace;
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::syntax::interaction::Interaction;
+
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MembershipVerdict {
+    Accepted,
+    /// number of multi-actions consumed on each component of the multi-trace
+    /// in the furthest state that was reached
+    Rejected(Vec<usize>)
+}
+
+
+/// Returns the new numbers of consumed multi-actions if the frontier element
+/// matches, on each component it involves, the multi-action at the head of this component.
+pub(crate) fn match_frontier_element(co_localizations : &CoLocalizations,
+                          multi_trace : &MultiTrace,
+                          consumed : &Vec<usize>,
+                          frt_elt : &FrontierElement) -> Option<Vec<usize>> {
+    let mut new_consumed = consumed.clone();
+    for coloc_id in co_localizations.get_coloc_ids_from_lf_ids(&frt_elt.target_lf_ids) {
+        let coloc_lfs = co_localizations.get_coloc_lfs_ids(coloc_id);
+        let on_coloc : BTreeSet<TraceAction> = frt_elt.target_actions.iter()
+            .filter(|act| coloc_lfs.contains(&act.lf_id))
+            .cloned().collect();
+        match multi_trace.get(coloc_id).unwrap().get(consumed[coloc_id]) {
+            Some( head ) if *head == on_coloc => {
+                new_consumed[coloc_id] += 1;
+            },
+            _ => {
+                return None;
+            }
+        }
+    }
+    Some(new_consumed)
+}
+
+/// Checks whether the multi-trace belongs to the semantics of the interaction.
+/// Frontier actions are executed step by step whenever they match the heads of the
+/// components of the multi-trace, so that actions on distinct components
+/// (e.g. in parallel) may be consumed in any interleaving.
+/// All interleavings are explored, up to identical states (interaction and consumed prefixes).
+pub fn is_multi_trace_accepted(interaction : &Interaction,
+                               co_localizations : &CoLocalizations,
+                               multi_trace : &MultiTrace) -> MembershipVerdict {
+    let initial_consumed = vec![0; multi_trace.len()];
+    let mut furthest = initial_consumed.clone();
+    let mut visited : HashSet<(Interaction,Vec<usize>)> = HashSet::new();
+    let mut to_visit = vec![(interaction.clone(),initial_consumed)];
+    while let Some((current,consumed)) = to_visit.pop() {
+        if visited.contains(&(current.clone(),consumed.clone())) {
+            continue;
+        }
+        let fully_consumed = consumed.iter().zip(multi_trace.iter()).all(|(c,trace)| *c == trace.len());
+        if fully_consumed && current.express_empty() {
+            return MembershipVerdict::Accepted;
+        }
+        if consumed.iter().sum::<usize>() > furthest.iter().sum::<usize>() {
+            furthest = consumed.clone();
+        }
+        for frt_elt in global_frontier(&current,true) {
+            if let Some(new_consumed) = match_frontier_element(co_localizations,multi_trace,&consumed,&frt_elt) {
+                let next = execute_interaction(&current,
+                                               &frt_elt.position,
+                                               &frt_elt.target_lf_ids,
+                                               false).interaction;
+                to_visit.push((next,new_consumed));
+            }
+        }
+        visited.insert((current,consumed));
+    }
+    MembershipVerdict::Rejected(furthest)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::execution::trace::trace::TraceActionKind;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    fn emission(lf_id : usize, ms_id : usize) -> Interaction {
+        Interaction::Emission(
+            EmissionAction::new(lf_id,ms_id,CommunicationSynchronicity::Asynchronous,vec![])
+        )
+    }
+
+    fn emission_act(lf_id : usize, ms_id : usize) -> BTreeSet<TraceAction> {
+        btreeset!{TraceAction::new(lf_id,TraceActionKind::Emission,ms_id)}
+    }
+
+    #[test]
+    fn membership_test() {
+        // par(l0!m0, loopS(l1!m1))
+        let i = Interaction::Par(
+            Box::new(emission(0,0)),
+            Box::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(emission(1,1))))
+        );
+        let global = CoLocalizations::get_trivial_partition(2);
+        // any interleaving of the parallel actions
+        let mt = vec![vec![emission_act(1,1),emission_act(0,0),emission_act(1,1)]];
+        assert_eq!(is_multi_trace_accepted(&i,&global,&mt), MembershipVerdict::Accepted);
+        // the action at index 2 cannot be fired
+        let mt = vec![vec![emission_act(1,1),emission_act(0,0),emission_act(0,0)]];
+        assert_eq!(is_multi_trace_accepted(&i,&global,&mt), MembershipVerdict::Rejected(vec![2]));
+        // l0!m0 is missing
+        let mt = vec![vec![emission_act(1,1)]];
+        assert_eq!(is_multi_trace_accepted(&i,&global,&mt), MembershipVerdict::Rejected(vec![1]));
+        // distributed observation
+        let discrete = CoLocalizations::get_discrete_partition(2);
+        let mt = vec![vec![emission_act(0,0)],vec![emission_act(1,1),emission_act(1,1)]];
+        assert_eq!(is_multi_trace_accepted(&i,&discrete,&mt), MembershipVerdict::Accepted);
+    }
+
+    #[test]
+    fn membership_broadcast_test() {
+        // l0 -- m0 -> l1 is an emission followed by a reception
+        let i = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+        );
+        let global = CoLocalizations::get_trivial_partition(2);
+        let reception = btreeset!{TraceAction::new(1,TraceActionKind::Reception,0)};
+        let mt = vec![vec![emission_act(0,0),reception.clone()]];
+        assert_eq!(is_multi_trace_accepted(&i,&global,&mt), MembershipVerdict::Accepted);
+        let mt = vec![vec![reception]];
+        assert_eq!(is_multi_trace_accepted(&i,&global,&mt), MembershipVerdict::Rejected(vec![0]));
+    }
+
+}
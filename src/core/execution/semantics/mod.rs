@@ -17,3 +17,5 @@ limitations under the License.
 
 pub mod frontier;
 pub mod execute;
+pub mod membership;
+pub mod coverage;
@@ -65,6 +65,9 @@ impl InteractionInterpretableAsTraceAction for Interaction {
             &Interaction::Loop(_, i1) => {
                 return i1.get_all_trace_actions();
             },
+            &Interaction::Not(_) => {
+                return btreeset!{};
+            },
             &Interaction::Sync(_, ref i1, ref i2) => {
                 let mut acts1 = i1.get_all_trace_actions();
                 let acts2 = i2.get_all_trace_actions();
@@ -121,6 +124,9 @@ impl InteractionInterpretableAsTraceAction for Interaction {
             &Interaction::Loop(_, i1) => {
                 return btreeset!{};
             },
+            &Interaction::Not(_) => {
+                return btreeset!{};
+            },
             &Interaction::Sync(_, ref i1, ref i2) => {
                 let mut acts1 = i1.get_all_trace_actions();
                 let acts2 = i2.get_all_trace_actions();
@@ -162,6 +168,9 @@ impl InteractionInterpretableAsTraceAction for Interaction {
             &Interaction::Loop(_, i1) => {
                 return i1.get_atomic_actions_number();
             },
+            &Interaction::Not(_) => {
+                return 0;
+            },
             &Interaction::Sync(_, ref i1, ref i2) => {
                 return i1.get_atomic_actions_number() + i2.get_atomic_actions_number();
             },
@@ -200,6 +209,9 @@ impl InteractionInterpretableAsTraceAction for Interaction {
             &Interaction::Loop(_, i1) => {
                 return 0;
             },
+            &Interaction::Not(_) => {
+                return 0;
+            },
             &Interaction::Sync(_, ref i1, ref i2) => {
                 return i1.get_atomic_actions_number() + i2.get_atomic_actions_number();
             },
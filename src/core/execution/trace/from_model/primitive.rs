@@ -29,7 +29,7 @@ impl PrimitiveInterpretableAsTraceAction for EmissionAction {
         for target_ref in &self.targets {
             match target_ref {
                 &EmissionTargetRef::Lifeline(tar_lf_id) => {
-                    contents.insert( TraceAction::new(tar_lf_id,TraceActionKind::Reception, self.ms_id) );
+                    contents.insert( TraceAction::new(tar_lf_id,TraceActionKind::Reception, self.ms_id).with_param(self.ms_param) );
                 },
                 _ => {}
             }
@@ -40,7 +40,7 @@ impl PrimitiveInterpretableAsTraceAction for EmissionAction {
     fn get_first_atomic_action(&self) -> TraceAction {
         return TraceAction::new(self.origin_lf_id,
                                 TraceActionKind::Emission,
-                                self.ms_id);
+                                self.ms_id).with_param(self.ms_param);
     }
 
     fn get_specific_atomic_action(&self,idx : usize) -> TraceAction {
@@ -53,7 +53,7 @@ impl PrimitiveInterpretableAsTraceAction for EmissionAction {
                     EmissionTargetRef::Lifeline(tar_lf_id) => {
                         return TraceAction::new(*tar_lf_id,
                                                 TraceActionKind::Reception,
-                                                self.ms_id);
+                                                self.ms_id).with_param(self.ms_param);
                     },
                     _ => {
                         panic!();
@@ -71,7 +71,7 @@ impl PrimitiveInterpretableAsTraceAction for ReceptionAction {
         for rc_lf_id in &self.recipients {
             contents.insert( TraceAction::new(*rc_lf_id,
                                               TraceActionKind::Reception,
-                                              self.ms_id) );
+                                              self.ms_id).with_param(self.ms_param) );
         }
         return contents;
     }
@@ -83,6 +83,6 @@ impl PrimitiveInterpretableAsTraceAction for ReceptionAction {
     fn get_specific_atomic_action(&self, idx : usize) -> TraceAction {
         return TraceAction::new(*self.recipients.get(idx).unwrap(),
                                 TraceActionKind::Reception,
-                                self.ms_id);
+                                self.ms_id).with_param(self.ms_param);
     }
 }
\ No newline at end of file
@@ -17,4 +17,4 @@ limitations under the License.
 
 pub mod trace;
 pub mod multitrace;
-pub mod from_model;
\ No newline at end of file
+pub mod from_model;pub mod to_interaction;
@@ -0,0 +1,127 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::collections::BTreeSet;
+
+use crate::core::execution::trace::multitrace::Trace;
+use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::util::fold_recursive_frags::fold_recursive_strict_frags;
+
+
+/// Returns the interaction whose only trace is 'trace' i.e. the strict sequence of its actions,
+/// or the empty interaction if the trace is empty.
+/// A set of simultaneous actions is lifted into a synchronous emission (of its single emission
+/// received by the other lifelines) or into a synchronous reception (if it only has receptions).
+pub fn lift_trace_to_interaction(gen_ctx : &GeneralContext, trace : &Trace) -> Result<Interaction,String> {
+    let mut lifted : Vec<Interaction> = vec![];
+    for (idx,actions) in trace.iter().enumerate() {
+        for action in actions {
+            check_trace_action(gen_ctx,action).map_err(|e| format!("action {:} of the trace : {:}", idx, e))?;
+        }
+        lifted.push( lift_trace_element(gen_ctx,actions).map_err(|e| format!("action {:} of the trace : {:}", idx, e))? );
+    }
+    return Ok( fold_recursive_strict_frags(&mut lifted.iter().collect()) );
+}
+
+fn check_trace_action(gen_ctx : &GeneralContext, action : &TraceAction) -> Result<(),String> {
+    gen_ctx.get_lf_name(action.lf_id).map_err(|e| e.to_string())?;
+    gen_ctx.get_ms_label(action.ms_id,action.ms_param).map_err(|e| e.to_string())?;
+    return Ok(());
+}
+
+fn lift_trace_element(gen_ctx : &GeneralContext, actions : &BTreeSet<TraceAction>) -> Result<Interaction,String> {
+    let first = actions.iter().next().ok_or("empty set of actions".to_string())?;
+    if actions.iter().any(|act| act.ms_id != first.ms_id || act.ms_param != first.ms_param) {
+        return Err( "simultaneous actions must all concern the same message".to_string() );
+    }
+    let emitters : Vec<usize> = actions.iter().filter(|act| act.act_kind == TraceActionKind::Emission).map(|act| act.lf_id).collect();
+    let receivers : Vec<usize> = actions.iter().filter(|act| act.act_kind == TraceActionKind::Reception).map(|act| act.lf_id).collect();
+    let synchronicity = if actions.len() > 1 {
+        CommunicationSynchronicity::Synchronous
+    } else {
+        CommunicationSynchronicity::Asynchronous
+    };
+    match emitters.as_slice() {
+        [] => {
+            return Ok( Interaction::Reception(
+                ReceptionAction::new(None,first.ms_id,synchronicity,receivers).with_param(first.ms_param)
+            ) );
+        },
+        [orig_lf_id] => {
+            let ms_name = gen_ctx.get_ms_name(first.ms_id).unwrap();
+            let mut targets = vec![];
+            for tar_lf_id in receivers {
+                if tar_lf_id == *orig_lf_id {
+                    return Err( format!("lifeline '{:}' cannot receive its own message '{:}'", gen_ctx.get_lf_name(tar_lf_id).unwrap(), ms_name) );
+                }
+                if !gen_ctx.is_transmission_allowed(first.ms_id,*orig_lf_id,tar_lf_id) {
+                    return Err( format!("message '{:}' may not be transmitted from '{:}' to '{:}'",
+                                        ms_name, gen_ctx.get_lf_name(*orig_lf_id).unwrap(), gen_ctx.get_lf_name(tar_lf_id).unwrap()) );
+                }
+                targets.push(EmissionTargetRef::Lifeline(tar_lf_id));
+            }
+            return Ok( Interaction::Emission(
+                EmissionAction::new(*orig_lf_id,first.ms_id,synchronicity,targets).with_param(first.ms_param)
+            ) );
+        },
+        _ => {
+            return Err( "simultaneous actions may contain at most one emission".to_string() );
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::colocalizations::CoLocalizations;
+    use crate::core::execution::semantics::membership::{is_multi_trace_accepted, MembershipVerdict};
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::input::htf::implem::multitrace_from_text;
+    use super::*;
+
+    #[test]
+    fn lift_trace_test() {
+        let gen_ctx = parse_hsf_string("@message{m(x,y);n}\n@lifeline{a;b;c}\n@direction{a -- n -> b}".to_string()).unwrap();
+        let lift = |htf : &str| {
+            let (_,multi_trace) = multitrace_from_text(&gen_ctx,&htf.to_string()).unwrap();
+            lift_trace_to_interaction(&gen_ctx,&multi_trace[0])
+        };
+        assert_eq!(lift("").unwrap(), Interaction::Empty);
+        let got = lift("a!m(x).b?m(x).{a!n,b?n}.{b?m,c?m}.c!m").unwrap();
+        assert_eq!(got, parse_hif_string(&gen_ctx,
+            "strict(a -- m(x) ->|, m(x) -> b, a -- <synch>n -> b, <synch>m -> (b,c), c -- m ->|)".to_string()).unwrap());
+        // the trace is the only one of the lifted interaction
+        let (_,multi_trace) = multitrace_from_text(&gen_ctx,&"a!m(x).b?m(x).{a!n,b?n}.{b?m,c?m}.c!m".to_string()).unwrap();
+        let trivial = CoLocalizations::get_trivial_partition(gen_ctx.get_lf_num());
+        assert_eq!(is_multi_trace_accepted(&got,&trivial,&multi_trace), MembershipVerdict::Accepted);
+        let (_,prefix) = multitrace_from_text(&gen_ctx,&"a!m(x).b?m(x).{a!n,b?n}.{b?m,c?m}".to_string()).unwrap();
+        assert_ne!(is_multi_trace_accepted(&got,&trivial,&prefix), MembershipVerdict::Accepted);
+        let (_,swapped) = multitrace_from_text(&gen_ctx,&"b?m(x).a!m(x).{a!n,b?n}.{b?m,c?m}.c!m".to_string()).unwrap();
+        assert_ne!(is_multi_trace_accepted(&got,&trivial,&swapped), MembershipVerdict::Accepted);
+        // ***
+        assert!(lift("{a!m,b!m}").is_err());
+        assert!(lift("{a!m,b?n}").is_err());
+        assert!(lift("{b!n,a?n}").is_err());
+    }
+}
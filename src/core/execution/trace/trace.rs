@@ -30,7 +30,8 @@ pub enum TraceActionKind {
 pub struct TraceAction {
     pub lf_id : usize,
     pub act_kind : TraceActionKind,
-    pub ms_id : usize
+    pub ms_id : usize,
+    pub ms_param : Option<usize>
 }
 
 impl TraceAction {
@@ -38,7 +39,12 @@ impl TraceAction {
     pub fn new(lf_id : usize,
                act_kind : TraceActionKind,
                ms_id : usize) -> TraceAction {
-        return TraceAction{lf_id,act_kind,ms_id};
+        return TraceAction{lf_id,act_kind,ms_id,ms_param:None};
+    }
+
+    pub fn with_param(mut self, ms_param : Option<usize>) -> TraceAction {
+        self.ms_param = ms_param;
+        return self;
     }
 
     pub fn get_actions_kinds(set_of_actions : &BTreeSet<TraceAction>) -> (i32,i32) {
@@ -25,7 +25,14 @@ use crate::core::error::HibouCoreError;
 pub struct GeneralContext {
     lf_names : Vec<String>,
     ms_names : Vec<String>,
-    gt_names : Vec<String>
+    // for each message, the values its parameter may take (empty if it has no parameter)
+    ms_params : Vec<Vec<String>>,
+    gt_names : Vec<String>,
+    // multicast groups : names and (sorted) ids of the lifelines they contain
+    groups : Vec<(String,Vec<usize>)>,
+    // for each message, the (origin,target) pairs of lifelines between which it may be transmitted
+    // (empty if the message may be transmitted in any direction)
+    ms_directions : Vec<Vec<(usize,usize)>>
 }
 
 
@@ -36,7 +43,10 @@ impl GeneralContext {
         return GeneralContext {
             lf_names: Vec::new(),
             ms_names: Vec::new(),
-            gt_names: Vec::new()
+            ms_params: Vec::new(),
+            gt_names: Vec::new(),
+            groups: Vec::new(),
+            ms_directions: Vec::new()
         }
     }
 
@@ -60,6 +70,8 @@ impl GeneralContext {
         match self.get_ms_id(&ms_name) {
             None => {
                 self.ms_names.push(ms_name);
+                self.ms_params.push(Vec::new());
+                self.ms_directions.push(Vec::new());
                 return self.ms_names.len() - 1;
             },
             Some(ms_id) => {
@@ -68,6 +80,21 @@ impl GeneralContext {
         }
     }
 
+    /// Adds a value to the domain of the parameter of a message.
+    /// Returns the id of this value within the domain.
+    pub fn add_ms_param(&mut self, ms_id : usize, param_name : String) -> usize {
+        match self.get_ms_param_id(ms_id, &param_name) {
+            None => {
+                let params = self.ms_params.get_mut(ms_id).unwrap();
+                params.push(param_name);
+                return params.len() - 1;
+            },
+            Some(param_id) => {
+                return param_id;
+            }
+        }
+    }
+
     pub fn add_gt(&mut self, gt_name : String) -> usize {
         match self.get_gt_id(&gt_name) {
             None => {
@@ -80,6 +107,31 @@ impl GeneralContext {
         }
     }
 
+    /// Declares a named multicast group of lifelines.
+    /// If a group with this name already exists, it is returned unchanged.
+    pub fn add_group(&mut self, group_name : String, mut lf_ids : Vec<usize>) -> usize {
+        match self.get_group_id(&group_name) {
+            None => {
+                lf_ids.sort();
+                lf_ids.dedup();
+                self.groups.push((group_name,lf_ids));
+                return self.groups.len() - 1;
+            },
+            Some(group_id) => {
+                return group_id;
+            }
+        }
+    }
+
+    /// Declares that the message may be transmitted from the origin lifeline to the target lifeline.
+    /// Once a direction is declared for a message, it may only be transmitted in its declared directions.
+    pub fn add_ms_direction(&mut self, ms_id : usize, orig_lf_id : usize, tar_lf_id : usize) {
+        let directions = self.ms_directions.get_mut(ms_id).unwrap();
+        if !directions.contains(&(orig_lf_id,tar_lf_id)) {
+            directions.push((orig_lf_id,tar_lf_id));
+        }
+    }
+
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
@@ -96,6 +148,14 @@ impl GeneralContext {
         return self.gt_names.iter().position(|n| n == gt_name);
     }
 
+    pub fn get_group_id(&self, group_name : &str) -> Option<usize> {
+        return self.groups.iter().position(|(n,_)| n == group_name);
+    }
+
+    pub fn get_ms_param_id(&self, ms_id : usize, param_name : &str) -> Option<usize> {
+        return self.ms_params.get(ms_id)?.iter().position(|n| n == param_name);
+    }
+
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
@@ -108,6 +168,19 @@ impl GeneralContext {
         return self.ms_names.len();
     }
 
+    pub fn get_gt_num(&self) -> usize {
+        return self.gt_names.len();
+    }
+
+    pub fn get_group_num(&self) -> usize {
+        return self.groups.len();
+    }
+
+    /// Number of values the parameter of a message may take (0 if it has no parameter).
+    pub fn get_ms_params_num(&self, ms_id : usize) -> usize {
+        return self.ms_params.get(ms_id).map_or(0, |params| params.len());
+    }
+
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
@@ -142,6 +215,32 @@ impl GeneralContext {
         }
     }
 
+    pub fn get_ms_param_name(&self, ms_id : usize, param_id : usize) -> Result<String,HibouCoreError> {
+        match self.ms_params.get(ms_id).and_then(|params| params.get(param_id)) {
+            None => {
+                return Err( HibouCoreError::UnknownMessageParameter(ms_id,param_id) );
+            },
+            Some( param_name ) => {
+                return Ok( param_name.to_string() );
+            }
+        }
+    }
+
+    /// Returns the label of a message as it is written in .hif and .htf files
+    /// i.e. either 'm' or 'm(x)' if it carries the value 'x' for its parameter.
+    pub fn get_ms_label(&self, ms_id : usize, ms_param : Option<usize>) -> Result<String,HibouCoreError> {
+        let ms_name = self.get_ms_name(ms_id)?;
+        match ms_param {
+            None => {
+                return Ok( ms_name );
+            },
+            Some( param_id ) => {
+                let param_name = self.get_ms_param_name(ms_id, param_id)?;
+                return Ok( format!("{}({})", ms_name, param_name) );
+            }
+        }
+    }
+
     pub fn get_gt_name(&self, gt_id : usize) -> Result<String,HibouCoreError> {
         match self.gt_names.get(gt_id) {
             None => {
@@ -153,4 +252,40 @@ impl GeneralContext {
         }
     }
 
+    pub fn get_group_name(&self, group_id : usize) -> Result<String,HibouCoreError> {
+        match self.groups.get(group_id) {
+            None => {
+                return Err( HibouCoreError::UnknownGroup(group_id) );
+            },
+            Some( (group_name,_) ) => {
+                return Ok( group_name.to_string() );
+            }
+        }
+    }
+
+    /// Returns the (origin,target) pairs of lifelines between which the message may be transmitted,
+    /// which is empty if no direction is declared for it (see 'is_transmission_allowed').
+    pub fn get_ms_directions(&self, ms_id : usize) -> &Vec<(usize,usize)> {
+        return self.ms_directions.get(ms_id).unwrap();
+    }
+
+    /// Whether the message may be transmitted from the origin lifeline to the target lifeline,
+    /// which is always the case if no direction is declared for it.
+    pub fn is_transmission_allowed(&self, ms_id : usize, orig_lf_id : usize, tar_lf_id : usize) -> bool {
+        let directions = self.get_ms_directions(ms_id);
+        return directions.is_empty() || directions.contains(&(orig_lf_id,tar_lf_id));
+    }
+
+    /// Returns the sorted ids of the lifelines of a multicast group.
+    pub fn get_group_lifelines(&self, group_id : usize) -> Result<&Vec<usize>,HibouCoreError> {
+        match self.groups.get(group_id) {
+            None => {
+                return Err( HibouCoreError::UnknownGroup(group_id) );
+            },
+            Some( (_,lf_ids) ) => {
+                return Ok( lf_ids );
+            }
+        }
+    }
+
 }
@@ -71,6 +71,9 @@ impl AvoidsLifelines for Interaction {
             &Interaction::Loop(_, _) => {
                 return true;
             },
+            &Interaction::Not(_) => {
+                return true;
+            },
             _ => {
                 panic!("non-conform interaction");
             }
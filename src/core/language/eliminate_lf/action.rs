@@ -46,7 +46,7 @@ impl LifelineEliminable for EmissionAction {
                 let new_act = ReceptionAction::new(None,
                                                       self.ms_id,
                                                       self.synchronicity.clone(),
-                                                      target_lfs);
+                                                      target_lfs).with_param(self.ms_param);
                 return Interaction::Reception( new_act );
             } else {
                 return Interaction::Empty;
@@ -65,10 +65,12 @@ impl LifelineEliminable for EmissionAction {
                     }
                 }
             }
+            // the group is kept only if none of its lifelines is removed
+            let target_group = if targets.len() == self.targets.len() {self.target_group} else {None};
             let new_act = EmissionAction::new(self.origin_lf_id,
                                                  self.ms_id,
                                                  self.synchronicity.clone(),
-                                                 targets);
+                                                 targets).with_param(self.ms_param).with_group(target_group);
             return Interaction::Emission( new_act );
         }
     }
@@ -91,7 +93,7 @@ impl LifelineEliminable for ReceptionAction {
             let new_act = ReceptionAction::new(self.origin_gt_id.clone(),
                                                   self.ms_id,
                                                   self.synchronicity.clone(),
-                                                  target_lfs);
+                                                  target_lfs).with_param(self.ms_param);
             return Interaction::Reception( new_act );
         } else {
             return Interaction::Empty;
@@ -188,6 +188,12 @@ impl LifelineEliminable for Interaction {
                     }
                 }
             },
+            Interaction::Not(act) => {
+                if lfs_to_eliminate.contains(&act.lf_id) {
+                    return Interaction::Empty;
+                }
+                return self.clone();
+            },
             _ => {
                 panic!("non-conform interaction");
             }
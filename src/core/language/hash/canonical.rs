@@ -0,0 +1,204 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::hash::{Hash, Hasher};
+
+use fnv::FnvHasher;
+
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+
+
+/// Hasher of all the hashes which are written to files (checkpoints, manifests, provenance comments, language signature caches)
+/// and must therefore be the same from one run (and one build) to the next : the 64 bits FNV-1a of a pinned version of the 'fnv' crate,
+/// which is fully specified contrary to 'DefaultHasher' whose algorithm may change between releases of Rust.
+/// The hashed bytes are those fed by the 'Hash' implementations hence they depend on the endianness and the pointer width of the platform.
+pub type StableHasher = FnvHasher;
+
+/// Hash of the root node of an interaction : its own content together with the hashes of its sub-interactions.
+fn get_node_hash(interaction : &Interaction, sub_hashes : &[u64]) -> u64 {
+    let mut hasher = StableHasher::default();
+    match interaction {
+        Interaction::Empty => {
+            0_u8.hash(&mut hasher);
+        },
+        Interaction::Emission(em_act) => {
+            1_u8.hash(&mut hasher);
+            em_act.hash(&mut hasher);
+        },
+        Interaction::Reception(rc_act) => {
+            2_u8.hash(&mut hasher);
+            rc_act.hash(&mut hasher);
+        },
+        Interaction::Strict(_,_) => {
+            3_u8.hash(&mut hasher);
+        },
+        Interaction::Seq(_,_) => {
+            4_u8.hash(&mut hasher);
+        },
+        Interaction::CoReg(cr,_,_) => {
+            5_u8.hash(&mut hasher);
+            cr.hash(&mut hasher);
+        },
+        Interaction::Alt(_,_) => {
+            6_u8.hash(&mut hasher);
+        },
+        Interaction::Par(_,_) => {
+            7_u8.hash(&mut hasher);
+        },
+        Interaction::Loop(lk,_) => {
+            8_u8.hash(&mut hasher);
+            lk.hash(&mut hasher);
+        },
+        Interaction::And(_,_) => {
+            9_u8.hash(&mut hasher);
+        },
+        Interaction::Sync(acts,_,_) => {
+            10_u8.hash(&mut hasher);
+            acts.hash(&mut hasher);
+        },
+        Interaction::Not(act) => {
+            11_u8.hash(&mut hasher);
+            act.hash(&mut hasher);
+        }
+    }
+    for sub_hash in sub_hashes {
+        sub_hash.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Merkle-style hash of an interaction : each node hashes its own content
+/// together with the hashes of its sub-interactions.
+/// As it relies on 'StableHasher', hashes are stable across runs.
+/// This walks the whole term, see 'HashedInteraction::new_binary' to derive the hash of a term from those of its operands instead.
+pub fn canonical_hash(interaction : &Interaction) -> u64 {
+    match interaction {
+        Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) | Interaction::CoReg(_,i1,i2)
+        | Interaction::Alt(i1,i2) | Interaction::Par(i1,i2) | Interaction::And(i1,i2) | Interaction::Sync(_,i1,i2) => {
+            get_node_hash(interaction, &[canonical_hash(i1), canonical_hash(i2)])
+        },
+        Interaction::Loop(_,i1) => {
+            get_node_hash(interaction, &[canonical_hash(i1)])
+        },
+        Interaction::Empty | Interaction::Emission(_) | Interaction::Reception(_) | Interaction::Not(_) => {
+            get_node_hash(interaction, &[])
+        }
+    }
+}
+
+
+/// An interaction together with its canonical hash, which is computed once when wrapping it
+/// (or derived from those of its operands when building it, see 'new_binary').
+/// Hashing a 'HashedInteraction' only feeds the cached hash to the hasher, and two of them
+/// are equal if their hashes are equal and their interactions are structurally equal,
+/// so that sets of 'HashedInteraction' remain exact in case of collisions.
+#[derive(Clone, Debug)]
+pub struct HashedInteraction {
+    hash : u64,
+    interaction : Interaction
+}
+
+impl HashedInteraction {
+
+    pub fn new(interaction : Interaction) -> Self {
+        Self { hash : canonical_hash(&interaction), interaction }
+    }
+
+    /// Builds the binary node 'make_node(i1,i2)' (e.g. 'Interaction::Strict'), its hash being derived
+    /// from the cached hashes of its operands in constant time, so that a term built bottom-up
+    /// (as by the random generation) is hashed without walking it again.
+    pub fn new_binary<F : FnOnce(Box<Interaction>,Box<Interaction>) -> Interaction>(make_node : F,
+                                                                                 i1 : HashedInteraction,
+                                                                                 i2 : HashedInteraction) -> Self {
+        let interaction = make_node(Box::new(i1.interaction), Box::new(i2.interaction));
+        Self { hash : get_node_hash(&interaction, &[i1.hash, i2.hash]), interaction }
+    }
+
+    /// Same as 'new_binary' for a loop of kind 'lk'.
+    pub fn new_loop(lk : LoopKind, i1 : HashedInteraction) -> Self {
+        let interaction = Interaction::Loop(lk, Box::new(i1.interaction));
+        Self { hash : get_node_hash(&interaction, &[i1.hash]), interaction }
+    }
+
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn get_interaction(&self) -> &Interaction {
+        &self.interaction
+    }
+
+    pub fn into_interaction(self) -> Interaction {
+        self.interaction
+    }
+
+}
+
+impl Hash for HashedInteraction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl PartialEq for HashedInteraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.interaction == other.interaction
+    }
+}
+
+impl Eq for HashedInteraction {}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use super::*;
+
+    #[test]
+    fn hashed_interaction_test() {
+        let a = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b = Interaction::Emission(EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let ab = Interaction::Seq(Box::new(a.clone()),Box::new(b.clone()));
+        let ba = Interaction::Seq(Box::new(b.clone()),Box::new(a.clone()));
+        assert_eq!(canonical_hash(&ab), canonical_hash(&ab.clone()));
+        assert_ne!(canonical_hash(&ab), canonical_hash(&ba));
+        assert_ne!(canonical_hash(&ab), canonical_hash(&Interaction::Strict(Box::new(a.clone()),Box::new(b.clone()))));
+        assert_ne!(canonical_hash(&Interaction::Loop(LoopKind::SStrictSeq,Box::new(a.clone()))),
+                   canonical_hash(&Interaction::Loop(LoopKind::WWeakSeq,Box::new(a.clone()))));
+        // ***
+        let mut set = HashSet::new();
+        assert!(set.insert(HashedInteraction::new(ab.clone())));
+        assert!(!set.insert(HashedInteraction::new(ab.clone())));
+        assert!(set.insert(HashedInteraction::new(ba)));
+        // a forged collision is told apart by the structural comparison
+        let forged = HashedInteraction { hash : canonical_hash(&ab), interaction : a.clone() };
+        assert_ne!(forged, HashedInteraction::new(ab));
+        assert!(set.insert(forged));
+        assert_eq!(set.len(), 3);
+        // hashes derived from those of the operands are those of the whole terms
+        let hashed_a = HashedInteraction::new(a.clone());
+        let hashed_b = HashedInteraction::new(b.clone());
+        let built = HashedInteraction::new_loop(LoopKind::WWeakSeq,
+                                                HashedInteraction::new_binary(|x,y| Interaction::CoReg(vec![0],x,y), hashed_a, hashed_b));
+        let whole = Interaction::Loop(LoopKind::WWeakSeq,Box::new(Interaction::CoReg(vec![0],Box::new(a),Box::new(b))));
+        assert_eq!(built, HashedInteraction::new(whole.clone()));
+        assert_eq!(built.get_hash(), canonical_hash(&whole));
+    }
+
+}
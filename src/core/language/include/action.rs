@@ -22,7 +22,7 @@ use crate::core::language::syntax::action::{EmissionAction, EmissionTargetRef};
 impl PartialSemanticInclusion for EmissionAction {
 
     fn is_included(&self, other: &Self) -> bool {
-        if self.ms_id != other.ms_id {
+        if self.ms_id != other.ms_id || self.ms_param != other.ms_param {
             return false;
         }
         if self.origin_lf_id != other.origin_lf_id {
@@ -70,6 +70,9 @@ impl InvolvesLifelines for Interaction {
             &Interaction::Loop(_, i1) => {
                 return i1.involved_lifelines();
             },
+            &Interaction::Not(ref act) => {
+                return btreeset!{act.lf_id};
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -108,6 +111,9 @@ impl InvolvesLifelines for Interaction {
             &Interaction::Loop(_, ref i1) => {
                 return i1.involves_any_of(lf_ids);
             },
+            &Interaction::Not(ref act) => {
+                return lf_ids.contains(&act.lf_id);
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -0,0 +1,230 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::involve::involves::InvolvesLifelines;
+use crate::core::language::position::position::Position;
+use crate::core::language::syntax::action::EmissionTargetRef;
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// What is semantically dubious about a (syntactically valid) interaction.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InteractionLintKind {
+    /// reception of the message with this id, not from a gate, whereas the message is emitted nowhere in the interaction
+    OrphanReception(usize),
+    /// emission of a message to the lifeline with this id, which occurs nowhere else in the interaction
+    IsolatedTarget(usize),
+    /// lifeline of the signature with this id which the interaction does not involve
+    UnusedLifeline(usize),
+    /// branch of an alternative without any action whereas the other branch may already be executed
+    /// without any action, so that it adds no behavior
+    RedundantEmptyAltBranch,
+    /// branch of an alternative which is the same as the other branch
+    DuplicateAltBranch,
+    /// loop whose body contains no action
+    LoopWithoutActions
+}
+
+/// A dubious sub-interaction together with its position in the interaction.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InteractionLintWarning {
+    pub position : Position,
+    pub kind : InteractionLintKind
+}
+
+impl InteractionLintWarning {
+
+    pub fn describe(&self, gen_ctx : &GeneralContext) -> String {
+        let lf_name = |lf_id : &usize| gen_ctx.get_lf_name(*lf_id).unwrap();
+        let description = match &self.kind {
+            InteractionLintKind::OrphanReception(ms_id) => {
+                format!("reception of '{:}' which is emitted nowhere in the interaction (nor received from a gate)", gen_ctx.get_ms_name(*ms_id).unwrap())
+            },
+            InteractionLintKind::IsolatedTarget(lf_id) => {
+                format!("emission to lifeline '{:}' which occurs nowhere else in the interaction (misspelled lifeline ?)", lf_name(lf_id))
+            },
+            InteractionLintKind::UnusedLifeline(lf_id) => {
+                format!("lifeline '{:}' of the signature occurs nowhere in the interaction", lf_name(lf_id))
+            },
+            InteractionLintKind::RedundantEmptyAltBranch => {
+                "branch of an alternative without any action whereas the other branch may already be executed without any action".to_string()
+            },
+            InteractionLintKind::DuplicateAltBranch => {
+                "branch of an alternative which is the same as the other branch".to_string()
+            },
+            InteractionLintKind::LoopWithoutActions => {
+                "loop whose body contains no action".to_string()
+            }
+        };
+        format!("at position {:?} : {:}", self.position, description)
+    }
+
+}
+
+
+/// Warnings about the interaction, the ones about its sub-interactions coming in prefix order.
+/// Warnings which do not concern a particular sub-interaction (e.g. unused lifelines) are at the root position.
+pub fn lint_interaction(gen_ctx : &GeneralContext, interaction : &Interaction) -> Vec<InteractionLintWarning> {
+    let mut warnings = vec![];
+    let involved = interaction.involved_lifelines();
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        if !involved.contains(&lf_id) {
+            warnings.push(InteractionLintWarning{ position : Position::Epsilon(None), kind : InteractionLintKind::UnusedLifeline(lf_id) });
+        }
+    }
+    // ***
+    let mut emitted = HashSet::new();
+    let mut lf_occurrences = HashMap::new();
+    count_occurrences(interaction, &mut emitted, &mut lf_occurrences);
+    warnings.extend(lint_rec(interaction, &emitted, &lf_occurrences));
+    warnings
+}
+
+/// Collects the emitted messages and the number of occurrences of each lifeline in the actions.
+fn count_occurrences(interaction : &Interaction,
+                     emitted : &mut HashSet<usize>,
+                     lf_occurrences : &mut HashMap<usize,u32>) {
+    match interaction {
+        Interaction::Empty | Interaction::Not(_) => {},
+        Interaction::Emission(em_act) => {
+            emitted.insert(em_act.ms_id);
+            *lf_occurrences.entry(em_act.origin_lf_id).or_insert(0) += 1;
+            for target in &em_act.targets {
+                if let EmissionTargetRef::Lifeline(tar_lf_id) = target {
+                    *lf_occurrences.entry(*tar_lf_id).or_insert(0) += 1;
+                }
+            }
+        },
+        Interaction::Reception(rc_act) => {
+            for rcp_lf_id in &rc_act.recipients {
+                *lf_occurrences.entry(*rcp_lf_id).or_insert(0) += 1;
+            }
+        },
+        Interaction::Loop(_, i1) => {
+            count_occurrences(i1, emitted, lf_occurrences);
+        },
+        Interaction::Strict(i1, i2) |
+        Interaction::Seq(i1, i2) |
+        Interaction::CoReg(_, i1, i2) |
+        Interaction::Par(i1, i2) |
+        Interaction::Alt(i1, i2) |
+        Interaction::Sync(_, i1, i2) |
+        Interaction::And(i1, i2) => {
+            count_occurrences(i1, emitted, lf_occurrences);
+            count_occurrences(i2, emitted, lf_occurrences);
+        }
+    }
+}
+
+fn lint_rec(interaction : &Interaction,
+            emitted : &HashSet<usize>,
+            lf_occurrences : &HashMap<usize,u32>) -> Vec<InteractionLintWarning> {
+    let at_root = |kind : InteractionLintKind| InteractionLintWarning{ position : Position::Epsilon(None), kind };
+    let mut warnings = vec![];
+    match interaction {
+        Interaction::Empty | Interaction::Not(_) => {},
+        Interaction::Emission(em_act) => {
+            for target in &em_act.targets {
+                if let EmissionTargetRef::Lifeline(tar_lf_id) = target {
+                    if lf_occurrences.get(tar_lf_id) == Some(&1) {
+                        warnings.push(at_root(InteractionLintKind::IsolatedTarget(*tar_lf_id)));
+                    }
+                }
+            }
+        },
+        Interaction::Reception(rc_act) => {
+            if rc_act.origin_gt_id.is_none() && !emitted.contains(&rc_act.ms_id) {
+                warnings.push(at_root(InteractionLintKind::OrphanReception(rc_act.ms_id)));
+            }
+        },
+        Interaction::Loop(_, i1) => {
+            if !i1.contains_actions() {
+                warnings.push(at_root(InteractionLintKind::LoopWithoutActions));
+            }
+            for warning in lint_rec(i1, emitted, lf_occurrences) {
+                warnings.push(InteractionLintWarning{ position : Position::Left(Box::new(warning.position)), kind : warning.kind });
+            }
+        },
+        Interaction::Strict(i1, i2) |
+        Interaction::Seq(i1, i2) |
+        Interaction::CoReg(_, i1, i2) |
+        Interaction::Par(i1, i2) |
+        Interaction::Alt(i1, i2) |
+        Interaction::Sync(_, i1, i2) |
+        Interaction::And(i1, i2) => {
+            let mut left = lint_rec(i1, emitted, lf_occurrences);
+            let mut right = lint_rec(i2, emitted, lf_occurrences);
+            if let Interaction::Alt(_,_) = interaction {
+                if i1 == i2 {
+                    right.insert(0, at_root(InteractionLintKind::DuplicateAltBranch));
+                } else {
+                    if !i1.contains_actions() && i2.min_num_actions() == 0 {
+                        left.insert(0, at_root(InteractionLintKind::RedundantEmptyAltBranch));
+                    }
+                    if !i2.contains_actions() && i1.min_num_actions() == 0 {
+                        right.insert(0, at_root(InteractionLintKind::RedundantEmptyAltBranch));
+                    }
+                }
+            }
+            for warning in left {
+                warnings.push(InteractionLintWarning{ position : Position::Left(Box::new(warning.position)), kind : warning.kind });
+            }
+            for warning in right {
+                warnings.push(InteractionLintWarning{ position : Position::Right(Box::new(warning.position)), kind : warning.kind });
+            }
+        }
+    }
+    warnings
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+
+    use super::*;
+
+    #[test]
+    fn lint_test() {
+        let gen_ctx = parse_hsf_string("@lifeline{a;b;c;d} @message{m;n}".to_string()).unwrap();
+        let int = parse_hif_string(&gen_ctx,"seq(a -- m -> b, b -- m -> a, n -> a, alt(o,loopS(o)), alt(a -- m -> d, a -- m -> d))".to_string()).unwrap();
+        let warnings = lint_interaction(&gen_ctx,&int);
+        let kinds : Vec<InteractionLintKind> = warnings.iter().map(|w| w.kind.clone()).collect();
+        assert_eq!(kinds, vec![
+            InteractionLintKind::UnusedLifeline(2),
+            InteractionLintKind::OrphanReception(1),
+            InteractionLintKind::RedundantEmptyAltBranch,
+            InteractionLintKind::RedundantEmptyAltBranch,
+            InteractionLintKind::LoopWithoutActions,
+            InteractionLintKind::DuplicateAltBranch
+        ]);
+        // the orphan reception is the third operand of the seq
+        assert_eq!(warnings[1].position, Position::Right(Box::new(Position::Right(Box::new(Position::Left(Box::new(Position::Epsilon(None))))))));
+        assert!(warnings[0].describe(&gen_ctx).contains("'c'"));
+        // ***
+        let isolated = parse_hif_string(&gen_ctx,"seq(a -- m -> b, b -- n -> c, c -- n -> d)".to_string()).unwrap();
+        let kinds : Vec<InteractionLintKind> = lint_interaction(&gen_ctx,&isolated).iter().map(|w| w.kind.clone()).collect();
+        assert_eq!(kinds, vec![InteractionLintKind::IsolatedTarget(3)]);
+    }
+
+}
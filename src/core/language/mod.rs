@@ -22,8 +22,12 @@ pub mod position;
 pub mod avoid;
 pub mod prune;
 pub mod unfold;
+pub mod hash;
+pub mod signature;
+pub mod lint;
+pub mod mutate;
 //pub mod include;
-mod ord;
+pub mod ord;
 
 
 
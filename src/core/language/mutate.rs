@@ -0,0 +1,283 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::position::position::Position;
+use crate::core::language::syntax::action::EmissionTargetRef;
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+
+
+/// Single edit of an interaction term.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InteractionMutationKind {
+    /// alternative replaced by an interleaving or interleaving replaced by an alternative
+    SwapAltPar,
+    /// message of the action replaced by the message with this id (without parameter value)
+    ReplaceMessage(usize),
+    /// binary operator replaced by its operand with this index (0 for the left one, 1 for the right one), the other one being deleted
+    DeleteOperand(usize),
+    /// sub-interaction put under a loop of this kind
+    WrapInLoop(LoopKind)
+}
+
+/// Single edit of the sub-interaction at a position.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InteractionMutation {
+    pub position : Position,
+    pub kind : InteractionMutationKind
+}
+
+impl InteractionMutation {
+
+    pub fn describe(&self, gen_ctx : &GeneralContext) -> String {
+        let description = match &self.kind {
+            InteractionMutationKind::SwapAltPar => {
+                "swapped alt and par".to_string()
+            },
+            InteractionMutationKind::ReplaceMessage(ms_id) => {
+                format!("replaced the message of the action by '{:}'", gen_ctx.get_ms_name(*ms_id).unwrap())
+            },
+            InteractionMutationKind::DeleteOperand(kept) => {
+                format!("deleted the {:} operand", if *kept == 0 { "right" } else { "left" })
+            },
+            InteractionMutationKind::WrapInLoop(lk) => {
+                format!("wrapped in a loop{:}", get_loop_kind_suffix(lk))
+            }
+        };
+        format!("at position {:?} : {:}", self.position, description)
+    }
+
+    pub fn apply(&self, interaction : &Interaction) -> Interaction {
+        rewrite_at(interaction, &self.position, &|sub_int| {
+            match (&self.kind, sub_int) {
+                (InteractionMutationKind::SwapAltPar, Interaction::Alt(i1,i2)) => {
+                    Interaction::Par(i1.clone(),i2.clone())
+                },
+                (InteractionMutationKind::SwapAltPar, Interaction::Par(i1,i2)) => {
+                    Interaction::Alt(i1.clone(),i2.clone())
+                },
+                (InteractionMutationKind::ReplaceMessage(ms_id), Interaction::Emission(em_act)) => {
+                    let mut em_act = em_act.clone();
+                    em_act.ms_id = *ms_id;
+                    em_act.ms_param = None;
+                    Interaction::Emission(em_act)
+                },
+                (InteractionMutationKind::ReplaceMessage(ms_id), Interaction::Reception(rc_act)) => {
+                    let mut rc_act = rc_act.clone();
+                    rc_act.ms_id = *ms_id;
+                    rc_act.ms_param = None;
+                    Interaction::Reception(rc_act)
+                },
+                (InteractionMutationKind::DeleteOperand(kept), _) => {
+                    get_operands(sub_int).get(*kept).map_or(sub_int.clone(), |operand| (*operand).clone())
+                },
+                (InteractionMutationKind::WrapInLoop(lk), _) => {
+                    Interaction::Loop(lk.clone(), Box::new(sub_int.clone()))
+                },
+                _ => {
+                    sub_int.clone()
+                }
+            }
+        })
+    }
+}
+
+fn get_loop_kind_suffix(lk : &LoopKind) -> &'static str {
+    match lk {
+        LoopKind::SStrictSeq => "S",
+        LoopKind::HHeadFirstWS => "H",
+        LoopKind::WWeakSeq => "W",
+        LoopKind::PInterleaving => "P"
+    }
+}
+
+fn get_operands(interaction : &Interaction) -> Vec<&Interaction> {
+    match interaction {
+        Interaction::Strict(i1, i2) |
+        Interaction::Seq(i1, i2) |
+        Interaction::CoReg(_, i1, i2) |
+        Interaction::Par(i1, i2) |
+        Interaction::Alt(i1, i2) |
+        Interaction::And(i1, i2) |
+        Interaction::Sync(_, i1, i2) => vec![i1,i2],
+        Interaction::Loop(_, i1) => vec![i1],
+        _ => vec![]
+    }
+}
+
+/// Sub-interactions together with their positions, in prefix order.
+fn get_positioned_sub_interactions(interaction : &Interaction) -> Vec<(Position,&Interaction)> {
+    let mut got = vec![(Position::Epsilon(None),interaction)];
+    for (idx,operand) in get_operands(interaction).into_iter().enumerate() {
+        for (position,sub_int) in get_positioned_sub_interactions(operand) {
+            let position = if idx == 0 { Position::Left(Box::new(position)) } else { Position::Right(Box::new(position)) };
+            got.push((position,sub_int));
+        }
+    }
+    got
+}
+
+fn rewrite_at<F>(interaction : &Interaction, position : &Position, rewrite : &F) -> Interaction
+        where F : Fn(&Interaction) -> Interaction {
+    let (idx,sub_position) = match position {
+        Position::Left(sub_position) => (0,sub_position),
+        Position::Right(sub_position) => (1,sub_position),
+        _ => {
+            return rewrite(interaction);
+        }
+    };
+    let mut rewritten = interaction.clone();
+    match &mut rewritten {
+        Interaction::Strict(i1, i2) |
+        Interaction::Seq(i1, i2) |
+        Interaction::CoReg(_, i1, i2) |
+        Interaction::Par(i1, i2) |
+        Interaction::Alt(i1, i2) |
+        Interaction::And(i1, i2) |
+        Interaction::Sync(_, i1, i2) => {
+            let operand = if idx == 0 { i1 } else { i2 };
+            **operand = rewrite_at(operand, sub_position, rewrite);
+        },
+        Interaction::Loop(_, i1) => {
+            **i1 = rewrite_at(i1, sub_position, rewrite);
+        },
+        _ => {}
+    }
+    rewritten
+}
+
+/// All the single edits of the interaction, grouped by the kind of edit, which keep it well-defined over the signature
+/// (in particular, messages are only replaced by messages which may be transmitted between the same lifelines).
+pub fn get_single_edit_mutations(gen_ctx : &GeneralContext, interaction : &Interaction) -> Vec<Vec<InteractionMutation>> {
+    let mut swaps = vec![];
+    let mut replacements = vec![];
+    let mut deletions = vec![];
+    let mut wrappings = vec![];
+    for (position,sub_int) in get_positioned_sub_interactions(interaction) {
+        let mutation = |kind : InteractionMutationKind| InteractionMutation{ position : position.clone(), kind };
+        match sub_int {
+            Interaction::Alt(_,_) | Interaction::Par(_,_) => {
+                swaps.push(mutation(InteractionMutationKind::SwapAltPar));
+            },
+            Interaction::Emission(em_act) => {
+                for ms_id in (0..gen_ctx.get_ms_num()).filter(|ms_id| *ms_id != em_act.ms_id) {
+                    let allowed = em_act.targets.iter().all(|target| match target {
+                        EmissionTargetRef::Lifeline(tar_lf_id) => gen_ctx.is_transmission_allowed(ms_id,em_act.origin_lf_id,*tar_lf_id),
+                        EmissionTargetRef::Gate(_) => true
+                    });
+                    if allowed {
+                        replacements.push(mutation(InteractionMutationKind::ReplaceMessage(ms_id)));
+                    }
+                }
+            },
+            Interaction::Reception(rc_act) => {
+                for ms_id in (0..gen_ctx.get_ms_num()).filter(|ms_id| *ms_id != rc_act.ms_id) {
+                    replacements.push(mutation(InteractionMutationKind::ReplaceMessage(ms_id)));
+                }
+            },
+            _ => {}
+        }
+        if get_operands(sub_int).len() == 2 {
+            deletions.push(mutation(InteractionMutationKind::DeleteOperand(0)));
+            deletions.push(mutation(InteractionMutationKind::DeleteOperand(1)));
+        }
+        match sub_int {
+            Interaction::Empty | Interaction::Loop(_,_) => {},
+            _ => {
+                for lk in [LoopKind::SStrictSeq,LoopKind::HHeadFirstWS,LoopKind::WWeakSeq,LoopKind::PInterleaving] {
+                    wrappings.push(mutation(InteractionMutationKind::WrapInLoop(lk)));
+                }
+            }
+        }
+    }
+    vec![swaps,replacements,deletions,wrappings].into_iter().filter(|x| !x.is_empty()).collect()
+}
+
+/// Up to 'num_mutants' distinct mutants of the interaction, each obtained by a single edit.
+/// Each mutant is drawn by choosing uniformly a kind of edit and then one of the edits of this kind,
+/// so that the numerous loop wrappings do not crowd out the other edits.
+pub fn generate_mutants<R : Rng>(gen_ctx : &GeneralContext,
+                                 interaction : &Interaction,
+                                 num_mutants : usize,
+                                 rng : &mut R) -> Vec<(InteractionMutation,Interaction)> {
+    let mut candidates = get_single_edit_mutations(gen_ctx, interaction);
+    for group in &mut candidates {
+        group.shuffle(rng);
+    }
+    let mut mutants : Vec<(InteractionMutation,Interaction)> = vec![];
+    while mutants.len() < num_mutants && !candidates.is_empty() {
+        let group_idx = rng.gen_range(0..candidates.len());
+        let mutation = candidates[group_idx].pop().unwrap();
+        if candidates[group_idx].is_empty() {
+            candidates.remove(group_idx);
+        }
+        let mutant = mutation.apply(interaction);
+        // distinct edits may yield the same term e.g. deleting either operand of 'alt(i,i)'
+        if &mutant != interaction && mutants.iter().all(|(_,other)| *other != mutant) {
+            mutants.push((mutation,mutant));
+        }
+    }
+    mutants
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn mutate_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n;p}\n@lifeline{a;b}\n@direction{a -- m -> b;a -- n -> b;b -- p -> a}".to_string()).unwrap();
+        let parse = |hif : &str| parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let i = parse("seq(alt(a -- m -> b, o), loopS(b -- p -> a))");
+        let mutation = |position : Position, kind : InteractionMutationKind| InteractionMutation{ position, kind };
+        let left = |p : Position| Position::Left(Box::new(p));
+        let right = |p : Position| Position::Right(Box::new(p));
+        let root = || Position::Epsilon(None);
+        assert_eq!(mutation(left(root()),InteractionMutationKind::SwapAltPar).apply(&i),
+                   parse("seq(par(a -- m -> b, o), loopS(b -- p -> a))"));
+        assert_eq!(mutation(left(left(root())),InteractionMutationKind::ReplaceMessage(1)).apply(&i),
+                   parse("seq(alt(a -- n -> b, o), loopS(b -- p -> a))"));
+        assert_eq!(mutation(root(),InteractionMutationKind::DeleteOperand(1)).apply(&i),
+                   parse("loopS(b -- p -> a)"));
+        assert_eq!(mutation(left(left(root())),InteractionMutationKind::WrapInLoop(LoopKind::HHeadFirstWS)).apply(&i),
+                   parse("seq(alt(loopH(a -- m -> b), o), loopS(b -- p -> a))"));
+        // 'p' may not be sent from 'a' to 'b' and 'o' is not wrapped in loops
+        let mutations : Vec<InteractionMutation> = get_single_edit_mutations(&gen_ctx,&i).into_iter().flatten().collect();
+        assert!(mutations.iter().filter(|x| matches!(x.kind, InteractionMutationKind::ReplaceMessage(_))).count() == 1);
+        assert!(!mutations.contains(&mutation(left(left(root())),InteractionMutationKind::ReplaceMessage(2))));
+        assert!(!mutations.contains(&mutation(left(right(root())),InteractionMutationKind::WrapInLoop(LoopKind::HHeadFirstWS))));
+        // ***
+        let mutants = generate_mutants(&gen_ctx,&i,10,&mut StdRng::seed_from_u64(0));
+        assert_eq!(mutants.len(), 10);
+        for (x,(mutation,mutant)) in mutants.iter().enumerate() {
+            assert_ne!(mutant, &i);
+            assert_eq!(&mutation.apply(&i), mutant);
+            assert!(mutants[x+1..].iter().all(|(_,other)| other != mutant));
+        }
+        assert_eq!(generate_mutants(&gen_ctx,&i,10,&mut StdRng::seed_from_u64(0)), mutants);
+    }
+
+}
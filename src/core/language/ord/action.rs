@@ -22,6 +22,14 @@ use crate::core::language::syntax::action::{EmissionAction, EmissionTargetRef, R
 
 
 
+// consistent with 'Ord' (which a derived implementation would not be)
+impl PartialOrd for EmissionTargetRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
 impl Ord for EmissionTargetRef {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self,other) {
@@ -43,6 +51,14 @@ impl Ord for EmissionTargetRef {
 
 
 
+// consistent with 'Ord' (which a derived implementation would not be)
+impl PartialOrd for EmissionAction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
 impl Ord for EmissionAction {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.ms_id < other.ms_id {
@@ -51,6 +67,9 @@ impl Ord for EmissionAction {
         if self.ms_id > other.ms_id {
             return Ordering::Greater;
         }
+        if self.ms_param != other.ms_param {
+            return self.ms_param.cmp(&other.ms_param);
+        }
         // ***
         if self.origin_lf_id < other.origin_lf_id {
             return Ordering::Less;
@@ -87,12 +106,20 @@ impl Ord for EmissionAction {
             return Ordering::Greater;
         }
         // ***
-        return Ordering::Equal;
+        return self.target_group.cmp(&other.target_group);
     }
 }
 
 
 
+// consistent with 'Ord' (which a derived implementation would not be)
+impl PartialOrd for ReceptionAction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
 impl Ord for ReceptionAction {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.ms_id < other.ms_id {
@@ -101,6 +128,9 @@ impl Ord for ReceptionAction {
         if self.ms_id > other.ms_id {
             return Ordering::Greater;
         }
+        if self.ms_param != other.ms_param {
+            return self.ms_param.cmp(&other.ms_param);
+        }
         // ***
         match (self.origin_gt_id,other.origin_gt_id) {
             (None,Some(_)) => {
@@ -0,0 +1,124 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::cmp::Ordering;
+
+use crate::core::language::hash::canonical::canonical_hash;
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::metrics::InteractionMetrics;
+
+
+/// An interaction together with its number of symbols and its canonical hash,
+/// which are computed once when wrapping it.
+/// 'CorpusInteraction's are totally ordered so as to sort corpora :
+/// smaller interactions (in number of symbols) come first,
+/// then interactions are ordered by their canonical hash
+/// and, in case of a collision, by the lexicographic order on their syntax.
+/// Two of them compare as 'Equal' if and only if their interactions are equal.
+#[derive(Clone, Debug)]
+pub struct CorpusInteraction {
+    num_symbols : u32,
+    hash : u64,
+    interaction : Interaction
+}
+
+impl CorpusInteraction {
+
+    pub fn new(interaction : Interaction) -> Self {
+        let num_symbols = InteractionMetrics::extract_from_interaction(&interaction).get_num_symbols();
+        Self { num_symbols, hash : canonical_hash(&interaction), interaction }
+    }
+
+    pub fn get_num_symbols(&self) -> u32 {
+        self.num_symbols
+    }
+
+    pub fn get_interaction(&self) -> &Interaction {
+        &self.interaction
+    }
+
+    pub fn into_interaction(self) -> Interaction {
+        self.interaction
+    }
+
+}
+
+impl Ord for CorpusInteraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.num_symbols.cmp(&other.num_symbols)
+            .then_with(|| self.hash.cmp(&other.hash))
+            .then_with(|| self.interaction.cmp(&other.interaction))
+    }
+}
+
+impl PartialOrd for CorpusInteraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for CorpusInteraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.interaction == other.interaction
+    }
+}
+
+impl Eq for CorpusInteraction {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    #[test]
+    fn corpus_order_test() {
+        let a = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b = Interaction::Emission(EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let ab = Interaction::Seq(Box::new(a.clone()),Box::new(b.clone()));
+        let ba = Interaction::Seq(Box::new(b.clone()),Box::new(a.clone()));
+        let all : Vec<Interaction> = vec![Interaction::Empty, a.clone(), b.clone(), ab.clone(), ba.clone(),
+                       Interaction::Strict(Box::new(a.clone()),Box::new(b.clone())),
+                       Interaction::Loop(LoopKind::SStrictSeq,Box::new(ab.clone())),
+                       Interaction::Loop(LoopKind::WWeakSeq,Box::new(ab.clone()))];
+        for i1 in &all {
+            let c1 = CorpusInteraction::new(i1.clone());
+            for i2 in &all {
+                // 'PartialOrd' agrees with 'Ord' and both agree with 'Eq'
+                assert_eq!(i1.partial_cmp(i2), Some(i1.cmp(i2)));
+                assert_eq!(i1.cmp(i2) == Ordering::Equal, i1 == i2);
+                let c2 = CorpusInteraction::new(i2.clone());
+                assert_eq!(c1.partial_cmp(&c2), Some(c1.cmp(&c2)));
+                assert_eq!(c1.cmp(&c2) == Ordering::Equal, c1 == c2);
+                assert_eq!(c1 == c2, i1 == i2);
+                assert_eq!(c1.cmp(&c2).reverse(), c2.cmp(&c1));
+            }
+        }
+        // smaller interactions first, whatever their syntax
+        assert!(CorpusInteraction::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(a.clone()))) < CorpusInteraction::new(ab.clone()));
+        assert!(CorpusInteraction::new(b.clone()) < CorpusInteraction::new(ab.clone()));
+        // sorting does not depend on the initial order
+        let mut sorted : Vec<CorpusInteraction> = all.iter().cloned().map(CorpusInteraction::new).collect();
+        sorted.sort();
+        let mut reversed : Vec<CorpusInteraction> = all.iter().rev().cloned().map(CorpusInteraction::new).collect();
+        reversed.sort();
+        assert_eq!(sorted, reversed);
+        assert!(sorted.windows(2).all(|w| w[0].get_num_symbols() <= w[1].get_num_symbols()));
+    }
+}
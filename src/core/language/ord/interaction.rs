@@ -18,6 +18,13 @@ limitations under the License.
 use std::cmp::Ordering;
 use crate::core::language::syntax::interaction::Interaction;
 
+// consistent with 'Ord' (which a derived implementation would not be, as variants are not ordered the same)
+impl PartialOrd for Interaction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for Interaction {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self,other) {
@@ -51,6 +58,16 @@ impl Ord for Interaction {
                 return Ordering::Greater;
             },
             // ***
+            (Interaction::Not(act1),Interaction::Not(act2)) => {
+                return act1.cmp(act2);
+            },
+            (Interaction::Not(_),_) => {
+                return Ordering::Less;
+            },
+            (_,Interaction::Not(_)) => {
+                return Ordering::Greater;
+            },
+            // ***
             (Interaction::Par(self_i1,self_i2),Interaction::Par(other_i1,other_i2)) => {
                 let cmp_left = self_i1.cmp(other_i1);
                 match &cmp_left {
@@ -16,4 +16,5 @@ limitations under the License.
 
 
 mod action;
-mod interaction;
\ No newline at end of file
+mod interaction;
+pub mod corpus;
@@ -156,6 +156,12 @@ impl LifelinePrunable for Interaction {
                 }
                 return Interaction::Empty;
             },
+            Interaction::Not(act) => {
+                if lf_ids.contains(&act.lf_id) {
+                    return Interaction::Empty;
+                }
+                return self.clone();
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -260,6 +266,12 @@ impl LifelinePrunable for Interaction {
                 }
                 return (Interaction::Empty,i1.involved_lifelines());
             },
+            Interaction::Not(act) => {
+                if lf_ids.contains(&act.lf_id) {
+                    return (Interaction::Empty,btreeset!{act.lf_id});
+                }
+                return (self.clone(),btreeset!{});
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -0,0 +1,192 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::collections::BTreeSet;
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::EmissionTargetRef;
+use crate::core::language::syntax::fold::{InteractionLeaf, InteractionOperator};
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// Ids of the lifelines, messages, gates and groups an interaction refers to.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct UsedSignatureIds {
+    pub lf_ids : BTreeSet<usize>,
+    pub ms_ids : BTreeSet<usize>,
+    pub gt_ids : BTreeSet<usize>,
+    pub group_ids : BTreeSet<usize>
+}
+
+impl UsedSignatureIds {
+    fn merge(mut self, other : UsedSignatureIds) -> UsedSignatureIds {
+        self.lf_ids.extend(other.lf_ids);
+        self.ms_ids.extend(other.ms_ids);
+        self.gt_ids.extend(other.gt_ids);
+        self.group_ids.extend(other.group_ids);
+        return self;
+    }
+}
+
+/// A co-region over all the lifelines is written without its list of lifelines
+/// (see the .hif syntax) hence it does not count as referring to them.
+pub fn get_used_signature_ids(gen_ctx : &GeneralContext, interaction : &Interaction) -> UsedSignatureIds {
+    let all_lfs : Vec<usize> = (0..gen_ctx.get_lf_num()).collect();
+    interaction.fold(|leaf| {
+        let mut used = UsedSignatureIds::default();
+        match leaf {
+            InteractionLeaf::Empty => {},
+            InteractionLeaf::Emission(em_act) => {
+                used.lf_ids.insert(em_act.origin_lf_id);
+                used.ms_ids.insert(em_act.ms_id);
+                used.group_ids.extend(em_act.target_group);
+                for target in &em_act.targets {
+                    match target {
+                        EmissionTargetRef::Lifeline(tar_lf_id) => {
+                            used.lf_ids.insert(*tar_lf_id);
+                        },
+                        EmissionTargetRef::Gate(tar_gt_id) => {
+                            used.gt_ids.insert(*tar_gt_id);
+                        }
+                    }
+                }
+            },
+            InteractionLeaf::Reception(rc_act) => {
+                used.ms_ids.insert(rc_act.ms_id);
+                used.gt_ids.extend(rc_act.origin_gt_id);
+                used.lf_ids.extend(rc_act.recipients.iter().cloned());
+            },
+            InteractionLeaf::Not(act) => {
+                used.lf_ids.insert(act.lf_id);
+                used.ms_ids.insert(act.ms_id);
+            }
+        }
+        used
+    }, |op, operands| {
+        let mut used = operands.into_iter().fold(UsedSignatureIds::default(), UsedSignatureIds::merge);
+        match op {
+            InteractionOperator::CoReg(cr) if *cr != all_lfs => {
+                used.lf_ids.extend(cr.iter().cloned());
+            },
+            InteractionOperator::Sync(sync_acts) => {
+                for sync_act in sync_acts {
+                    used.lf_ids.insert(sync_act.lf_id);
+                    used.ms_ids.insert(sync_act.ms_id);
+                }
+            },
+            _ => {}
+        }
+        used
+    })
+}
+
+/// Restricts the signature to the declarations the interaction refers to, in their original order.
+/// The kept messages keep all the values of their parameter, and only the directions
+/// between kept lifelines are kept.
+pub fn get_used_signature(gen_ctx : &GeneralContext, interaction : &Interaction) -> GeneralContext {
+    let used = get_used_signature_ids(gen_ctx, interaction);
+    let mut used_ctx = GeneralContext::new();
+    for ms_id in &used.ms_ids {
+        let new_ms_id = used_ctx.add_msg(gen_ctx.get_ms_name(*ms_id).unwrap());
+        for param_id in 0..gen_ctx.get_ms_params_num(*ms_id) {
+            used_ctx.add_ms_param(new_ms_id, gen_ctx.get_ms_param_name(*ms_id,param_id).unwrap());
+        }
+    }
+    for lf_id in &used.lf_ids {
+        used_ctx.add_lf(gen_ctx.get_lf_name(*lf_id).unwrap());
+    }
+    for gt_id in &used.gt_ids {
+        used_ctx.add_gt(gen_ctx.get_gt_name(*gt_id).unwrap());
+    }
+    let new_lf_id = |lf_id : &usize| used_ctx.get_lf_id(&gen_ctx.get_lf_name(*lf_id).unwrap());
+    let mut groups = vec![];
+    for group_id in &used.group_ids {
+        let lf_ids : Vec<usize> = gen_ctx.get_group_lifelines(*group_id).unwrap().iter().filter_map(new_lf_id).collect();
+        groups.push((gen_ctx.get_group_name(*group_id).unwrap(), lf_ids));
+    }
+    let mut directions = vec![];
+    for ms_id in &used.ms_ids {
+        for (orig_lf_id,tar_lf_id) in gen_ctx.get_ms_directions(*ms_id) {
+            if let (Some(new_orig),Some(new_tar)) = (new_lf_id(orig_lf_id),new_lf_id(tar_lf_id)) {
+                directions.push((used_ctx.get_ms_id(&gen_ctx.get_ms_name(*ms_id).unwrap()).unwrap(),new_orig,new_tar));
+            }
+        }
+    }
+    for (group_name,lf_ids) in groups {
+        used_ctx.add_group(group_name,lf_ids);
+    }
+    for (ms_id,orig_lf_id,tar_lf_id) in directions {
+        used_ctx.add_ms_direction(ms_id,orig_lf_id,tar_lf_id);
+    }
+    return used_ctx;
+}
+
+/// Reorders the declarations of the signature into a canonical order so that signatures
+/// declaring the same elements have the same ids whatever the order in which they were written.
+/// Lifelines, messages, gates and groups are sorted by name and directions by (message, origin, target) names.
+/// The values of the parameter of a message keep their declared order.
+pub fn get_normalized_signature(gen_ctx : &GeneralContext) -> GeneralContext {
+    let mut normalized = GeneralContext::new();
+    let mut ms_ids : Vec<usize> = (0..gen_ctx.get_ms_num()).collect();
+    ms_ids.sort_by_key(|ms_id| gen_ctx.get_ms_name(*ms_id).unwrap());
+    for ms_id in &ms_ids {
+        let new_ms_id = normalized.add_msg(gen_ctx.get_ms_name(*ms_id).unwrap());
+        for param_id in 0..gen_ctx.get_ms_params_num(*ms_id) {
+            normalized.add_ms_param(new_ms_id, gen_ctx.get_ms_param_name(*ms_id,param_id).unwrap());
+        }
+    }
+    let mut lf_names : Vec<String> = (0..gen_ctx.get_lf_num()).map(|lf_id| gen_ctx.get_lf_name(lf_id).unwrap()).collect();
+    lf_names.sort();
+    for lf_name in lf_names {
+        normalized.add_lf(lf_name);
+    }
+    let mut gt_names : Vec<String> = (0..gen_ctx.get_gt_num()).map(|gt_id| gen_ctx.get_gt_name(gt_id).unwrap()).collect();
+    gt_names.sort();
+    for gt_name in gt_names {
+        normalized.add_gt(gt_name);
+    }
+    let new_lf_id = |lf_id : &usize| normalized.get_lf_id(&gen_ctx.get_lf_name(*lf_id).unwrap()).unwrap();
+    let mut groups : Vec<(String,Vec<usize>)> = (0..gen_ctx.get_group_num()).map(|group_id| {
+        let lf_ids = gen_ctx.get_group_lifelines(group_id).unwrap().iter().map(new_lf_id).collect();
+        (gen_ctx.get_group_name(group_id).unwrap(), lf_ids)
+    }).collect();
+    groups.sort();
+    let mut directions = vec![];
+    for ms_id in &ms_ids {
+        let new_ms_id = normalized.get_ms_id(&gen_ctx.get_ms_name(*ms_id).unwrap()).unwrap();
+        for (orig_lf_id,tar_lf_id) in gen_ctx.get_ms_directions(*ms_id) {
+            directions.push((new_ms_id,new_lf_id(orig_lf_id),new_lf_id(tar_lf_id)));
+        }
+    }
+    // as lifelines are sorted by name, sorting their new ids amounts to sorting their names
+    directions.sort();
+    for (group_name,lf_ids) in groups {
+        normalized.add_group(group_name,lf_ids);
+    }
+    for (ms_id,orig_lf_id,tar_lf_id) in directions {
+        normalized.add_ms_direction(ms_id,orig_lf_id,tar_lf_id);
+    }
+    return normalized;
+}
+
+/// Total number of declarations (of lifelines, messages, gates, groups and directions) of a signature.
+pub fn get_signature_declarations_num(gen_ctx : &GeneralContext) -> usize {
+    let directions_num : usize = (0..gen_ctx.get_ms_num()).map(|ms_id| gen_ctx.get_ms_directions(ms_id).len()).sum();
+    return gen_ctx.get_lf_num() + gen_ctx.get_ms_num() + gen_ctx.get_gt_num() + gen_ctx.get_group_num() + directions_num;
+}
@@ -20,7 +20,7 @@ use std::fmt::Debug;
 
 
 
-#[derive(Clone, PartialEq, Debug, Eq, Hash, PartialOrd)]
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
 pub enum EmissionTargetRef {
     Lifeline(usize),
     Gate(usize)
@@ -32,12 +32,14 @@ pub enum CommunicationSynchronicity {
     Synchronous
 }
 
-#[derive(Clone, PartialEq, Debug, Eq, Hash, PartialOrd)]
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
 pub struct EmissionAction {
     pub origin_lf_id : usize,
     pub ms_id : usize,
+    pub ms_param : Option<usize>, // id of the value of the message's parameter (see 'GeneralContext::add_ms_param')
     pub synchronicity : CommunicationSynchronicity,
-    pub targets : Vec<EmissionTargetRef> // both lf_ids and gt_ids possible
+    pub targets : Vec<EmissionTargetRef>, // both lf_ids and gt_ids possible
+    pub target_group : Option<usize> // id of the multicast group the targets were given as (see 'GeneralContext::add_group')
 }
 
 impl EmissionAction {
@@ -45,14 +47,26 @@ impl EmissionAction {
                ms_id : usize,
                synchronicity : CommunicationSynchronicity,
                targets : Vec<EmissionTargetRef>) -> EmissionAction {
-        return EmissionAction{origin_lf_id,ms_id,synchronicity,targets}
+        return EmissionAction{origin_lf_id,ms_id,ms_param:None,synchronicity,targets,target_group:None}
+    }
+
+    pub fn with_param(mut self, ms_param : Option<usize>) -> EmissionAction {
+        self.ms_param = ms_param;
+        return self;
+    }
+
+    /// The targets must be the lifelines of the group.
+    pub fn with_group(mut self, target_group : Option<usize>) -> EmissionAction {
+        self.target_group = target_group;
+        return self;
     }
 }
 
-#[derive(Clone, PartialEq, Debug, Eq, Hash, PartialOrd)]
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
 pub struct ReceptionAction {
     pub origin_gt_id : Option<usize>,
     pub ms_id : usize,
+    pub ms_param : Option<usize>,
     pub synchronicity : CommunicationSynchronicity,
     pub recipients : Vec<usize> // only lf_ids here
 }
@@ -62,7 +76,12 @@ impl ReceptionAction {
                ms_id : usize,
                synchronicity : CommunicationSynchronicity,
                recipients : Vec<usize>) -> ReceptionAction {
-        return ReceptionAction{origin_gt_id,ms_id,synchronicity,recipients}
+        return ReceptionAction{origin_gt_id,ms_id,ms_param:None,synchronicity,recipients}
+    }
+
+    pub fn with_param(mut self, ms_param : Option<usize>) -> ReceptionAction {
+        self.ms_param = ms_param;
+        return self;
     }
 }
 
@@ -0,0 +1,176 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::error::HibouCoreError;
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+use crate::core::language::syntax::util::fold_recursive_frags::{fold_recursive_alt_frags, fold_recursive_par_frags, fold_recursive_seq_frags, fold_recursive_strict_frags};
+
+
+/// Builds interactions by referring to lifelines and messages by their names in a signature
+/// instead of by their ids.
+/// Actions are asynchronous; n-ary operators are folded to the right as in the .hif parser.
+pub struct InteractionBuilder<'a> {
+    gen_ctx : &'a GeneralContext
+}
+
+impl<'a> InteractionBuilder<'a> {
+
+    pub fn new(gen_ctx : &'a GeneralContext) -> InteractionBuilder<'a> {
+        return InteractionBuilder{gen_ctx};
+    }
+
+    fn lf_id(&self, lf_name : &str) -> Result<usize,HibouCoreError> {
+        return self.gen_ctx.get_lf_id(lf_name).ok_or_else(|| HibouCoreError::UnknownLifelineName(lf_name.to_string()));
+    }
+
+    fn ms_id(&self, ms_name : &str) -> Result<usize,HibouCoreError> {
+        return self.gen_ctx.get_ms_id(ms_name).ok_or_else(|| HibouCoreError::UnknownMessageName(ms_name.to_string()));
+    }
+
+    pub fn empty(&self) -> Interaction {
+        return Interaction::Empty;
+    }
+
+    /// Emission of a message towards the environment.
+    pub fn emit(&self, origin : &str, message : &str) -> Result<Interaction,HibouCoreError> {
+        return self.broadcast(origin, message, &[]);
+    }
+
+    /// Reception of a message coming from the environment.
+    pub fn receive(&self, message : &str, recipient : &str) -> Result<Interaction,HibouCoreError> {
+        let ms_id = self.ms_id(message)?;
+        let lf_id = self.lf_id(recipient)?;
+        return Ok( Interaction::Reception(ReceptionAction::new(None,ms_id,CommunicationSynchronicity::Asynchronous,vec![lf_id])) );
+    }
+
+    /// Transmission of a message from a lifeline to another, as written 'origin -- message -> target'.
+    pub fn transmission(&self, origin : &str, message : &str, target : &str) -> Result<Interaction,HibouCoreError> {
+        return self.broadcast(origin, message, &[target]);
+    }
+
+    /// Transmission of a message from a lifeline to several others, as written 'origin -- message -> (t1,t2)'.
+    pub fn broadcast(&self, origin : &str, message : &str, targets : &[&str]) -> Result<Interaction,HibouCoreError> {
+        let ms_id = self.ms_id(message)?;
+        let orig_lf_id = self.lf_id(origin)?;
+        let mut target_refs = vec![];
+        for target in targets {
+            let tar_lf_id = self.lf_id(target)?;
+            if !self.gen_ctx.is_transmission_allowed(ms_id,orig_lf_id,tar_lf_id) {
+                return Err( HibouCoreError::ForbiddenTransmission(message.to_string(),origin.to_string(),target.to_string()) );
+            }
+            target_refs.push(EmissionTargetRef::Lifeline(tar_lf_id));
+        }
+        return Ok( Interaction::Emission(EmissionAction::new(orig_lf_id,ms_id,CommunicationSynchronicity::Asynchronous,target_refs)) );
+    }
+
+    pub fn strict(&self, i1 : Interaction, i2 : Interaction) -> Interaction {
+        return Interaction::Strict(Box::new(i1),Box::new(i2));
+    }
+
+    pub fn seq(&self, i1 : Interaction, i2 : Interaction) -> Interaction {
+        return Interaction::Seq(Box::new(i1),Box::new(i2));
+    }
+
+    pub fn par(&self, i1 : Interaction, i2 : Interaction) -> Interaction {
+        return Interaction::Par(Box::new(i1),Box::new(i2));
+    }
+
+    pub fn alt(&self, children : Vec<Interaction>) -> Interaction {
+        return fold_recursive_alt_frags(&mut children.iter().collect());
+    }
+
+    pub fn strict_all(&self, children : Vec<Interaction>) -> Interaction {
+        return fold_recursive_strict_frags(&mut children.iter().collect());
+    }
+
+    pub fn seq_all(&self, children : Vec<Interaction>) -> Interaction {
+        return fold_recursive_seq_frags(&mut children.iter().collect());
+    }
+
+    pub fn par_all(&self, children : Vec<Interaction>) -> Interaction {
+        return fold_recursive_par_frags(&mut children.iter().collect());
+    }
+
+    /// Co-region over the given lifelines, whose ids are sorted as in the .hif parser.
+    pub fn coreg(&self, lifelines : &[&str], i1 : Interaction, i2 : Interaction) -> Result<Interaction,HibouCoreError> {
+        let mut cr = vec![];
+        for lf_name in lifelines {
+            cr.push(self.lf_id(lf_name)?);
+        }
+        cr.sort();
+        cr.dedup();
+        return Ok( Interaction::CoReg(cr,Box::new(i1),Box::new(i2)) );
+    }
+
+    pub fn loop_kind(&self, kind : LoopKind, i1 : Interaction) -> Interaction {
+        return Interaction::Loop(kind,Box::new(i1));
+    }
+
+    pub fn loop_s(&self, i1 : Interaction) -> Interaction {
+        return self.loop_kind(LoopKind::SStrictSeq,i1);
+    }
+
+    pub fn loop_h(&self, i1 : Interaction) -> Interaction {
+        return self.loop_kind(LoopKind::HHeadFirstWS,i1);
+    }
+
+    pub fn loop_w(&self, i1 : Interaction) -> Interaction {
+        return self.loop_kind(LoopKind::WWeakSeq,i1);
+    }
+
+    pub fn loop_p(&self, i1 : Interaction) -> Interaction {
+        return self.loop_kind(LoopKind::PInterleaving,i1);
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn builder_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b;c}\n@direction{a -- n -> b}".to_string()).unwrap();
+        let builder = InteractionBuilder::new(&gen_ctx);
+        let built = builder.seq(
+            builder.alt(vec![
+                builder.transmission("a","m","b").unwrap(),
+                builder.emit("c","n").unwrap(),
+                builder.receive("m","c").unwrap()
+            ]),
+            builder.loop_s(builder.coreg(&["c","a"],
+                builder.broadcast("b","m",&["a","c"]).unwrap(),
+                builder.empty()).unwrap())
+        );
+        let parsed = parse_hif_string(&gen_ctx,
+            "seq(alt(a -- m -> b, c -- n ->|, m -> c), loopS(coreg(a,c)(b -- m -> (a,c), o)))".to_string()).unwrap();
+        assert_eq!(built, parsed);
+        // unknown names and forbidden transmissions are reported
+        assert!(matches!(builder.emit("d","m"), Err(HibouCoreError::UnknownLifelineName(ref l)) if l == "d"));
+        assert!(matches!(builder.transmission("a","p","b"), Err(HibouCoreError::UnknownMessageName(ref m)) if m == "p"));
+        assert!(matches!(builder.coreg(&["a","x"],builder.empty(),builder.empty()), Err(HibouCoreError::UnknownLifelineName(ref l)) if l == "x"));
+        assert!(matches!(builder.transmission("a","n","c"), Err(HibouCoreError::ForbiddenTransmission(..))));
+    }
+
+}
@@ -0,0 +1,163 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::syntax::action::{EmissionAction, ReceptionAction};
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+
+
+/// Leaf of an interaction term.
+#[derive(Clone, PartialEq, Debug)]
+pub enum InteractionLeaf<'a> {
+    Empty,
+    Emission(&'a EmissionAction),
+    Reception(&'a ReceptionAction),
+    Not(&'a TraceAction)
+}
+
+impl<'a> InteractionLeaf<'a> {
+    pub fn to_interaction(&self) -> Interaction {
+        match self {
+            InteractionLeaf::Empty => Interaction::Empty,
+            InteractionLeaf::Emission(em_act) => Interaction::Emission((*em_act).clone()),
+            InteractionLeaf::Reception(rc_act) => Interaction::Reception((*rc_act).clone()),
+            InteractionLeaf::Not(act) => Interaction::Not((*act).clone())
+        }
+    }
+}
+
+/// Operator at an inner node of an interaction term, together with its parameters.
+/// Loops have a single operand and the other operators two.
+#[derive(Clone, PartialEq, Debug)]
+pub enum InteractionOperator<'a> {
+    Strict,
+    Seq,
+    CoReg(&'a Vec<usize>),
+    Alt,
+    Par,
+    Loop(&'a LoopKind),
+    And,
+    Sync(&'a Vec<TraceAction>)
+}
+
+impl<'a> InteractionOperator<'a> {
+    /// Rebuilds the interaction with this operator at its root and 'operands' as sub-interactions.
+    pub fn build(&self, operands : Vec<Interaction>) -> Interaction {
+        let mut operands = operands.into_iter();
+        let mut next = || Box::new(operands.next().expect("missing operand"));
+        match self {
+            InteractionOperator::Strict => Interaction::Strict(next(),next()),
+            InteractionOperator::Seq => Interaction::Seq(next(),next()),
+            InteractionOperator::CoReg(cr) => Interaction::CoReg((*cr).clone(),next(),next()),
+            InteractionOperator::Alt => Interaction::Alt(next(),next()),
+            InteractionOperator::Par => Interaction::Par(next(),next()),
+            InteractionOperator::Loop(lk) => Interaction::Loop((*lk).clone(),next()),
+            InteractionOperator::And => Interaction::And(next(),next()),
+            InteractionOperator::Sync(sync_acts) => Interaction::Sync((*sync_acts).clone(),next(),next())
+        }
+    }
+}
+
+
+impl Interaction {
+
+    /// Bottom-up traversal of the term : leaves are evaluated with 'on_leaf' and each inner node
+    /// with 'on_node' from its operator and the values of its operands (in order).
+    pub fn fold<T, L, N>(&self, on_leaf : L, on_node : N) -> T
+            where L : Fn(InteractionLeaf) -> T, N : Fn(InteractionOperator, Vec<T>) -> T {
+        self.fold_inner(&on_leaf,&on_node)
+    }
+
+    fn fold_inner<T, L, N>(&self, on_leaf : &L, on_node : &N) -> T
+            where L : Fn(InteractionLeaf) -> T, N : Fn(InteractionOperator, Vec<T>) -> T {
+        let binary = |op : InteractionOperator, i1 : &Interaction, i2 : &Interaction| {
+            let operands = vec![i1.fold_inner(on_leaf,on_node), i2.fold_inner(on_leaf,on_node)];
+            on_node(op,operands)
+        };
+        match self {
+            Interaction::Empty => on_leaf(InteractionLeaf::Empty),
+            Interaction::Emission(em_act) => on_leaf(InteractionLeaf::Emission(em_act)),
+            Interaction::Reception(rc_act) => on_leaf(InteractionLeaf::Reception(rc_act)),
+            Interaction::Not(act) => on_leaf(InteractionLeaf::Not(act)),
+            Interaction::Strict(i1,i2) => binary(InteractionOperator::Strict,i1,i2),
+            Interaction::Seq(i1,i2) => binary(InteractionOperator::Seq,i1,i2),
+            Interaction::CoReg(cr,i1,i2) => binary(InteractionOperator::CoReg(cr),i1,i2),
+            Interaction::Alt(i1,i2) => binary(InteractionOperator::Alt,i1,i2),
+            Interaction::Par(i1,i2) => binary(InteractionOperator::Par,i1,i2),
+            Interaction::And(i1,i2) => binary(InteractionOperator::And,i1,i2),
+            Interaction::Sync(sync_acts,i1,i2) => binary(InteractionOperator::Sync(sync_acts),i1,i2),
+            Interaction::Loop(lk,i1) => {
+                let operand = i1.fold_inner(on_leaf,on_node);
+                on_node(InteractionOperator::Loop(lk),vec![operand])
+            }
+        }
+    }
+
+    /// Bottom-up rewriting of the term : 'rewrite' is applied to each leaf and then to each inner node
+    /// once its operands have been rewritten.
+    pub fn map<F>(&self, rewrite : F) -> Interaction
+            where F : Fn(Interaction) -> Interaction {
+        self.fold(|leaf| rewrite(leaf.to_interaction()),
+                  |op, operands| rewrite(op.build(operands)))
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::core::language::syntax::metrics::InteractionMetrics;
+    use crate::experiments::interaction_random_gen::interface::{generate_random_interactions, GenerationParams};
+    use crate::experiments::interaction_random_gen::probas::InteractionSymbolsProbabilities;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn fold_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let params = GenerationParams::new(20,5,1,1000,InteractionSymbolsProbabilities::default_non_regular());
+        let mut ints = generate_random_interactions(&gen_ctx,params,&mut StdRng::seed_from_u64(0));
+        ints.push(parse_hif_string(&gen_ctx,"coreg(a,b)(loopP(m -> (a,b)), and(o, a -- n -> c))".to_string()).unwrap());
+        for i in &ints {
+            let metrics = InteractionMetrics::extract_from_interaction(i);
+            let num_symbols = i.fold(|_| 1_u32, |_,operands| 1 + operands.iter().sum::<u32>());
+            assert_eq!(num_symbols, metrics.get_num_symbols());
+            let depth = i.fold(|_| 0_u32, |_,operands| 1 + operands.into_iter().max().unwrap());
+            assert_eq!(depth, metrics.depth);
+            // rewriting each node into itself rebuilds the same term
+            assert_eq!(&i.map(|x| x), i);
+        }
+    }
+
+    #[test]
+    fn map_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let parse = |hif : &str| parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let i = parse("strict(loopS(strict(a -- m -> b, o)), b -- n ->|)");
+        let weakened = i.map(|x| match x {
+            Interaction::Strict(i1,i2) => Interaction::Seq(i1,i2),
+            Interaction::Loop(LoopKind::SStrictSeq,i1) => Interaction::Loop(LoopKind::WWeakSeq,i1),
+            other => other
+        });
+        assert_eq!(weakened, parse("seq(loopW(seq(a -- m -> b, o)), b -- n ->|)"));
+    }
+
+}
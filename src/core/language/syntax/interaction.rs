@@ -29,7 +29,7 @@ pub enum LoopKind {
     SStrictSeq     = 4
 }
 
-#[derive(Clone, PartialEq, Debug, Eq, Hash, PartialOrd)]
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
 pub enum Interaction {
     Empty,
     Emission(EmissionAction),
@@ -41,7 +41,8 @@ pub enum Interaction {
     Par(Box<Interaction>,Box<Interaction>),
     Loop(LoopKind,Box<Interaction>),
     And(Box<Interaction>,Box<Interaction>),
-    Sync(Vec<TraceAction>,Box<Interaction>,Box<Interaction>)
+    Sync(Vec<TraceAction>,Box<Interaction>,Box<Interaction>),
+    Not(TraceAction)
 }
 
 
@@ -79,6 +80,9 @@ impl Interaction {
             Interaction::Sync(ref s,ref i1, ref i2) => {
                 Interaction::Sync(s.clone(),Box::new(i2.reverse()),Box::new(i1.reverse()))
             },
+            Interaction::Not(ref act) => {
+                Interaction::Not(act.clone())
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -117,6 +121,9 @@ impl Interaction {
             Interaction::Sync(_,ref i1, ref i2) => {
                 i1.express_empty() && i2.express_empty()
             },
+            Interaction::Not(_) => {
+                true
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -179,6 +186,9 @@ impl Interaction {
                 rc.extend(rc2);
                 return (em,rc);
             },
+            &Interaction::Not(_) => {
+                return (hashset!{},hashset!{});
+            },
             _ => {
                 panic!("non-conform interaction");
             }
@@ -38,7 +38,8 @@ pub enum SymbolKind {
     CoReg,
     Alt,
     Sync,
-    And
+    And,
+    Not
 }
 
 
@@ -47,17 +48,27 @@ pub struct InteractionMetrics {
     pub depth : u32,
     pub max_nested_loop_depth : u32,
     pub lifelines : HashSet<usize>,
+    pub messages : HashSet<usize>,
+    /// pairs (parent operator, child symbol) of adjacent symbols in the term tree
+    pub operator_pairs : HashSet<(SymbolKind,SymbolKind)>
 }
 
 impl InteractionMetrics {
 
     pub fn string_summary(&self) -> Vec<String> {
         let mut mystrings = vec![];
+        mystrings.push(format!("number of symbols   : {:}", self.get_num_symbols()));
         mystrings.push(format!("depth               : {:}", self.depth));
         mystrings.push(format!("loop depth          : {:}", self.max_nested_loop_depth));
+        for symbol in SymbolKind::iter() {
+            let as_static_str : &'static str = symbol.into();
+            mystrings.push(format!("  {:<18}: {:}", as_static_str, self.symbols.get(&symbol).unwrap()));
+        }
         mystrings.push(format!("number of lifelines : {:}", self.lifelines.len()));
+        mystrings.push(format!("number of messages  : {:}", self.messages.len()));
         let num_actions = self.symbols.get(&SymbolKind::Action).unwrap();
         mystrings.push(format!("number of actions   : {:}", num_actions));
+        mystrings.push(format!("loop-free           : {:}", self.is_loop_free()));
         mystrings
     }
 
@@ -77,6 +88,27 @@ impl InteractionMetrics {
         self.symbols.iter().fold(0_u32,|x,(_,c)|x + *c)
     }
 
+    pub fn is_loop_free(&self) -> bool {
+        self.symbols.get(&SymbolKind::LoopS).unwrap() + self.symbols.get(&SymbolKind::LoopOther).unwrap() == 0
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut symbols = serde_json::Map::new();
+        for symbol in SymbolKind::iter() {
+            let as_static_str : &'static str = symbol.into();
+            symbols.insert(as_static_str.to_string(), serde_json::Value::from(*self.symbols.get(&symbol).unwrap()));
+        }
+        serde_json::json!({
+            "num_symbols" : self.get_num_symbols(),
+            "depth" : self.depth,
+            "loop_depth" : self.max_nested_loop_depth,
+            "symbols" : symbols,
+            "num_lifelines" : self.lifelines.len(),
+            "num_messages" : self.messages.len(),
+            "loop_free" : self.is_loop_free()
+        })
+    }
+
     pub fn add_csv_line(&self, results : &mut String) {
         for (_,num_occ) in self.symbols.iter()
             .sorted_by(|(s1,_),(s2,_)| Ord::cmp(s1,s2)) {
@@ -99,19 +131,26 @@ impl InteractionMetrics {
             symbols:HashMap::new(),
             depth:0,
             max_nested_loop_depth:0,
-            lifelines:HashSet::new()
+            lifelines:HashSet::new(),
+            messages:HashSet::new(),
+            operator_pairs:HashSet::new()
         };
         for symbol_kind in SymbolKind::iter() {
             metrics.symbols.insert(symbol_kind,0);
         }
-        metrics.extract(i,0,0);
+        metrics.extract(i,None,0,0);
         metrics
     }
 
     fn extract(&mut self,
                i : &Interaction,
+               parent : Option<SymbolKind>,
                depth : u32,
                loop_depth : u32) {
+        let kind = i.get_symbol_kind();
+        if let Some(parent) = parent {
+            self.operator_pairs.insert((parent,kind));
+        }
         match i {
             Interaction::Empty => {
                 self.depth = self.depth.max(depth);
@@ -124,6 +163,7 @@ impl InteractionMetrics {
                 let count = self.symbols.get_mut(&SymbolKind::Action).unwrap();
                 *count += 1;
                 self.lifelines.insert(em.origin_lf_id);
+                self.messages.insert(em.ms_id);
                 for target in &em.targets {
                     match target {
                         EmissionTargetRef::Lifeline(lf) => {
@@ -137,44 +177,52 @@ impl InteractionMetrics {
                 self.max_nested_loop_depth = self.max_nested_loop_depth.max(loop_depth);
                 let count = self.symbols.get_mut(&SymbolKind::Action).unwrap();
                 *count += 1;
+                self.messages.insert(rc.ms_id);
                 for lf in &rc.recipients {
                     self.lifelines.insert(*lf);
                 }
             }, Interaction::Strict(ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::Strict).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::Seq(ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::Seq).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::CoReg(_, ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::CoReg).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::Par(ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::Par).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::Alt(ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::Alt).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::Sync(_, ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::Sync).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
             }, Interaction::And( ref i1, ref i2) => {
                 let count = self.symbols.get_mut(&SymbolKind::And).unwrap();
                 *count += 1;
-                self.extract(i1,depth + 1, loop_depth);
-                self.extract(i2,depth + 1, loop_depth);
+                self.extract(i1,Some(kind),depth + 1, loop_depth);
+                self.extract(i2,Some(kind),depth + 1, loop_depth);
+            }, Interaction::Not(act) => {
+                self.depth = self.depth.max(depth);
+                self.max_nested_loop_depth = self.max_nested_loop_depth.max(loop_depth);
+                let count = self.symbols.get_mut(&SymbolKind::Not).unwrap();
+                *count += 1;
+                self.lifelines.insert(act.lf_id);
+                self.messages.insert(act.ms_id);
             }, Interaction::Loop(lk, ref i1) => {
                 match *lk {
                     LoopKind::SStrictSeq => {
@@ -186,15 +234,85 @@ impl InteractionMetrics {
                         *count += 1;
                     }
                 }
-                self.extract(i1,depth + 1, loop_depth + 1);
+                self.extract(i1,Some(kind),depth + 1, loop_depth + 1);
             }
         }
     }
 }
 
 
+/// Occurrences of each symbol accumulated over a batch of interactions.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolCounts {
+    pub counts : HashMap<SymbolKind,u64>
+}
+
+impl SymbolCounts {
+
+    pub fn add_metrics(&mut self, metrics : &InteractionMetrics) {
+        for (symbol,num_occ) in &metrics.symbols {
+            *self.counts.entry(*symbol).or_insert(0) += *num_occ as u64;
+        }
+    }
+
+    /// Adds the symbol occurrences of the 'symbols' field of 'InteractionMetrics::to_json'.
+    pub fn add_json(&mut self, symbols : &serde_json::Value) {
+        for symbol in SymbolKind::iter() {
+            let as_static_str : &'static str = symbol.into();
+            if let Some(num_occ) = symbols.get(as_static_str).and_then(|x| x.as_u64()) {
+                *self.counts.entry(symbol).or_insert(0) += num_occ;
+            }
+        }
+    }
+
+    /// Same format as the 'symbols' field of 'InteractionMetrics::to_json'.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut symbols = serde_json::Map::new();
+        for symbol in SymbolKind::iter() {
+            let as_static_str : &'static str = symbol.into();
+            symbols.insert(as_static_str.to_string(), serde_json::Value::from(*self.counts.get(&symbol).unwrap_or(&0)));
+        }
+        serde_json::Value::Object(symbols)
+    }
+
+    /// Returns e.g. "Action: 120, Strict: 41, LoopS: 7", omitting symbols which never occur.
+    pub fn to_summary(&self) -> String {
+        SymbolKind::iter()
+            .filter_map(|symbol| {
+                let num_occ = *self.counts.get(&symbol).unwrap_or(&0);
+                if num_occ == 0 {
+                    None
+                } else {
+                    let as_static_str : &'static str = symbol.into();
+                    Some(format!("{:}: {:}", as_static_str, num_occ))
+                }
+            })
+            .join(", ")
+    }
+
+}
+
+
 impl Interaction {
 
+    /// Kind of the root symbol.
+    pub fn get_symbol_kind(&self) -> SymbolKind {
+        match self {
+            Interaction::Empty => SymbolKind::Empty,
+            Interaction::Emission(_) | Interaction::Reception(_) => SymbolKind::Action,
+            Interaction::Strict(_,_) => SymbolKind::Strict,
+            Interaction::Seq(_,_) => SymbolKind::Seq,
+            Interaction::CoReg(_,_,_) => SymbolKind::CoReg,
+            Interaction::Par(_,_) => SymbolKind::Par,
+            Interaction::Alt(_,_) => SymbolKind::Alt,
+            Interaction::Sync(_,_,_) => SymbolKind::Sync,
+            Interaction::And(_,_) => SymbolKind::And,
+            Interaction::Not(_) => SymbolKind::Not,
+            Interaction::Loop(LoopKind::SStrictSeq,_) => SymbolKind::LoopS,
+            Interaction::Loop(_,_) => SymbolKind::LoopOther
+        }
+    }
+
 
     pub fn max_nested_loop_depth(&self) -> u32 {
         match *self {
@@ -218,6 +336,8 @@ impl Interaction {
                 return 1 + i1.max_nested_loop_depth();
             }, Interaction::Sync(_, ref i1, ref i2) => {
                 return i1.max_nested_loop_depth().max(i2.max_nested_loop_depth());
+            }, Interaction::Not(_) => {
+                return 0;
             },
             _ => {
                 panic!("non-conform interaction");
@@ -247,6 +367,8 @@ impl Interaction {
                 return 1 + i1.total_loop_num();
             }, &Interaction::Sync(_, ref i1, ref i2) => {
                 return i1.total_loop_num() + i2.total_loop_num();
+            }, &Interaction::Not(_) => {
+                return 0;
             },
             _ => {
                 panic!("non-conform interaction");
@@ -254,5 +376,248 @@ impl Interaction {
         }
     }
 
+    /// Returns the largest horizontal fan-out found in the interaction term.
+    /// Given that binary operators are associative, a chain of the same operator
+    /// (e.g. par(par(i1,i2),i3)) is counted as a single node with as many children as
+    /// it has operands (here 3).
+    /// Loops count as nodes with a single child and leaves have a width of 0.
+    pub fn max_width(&self) -> u32 {
+        match self {
+            Interaction::Empty | Interaction::Emission(_) | Interaction::Reception(_) | Interaction::Not(_) => {
+                0
+            },
+            Interaction::Loop(_, i1) => {
+                1.max(i1.max_width())
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                let local_width = i1.operands_under(self) + i2.operands_under(self);
+                local_width.max(i1.max_width()).max(i2.max_width())
+            }
+        }
+    }
 
-}
\ No newline at end of file
+    /// Returns the minimal number of actions among the executions of the interaction.
+    pub fn min_num_actions(&self) -> u32 {
+        match self {
+            Interaction::Empty | Interaction::Loop(_, _) | Interaction::Not(_) => {
+                0
+            },
+            Interaction::Emission(_) | Interaction::Reception(_) => {
+                1
+            },
+            Interaction::Alt(i1, i2) => {
+                i1.min_num_actions().min(i2.min_num_actions())
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.min_num_actions() + i2.min_num_actions()
+            }
+        }
+    }
+
+    /// Whether the interaction contains a loop whose body may be executed without any action
+    /// (e.g. loopS(∅) or loopS(alt(∅,a -- m ->|))), which repeats nothing more than the same loop
+    /// without the empty executions of its body.
+    pub fn has_vacuous_loop(&self) -> bool {
+        match self {
+            Interaction::Empty | Interaction::Emission(_) | Interaction::Reception(_) | Interaction::Not(_) => {
+                false
+            },
+            Interaction::Loop(_, i1) => {
+                i1.min_num_actions() == 0 || i1.has_vacuous_loop()
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.has_vacuous_loop() || i2.has_vacuous_loop()
+            }
+        }
+    }
+
+    /// Whether the set of traces of the interaction is finite, that is, whether no loop has
+    /// a body which contains actions.
+    /// This is exact if every sub-interaction contributes to some trace, which is the case
+    /// without 'sync', 'and' and 'not' operators. With them, a loop may never be executed
+    /// and the language may be finite even if this returns false.
+    pub fn has_finite_language(&self) -> bool {
+        match self {
+            Interaction::Empty | Interaction::Emission(_) | Interaction::Reception(_) | Interaction::Not(_) => {
+                true
+            },
+            Interaction::Loop(_, i1) => {
+                !i1.contains_actions()
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.has_finite_language() && i2.has_finite_language()
+            }
+        }
+    }
+
+    pub(crate) fn contains_actions(&self) -> bool {
+        match self {
+            Interaction::Empty | Interaction::Not(_) => {
+                false
+            },
+            Interaction::Emission(_) => {
+                true
+            },
+            Interaction::Reception(rc_act) => {
+                !rc_act.recipients.is_empty()
+            },
+            Interaction::Loop(_, i1) => {
+                i1.contains_actions()
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.contains_actions() || i2.contains_actions()
+            }
+        }
+    }
+
+    /// Whether the interaction contains a 'not' (forbidden action).
+    pub fn contains_forbidden_actions(&self) -> bool {
+        match self {
+            Interaction::Empty | Interaction::Emission(_) | Interaction::Reception(_) => {
+                false
+            },
+            Interaction::Not(_) => {
+                true
+            },
+            Interaction::Loop(_, i1) => {
+                i1.contains_forbidden_actions()
+            },
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.contains_forbidden_actions() || i2.contains_forbidden_actions()
+            }
+        }
+    }
+
+    fn operands_under(&self, parent : &Interaction) -> u32 {
+        let same_operator = match (self,parent) {
+            (Interaction::CoReg(cr1,_,_), Interaction::CoReg(cr2,_,_)) => cr1 == cr2,
+            (Interaction::Sync(sc1,_,_), Interaction::Sync(sc2,_,_)) => sc1 == sc2,
+            (Interaction::Loop(_,_), _) => false,
+            (x,y) => std::mem::discriminant(x) == std::mem::discriminant(y)
+        };
+        if !same_operator {
+            return 1;
+        }
+        match self {
+            Interaction::Strict(i1, i2) |
+            Interaction::Seq(i1, i2) |
+            Interaction::CoReg(_, i1, i2) |
+            Interaction::Par(i1, i2) |
+            Interaction::Alt(i1, i2) |
+            Interaction::Sync(_, i1, i2) |
+            Interaction::And(i1, i2) => {
+                i1.operands_under(parent) + i2.operands_under(parent)
+            },
+            _ => {
+                1
+            }
+        }
+    }
+
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use super::*;
+
+    #[test]
+    fn max_width_test() {
+        let par_chain = Interaction::Par(
+            Box::new(Interaction::Par(Box::new(Interaction::Empty),Box::new(Interaction::Empty))),
+            Box::new(Interaction::Empty)
+        );
+        assert_eq!(par_chain.max_width(), 3);
+        let mixed = Interaction::Par(
+            Box::new(Interaction::Alt(Box::new(Interaction::Empty),Box::new(Interaction::Empty))),
+            Box::new(Interaction::Empty)
+        );
+        assert_eq!(mixed.max_width(), 2);
+        let looped = Interaction::Loop(LoopKind::SStrictSeq, Box::new(par_chain));
+        assert_eq!(looped.max_width(), 3);
+        assert_eq!(Interaction::Empty.max_width(), 0);
+    }
+
+    #[test]
+    fn extract_metrics_test() {
+        let a = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+        );
+        let b = Interaction::Emission(
+            EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let i = Interaction::Seq(
+            Box::new(a),
+            Box::new(Interaction::Loop(LoopKind::SStrictSeq, Box::new(b)))
+        );
+        let metrics = InteractionMetrics::extract_from_interaction(&i);
+        assert_eq!(metrics.get_num_symbols(), 4);
+        assert_eq!(metrics.depth, 2);
+        assert_eq!(metrics.lifelines.len(), 2);
+        assert_eq!(metrics.messages.len(), 1);
+        assert!(!metrics.is_loop_free());
+        assert_eq!(metrics.operator_pairs, HashSet::from([(SymbolKind::Seq,SymbolKind::Action),
+                                                          (SymbolKind::Seq,SymbolKind::LoopS),
+                                                          (SymbolKind::LoopS,SymbolKind::Action)]));
+        // ***
+        let empty = InteractionMetrics::extract_from_interaction(&Interaction::Empty);
+        assert_eq!(empty.depth, 0);
+        assert!(empty.lifelines.is_empty());
+        assert!(empty.messages.is_empty());
+        assert!(empty.operator_pairs.is_empty());
+        assert!(empty.is_loop_free());
+        assert_eq!(empty.to_json()["num_messages"], 0);
+    }
+
+    #[test]
+    fn symbol_counts_test() {
+        let a = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let looped = Interaction::Loop(LoopKind::SStrictSeq, Box::new(a.clone()));
+        let strict = Interaction::Strict(Box::new(a.clone()),Box::new(looped.clone()));
+        let mut counts = SymbolCounts::default();
+        counts.add_metrics(&InteractionMetrics::extract_from_interaction(&looped));
+        counts.add_json(&InteractionMetrics::extract_from_interaction(&strict).to_json()["symbols"]);
+        assert_eq!(counts.to_summary(), "Action: 3, Strict: 1, LoopS: 2");
+        assert_eq!(SymbolCounts::default().to_summary(), "");
+    }
+
+}
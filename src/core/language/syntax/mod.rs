@@ -20,5 +20,8 @@ pub mod action;
 pub mod interaction;
 pub mod util;
 pub mod metrics;
+pub mod builder;
+pub mod fold;
+pub mod skeleton;
 
 
@@ -0,0 +1,67 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+use crate::core::language::syntax::interaction::Interaction;
+
+
+impl Interaction {
+
+    /// Combinator and loop skeleton of the term : every action is replaced by the same placeholder
+    /// and the lifelines of 'coreg' and actions of 'sync' operators are dropped, so that interactions
+    /// differing only in their choice of messages and lifelines have identical skeletons.
+    /// Empty leaves are kept as they are part of the shape.
+    pub fn get_skeleton(&self) -> Interaction {
+        self.map(|x| match x {
+            Interaction::Emission(_) | Interaction::Reception(_) => {
+                Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]))
+            },
+            Interaction::CoReg(_,i1,i2) => Interaction::CoReg(vec![],i1,i2),
+            Interaction::Sync(_,i1,i2) => Interaction::Sync(vec![],i1,i2),
+            Interaction::Not(_) => Interaction::Not(TraceAction::new(0,TraceActionKind::Emission,0)),
+            other => other
+        })
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+
+    #[test]
+    fn skeleton_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let parse = |hif : &str| parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let i1 = parse("seq(alt(a -- m -> b, o), loopS(coreg(a,b)(b -- n ->|, m -> c)))");
+        let i2 = parse("seq(alt(c -- n -> (a,b), o), loopS(coreg(c)(m -> a, b -- n ->|)))");
+        assert_ne!(i1, i2);
+        assert_eq!(i1.get_skeleton(), i2.get_skeleton());
+        // the skeleton of a skeleton is itself
+        assert_eq!(i1.get_skeleton().get_skeleton(), i1.get_skeleton());
+        // the shape itself matters
+        assert_ne!(parse("seq(a -- m ->|, b -- m ->|)").get_skeleton(), parse("par(a -- m ->|, b -- m ->|)").get_skeleton());
+        assert_ne!(parse("seq(a -- m ->|, o)").get_skeleton(), parse("seq(a -- m ->|, b -- m ->|)").get_skeleton());
+        assert_ne!(parse("loopS(a -- m ->|)").get_skeleton(), parse("loopW(a -- m ->|)").get_skeleton());
+    }
+
+}
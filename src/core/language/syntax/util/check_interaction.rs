@@ -154,6 +154,9 @@ impl Interaction {
                 let mut charac = i1.get_characteristics().merge( &i2.get_characteristics() );
                 charac.has_sync = true;
                 return charac;
+            },
+            Interaction::Not(_) => {
+                return InteractionCharacteristics::new_empty();
             }
         }
     }
@@ -33,6 +33,8 @@ pub fn get_all_transformations_rec(transfos : &Vec<InteractionTransformationKind
             // ***
         }, &Interaction::Reception(_) => {
             // ***
+        }, &Interaction::Not(_) => {
+            // ***
         }, &Interaction::Strict(ref i1, ref i2) => {
             for left_transfo in get_all_transformations_rec(transfos,i1) {
                 results.push( InteractionTransformationResult::new(left_transfo.kind,
@@ -37,6 +37,8 @@ pub fn get_one_transformation_rec(transfos : &Vec<InteractionTransformationKind>
                     // ***
                 }, &Interaction::Reception(_) => {
                     // ***
+                }, &Interaction::Not(_) => {
+                    // ***
                 }, &Interaction::Strict(ref i1, ref i2) => {
                     match get_one_transformation_rec(transfos,i1) {
                         Some(left_transfo) => {
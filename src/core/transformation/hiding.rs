@@ -0,0 +1,82 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::eliminate_lf::eliminable::LifelineEliminable;
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::transformation::simplify::simplify_interaction;
+
+
+/// Projects an interaction onto the lifelines which are not hidden.
+/// Actions on hidden lifelines are removed while the operators keep ordering the remaining ones :
+/// a transmission whose emitter is hidden becomes a reception from the environment
+/// and one whose receivers are all hidden becomes an emission towards the environment.
+/// Ids which do not correspond to a lifeline of the signature are ignored.
+/// The resulting interaction is simplified (see 'simplify_interaction').
+pub fn hide(ist : &Interaction, hidden : &HashSet<usize>, gen_ctx : &GeneralContext) -> Interaction {
+    let lfs_to_remove : BTreeSet<usize> = hidden.iter()
+        .filter(|lf_id| **lf_id < gen_ctx.get_lf_num())
+        .cloned()
+        .collect();
+    let (projected,_) = simplify_interaction(&ist.eliminate_lifelines(&lfs_to_remove));
+    projected
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+    use super::*;
+
+    #[test]
+    fn hide_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("a".to_string());
+        gen_ctx.add_lf("b".to_string());
+        gen_ctx.add_lf("c".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let a_to_b = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+        );
+        let c_to_a = Interaction::Emission(
+            EmissionAction::new(2,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(0)])
+        );
+        // only the receiver is hidden
+        assert_eq!(hide(&a_to_b,&hashset!{1},&gen_ctx),
+                   Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])));
+        // only the emitter is hidden
+        assert_eq!(hide(&a_to_b,&hashset!{0},&gen_ctx),
+                   Interaction::Reception(ReceptionAction::new(None,0,CommunicationSynchronicity::Asynchronous,vec![1])));
+        // the ordering among surviving actions is preserved and emptied operands are removed
+        let i = Interaction::Strict(Box::new(a_to_b.clone()),Box::new(c_to_a.clone()));
+        assert_eq!(hide(&i,&hashset!{2},&gen_ctx),
+                   Interaction::Strict(
+                       Box::new(a_to_b.clone()),
+                       Box::new(Interaction::Reception(ReceptionAction::new(None,0,CommunicationSynchronicity::Asynchronous,vec![0])))
+                   ));
+        let alt = Interaction::Alt(Box::new(a_to_b.clone()),Box::new(Interaction::Empty));
+        assert_eq!(hide(&Interaction::Seq(Box::new(alt),Box::new(c_to_a)),&hashset!{0,1},&gen_ctx),
+                   Interaction::Emission(EmissionAction::new(2,0,CommunicationSynchronicity::Asynchronous,vec![])));
+        // unknown lifelines are ignored
+        assert_eq!(hide(&a_to_b,&hashset!{5},&gen_ctx), a_to_b);
+    }
+
+}
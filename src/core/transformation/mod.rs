@@ -21,4 +21,6 @@ pub mod transfores;
 mod get_transfos;
 
 pub mod transfophase;
-
+pub mod simplify;
+pub mod hiding;
+pub mod unrolling;
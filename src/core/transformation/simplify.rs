@@ -0,0 +1,80 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::transformation::get_transfos::get_one_transfo::get_one_transformation_rec;
+use crate::core::transformation::transfokind::InteractionTransformationKind;
+
+
+/// Semantics-preserving rewrite rules applied by 'simplify_interaction':
+/// - removal of 'empty' operands of strict, seq, par and coreg
+/// - flattening of nested strict, seq, par, coreg and alt (operands are flushed to the right)
+/// - loop(empty) = empty
+pub fn get_simplification_transformations() -> Vec<InteractionTransformationKind> {
+    vec![
+        InteractionTransformationKind::Simpl,
+        InteractionTransformationKind::FlushRight,
+        InteractionTransformationKind::LoopSimpl
+    ]
+}
+
+/// Rewrites the interaction until none of the simplification rules apply.
+/// Returns the simplified interaction and the number of rewrite steps that were applied.
+pub fn simplify_interaction(interaction : &Interaction) -> (Interaction,u32) {
+    let transfos = get_simplification_transformations();
+    let mut simplified = interaction.clone();
+    let mut num_steps = 0;
+    while let Some(got_transfo) = get_one_transformation_rec(&transfos,&simplified) {
+        simplified = got_transfo.result;
+        num_steps += 1;
+    }
+    (simplified,num_steps)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    #[test]
+    fn simplify_test() {
+        let a = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let b = Interaction::Emission(
+            EmissionAction::new(0,1,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        // seq(seq(a,loopS(empty)),par(empty,b))
+        let i = Interaction::Seq(
+            Box::new(Interaction::Seq(
+                Box::new(a.clone()),
+                Box::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(Interaction::Empty)))
+            )),
+            Box::new(Interaction::Par(Box::new(Interaction::Empty),Box::new(b.clone())))
+        );
+        let (simplified,num_steps) = simplify_interaction(&i);
+        assert_eq!(simplified, Interaction::Seq(Box::new(a),Box::new(b)));
+        assert!(num_steps > 0);
+        // already simplified
+        assert_eq!(simplify_interaction(&simplified), (simplified.clone(),0));
+    }
+
+}
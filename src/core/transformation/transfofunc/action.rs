@@ -28,7 +28,7 @@ pub fn transfo_sort_action_content(interaction : &Interaction) -> Vec<Interactio
             let mut new_targets = em_act.targets.clone();
             new_targets.sort();
             if new_targets != em_act.targets {
-                let new_emission = EmissionAction::new(em_act.origin_lf_id,em_act.ms_id,em_act.synchronicity.clone(),new_targets);
+                let new_emission = EmissionAction::new(em_act.origin_lf_id,em_act.ms_id,em_act.synchronicity.clone(),new_targets).with_param(em_act.ms_param).with_group(em_act.target_group);
                 return vec![Interaction::Emission(new_emission)];
             }
         },
@@ -36,7 +36,7 @@ pub fn transfo_sort_action_content(interaction : &Interaction) -> Vec<Interactio
             let mut new_targets = rc_act.recipients.clone();
             new_targets.sort();
             if new_targets != rc_act.recipients {
-                let new_reception = ReceptionAction::new(rc_act.origin_gt_id.clone(),rc_act.ms_id,rc_act.synchronicity.clone(),new_targets);
+                let new_reception = ReceptionAction::new(rc_act.origin_gt_id.clone(),rc_act.ms_id,rc_act.synchronicity.clone(),new_targets).with_param(rc_act.ms_param);
                 return vec![Interaction::Reception(new_reception)];
             }
         },
@@ -64,7 +64,7 @@ pub fn transfo_unfold_action(interaction : &Interaction) -> Vec<Interaction> {
                                         None,
                                         em_act.ms_id,
                                         CommunicationSynchronicity::Asynchronous,
-                                        vec![lf_id])
+                                        vec![lf_id]).with_param(em_act.ms_param)
                                 )
                             );
                         },
@@ -78,7 +78,7 @@ pub fn transfo_unfold_action(interaction : &Interaction) -> Vec<Interaction> {
                     return vec![];
                 }
                 let new_em = Interaction::Emission(
-                    EmissionAction::new(em_act.origin_lf_id,em_act.ms_id,CommunicationSynchronicity::Asynchronous,gates_targets)
+                    EmissionAction::new(em_act.origin_lf_id,em_act.ms_id,CommunicationSynchronicity::Asynchronous,gates_targets).with_param(em_act.ms_param)
                 );
                 let receptions = fold_recursive_par_frags(&mut targets_as_ints.iter().collect());
                 let new_int = Interaction::Strict(
@@ -107,7 +107,7 @@ pub fn transfo_unfold_action(interaction : &Interaction) -> Vec<Interaction> {
                                     rc_act.ms_id,
                                     CommunicationSynchronicity::Asynchronous,
                                     vec![*rec_lf_id]
-                                )
+                                ).with_param(rc_act.ms_param)
                             )
                         );
                     }
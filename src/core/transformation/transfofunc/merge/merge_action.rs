@@ -41,7 +41,7 @@ pub fn transfo_merge_action(interaction : &Interaction) -> Option<Interaction> {
 
 fn merge_action_inner(emission : &EmissionAction,
                       reception : &ReceptionAction) -> Option<Interaction> {
-    if emission.ms_id != reception.ms_id {
+    if emission.ms_id != reception.ms_id || emission.ms_param != reception.ms_param {
         return None;
     }
     // ***
@@ -75,7 +75,7 @@ fn merge_action_inner(emission : &EmissionAction,
             }
             // ***
             if is_match {
-                let new_action = EmissionAction::new(emission.origin_lf_id,emission.ms_id,emission.synchronicity.clone(),targets);
+                let new_action = EmissionAction::new(emission.origin_lf_id,emission.ms_id,emission.synchronicity.clone(),targets).with_param(emission.ms_param);
                 return Some(Interaction::Emission(new_action));
             } else {
                 return None;
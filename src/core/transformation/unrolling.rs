@@ -0,0 +1,90 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+use crate::core::language::syntax::util::fold_recursive_frags::fold_recursive_alt_frags;
+
+
+/// Replaces every loop by the alternative between at most 'bound' repetitions of its (unrolled) body,
+/// i.e. 'alt(∅, i, strict(i,i), ...)' for 'loopS(i)'.
+/// Repetitions are composed with 'strict' for 'loopS', with 'seq' for 'loopW' and 'loopH' and with 'par' for 'loopP'.
+/// The resulting interaction has no loop and accepts the traces of the original one
+/// in which no loop instance is repeated more than 'bound' times.
+pub fn unroll_loops(interaction : &Interaction, bound : u32) -> Interaction {
+    // the rewriting is bottom-up so that the body of each loop is already unrolled
+    interaction.map(|i| match i {
+        Interaction::Loop(lk,body) => {
+            let repeat = |i_prev : Interaction| -> Interaction {
+                match lk {
+                    LoopKind::SStrictSeq => Interaction::Strict(body.clone(),Box::new(i_prev)),
+                    LoopKind::HHeadFirstWS | LoopKind::WWeakSeq => Interaction::Seq(body.clone(),Box::new(i_prev)),
+                    LoopKind::PInterleaving => Interaction::Par(body.clone(),Box::new(i_prev))
+                }
+            };
+            let mut repetitions = vec![Interaction::Empty];
+            if bound > 0 {
+                repetitions.push(*body.clone());
+            }
+            for _ in 1..bound {
+                let next = repeat(repetitions.last().unwrap().clone());
+                repetitions.push(next);
+            }
+            fold_recursive_alt_frags(&mut repetitions.iter().collect())
+        },
+        other => other
+    })
+}
+
+/// Number of copies of the innermost loop bodies in the unrolling of 'nesting' nested loops,
+/// each loop giving rise to 1 + 2 + ... + 'bound' copies of its body.
+pub fn get_unrolled_copies_num(bound : u32, nesting : u32) -> f64 {
+    let per_loop = (bound as f64) * (bound as f64 + 1.0) / 2.0;
+    per_loop.powi(nesting as i32)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::builder::InteractionBuilder;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::count::{count_nfa_traces, TraceLanguageCardinality};
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn unroll_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let builder = InteractionBuilder::new(&gen_ctx);
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let count = |i : &Interaction| count_nfa_traces(&get_nfa_from_interaction_exploration(&gen_ctx,i,alphabet.clone()).0);
+        let a = builder.emit("a","m").unwrap();
+        let looped = builder.loop_s(a.clone());
+        assert_eq!(unroll_loops(&looped,2),
+                   builder.alt(vec![builder.empty(), a.clone(), builder.strict(a.clone(),a.clone())]));
+        assert_eq!(unroll_loops(&looped,0), Interaction::Empty);
+        // nested loops are unrolled recursively
+        let nested = builder.seq(builder.loop_s(looped.clone()),builder.loop_p(builder.emit("b","n").unwrap()));
+        let unrolled = unroll_loops(&nested,2);
+        assert_eq!(unrolled.total_loop_num(), 0);
+        // interleavings of up to 4 'a!m' with up to 2 'b!n' (on distinct lifelines) : sum of C(i+j,i) for i<=4 and j<=2
+        assert_eq!(count(&unrolled), TraceLanguageCardinality::Finite(55));
+        assert_eq!(get_unrolled_copies_num(2,3), 27.0);
+    }
+
+}
@@ -0,0 +1,123 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::core::execution::semantics::execute::execute_interaction;
+use crate::core::execution::semantics::frontier::global_frontier;
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// A node of the execution tree together with the step which leads to it from its parent
+/// (there is neither a parent nor an action for the root).
+pub struct ExecutionTreeStep {
+    pub parent_id : Option<usize>,
+    pub action : Option<BTreeSet<TraceAction>>,
+    pub child_id : usize,
+    /// residual interaction after the execution of 'action'
+    pub interaction : Interaction,
+    /// whether the residual interaction accepts the empty trace
+    pub is_accepting : bool
+}
+
+pub struct ExecutionTree {
+    /// steps in breadth first order, the root coming first
+    pub steps : Vec<ExecutionTreeStep>,
+    /// whether some node was not expanded because of the bounds
+    pub truncated : bool
+}
+
+/// Unfolds the operational semantics of the interaction from its root : each node is expanded
+/// by executing each of the actions of its frontier.
+/// Identical residual interactions reached via distinct executions are distinct nodes.
+/// Nodes at depth 'max_depth' are not expanded and the exploration stops once 'max_nodes' nodes are created.
+pub fn explore_execution_tree(interaction : &Interaction,
+                              max_depth : u32,
+                              max_nodes : usize) -> ExecutionTree {
+    let mut steps = vec![ExecutionTreeStep{
+        parent_id : None,
+        action : None,
+        child_id : 0,
+        interaction : interaction.clone(),
+        is_accepting : interaction.express_empty()
+    }];
+    let mut truncated = false;
+    let mut queue : VecDeque<(usize,u32)> = VecDeque::new();
+    queue.push_back((0,0));
+    while let Some((node_id,depth)) = queue.pop_front() {
+        let frontier = global_frontier(&steps[node_id].interaction,true);
+        if frontier.is_empty() {
+            continue;
+        }
+        if depth >= max_depth || steps.len() >= max_nodes {
+            truncated = true;
+            continue;
+        }
+        for frt_elt in frontier {
+            if steps.len() >= max_nodes {
+                truncated = true;
+                break;
+            }
+            let child = execute_interaction(&steps[node_id].interaction,
+                                            &frt_elt.position,
+                                            &frt_elt.target_lf_ids,
+                                            false).interaction;
+            let child_id = steps.len();
+            steps.push(ExecutionTreeStep{
+                parent_id : Some(node_id),
+                action : Some(frt_elt.target_actions),
+                child_id,
+                is_accepting : child.express_empty(),
+                interaction : child
+            });
+            queue.push_back((child_id,depth+1));
+        }
+    }
+    ExecutionTree{steps,truncated}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    #[test]
+    fn execution_tree_test() {
+        let a = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b = Interaction::Emission(EmissionAction::new(1,1,CommunicationSynchronicity::Asynchronous,vec![]));
+        // both interleavings of a and b
+        let tree = explore_execution_tree(&Interaction::Par(Box::new(a.clone()),Box::new(b.clone())),10,100);
+        assert!(!tree.truncated);
+        assert_eq!(tree.steps.len(), 5);
+        assert!(!tree.steps[0].is_accepting);
+        assert_eq!(tree.steps.iter().filter(|step| step.is_accepting).count(), 2);
+        assert!(tree.steps.iter().skip(1).all(|step| step.parent_id.unwrap() < step.child_id));
+        // a loop is unfolded up to the bounds
+        let looping = Interaction::Loop(LoopKind::SStrictSeq,Box::new(a));
+        let tree = explore_execution_tree(&looping,3,100);
+        assert!(tree.truncated);
+        assert_eq!(tree.steps.len(), 4);
+        let tree = explore_execution_tree(&looping,10,2);
+        assert!(tree.truncated);
+        assert_eq!(tree.steps.len(), 2);
+    }
+
+}
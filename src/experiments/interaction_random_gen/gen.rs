@@ -21,51 +21,156 @@ use rand::prelude::{SliceRandom, StdRng};
 use rand::Rng;
 
 use crate::core::general_context::GeneralContext;
+use crate::core::language::hash::canonical::HashedInteraction;
 use crate::core::language::involve::involves::InvolvesLifelines;
 use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
 use crate::core::language::syntax::interaction::{Interaction, LoopKind};
 use crate::core::language::syntax::metrics::{InteractionMetrics, SymbolKind};
 use crate::experiments::interaction_random_gen::probas::{InteractionGenerationSymbol, InteractionSymbolsProbabilities};
+use crate::experiments::interaction_random_gen::weights::GenerationWeights;
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::experiments2::NfaMetrics;
 use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
 
 
-pub fn generate_random_action(signature : &GeneralContext,rng : &mut StdRng) -> Interaction {
-    let ms_id = rng.gen_range(0..signature.get_ms_num());
-    let mut lifelines : Vec<usize> = (0..signature.get_lf_num()).collect();
-    lifelines.shuffle(rng);
-    if rng.gen_bool(0.5) {
-        let emission = EmissionAction::new(
-            *lifelines.get(0).unwrap(),
+/// Generates an emission if 'emission' is Some(true), a reception if it is Some(false)
+/// and either with equal probability if it is None.
+/// With probability 'weights.handshake_bias', an emission is directly followed (under strict)
+/// by the reception of its message on another lifeline so that it does not remain unmatched.
+pub fn generate_random_action<R : Rng>(signature : &GeneralContext,
+                              lifelines : &[usize],
+                              emission : Option<bool>,
+                              weights : &GenerationWeights,
+                              rng : &mut R) -> Interaction {
+    let ms_id = weights.messages.sample_message(signature,rng);
+    let lf_id = if weights.lifelines.is_uniform() {
+        let mut lifelines : Vec<usize> = lifelines.to_vec();
+        lifelines.shuffle(rng);
+        *lifelines.get(0).unwrap()
+    } else {
+        weights.lifelines.sample_lifeline(lifelines,rng)
+    };
+    if emission.unwrap_or_else(|| rng.gen_bool(0.5)) {
+        let emission = Interaction::Emission(EmissionAction::new(
+            lf_id,
             ms_id,
             CommunicationSynchronicity::Asynchronous,vec![]
-        );
-        Interaction::Emission(emission)
+        ));
+        // the bias is only drawn if it is set so that the generation from a given seed is unchanged otherwise
+        if weights.handshake_bias > 0.0 && lifelines.len() > 1 && rng.gen_bool(weights.handshake_bias) {
+            let others : Vec<usize> = lifelines.iter()
+                .filter(|x| **x != lf_id && signature.is_transmission_allowed(ms_id,lf_id,**x))
+                .cloned().collect();
+            if others.is_empty() {
+                return emission;
+            }
+            let reception = ReceptionAction::new(
+                None,
+                ms_id,
+                CommunicationSynchronicity::Asynchronous,
+                vec![weights.lifelines.sample_lifeline(&others,rng)]
+            );
+            Interaction::Strict(Box::new(emission),Box::new(Interaction::Reception(reception)))
+        } else {
+            emission
+        }
     } else {
         let reception = ReceptionAction::new(
             None,
             ms_id,
             CommunicationSynchronicity::Asynchronous,
-            vec![*lifelines.get(0).unwrap()]
+            vec![lf_id]
         );
         Interaction::Reception(reception)
     }
 }
 
-pub fn generate_random_pattern(signature : &GeneralContext,
+/// Returns None if there are not enough lifelines to involve a target lifeline
+/// distinct from the emitting one.
+/// If directions are declared for the drawn message, it is only transmitted in these directions
+/// (and None is returned if none of them is between the lifelines which may be involved).
+/// Otherwise, if the signature declares multicast groups, broadcasts target one of them half of the time
+/// (among the groups whose lifelines may be involved and which leave a lifeline to emit the message).
+pub fn generate_random_pattern<R : Rng>(signature : &GeneralContext,
+                               lifelines : &[usize],
                                is_broadcast : bool,
-                               rng : &mut StdRng) -> Interaction {
-    let ms_id = rng.gen_range(0..signature.get_ms_num());
-    let mut lifelines : Vec<usize> = (0..signature.get_lf_num()).collect();
-    lifelines.shuffle(rng);
-    let orig_lf_id = lifelines.pop().unwrap();
-    let targets : Vec<EmissionTargetRef> = if is_broadcast {
-        let number_of_targets = rng.gen_range(0..lifelines.len());
-        lifelines[0..number_of_targets].iter()
-            .map(|x| EmissionTargetRef::Lifeline(*x)).collect()
+                               weights : &GenerationWeights,
+                               rng : &mut R) -> Option<Interaction> {
+    if lifelines.len() < 2 {
+        return None;
+    }
+    let ms_id = weights.messages.sample_message(signature,rng);
+    if !signature.get_ms_directions(ms_id).is_empty() {
+        let directions : Vec<&(usize,usize)> = signature.get_ms_directions(ms_id).iter()
+            .filter(|(orig_lf_id,tar_lf_id)| lifelines.contains(orig_lf_id) && lifelines.contains(tar_lf_id))
+            .collect();
+        if directions.is_empty() {
+            return None;
+        }
+        let mut origins : Vec<usize> = directions.iter().map(|(orig_lf_id,_)| *orig_lf_id).collect();
+        origins.sort();
+        origins.dedup();
+        let orig_lf_id = weights.lifelines.sample_lifeline(&origins,rng);
+        let allowed_targets : Vec<usize> = directions.iter()
+            .filter(|(x,_)| *x == orig_lf_id)
+            .map(|(_,tar_lf_id)| *tar_lf_id).collect();
+        let number_of_targets = if is_broadcast {
+            rng.gen_range(1..=allowed_targets.len())
+        } else {
+            1
+        };
+        let targets : Vec<EmissionTargetRef> = weights.lifelines.sample_distinct_lifelines(&allowed_targets,number_of_targets,rng)
+            .into_iter().map(EmissionTargetRef::Lifeline).collect();
+        let emission = EmissionAction::new(
+            orig_lf_id,
+            ms_id,
+            CommunicationSynchronicity::Asynchronous,
+            targets
+        );
+        return Some(Interaction::Emission(emission));
+    }
+    if is_broadcast {
+        let groups : Vec<usize> = (0..signature.get_group_num())
+            .filter(|group_id| {
+                let group_lfs = signature.get_group_lifelines(*group_id).unwrap();
+                group_lfs.iter().all(|lf_id| lifelines.contains(lf_id)) && group_lfs.len() < lifelines.len()
+            }).collect();
+        if !groups.is_empty() && rng.gen_bool(0.5) {
+            let group_id = *groups.choose(rng).unwrap();
+            let group_lfs = signature.get_group_lifelines(group_id).unwrap();
+            let origins : Vec<usize> = lifelines.iter().filter(|lf_id| !group_lfs.contains(lf_id)).cloned().collect();
+            let emission = EmissionAction::new(
+                weights.lifelines.sample_lifeline(&origins,rng),
+                ms_id,
+                CommunicationSynchronicity::Asynchronous,
+                group_lfs.iter().map(|lf_id| EmissionTargetRef::Lifeline(*lf_id)).collect()
+            ).with_group(Some(group_id));
+            return Some(Interaction::Emission(emission));
+        }
+    }
+    let (orig_lf_id,targets) = if weights.lifelines.is_uniform() {
+        let mut lifelines : Vec<usize> = lifelines.to_vec();
+        lifelines.shuffle(rng);
+        let orig_lf_id = lifelines.pop().unwrap();
+        let targets : Vec<EmissionTargetRef> = if is_broadcast {
+            let number_of_targets = rng.gen_range(0..lifelines.len());
+            lifelines[0..number_of_targets].iter()
+                .map(|x| EmissionTargetRef::Lifeline(*x)).collect()
+        } else {
+            vec![EmissionTargetRef::Lifeline(lifelines.pop().unwrap())]
+        };
+        (orig_lf_id,targets)
     } else {
-        vec![EmissionTargetRef::Lifeline(lifelines.pop().unwrap())]
+        let orig_lf_id = weights.lifelines.sample_lifeline(lifelines,rng);
+        let others : Vec<usize> = lifelines.iter().filter(|lf_id| **lf_id != orig_lf_id).cloned().collect();
+        let number_of_targets = if is_broadcast {
+            rng.gen_range(0..others.len())
+        } else {
+            1
+        };
+        let targets : Vec<EmissionTargetRef> = weights.lifelines.sample_distinct_lifelines(&others,number_of_targets,rng)
+            .into_iter().map(EmissionTargetRef::Lifeline).collect();
+        (orig_lf_id,targets)
     };
     let emission = EmissionAction::new(
         orig_lf_id,
@@ -73,77 +178,170 @@ pub fn generate_random_pattern(signature : &GeneralContext,
         CommunicationSynchronicity::Asynchronous,
         targets
     );
-    Interaction::Emission(emission)
+    Some(Interaction::Emission(emission))
 }
 
-pub fn generate_random_interaction(probas : &InteractionSymbolsProbabilities,
+/// Generates either an emission towards a gate or a reception from a gate.
+/// Returns None if the signature declares no gate.
+pub fn generate_random_gate_action<R : Rng>(signature : &GeneralContext,
+                                   lifelines : &[usize],
+                                   weights : &GenerationWeights,
+                                   rng : &mut R) -> Option<Interaction> {
+    if signature.get_gt_num() == 0 {
+        return None;
+    }
+    let ms_id = weights.messages.sample_message(signature,rng);
+    let gt_id = rng.gen_range(0..signature.get_gt_num());
+    let lf_id = weights.lifelines.sample_lifeline(lifelines,rng);
+    if rng.gen_bool(0.5) {
+        let emission = EmissionAction::new(
+            lf_id,
+            ms_id,
+            CommunicationSynchronicity::Asynchronous,
+            vec![EmissionTargetRef::Gate(gt_id)]
+        );
+        Some(Interaction::Emission(emission))
+    } else {
+        let reception = ReceptionAction::new(
+            Some(gt_id),
+            ms_id,
+            CommunicationSynchronicity::Asynchronous,
+            vec![lf_id]
+        );
+        Some(Interaction::Reception(reception))
+    }
+}
+
+pub fn generate_random_interaction<R : Rng>(probas : &InteractionSymbolsProbabilities,
                                    //resolve_basic_at_low_depth : bool,
                                    depth : u32,
                                    max_depth : u32,
                                    signature : &GeneralContext,
-                                   rng : &mut StdRng) -> Interaction {
+                                   lifelines : &[usize],
+                                   weights : &GenerationWeights,
+                                   rng : &mut R) -> Interaction {
+    generate_random_interaction_with_basic_blocks(probas,depth,max_depth,signature,lifelines,weights,rng,&mut vec![])
+}
+
+/// Same as 'generate_random_interaction' but also pushes into 'basic_blocks' the sub-interactions drawn
+/// for the 'basic' symbol (see 'InteractionSymbolsProbabilities::describe_basic'), which are otherwise
+/// undistinguishable from the rest of the term.
+pub fn generate_random_interaction_with_basic_blocks<R : Rng>(probas : &InteractionSymbolsProbabilities,
+                                   depth : u32,
+                                   max_depth : u32,
+                                   signature : &GeneralContext,
+                                   lifelines : &[usize],
+                                   weights : &GenerationWeights,
+                                   rng : &mut R,
+                                   basic_blocks : &mut Vec<Interaction>) -> Interaction {
+    generate_random_hashed_interaction(probas,depth,max_depth,signature,lifelines,weights,rng,basic_blocks).into_interaction()
+}
+
+/// Same as 'generate_random_interaction_with_basic_blocks' but the interaction is built bottom-up together
+/// with its canonical hash (see 'HashedInteraction::new_binary'), which is then obtained without walking the term again.
+pub fn generate_random_hashed_interaction<R : Rng>(probas : &InteractionSymbolsProbabilities,
+                                   depth : u32,
+                                   max_depth : u32,
+                                   signature : &GeneralContext,
+                                   lifelines : &[usize],
+                                   weights : &GenerationWeights,
+                                   rng : &mut R,
+                                   basic_blocks : &mut Vec<Interaction>) -> HashedInteraction {
     if depth >= max_depth {
-        return generate_random_action(signature,rng);
+        return HashedInteraction::new(generate_random_action(signature,lifelines,None,weights,rng));
     }
     let mut symbol = probas.get_random_symbol(rng);
     match symbol {
-        InteractionGenerationSymbol::Transmission => {
-            generate_random_pattern(signature,false,rng)
+        InteractionGenerationSymbol::Transmission | InteractionGenerationSymbol::Broadcast => {
+            let is_broadcast = symbol == InteractionGenerationSymbol::Broadcast;
+            match generate_random_pattern(signature,lifelines,is_broadcast,weights,rng) {
+                Some(pattern) => {
+                    HashedInteraction::new(pattern)
+                },
+                None => {
+                    // not enough lifelines for a transmission, draw another symbol instead
+                    generate_random_hashed_interaction(probas,depth,max_depth,signature,lifelines,weights,rng,basic_blocks)
+                }
+            }
         },
-        InteractionGenerationSymbol::Broadcast => {
-            generate_random_pattern(signature,true,rng)
+        InteractionGenerationSymbol::Gate => {
+            match generate_random_gate_action(signature,lifelines,weights,rng) {
+                Some(action) => {
+                    HashedInteraction::new(action)
+                },
+                None => {
+                    // no gate declared, draw another symbol instead
+                    generate_random_hashed_interaction(probas,depth,max_depth,signature,lifelines,weights,rng,basic_blocks)
+                }
+            }
         },
         InteractionGenerationSymbol::Basic => {
-            generate_random_interaction(
+            let block = generate_random_hashed_interaction(
                 &InteractionSymbolsProbabilities::default_basic(),
                 depth,
                 max_depth,
                 signature,
-                rng
-            )
+                lifelines,
+                weights,
+                rng,
+                &mut vec![]
+            );
+            basic_blocks.push(block.get_interaction().clone());
+            block
         },
         InteractionGenerationSymbol::Empty => {
-            Interaction::Empty
+            HashedInteraction::new(Interaction::Empty)
         },
         InteractionGenerationSymbol::Action => {
-            generate_random_action(signature,rng)
+            HashedInteraction::new(generate_random_action(signature,lifelines,None,weights,rng))
+        },
+        InteractionGenerationSymbol::Emission => {
+            HashedInteraction::new(generate_random_action(signature,lifelines,Some(true),weights,rng))
+        },
+        InteractionGenerationSymbol::Reception => {
+            HashedInteraction::new(generate_random_action(signature,lifelines,Some(false),weights,rng))
         },
         InteractionGenerationSymbol::LoopS => {
-            let i1 = generate_random_interaction(probas,depth+1,max_depth,signature,rng);
-            Interaction::Loop(LoopKind::SStrictSeq,Box::new(i1))
+            let i1 = generate_random_hashed_interaction(probas,depth+1,max_depth,signature,lifelines,weights,rng,basic_blocks);
+            HashedInteraction::new_loop(LoopKind::SStrictSeq,i1)
         },
         InteractionGenerationSymbol::LoopW => {
-            let i1 = generate_random_interaction(probas,depth+1,max_depth,signature,rng);
-            Interaction::Loop(LoopKind::WWeakSeq,Box::new(i1))
+            let i1 = generate_random_hashed_interaction(probas,depth+1,max_depth,signature,lifelines,weights,rng,basic_blocks);
+            HashedInteraction::new_loop(LoopKind::WWeakSeq,i1)
         },
         InteractionGenerationSymbol::LoopP => {
-            let i1 = generate_random_interaction(probas,depth+1,max_depth,signature,rng);
-            Interaction::Loop(LoopKind::PInterleaving,Box::new(i1))
+            let i1 = generate_random_hashed_interaction(probas,depth+1,max_depth,signature,lifelines,weights,rng,basic_blocks);
+            HashedInteraction::new_loop(LoopKind::PInterleaving,i1)
         },
         x => {
-            let i1 = Box::new(generate_random_interaction(probas,depth+1,max_depth,signature,rng));
-            let i2 = Box::new(generate_random_interaction(probas,depth+1,max_depth,signature,rng));
+            let i1 = generate_random_hashed_interaction(probas,depth+1,max_depth,signature,lifelines,weights,rng,basic_blocks);
+            let i2 = generate_random_hashed_interaction(probas,depth+1,max_depth,signature,lifelines,weights,rng,basic_blocks);
             match x {
                 InteractionGenerationSymbol::Strict => {
-                    Interaction::Strict(i1,i2)
+                    HashedInteraction::new_binary(Interaction::Strict,i1,i2)
                 },
                 InteractionGenerationSymbol::Seq => {
-                    Interaction::Seq(i1,i2)
+                    HashedInteraction::new_binary(Interaction::Seq,i1,i2)
                 },
                 InteractionGenerationSymbol::Par => {
-                    Interaction::Par(i1,i2)
+                    HashedInteraction::new_binary(Interaction::Par,i1,i2)
                 },
                 InteractionGenerationSymbol::Alt => {
-                    Interaction::Alt(i1,i2)
+                    HashedInteraction::new_binary(Interaction::Alt,i1,i2)
                 },
                 InteractionGenerationSymbol::Coreg => {
-                    let involved_in_both : Vec<usize> = i1.involved_lifelines().intersection(&i2.involved_lifelines())
+                    let involved_in_both : Vec<usize> = i1.get_interaction().involved_lifelines().intersection(&i2.get_interaction().involved_lifelines())
                         .into_iter().cloned().collect();
                     if involved_in_both.is_empty() {
-                        Interaction::Seq(i1,i2)
+                        HashedInteraction::new_binary(Interaction::Seq,i1,i2)
                     } else {
-                        let lf_id = involved_in_both.get(rng.gen_range(0..involved_in_both.len())).unwrap();
-                        Interaction::CoReg(vec![*lf_id],i1,i2)
+                        // a random non-empty subset of the shared lifelines is in the co-region
+                        let lf_id = *involved_in_both.get(rng.gen_range(0..involved_in_both.len())).unwrap();
+                        let mut cr : Vec<usize> = involved_in_both.into_iter()
+                            .filter(|x| *x == lf_id || rng.gen_bool(0.5))
+                            .collect();
+                        cr.sort();
+                        HashedInteraction::new_binary(|x,y| Interaction::CoReg(cr,x,y),i1,i2)
                     }
                 },
                 _ => {
@@ -158,6 +356,7 @@ pub fn generate_random_interaction(probas : &InteractionSymbolsProbabilities,
 #[cfg(test)]
 mod tests {
     use rand::SeedableRng;
+    use crate::core::language::hash::canonical::canonical_hash;
     use super::*;
 
     #[test]
@@ -170,10 +369,209 @@ mod tests {
 
         let mut rng = StdRng::seed_from_u64(0);
         let probas = InteractionSymbolsProbabilities::default_regular();
-        let int = generate_random_interaction(&probas,0,3,&gen_ctx, &mut rng);
+        let int = generate_random_interaction(&probas,0,3,&gen_ctx,&[0,1],&GenerationWeights::default(), &mut rng);
         println!("{:?}", int);
     }
 
-}
 
+    #[test]
+    fn basic_blocks_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(hashmap!{
+            InteractionGenerationSymbol::Basic => 0.5,
+            InteractionGenerationSymbol::Par => 0.5
+        }).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let mut basic_blocks = vec![];
+            let int = generate_random_interaction_with_basic_blocks(&probas,0,4,&gen_ctx,&[0,1],&GenerationWeights::default(),&mut rng,&mut basic_blocks);
+            assert!(!basic_blocks.is_empty());
+            let metrics = InteractionMetrics::extract_from_interaction(&int);
+            let mut block_symbols : HashMap<SymbolKind,u32> = HashMap::new();
+            for block in &basic_blocks {
+                for (symbol,num_occ) in InteractionMetrics::extract_from_interaction(block).symbols {
+                    if num_occ > 0 {
+                        assert!(matches!(symbol, SymbolKind::Action | SymbolKind::Strict | SymbolKind::Seq));
+                    }
+                    *block_symbols.entry(symbol).or_insert(0) += num_occ;
+                }
+            }
+            // only 'basic' draws strict and seq while actions are also drawn once the depth cap is reached
+            assert_eq!(block_symbols[&SymbolKind::Strict], metrics.symbols[&SymbolKind::Strict]);
+            assert_eq!(block_symbols[&SymbolKind::Seq], metrics.symbols[&SymbolKind::Seq]);
+            assert!(block_symbols[&SymbolKind::Action] <= metrics.symbols[&SymbolKind::Action]);
+        }
+        // the blocks are drawn from the same random choices as without tracking them
+        let (mut rng1, mut rng2) = (StdRng::seed_from_u64(1), StdRng::seed_from_u64(1));
+        assert_eq!(generate_random_interaction(&probas,0,4,&gen_ctx,&[0,1],&GenerationWeights::default(),&mut rng1),
+                   generate_random_interaction_with_basic_blocks(&probas,0,4,&gen_ctx,&[0,1],&GenerationWeights::default(),&mut rng2,&mut vec![]));
+    }
 
+    #[test]
+    fn hashed_generation_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::default_non_regular();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let hashed = generate_random_hashed_interaction(&probas,0,5,&gen_ctx,&[0,1],&GenerationWeights::default(),&mut rng,&mut vec![]);
+            // the hash built bottom-up is the canonical hash of the whole term
+            assert_eq!(hashed.get_hash(), canonical_hash(hashed.get_interaction()));
+        }
+    }
+
+    #[test]
+    fn handshake_bias_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut weights = GenerationWeights::default();
+        weights.handshake_bias = 1.0;
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            match generate_random_action(&gen_ctx,&[0,1],Some(true),&weights,&mut rng) {
+                Interaction::Strict(i1,i2) => {
+                    match (*i1,*i2) {
+                        (Interaction::Emission(em_act),Interaction::Reception(rc_act)) => {
+                            assert_eq!(em_act.ms_id, rc_act.ms_id);
+                            assert_eq!(rc_act.recipients.len(), 1);
+                            assert_ne!(em_act.origin_lf_id, rc_act.recipients[0]);
+                        },
+                        _ => {
+                            panic!("expected an emission followed by a reception");
+                        }
+                    }
+                },
+                _ => {
+                    panic!("expected a handshake");
+                }
+            }
+        }
+        // no other lifeline to receive the message
+        assert!(matches!(generate_random_action(&gen_ctx,&[0],Some(true),&weights,&mut rng), Interaction::Emission(_)));
+    }
+
+    #[test]
+    fn directed_transmission_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_lf("l3".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_ms_direction(0,0,1);
+        gen_ctx.add_ms_direction(0,0,2);
+        let mut rng = StdRng::seed_from_u64(0);
+        for is_broadcast in [false,true] {
+            for _ in 0..20 {
+                match generate_random_pattern(&gen_ctx,&[0,1,2],is_broadcast,&GenerationWeights::default(),&mut rng) {
+                    Some(Interaction::Emission(em_act)) => {
+                        assert_eq!(em_act.origin_lf_id, 0);
+                        assert!(!em_act.targets.is_empty());
+                        assert!(!em_act.targets.contains(&EmissionTargetRef::Lifeline(0)));
+                    },
+                    _ => {
+                        panic!("expected a transmission");
+                    }
+                }
+            }
+        }
+        // no declared direction between the lifelines which may be involved
+        assert_eq!(generate_random_pattern(&gen_ctx,&[1,2],false,&GenerationWeights::default(),&mut rng), None);
+    }
+
+    #[test]
+    fn emission_reception_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            assert!(matches!(generate_random_action(&gen_ctx,&[0,1],Some(true),&GenerationWeights::default(),&mut rng), Interaction::Emission(_)));
+            assert!(matches!(generate_random_action(&gen_ctx,&[0,1],Some(false),&GenerationWeights::default(),&mut rng), Interaction::Reception(_)));
+        }
+        // ***
+        let probas = InteractionSymbolsProbabilities::custom(0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.8,0.2);
+        let mut num_emissions = 0;
+        for _ in 0..500 {
+            match generate_random_interaction(&probas,0,3,&gen_ctx,&[0,1],&GenerationWeights::default(),&mut rng) {
+                Interaction::Emission(_) => {
+                    num_emissions += 1;
+                },
+                Interaction::Reception(_) => {},
+                other => {
+                    panic!("expected a single action, got {:?}", other);
+                }
+            }
+        }
+        assert!((350..450).contains(&num_emissions));
+    }
+
+    #[test]
+    fn gate_action_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(generate_random_gate_action(&gen_ctx,&[0],&GenerationWeights::default(),&mut rng), None);
+        gen_ctx.add_gt("g1".to_string());
+        for _ in 0..10 {
+            match generate_random_gate_action(&gen_ctx,&[0],&GenerationWeights::default(),&mut rng) {
+                Some(Interaction::Emission(em_act)) => {
+                    assert_eq!(em_act.targets, vec![EmissionTargetRef::Gate(0)]);
+                },
+                Some(Interaction::Reception(rc_act)) => {
+                    assert_eq!(rc_act.origin_gt_id, Some(0));
+                },
+                _ => {
+                    panic!("expected a gate action");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn group_broadcast_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("c".to_string());
+        gen_ctx.add_lf("s1".to_string());
+        gen_ctx.add_lf("s2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_group("servers".to_string(),vec![2,1]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut got_group = false;
+        for _ in 0..20 {
+            match generate_random_pattern(&gen_ctx,&[0,1,2],true,&GenerationWeights::default(),&mut rng) {
+                Some(Interaction::Emission(em_act)) => {
+                    if em_act.target_group == Some(0) {
+                        got_group = true;
+                        assert_eq!(em_act.origin_lf_id, 0);
+                        assert_eq!(em_act.targets, vec![EmissionTargetRef::Lifeline(1),EmissionTargetRef::Lifeline(2)]);
+                    }
+                },
+                _ => {
+                    panic!("expected an emission");
+                }
+            }
+        }
+        assert!(got_group);
+        // the group cannot be targeted if one of its lifelines may not be involved
+        for _ in 0..20 {
+            match generate_random_pattern(&gen_ctx,&[0,1],true,&GenerationWeights::default(),&mut rng) {
+                Some(Interaction::Emission(em_act)) => {
+                    assert_eq!(em_act.target_group, None);
+                },
+                _ => {
+                    panic!("expected an emission");
+                }
+            }
+        }
+    }
+
+}
@@ -0,0 +1,121 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs;
+use std::path::Path;
+
+
+const SVG_BAR_WIDTH : u64 = 40;
+const SVG_MAX_BAR_HEIGHT : u64 = 200;
+const SVG_MARGIN : u64 = 40;
+
+
+/// Numbers of interactions per bucket of sizes (i.e. numbers of symbols).
+/// Buckets have the same width and cover all sizes from the smallest to the largest one.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SizeHistogram {
+    /// inclusive bounds of each bucket and number of interactions in it
+    pub buckets : Vec<(u64,u64,u64)>
+}
+
+impl SizeHistogram {
+
+    /// There may be fewer than 'num_buckets' buckets if the sizes span fewer values.
+    pub fn from_sizes(sizes : &[u64], num_buckets : u32) -> SizeHistogram {
+        if sizes.is_empty() {
+            return SizeHistogram{buckets:vec![]};
+        }
+        let min = *sizes.iter().min().unwrap();
+        let max = *sizes.iter().max().unwrap();
+        let span = max - min + 1;
+        let num_buckets = (num_buckets.max(1) as u64).min(span);
+        let width = (span + num_buckets - 1) / num_buckets;
+        let mut buckets : Vec<(u64,u64,u64)> = (0..num_buckets)
+            .map(|idx| (min + idx*width, (min + (idx+1)*width - 1).min(max), 0))
+            .filter(|(lower,_,_)| *lower <= max)
+            .collect();
+        for size in sizes {
+            let idx = ((size - min) / width) as usize;
+            buckets[idx].2 += 1;
+        }
+        SizeHistogram{buckets}
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "min_symbols,max_symbols,count\n".to_string();
+        for (lower,upper,count) in &self.buckets {
+            csv.push_str(&format!("{:},{:},{:}\n", lower, upper, count));
+        }
+        csv
+    }
+
+    /// Draws the histogram as a bar chart, each bar being labelled with its count and its bucket.
+    pub fn to_svg(&self) -> String {
+        let max_count = self.buckets.iter().map(|(_,_,count)| *count).max().unwrap_or(0).max(1);
+        let width = 2*SVG_MARGIN + SVG_BAR_WIDTH * self.buckets.len() as u64;
+        let height = 2*SVG_MARGIN + SVG_MAX_BAR_HEIGHT;
+        let base = SVG_MARGIN + SVG_MAX_BAR_HEIGHT;
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:}\" height=\"{:}\" font-family=\"sans-serif\" font-size=\"10\">\n", width, height);
+        for (idx,(lower,upper,count)) in self.buckets.iter().enumerate() {
+            let x = SVG_MARGIN + SVG_BAR_WIDTH * idx as u64;
+            let bar_height = SVG_MAX_BAR_HEIGHT * count / max_count;
+            let middle = x + SVG_BAR_WIDTH / 2;
+            let bucket_label = if lower == upper {
+                lower.to_string()
+            } else {
+                format!("{:}-{:}", lower, upper)
+            };
+            svg.push_str(&format!("<rect x=\"{:}\" y=\"{:}\" width=\"{:}\" height=\"{:}\" fill=\"steelblue\" stroke=\"white\"/>\n",
+                                  x, base - bar_height, SVG_BAR_WIDTH, bar_height));
+            svg.push_str(&format!("<text x=\"{:}\" y=\"{:}\" text-anchor=\"middle\">{}</text>\n",
+                                  middle, base - bar_height - 4, count));
+            svg.push_str(&format!("<text x=\"{:}\" y=\"{:}\" text-anchor=\"middle\">{}</text>\n",
+                                  middle, base + 14, bucket_label));
+        }
+        svg.push_str(&format!("<text x=\"{:}\" y=\"{:}\" text-anchor=\"middle\">number of symbols</text>\n", width / 2, base + 30));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes 'sizes.csv' and 'sizes.svg' in the folder.
+    pub fn write_files(&self, folder : &str) -> std::io::Result<()> {
+        fs::write(Path::new(folder).join("sizes.csv"), self.to_csv())?;
+        fs::write(Path::new(folder).join("sizes.svg"), self.to_svg())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_histogram_test() {
+        let histogram = SizeHistogram::from_sizes(&[3,4,4,7,12], 3);
+        assert_eq!(histogram.buckets, vec![(3,6,3),(7,10,1),(11,12,1)]);
+        assert_eq!(histogram.to_csv(), "min_symbols,max_symbols,count\n3,6,3\n7,10,1\n11,12,1\n");
+        // the last buckets are dropped when they would start after the largest size
+        let histogram = SizeHistogram::from_sizes(&[3,4,4,7,12], 6);
+        assert_eq!(histogram.buckets, vec![(3,4,3),(5,6,0),(7,8,1),(9,10,0),(11,12,1)]);
+        // never more buckets than distinct sizes
+        assert_eq!(SizeHistogram::from_sizes(&[5,5,6], 10).buckets, vec![(5,5,2),(6,6,1)]);
+        assert!(SizeHistogram::from_sizes(&[], 10).buckets.is_empty());
+        assert_eq!(histogram.to_svg().matches("<rect").count(), 5);
+    }
+
+}
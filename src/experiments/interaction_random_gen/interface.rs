@@ -16,17 +16,30 @@ limitations under the License.
 
 
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::time::Instant;
+use rand::Rng;
+use serde_json::json;
 use rand::rngs::StdRng;
 
 use crate::core::execution::trace::trace::TraceAction;
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::semantics::membership::{is_multi_trace_accepted, MembershipVerdict};
+use crate::core::execution::trace::multitrace::MultiTrace;
 use crate::core::general_context::GeneralContext;
+use crate::core::language::hash::canonical::HashedInteraction;
 use crate::core::language::syntax::interaction::Interaction;
-use crate::core::language::syntax::metrics::InteractionMetrics;
-use crate::experiments::interaction_random_gen::gen::generate_random_interaction;
+use crate::core::language::syntax::metrics::{InteractionMetrics, SymbolKind};
+use crate::experiments::interaction_random_gen::predicate::MetricPredicate;
+use crate::experiments::interaction_random_gen::gen::{generate_random_hashed_interaction, generate_random_interaction};
 use crate::experiments::interaction_random_gen::probas::InteractionSymbolsProbabilities;
+use crate::experiments::interaction_random_gen::weights::GenerationWeights;
+use crate::io::input::sexpr::interaction::parse_sexpr;
+use crate::io::output::to_hfiles::interaction::to_sexpr::interaction_to_sexpr;
 use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
+use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::canonize::canonize_interaction;
+use crate::nfa_translation::get_nfa_from_logger::{get_nfa_from_interaction_exploration_with_budget, TranslationError};
 use crate::nfa_translation::experiments2::NfaGenerationExperiment2ResultMetrics;
 use crate::process::canon::param::default::DefaultCanonizationProcess;
 
@@ -42,11 +55,13 @@ pub fn generate_canonical_random_interaction(gen_ctx : &GeneralContext,
                                         0,
                                         max_depth,
                                         &gen_ctx,
+                                        &(0..gen_ctx.get_lf_num()).collect::<Vec<usize>>(),
+                                        &GenerationWeights::default(),
                                         rng);
 
     let imetrics = InteractionMetrics::extract_from_interaction(&i);
     let isymbs = imetrics.symbols.iter().fold(0_u32,|x,(_,c)| x + c);
-    println!("generated interaction of depth {:} with {:} symbols",
+    trace!("generated interaction of depth {:} with {:} symbols",
              imetrics.depth,
              isymbs
     );
@@ -54,25 +69,27 @@ pub fn generate_canonical_random_interaction(gen_ctx : &GeneralContext,
     let ican = canonize_interaction(&gen_ctx,&i, DefaultCanonizationProcess::BasicWithToSeq);
     let icanmetrics = InteractionMetrics::extract_from_interaction(&ican);
     let icansymbs = icanmetrics.symbols.iter().fold(0_u32,|x,(_,c)| x + c);
-    println!("canonized to interaction of depth {:} with {:} symbols",
+    trace!("canonized to interaction of depth {:} with {:} symbols",
              icanmetrics.depth,
              icansymbs
     );
     if icansymbs < min_symbols {
-        println!("not enough symbols");
+        trace!("not enough symbols");
         return None;
     }
     if icansymbs > isymbs {
-        println!("canonized has more symbols !!");
+        warn!("canonized has more symbols !!");
         draw_interaction(&gen_ctx,
                          &i,
                          &InteractionGraphicalRepresentation::AsSequenceDiagram,
+                         &None,
                          &"temp".to_string(),
                          &"canerror".to_string(),
                          &"init".to_string());
         draw_interaction(&gen_ctx,
                          &ican,
                          &InteractionGraphicalRepresentation::AsSequenceDiagram,
+                         &None,
                          &"temp".to_string(),
                          &"canerror".to_string(),
                          &"canned".to_string());
@@ -82,26 +99,1190 @@ pub fn generate_canonical_random_interaction(gen_ctx : &GeneralContext,
 }
 
 
-pub fn generate_raw_random_interaction(gen_ctx : &GeneralContext,
-                                                         rng : &mut StdRng,
-                                                         max_depth : u32,
-                                                         min_symbols : u32,
-                                                         probas : &InteractionSymbolsProbabilities) -> Option<Interaction> {
+/// Distribution from which the target depth of each generated interaction is sampled.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DepthDistribution {
+    /// uniform between the two bounds (both included)
+    Uniform(u32,u32)
+}
 
-    let i = generate_random_interaction(probas,
-                                        0,
-                                        max_depth,
-                                        &gen_ctx,
-                                        rng);
+impl DepthDistribution {
 
-    let imetrics = InteractionMetrics::extract_from_interaction(&i);
+    /// Parses a specification of the form 'uniform:MIN:MAX'.
+    pub fn from_spec(spec : &str) -> Option<Self> {
+        let parts : Vec<&str> = spec.split(':').map(|x| x.trim()).collect();
+        match parts.as_slice() {
+            ["uniform", min, max] => {
+                let min = min.parse::<u32>().ok()?;
+                let max = max.parse::<u32>().ok()?;
+                if min > max {
+                    return None;
+                }
+                Some(DepthDistribution::Uniform(min,max))
+            },
+            _ => {
+                None
+            }
+        }
+    }
+
+    pub fn get_max_depth(&self) -> u32 {
+        match self {
+            DepthDistribution::Uniform(_,max) => *max
+        }
+    }
+
+    pub fn sample<R : Rng>(&self, rng : &mut R) -> u32 {
+        match self {
+            DepthDistribution::Uniform(min,max) => rng.gen_range(*min..=*max)
+        }
+    }
+
+}
+
+impl std::fmt::Display for DepthDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepthDistribution::Uniform(min,max) => {
+                write!(f,"uniform:{:}:{:}", min, max)
+            }
+        }
+    }
+}
+
+
+/// Why an interaction is rejected by 'generate_raw_random_interaction'.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InteractionRejection {
+    /// shallower than the minimal depth
+    TooShallow,
+    /// fewer symbols than the minimal number of symbols
+    TooFewSymbols,
+    /// more operands under a single operator than the maximal width
+    TooWide,
+    /// contains a loop whose body may be executed without any action
+    VacuousLoop,
+    /// has infinitely many traces
+    InfiniteLanguage
+}
+
+/// Draws an interaction according to the depth, size and shape constraints of 'params'
+/// (the scaffold, deduplication and the other checks being left to the caller).
+/// If a depth distribution is given, a target depth is sampled from it and interactions
+/// are generated with this target as depth cap until one reaches it exactly.
+/// If none does within 'num_tries' attempts, the deepest attempt is kept instead.
+/// If 'loop_requires_content' is set, interactions with a loop whose body may be executed
+/// without any action (see 'Interaction::has_vacuous_loop') are rejected.
+/// If 'finite_only' is set, interactions with infinitely many traces (see 'Interaction::has_finite_language')
+/// are rejected.
+/// 'basic_blocks' is replaced by the sub-interactions drawn for the 'basic' symbol in the returned interaction.
+pub fn generate_raw_random_interaction<R : Rng>(gen_ctx : &GeneralContext,
+                                                params : &GenerationParams,
+                                                rng : &mut R,
+                                                basic_blocks : &mut Vec<Interaction>) -> Result<HashedInteraction,InteractionRejection> {
+    let lifelines = get_generation_lifelines(gen_ctx,params);
+    basic_blocks.clear();
+    let (i,imetrics) = match &params.depth_dist {
+        None => {
+            let i = generate_random_hashed_interaction(&params.probas,
+                                                0,
+                                                params.max_depth,
+                                                &gen_ctx,
+                                                &lifelines,
+                                                &params.weights,
+                                                rng,
+                                                basic_blocks);
+            let imetrics = InteractionMetrics::extract_from_interaction(i.get_interaction());
+            (i,imetrics)
+        },
+        Some( dist ) => {
+            let target_depth = dist.sample(rng).min(params.max_depth);
+            let mut deepest : Option<(HashedInteraction,InteractionMetrics)> = None;
+            for _ in 0..=params.num_tries {
+                let mut attempt_blocks = vec![];
+                let i = generate_random_hashed_interaction(&params.probas,
+                                                    0,
+                                                    target_depth,
+                                                    &gen_ctx,
+                                                    &lifelines,
+                                                    &params.weights,
+                                                    rng,
+                                                    &mut attempt_blocks);
+                let imetrics = InteractionMetrics::extract_from_interaction(i.get_interaction());
+                let reached = imetrics.depth >= target_depth;
+                if deepest.as_ref().map_or(true, |(_,m)| imetrics.depth > m.depth) {
+                    deepest = Some((i,imetrics));
+                    *basic_blocks = attempt_blocks;
+                }
+                if reached {
+                    break;
+                }
+            }
+            let (i,imetrics) = deepest.unwrap();
+            if imetrics.depth < target_depth {
+                debug!("could not reach target depth {:}, falling back to depth {:}", target_depth, imetrics.depth);
+            }
+            (i,imetrics)
+        }
+    };
     let isymbs = imetrics.symbols.iter().fold(0_u32, |x, (_, c)| x + c);
-    println!("generated interaction of depth {:} with {:} symbols", imetrics.depth, isymbs);
+    trace!("generated interaction of depth {:} with {:} symbols", imetrics.depth, isymbs);
+
+    if imetrics.depth < params.min_depth {
+        trace!("not deep enough");
+        return Err(InteractionRejection::TooShallow);
+    }
     
-    if isymbs < min_symbols {
-        println!("not enough symbols");
-        return None;
+    if isymbs < params.min_symbols {
+        trace!("not enough symbols");
+        return Err(InteractionRejection::TooFewSymbols);
+    }
+
+    let iwidth = i.get_interaction().max_width();
+    if iwidth > params.max_width {
+        trace!("too wide ({:} operands under a single operator)", iwidth);
+        return Err(InteractionRejection::TooWide);
+    }
+
+    if params.loop_requires_content && i.get_interaction().has_vacuous_loop() {
+        trace!("contains a loop with a possibly empty body");
+        return Err(InteractionRejection::VacuousLoop);
+    }
+
+    if params.finite_only && !i.get_interaction().has_finite_language() {
+        trace!("has infinitely many traces");
+        return Err(InteractionRejection::InfiniteLanguage);
     }
     
-    Some(i)
+    Ok(i)
+}
+
+
+
+/// How generated interactions are compared when discarding duplicates.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InteractionDeduplicationKind {
+    /// two interactions are duplicates if their terms are identical
+    Structural,
+    /// two interactions are duplicates if their terms are identical after normalization
+    /// (see 'normalize_interaction')
+    Semantic
+}
+
+impl InteractionDeduplicationKind {
+
+    pub fn from_name(name : &str) -> Option<Self> {
+        match name {
+            "structural" => Some(InteractionDeduplicationKind::Structural),
+            "semantic" => Some(InteractionDeduplicationKind::Semantic),
+            _ => None
+        }
+    }
+
+    /// Returns the term under which the interaction is memoized.
+    pub fn get_key(&self, gen_ctx : &GeneralContext, i : &Interaction) -> Interaction {
+        match self {
+            InteractionDeduplicationKind::Structural => {
+                i.clone()
+            },
+            InteractionDeduplicationKind::Semantic => {
+                normalize_interaction(gen_ctx,i)
+            }
+        }
+    }
+
+    /// Same as 'get_key' but the hash of the interaction is kept (instead of being computed again) when it is its own key.
+    pub fn get_hashed_key(&self, gen_ctx : &GeneralContext, i : &HashedInteraction) -> HashedInteraction {
+        match self {
+            InteractionDeduplicationKind::Structural => {
+                i.clone()
+            },
+            InteractionDeduplicationKind::Semantic => {
+                HashedInteraction::new(normalize_interaction(gen_ctx,i.get_interaction()))
+            }
+        }
+    }
+
+}
+
+
+/// Memoizes the keys (see 'InteractionDeduplicationKind::get_key') of generated interactions
+/// to detect duplicates.
+///
+/// Keys are stored as 'HashedInteraction' so that each lookup costs a single comparison of
+/// cached hashes (and a structural comparison only if they are equal).
+/// Checkpoints (see 'to_json') save the keys in their S-expression encoding, which is parsed back exactly,
+/// so that restored keys are compared structurally as well.
+/// Checkpoints written before the keys were saved only hold their hashes : keys restored from them are compared
+/// by their 64 bits hashes only, so that a new interaction may be wrongly considered a duplicate of one of them
+/// if their hashes collide. For n such restored interactions, this happens with probability about n^2 / 2^65.
+///
+/// If a window N is set, only the last N memoized keys are kept (bounding memory usage),
+/// so that a duplicate of an older interaction is no longer detected.
+#[derive(Clone, Debug)]
+pub struct InteractionMemo {
+    memoized : HashSet<HashedInteraction>,
+    // hashes of the keys restored from a checkpoint which only holds their hashes
+    restored : HashSet<u64>,
+    window : Option<usize>,
+    // keys in insertion order, only maintained when there is a window
+    order : VecDeque<MemoEntry>
+}
+
+#[derive(Clone, Debug)]
+enum MemoEntry {
+    Memoized(HashedInteraction),
+    Restored(u64)
+}
+
+impl MemoEntry {
+    fn get_hash(&self) -> u64 {
+        match self {
+            MemoEntry::Memoized(key) => key.get_hash(),
+            MemoEntry::Restored(hash) => *hash
+        }
+    }
+}
+
+impl InteractionMemo {
+
+    pub fn new(window : Option<usize>) -> Self {
+        Self { memoized : HashSet::new(), restored : HashSet::new(), window, order : VecDeque::new() }
+    }
+
+    /// Returns false if the key was already memoized, otherwise memoizes it and returns true.
+    pub fn insert(&mut self, key : HashedInteraction) -> bool {
+        if self.restored.contains(&key.get_hash()) || self.memoized.contains(&key) {
+            return false;
+        }
+        match self.window {
+            None => {
+                self.memoized.insert(key);
+            },
+            Some(window) => {
+                self.memoized.insert(key.clone());
+                self.order.push_back(MemoEntry::Memoized(key));
+                while self.order.len() > window {
+                    match self.order.pop_front().unwrap() {
+                        MemoEntry::Memoized(oldest) => {
+                            self.memoized.remove(&oldest);
+                        },
+                        MemoEntry::Restored(oldest) => {
+                            self.restored.remove(&oldest);
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Whether the key is memoized (without memoizing it).
+    pub fn contains(&self, key : &HashedInteraction) -> bool {
+        self.restored.contains(&key.get_hash()) || self.memoized.contains(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.memoized.len() + self.restored.len()
+    }
+
+    /// The hashes of the keys and their S-expression encodings (null for keys restored from their hashes only).
+    pub fn to_json(&self, gen_ctx : &GeneralContext) -> serde_json::Value {
+        // with a window, the insertion order is required to know which keys are evicted next
+        let entries : Vec<MemoEntry> = match self.window {
+            None => {
+                let mut entries : Vec<MemoEntry> = self.memoized.iter().map(|key| MemoEntry::Memoized(key.clone()))
+                    .chain(self.restored.iter().map(|hash| MemoEntry::Restored(*hash)))
+                    .collect();
+                entries.sort_by_key(|entry| entry.get_hash());
+                entries
+            },
+            Some(_) => {
+                self.order.iter().cloned().collect()
+            }
+        };
+        let keys : Vec<Option<String>> = entries.iter().map(|entry| {
+            match entry {
+                MemoEntry::Memoized(key) => Some(interaction_to_sexpr(gen_ctx,key.get_interaction())),
+                MemoEntry::Restored(_) => None
+            }
+        }).collect();
+        let hashes : Vec<u64> = entries.iter().map(|entry| entry.get_hash()).collect();
+        json!({
+            "window" : self.window,
+            "hashes" : hashes,
+            "keys" : keys
+        })
+    }
+
+    pub fn from_json(gen_ctx : &GeneralContext, value : &serde_json::Value) -> Option<Self> {
+        let window = match value.get("window")? {
+            serde_json::Value::Null => {
+                None
+            },
+            got => {
+                Some(got.as_u64()? as usize)
+            }
+        };
+        let hashes = value.get("hashes")?.as_array()?;
+        // absent from the checkpoints written before the keys were saved
+        let keys = match value.get("keys") {
+            None => {
+                vec![&serde_json::Value::Null;hashes.len()]
+            },
+            Some( got ) => {
+                let got : Vec<&serde_json::Value> = got.as_array()?.iter().collect();
+                if got.len() != hashes.len() {
+                    return None;
+                }
+                got
+            }
+        };
+        let mut memo = Self::new(window);
+        for (hash,key) in hashes.iter().zip(keys) {
+            let entry = match key {
+                serde_json::Value::Null => {
+                    let hash = hash.as_u64()?;
+                    memo.restored.insert(hash);
+                    MemoEntry::Restored(hash)
+                },
+                got => {
+                    let key = HashedInteraction::new(parse_sexpr(gen_ctx,got.as_str()?).ok()?);
+                    memo.memoized.insert(key.clone());
+                    MemoEntry::Memoized(key)
+                }
+            };
+            if window.is_some() {
+                memo.order.push_back(entry);
+            }
+        }
+        Some(memo)
+    }
+
+}
+
+
+/// Normalizes an interaction so that terms which only differ by the following equivalences
+/// are mapped to the same term:
+/// - neutrality of 'empty' as an operand of strict, seq, par and coreg
+/// - associativity of strict, seq, par, coreg and alt (operands are flushed to the right)
+/// - commutativity of par and alt (operands are sorted)
+/// - idempotence of alt (duplicate branches are removed)
+/// - loop(empty) = empty
+/// - directly nested loops are collapsed into the loop with the smallest kind
+///
+/// These are the transformations of the 'Basic' canonization process. Equivalent interactions
+/// outside of this fragment (e.g. requiring factorization) may still have distinct normal forms.
+pub fn normalize_interaction(gen_ctx : &GeneralContext, i : &Interaction) -> Interaction {
+    canonize_interaction(gen_ctx,i, DefaultCanonizationProcess::Basic)
+}
+
+
+
+/// Parameters of the generation of a batch of distinct random interactions.
+#[derive(Clone, Debug)]
+pub struct GenerationParams {
+    /// number of distinct interactions to generate
+    pub num_ints : u32,
+    pub max_depth : u32,
+    /// shallower interactions are rejected
+    pub min_depth : u32,
+    /// interactions with fewer symbols are rejected
+    pub min_symbols : u32,
+    /// number of failed attempts (rejected or duplicate interactions) after which the generation stops
+    pub num_tries : u32,
+    pub probas : InteractionSymbolsProbabilities,
+    /// interactions with more operands under a single (flattened) operator are rejected
+    pub max_width : u32,
+    pub dedup : InteractionDeduplicationKind,
+    /// ids of the lifelines which generated actions may involve (all lifelines if None)
+    pub lifelines : Option<Vec<usize>>,
+    /// number of most recent interactions against which duplicates are detected (all interactions if None)
+    pub dedup_window : Option<usize>,
+    /// distribution of the depths of the generated interactions (at most 'max_depth'),
+    /// if None, depths are only bounded by 'max_depth'
+    pub depth_dist : Option<DepthDistribution>,
+    /// weights with which messages and lifelines are drawn (uniformly by default)
+    pub weights : GenerationWeights,
+    /// interactions with a loop whose body may be executed without any action are rejected
+    pub loop_requires_content : bool,
+    /// interactions with infinitely many traces are rejected
+    pub finite_only : bool,
+    /// instant after which the generation stops even if 'num_ints' and 'num_tries' are not reached (no time limit if None)
+    pub deadline : Option<Instant>,
+    /// number of accepted interactions after which the remaining tries are re-estimated
+    /// from the observed acceptance rate (see 'get_adaptive_remaining_tries'), 'num_tries' is kept if None
+    pub adaptive_tries : Option<u32>,
+    /// interactions introducing no (parent operator, child symbol) pair unseen in the already generated ones
+    /// are rejected until the pair coverage saturates (see 'is_diversity_saturated')
+    pub diverse : bool,
+    /// interactions translating (incrementally) into NFAs with more states are rejected (no bound if None)
+    pub max_nfa_states : Option<usize>,
+    /// interactions with fewer symbols are not translated to check 'max_nfa_states', so as to bound the cost of the check
+    pub nfa_check_min_symbols : u32,
+    /// interactions whose metrics do not satisfy the predicate are rejected
+    pub accept_if : Option<MetricPredicate>,
+    /// fixed interactions before and after each generated one, which is embedded as 'strict(prefix, generated, suffix)'
+    /// just before deduplication so that all the other constraints (except 'max_nfa_states') only apply to the generated part
+    /// (hashed once so that the hash of the embedding is derived from that of the generated part)
+    pub scaffold : (Option<HashedInteraction>,Option<HashedInteraction>),
+    /// reference multi-traces (with their co-localizations) which all must be accepted by the (scaffolded) interactions
+    pub must_accept : Vec<(CoLocalizations,MultiTrace)>
+}
+
+impl GenerationParams {
+    pub fn new(num_ints: u32, max_depth: u32, min_symbols: u32, num_tries: u32, probas: InteractionSymbolsProbabilities) -> Self {
+        Self { num_ints, max_depth, min_depth : 0, min_symbols, num_tries, probas, max_width : u32::MAX, dedup : InteractionDeduplicationKind::Structural, lifelines : None, dedup_window : None, depth_dist : None, weights : GenerationWeights::default(), loop_requires_content : true, finite_only : false, deadline : None, adaptive_tries : None, diverse : false, max_nfa_states : None, nfa_check_min_symbols : 0, accept_if : None, scaffold : (None,None), must_accept : vec![] }
+    }
+
+    /// Embeds the generated interaction between the prefix and suffix of the scaffold (if any).
+    pub fn apply_scaffold(&self, generated : HashedInteraction) -> HashedInteraction {
+        let (prefix,suffix) = &self.scaffold;
+        let with_suffix = match suffix {
+            None => generated,
+            Some(suffix) => HashedInteraction::new_binary(Interaction::Strict,generated,suffix.clone())
+        };
+        match prefix {
+            None => with_suffix,
+            Some(prefix) => HashedInteraction::new_binary(Interaction::Strict,prefix.clone(),with_suffix)
+        }
+    }
+}
+
+
+/// Generates up to 'params.num_ints' distinct random interactions.
+/// Fewer interactions are returned if 'params.num_tries' failed attempts are exceeded.
+pub fn generate_random_interactions(gen_ctx : &GeneralContext,
+                                    params : GenerationParams,
+                                    rng : &mut StdRng) -> Vec<Interaction> {
+    let mut memo = InteractionMemo::new(params.dedup_window);
+    generate_random_interactions_memoized(gen_ctx,&params,rng,&mut memo)
+}
+
+/// State of an ongoing generation (see 'generate_random_interactions_streamed').
+/// Carrying on a generation from a copy of this state yields the same interactions
+/// as the uninterrupted generation.
+#[derive(Clone, Debug)]
+pub struct GenerationState<R : Rng> {
+    pub rng : R,
+    pub memo : InteractionMemo,
+    /// number of interactions generated so far
+    pub num_generated : u32,
+    /// number of failed attempts after which the generation stops
+    pub remaining_tries : u32,
+    /// number of failed attempts due to interactions shallower than 'params.min_depth'
+    pub shallow_rejections : u32,
+    /// number of failed attempts due to interactions with infinitely many traces (if 'params.finite_only')
+    pub infinite_rejections : u32,
+    /// number of failed attempts due to duplicates of already generated interactions
+    pub duplicate_rejections : u32,
+    /// number of failed attempts due to interactions with fewer than 'params.min_symbols' symbols
+    pub few_symbols_rejections : u32,
+    /// number of failed attempts due to the other constraints (too wide interactions and loops with a possibly empty body)
+    pub other_rejections : u32,
+    /// number of failed attempts due to interactions introducing no unseen operator pair (if 'params.diverse')
+    pub redundant_rejections : u32,
+    /// number of consecutive such failed attempts
+    pub redundant_streak : u32,
+    /// (parent operator, child symbol) pairs occurring in the interactions generated so far (if 'params.diverse')
+    pub covered_pairs : HashSet<(SymbolKind,SymbolKind)>,
+    /// number of failed attempts due to interactions translating into NFAs with more than 'params.max_nfa_states' states
+    pub nfa_rejections : u32,
+    /// number of failed attempts due to interactions not satisfying 'params.accept_if'
+    pub predicate_rejections : u32,
+    /// number of failed attempts due to interactions not accepting all the multi-traces of 'params.must_accept'
+    pub trace_rejections : u32,
+    /// for each number k, the number of such failed attempts with interactions accepting exactly k reference multi-traces
+    pub partial_acceptances : Vec<u32>,
+    /// number of reference multi-traces accepted by the last generated interaction if 'params.must_accept' is not empty
+    /// (not saved in checkpoints as it only concerns the last interaction)
+    pub last_accepted_traces : Option<usize>,
+    /// number of states of the NFA of the last generated interaction if it was translated to check 'params.max_nfa_states'
+    /// (not saved in checkpoints as it only concerns the last interaction)
+    pub last_nfa_states : Option<usize>,
+    /// sub-interactions drawn for the 'basic' symbol in the last generated interaction (not saved in checkpoints either)
+    pub last_basic_blocks : Vec<Interaction>,
+    /// rng as it was right before the attempt which yielded the last generated interaction
+    /// from which 'regenerate_interaction' draws it again (not saved in checkpoints either)
+    pub last_attempt_rng : Option<R>
+}
+
+impl<R : Rng> GenerationState<R> {
+    pub fn new(params : &GenerationParams, rng : R) -> Self {
+        Self { rng, memo : InteractionMemo::new(params.dedup_window), num_generated : 0, remaining_tries : params.num_tries, shallow_rejections : 0, infinite_rejections : 0, duplicate_rejections : 0, few_symbols_rejections : 0, other_rejections : 0, redundant_rejections : 0, redundant_streak : 0, covered_pairs : HashSet::new(), nfa_rejections : 0, predicate_rejections : 0, trace_rejections : 0, partial_acceptances : vec![], last_accepted_traces : None, last_nfa_states : None, last_basic_blocks : vec![], last_attempt_rng : None }
+    }
+
+    /// Number of failed attempts so far.
+    pub fn failed_tries(&self) -> u32 {
+        self.shallow_rejections + self.infinite_rejections + self.duplicate_rejections + self.few_symbols_rejections + self.other_rejections + self.redundant_rejections + self.nfa_rejections + self.predicate_rejections + self.trace_rejections
+    }
+
+    /// Fraction of the pairs of 'probas.get_reachable_operator_pairs' occurring in the interactions generated so far
+    /// (1.0 if there are none).
+    pub fn pair_coverage(&self, probas : &InteractionSymbolsProbabilities) -> f64 {
+        let reachable = probas.get_reachable_operator_pairs();
+        if reachable.is_empty() {
+            1.0
+        } else {
+            reachable.intersection(&self.covered_pairs).count() as f64 / reachable.len() as f64
+        }
+    }
+}
+
+
+/// Margin applied to the number of failed attempts expected from the observed acceptance rate.
+const ADAPTIVE_TRIES_MARGIN : f64 = 2.0;
+/// Acceptance rate below which the probability profile is deemed (practically) infeasible.
+const ADAPTIVE_TRIES_INFEASIBLE_RATE : f64 = 0.01;
+
+/// Number of failed attempts to allow for generating 'num_remaining' more interactions
+/// given that 'num_accepted' were accepted and 'num_failed' failed so far
+/// i.e. twice the number of failures expected at the observed acceptance rate,
+/// and at least one per remaining interaction.
+pub fn get_adaptive_remaining_tries(num_accepted : u32, num_failed : u32, num_remaining : u32) -> u32 {
+    let expected = num_remaining as f64 * num_failed as f64 / num_accepted.max(1) as f64;
+    let allowed = (ADAPTIVE_TRIES_MARGIN * expected).ceil().min(u32::MAX as f64) as u32;
+    allowed.max(num_remaining)
+}
+
+fn adapt_remaining_tries<R : Rng>(params : &GenerationParams, state : &mut GenerationState<R>) {
+    let failed = state.failed_tries();
+    let rate = state.num_generated as f64 / (state.num_generated + failed) as f64;
+    let remaining_tries = get_adaptive_remaining_tries(state.num_generated, failed, params.num_ints - state.num_generated);
+    info!("acceptance rate of {:.2}% over the first {} interactions : allowing {} more failed attempts instead of {}",
+          100.0 * rate, state.num_generated, remaining_tries, state.remaining_tries);
+    if rate < ADAPTIVE_TRIES_INFEASIBLE_RATE {
+        warn!("acceptance rate of {:.2}% : the probability profile looks infeasible", 100.0 * rate);
+    }
+    state.remaining_tries = remaining_tries;
+}
+
+fn get_generation_lifelines(gen_ctx : &GeneralContext, params : &GenerationParams) -> Vec<usize> {
+    match &params.lifelines {
+        None => {
+            (0..gen_ctx.get_lf_num()).collect()
+        },
+        Some( lfs ) => {
+            lfs.clone()
+        }
+    }
+}
+
+/// Draws again the interaction generated by an attempt from the rng as it was right before this attempt
+/// (see 'GenerationState::last_attempt_rng'), scaffold included.
+/// The checks which do not draw from the rng (deduplication, NFA size, reference multi-traces...) are skipped
+/// as the attempt is known to have passed them.
+pub fn regenerate_interaction<R : Rng>(gen_ctx : &GeneralContext,
+                                       params : &GenerationParams,
+                                       rng : &mut R) -> Result<Interaction,InteractionRejection> {
+    let i = generate_raw_random_interaction(gen_ctx,params,rng,&mut vec![])?;
+    Ok(params.apply_scaffold(i).into_interaction())
+}
+
+/// Number of consecutive interactions introducing no unseen operator pair after which
+/// the pair coverage is deemed saturated (some reachable pairs may be ruled out by the other constraints).
+const DIVERSITY_SATURATION_PATIENCE : u32 = 200;
+
+/// Whether the diverse generation has stopped rejecting interactions which introduce no unseen operator pair
+/// i.e. whether all the reachable pairs are covered or none was introduced by the last 'DIVERSITY_SATURATION_PATIENCE' interactions.
+fn is_diversity_saturated<R : Rng>(reachable : &HashSet<(SymbolKind,SymbolKind)>, state : &GenerationState<R>) -> bool {
+    state.redundant_streak >= DIVERSITY_SATURATION_PATIENCE || reachable.is_subset(&state.covered_pairs)
+}
+
+/// Same as 'generate_random_interactions' but also excludes (and then extends) an existing set
+/// of memoized interactions.
+pub fn generate_random_interactions_memoized(gen_ctx : &GeneralContext,
+                                             params : &GenerationParams,
+                                             rng : &mut StdRng,
+                                             memo : &mut InteractionMemo) -> Vec<Interaction> {
+    let mut state = GenerationState::new(params, rng.clone());
+    state.memo = std::mem::replace(memo, InteractionMemo::new(None));
+    let mut generated = vec![];
+    generate_random_interactions_streamed(gen_ctx,params,&mut state,&mut |i,_| generated.push(i));
+    *memo = state.memo;
+    *rng = state.rng;
+    generated
+}
+
+/// Carries on the generation from 'state' until 'params.num_ints' interactions have been generated
+/// (or 'state.remaining_tries' is exhausted or 'params.deadline' is passed), handing each interaction to 'on_generated' (together with
+/// the state right after its generation) as soon as it is generated instead of collecting them.
+pub fn generate_random_interactions_streamed<R : Rng + Clone>(gen_ctx : &GeneralContext,
+                                                              params : &GenerationParams,
+                                                              state : &mut GenerationState<R>,
+                                                              on_generated : &mut dyn FnMut(Interaction,&GenerationState<R>)) {
+    let reachable_pairs = params.probas.get_reachable_operator_pairs();
+    let nfa_alphabet = params.max_nfa_states.map(|_| get_alphabet_from_gen_ctx(gen_ctx));
+    while state.num_generated < params.num_ints {
+        if let Some(deadline) = params.deadline {
+            if Instant::now() >= deadline {
+                warn!("time budget exceeded after generating {} interactions out of {}", state.num_generated, params.num_ints);
+                break;
+            }
+        }
+        trace!("trying to generate interaction {} out of {}", state.num_generated, params.num_ints);
+        let mut got_one = false;
+        let mut basic_blocks = vec![];
+        let attempt_rng = state.rng.clone();
+        match generate_raw_random_interaction(gen_ctx,params,&mut state.rng,&mut basic_blocks) {
+            Ok( i ) if params.accept_if.as_ref().map_or(false, |predicate| !predicate.accepts(i.get_interaction())) => {
+                state.predicate_rejections += 1;
+            },
+            Ok( i ) => {
+                let new_pairs : Option<HashSet<(SymbolKind,SymbolKind)>> = if params.diverse {
+                    let pairs = InteractionMetrics::extract_from_interaction(i.get_interaction()).operator_pairs;
+                    Some(pairs.difference(&state.covered_pairs).cloned().collect())
+                } else {
+                    None
+                };
+                let redundant = match &new_pairs {
+                    Some(got) => got.is_empty() && !is_diversity_saturated(&reachable_pairs,state),
+                    None => false
+                };
+                // with structural deduplication, the hash of the key is the one computed while generating the interaction
+                let i = params.apply_scaffold(i);
+                let key = params.dedup.get_hashed_key(gen_ctx,&i);
+                let i = i.into_interaction();
+                // the (costly) translation into a NFA is only done for the candidates passing the other checks
+                // and it aborts as soon as the NFA exceeds 'max_nfa_states'
+                let nfa_states = match (&nfa_alphabet, params.max_nfa_states) {
+                    (Some(alphabet), Some(max_states)) if !redundant && !state.memo.contains(&key)
+                        && InteractionMetrics::extract_from_interaction(&i).get_num_symbols() >= params.nfa_check_min_symbols => {
+                        match get_nfa_from_interaction_exploration_with_budget(gen_ctx, &i, alphabet.clone(), Some(max_states), None) {
+                            Ok( (nfa,_) ) => {
+                                Some(nfa.transitions.len())
+                            },
+                            Err( TranslationError::BudgetExceeded(num_states) ) => {
+                                Some(num_states)
+                            },
+                            Err( TranslationError::Cancelled ) => {
+                                // no cancellation flag is given
+                                unreachable!()
+                            }
+                        }
+                    },
+                    _ => None
+                };
+                let too_many_states = nfa_states.is_some() && nfa_states > params.max_nfa_states;
+                // so is the membership check against the reference multi-traces
+                let accepted_traces = if !params.must_accept.is_empty() && !redundant && !too_many_states && !state.memo.contains(&key) {
+                    Some(params.must_accept.iter().filter(|(co_localizations,multi_trace)| {
+                        is_multi_trace_accepted(&i,co_localizations,multi_trace) == MembershipVerdict::Accepted
+                    }).count())
+                } else {
+                    None
+                };
+                if redundant {
+                    state.redundant_rejections += 1;
+                    state.redundant_streak += 1;
+                } else if too_many_states {
+                    state.nfa_rejections += 1;
+                } else if let Some(num_accepted) = accepted_traces.filter(|num_accepted| *num_accepted < params.must_accept.len()) {
+                    debug!("candidate accepts {} reference multi-traces out of {}", num_accepted, params.must_accept.len());
+                    state.trace_rejections += 1;
+                    if state.partial_acceptances.len() <= num_accepted {
+                        state.partial_acceptances.resize(num_accepted + 1, 0);
+                    }
+                    state.partial_acceptances[num_accepted] += 1;
+                } else if state.memo.insert(key) {
+                    state.last_nfa_states = nfa_states;
+                    state.last_accepted_traces = accepted_traces;
+                    state.last_basic_blocks = basic_blocks;
+                    state.last_attempt_rng = Some(attempt_rng);
+                    if let Some(got) = new_pairs {
+                        if !got.is_empty() {
+                            state.redundant_streak = 0;
+                        }
+                        state.covered_pairs.extend(got);
+                    }
+                    got_one = true;
+                    state.num_generated += 1;
+                    if params.adaptive_tries == Some(state.num_generated) && state.num_generated < params.num_ints {
+                        adapt_remaining_tries(params, state);
+                    }
+                    on_generated(i,state);
+                } else {
+                    state.duplicate_rejections += 1;
+                }
+            },
+            Err( InteractionRejection::TooShallow ) => {
+                state.shallow_rejections += 1;
+            },
+            Err( InteractionRejection::InfiniteLanguage ) => {
+                state.infinite_rejections += 1;
+            },
+            Err( InteractionRejection::TooFewSymbols ) => {
+                state.few_symbols_rejections += 1;
+            },
+            Err( InteractionRejection::TooWide ) | Err( InteractionRejection::VacuousLoop ) => {
+                state.other_rejections += 1;
+            }
+        }
+        if !got_one {
+            if let Some(num_accepted) = params.adaptive_tries {
+                // warns early instead of waiting for the first 'num_accepted' interactions which may never come
+                let warning_threshold = (num_accepted as f64 / ADAPTIVE_TRIES_INFEASIBLE_RATE) as u32;
+                if state.num_generated < num_accepted && state.failed_tries() == warning_threshold {
+                    warn!("only {} interactions accepted after {} failed attempts : the probability profile looks infeasible", state.num_generated, state.failed_tries());
+                }
+            }
+            if state.remaining_tries == 0 {
+                warn!("max retries exceeded after generating {} interactions out of {}", state.num_generated, params.num_ints);
+                break;
+            }
+            trace!("retrying...");
+            state.remaining_tries -= 1;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef};
+    use crate::core::language::syntax::builder::InteractionBuilder;
+    use std::collections::HashMap;
+    use crate::core::language::involve::involves::InvolvesLifelines;
+    use crate::experiments::interaction_random_gen::probas::InteractionGenerationSymbol;
+    use crate::experiments::interaction_random_gen::resume::CountingRng;
+    use crate::io::input::htf::implem::multitrace_from_text;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn generate_random_interactions_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        let params = GenerationParams::new(5,3,1,1000,InteractionSymbolsProbabilities::default_regular());
+        let mut rng = StdRng::seed_from_u64(0);
+        let ints = generate_random_interactions(&gen_ctx,params.clone(),&mut rng);
+        assert_eq!(ints.len(), 5);
+        let as_set : HashSet<&Interaction> = ints.iter().collect();
+        assert_eq!(as_set.len(), 5);
+        // same seed, same interactions
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(generate_random_interactions(&gen_ctx,params,&mut rng), ints);
+    }
+
+    #[test]
+    fn lifeline_subset_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_lf("l3".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::Transmission, 0.4),
+            (InteractionGenerationSymbol::Action, 0.2),
+            (InteractionGenerationSymbol::Seq, 0.4)
+        ])).unwrap();
+        let mut params = GenerationParams::new(3,3,1,1000,probas);
+        // a single lifeline leaves no target for transmissions, which must then be redrawn
+        params.lifelines = Some(vec![2]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let ints = generate_random_interactions(&gen_ctx,params,&mut rng);
+        assert!(!ints.is_empty());
+        for i in &ints {
+            assert!(i.involved_lifelines().is_subset(&BTreeSet::from([2])));
+        }
+    }
+
+    #[test]
+    fn semantic_dedup_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let builder = InteractionBuilder::new(&gen_ctx);
+        let a = builder.emit("l1","m1").unwrap();
+        let with_empty = builder.strict(a.clone(),builder.empty());
+        let semantic = InteractionDeduplicationKind::Semantic;
+        assert_eq!(semantic.get_key(&gen_ctx,&with_empty), semantic.get_key(&gen_ctx,&a));
+        let structural = InteractionDeduplicationKind::Structural;
+        assert_ne!(structural.get_key(&gen_ctx,&with_empty), structural.get_key(&gen_ctx,&a));
+    }
+
+
+    #[test]
+    fn memo_window_test() {
+        let a = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let b = Interaction::Loop(crate::core::language::syntax::interaction::LoopKind::SStrictSeq, Box::new(a.clone()));
+        let c = Interaction::Empty;
+        let key = |i : &Interaction| HashedInteraction::new(i.clone());
+        let mut unbounded = InteractionMemo::new(None);
+        let mut windowed = InteractionMemo::new(Some(2));
+        for i in [&a,&b,&c] {
+            assert!(unbounded.insert(key(i)));
+            assert!(windowed.insert(key(i)));
+        }
+        assert!(!unbounded.insert(key(&a)));
+        assert_eq!(unbounded.len(), 3);
+        // 'a' is out of the window and no longer detected as a duplicate, 'c' still is
+        assert_eq!(windowed.len(), 2);
+        assert!(!windowed.insert(key(&c)));
+        assert!(windowed.insert(key(&a)));
+        // restored keys are still detected as duplicates and evicted in order
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut restored = InteractionMemo::from_json(&gen_ctx,&windowed.to_json(&gen_ctx)).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(!restored.insert(key(&a)));
+        assert!(restored.insert(key(&b)));
+        assert!(restored.insert(key(&Interaction::Loop(crate::core::language::syntax::interaction::LoopKind::WWeakSeq, Box::new(a.clone())))));
+        assert!(restored.insert(key(&a)));
+        // the keys themselves are restored so that they are compared structurally
+        let exact = InteractionMemo::from_json(&gen_ctx,&unbounded.to_json(&gen_ctx)).unwrap();
+        assert!(exact.restored.is_empty());
+        assert_eq!(exact.memoized, unbounded.memoized);
+        // whereas checkpoints holding only the hashes are restored as such
+        let mut legacy = unbounded.to_json(&gen_ctx);
+        legacy.as_object_mut().unwrap().remove("keys");
+        let legacy = InteractionMemo::from_json(&gen_ctx,&legacy).unwrap();
+        assert!(legacy.memoized.is_empty());
+        assert_eq!(legacy.len(), 3);
+        assert!(legacy.contains(&key(&b)));
+        assert_eq!(InteractionMemo::from_json(&gen_ctx,&legacy.to_json(&gen_ctx)).unwrap().restored, legacy.restored);
+    }
+
+
+    #[test]
+    fn depth_dist_test() {
+        assert_eq!(DepthDistribution::from_spec("uniform:3:8"), Some(DepthDistribution::Uniform(3,8)));
+        assert_eq!(DepthDistribution::from_spec("uniform:8:3"), None);
+        assert_eq!(DepthDistribution::from_spec("normal:3:8"), None);
+        assert_eq!(DepthDistribution::Uniform(3,8).to_string(), "uniform:3:8");
+        // ***
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(20,6,1,1000,InteractionSymbolsProbabilities::default_regular());
+        params.depth_dist = Some(DepthDistribution::Uniform(2,4));
+        let mut rng = StdRng::seed_from_u64(0);
+        let ints = generate_random_interactions(&gen_ctx,params,&mut rng);
+        assert_eq!(ints.len(), 20);
+        let depths : HashSet<u32> = ints.iter()
+            .map(|i| InteractionMetrics::extract_from_interaction(i).depth)
+            .collect();
+        assert!(depths.iter().all(|d| *d >= 2 && *d <= 4));
+        assert!(depths.len() > 1);
+    }
+
+    #[test]
+    fn min_depth_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(10,6,1,1000,InteractionSymbolsProbabilities::default_regular());
+        params.min_depth = 4;
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut ints = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| ints.push(i));
+        assert_eq!(ints.len(), 10);
+        assert!(ints.iter().all(|i| InteractionMetrics::extract_from_interaction(i).depth >= 4));
+        assert!(state.shallow_rejections > 0);
+        assert!(state.shallow_rejections <= params.num_tries - state.remaining_tries);
+    }
+
+    #[test]
+    fn finite_only_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        let mut params = GenerationParams::new(10,4,1,1000,InteractionSymbolsProbabilities::default_regular());
+        params.finite_only = true;
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut ints = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| ints.push(i));
+        assert_eq!(ints.len(), 10);
+        assert!(ints.iter().all(|i| i.has_finite_language()));
+        assert!(state.infinite_rejections > 0);
+        // a loop over a body without actions does not make the language infinite
+        let builder = InteractionBuilder::new(&gen_ctx);
+        let a = builder.emit("l1","m1").unwrap();
+        let empty_loop = builder.loop_p(builder.empty());
+        assert!(builder.seq(a.clone(),empty_loop).has_finite_language());
+        assert!(!builder.loop_p(a).has_finite_language());
+    }
+
+    #[test]
+    fn rejection_counts_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::Action, 0.4),
+            (InteractionGenerationSymbol::Seq, 0.6)
+        ])).unwrap();
+        // single actions have too few symbols and nested sequences are too wide
+        // so that only the 16 sequences of two actions are accepted
+        let mut params = GenerationParams::new(12,2,2,100000,probas);
+        params.max_width = 2;
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut ints = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| ints.push(i));
+        assert_eq!(ints.len(), 12);
+        assert!(state.duplicate_rejections > 0);
+        assert!(state.few_symbols_rejections > 0);
+        assert!(state.other_rejections > 0);
+        // every consumed try is accounted for
+        assert_eq!(state.duplicate_rejections + state.few_symbols_rejections + state.other_rejections
+                       + state.shallow_rejections + state.infinite_rejections,
+                   params.num_tries - state.remaining_tries);
+    }
+
+    #[test]
+    fn adaptive_tries_test() {
+        // an acceptance rate of 1/3 over the first interactions leads to expect 2 failures per interaction
+        assert_eq!(get_adaptive_remaining_tries(10,20,90), 360);
+        assert_eq!(get_adaptive_remaining_tries(10,0,90), 90);
+        assert_eq!(get_adaptive_remaining_tries(1,u32::MAX,u32::MAX), u32::MAX);
+        // ***
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::Action, 0.4),
+            (InteractionGenerationSymbol::Seq, 0.6)
+        ])).unwrap();
+        let mut params = GenerationParams::new(12,2,2,100000,probas);
+        params.max_width = 2;
+        params.adaptive_tries = Some(4);
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut remaining_after_estimate = None;
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |_,got_state| {
+            if got_state.num_generated == 4 {
+                remaining_after_estimate = Some((got_state.remaining_tries,got_state.failed_tries()));
+            }
+        });
+        // the budget of failed attempts is re-estimated once the first 4 interactions are accepted
+        let (remaining_tries,failed_tries) = remaining_after_estimate.unwrap();
+        assert_eq!(remaining_tries, get_adaptive_remaining_tries(4,failed_tries,8));
+        assert!(remaining_tries < params.num_tries);
+    }
+
+    #[test]
+    fn diverse_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::Action, 0.4),
+            (InteractionGenerationSymbol::Seq, 0.2),
+            (InteractionGenerationSymbol::Alt, 0.2),
+            (InteractionGenerationSymbol::LoopS, 0.2)
+        ])).unwrap();
+        assert_eq!(probas.get_reachable_operator_pairs().len(), 12);
+        let mut params = GenerationParams::new(20,3,1,100000,probas);
+        params.diverse = true;
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| generated.push(i));
+        assert_eq!(generated.len(), 20);
+        let mut union = HashSet::new();
+        for i in &generated {
+            union.extend(InteractionMetrics::extract_from_interaction(i).operator_pairs);
+        }
+        assert_eq!(union, state.covered_pairs);
+        // directly nested loopS are never drawn so that the coverage saturates once no unseen pair comes up for long enough
+        assert!(!state.covered_pairs.contains(&(SymbolKind::LoopS,SymbolKind::LoopS)));
+        assert_eq!(state.pair_coverage(&params.probas), 11.0 / 12.0);
+        assert!(state.redundant_streak >= DIVERSITY_SATURATION_PATIENCE);
+        assert!(state.redundant_rejections > 0);
+        // the interactions generated before saturation all introduce unseen pairs
+        let mut covered = HashSet::new();
+        for i in &generated {
+            if covered == state.covered_pairs {
+                break;
+            }
+            let pairs = InteractionMetrics::extract_from_interaction(i).operator_pairs;
+            assert!(!pairs.is_subset(&covered));
+            covered.extend(pairs);
+        }
+    }
+
+    #[test]
+    fn max_nfa_states_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::Action, 0.4),
+            (InteractionGenerationSymbol::Seq, 0.2),
+            (InteractionGenerationSymbol::Par, 0.2),
+            (InteractionGenerationSymbol::Alt, 0.2)
+        ])).unwrap();
+        let mut params = GenerationParams::new(15,4,2,100000,probas);
+        params.max_nfa_states = Some(6);
+        params.nfa_check_min_symbols = 3;
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,
+                                              &mut |i,got_state| generated.push((i,got_state.last_nfa_states)));
+        assert_eq!(generated.len(), 15);
+        assert!(state.nfa_rejections > 0);
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        for (i,nfa_states) in &generated {
+            let num_symbols = InteractionMetrics::extract_from_interaction(i).get_num_symbols();
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx, i, alphabet.clone());
+            if num_symbols < params.nfa_check_min_symbols {
+                // small interactions are kept without being translated
+                assert_eq!(*nfa_states, None);
+            } else {
+                assert_eq!(*nfa_states, Some(nfa.transitions.len()));
+                assert!(nfa.transitions.len() <= 6);
+            }
+        }
+    }
+
+    #[test]
+    fn accept_if_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(10,4,1,100000,InteractionSymbolsProbabilities::default_non_regular());
+        let predicate = MetricPredicate::parse("alt >= 1 && depth <= 3").unwrap();
+        params.accept_if = Some(predicate.clone());
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| generated.push(i));
+        assert_eq!(generated.len(), 10);
+        assert!(state.predicate_rejections > 0);
+        for i in &generated {
+            assert!(predicate.accepts(i));
+        }
+    }
+
+    #[test]
+    fn scaffold_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let prefix = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)]));
+        let mut params = GenerationParams::new(10,3,3,100000,InteractionSymbolsProbabilities::default_regular());
+        params.scaffold = (Some(HashedInteraction::new(prefix.clone())),None);
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| generated.push(i));
+        assert_eq!(generated.len(), 10);
+        for i in &generated {
+            match i {
+                Interaction::Strict(i1,body) => {
+                    assert_eq!(**i1, prefix);
+                    // the minimal number of symbols applies to the generated body only
+                    let metrics = InteractionMetrics::extract_from_interaction(body);
+                    assert!(metrics.symbols.iter().fold(0_u32, |x, (_, c)| x + c) >= 3);
+                },
+                _ => panic!()
+            }
+        }
+    }
+
+    #[test]
+    fn regenerate_interaction_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let prefix = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)]));
+        let mut params = GenerationParams::new(10,3,5,100000,InteractionSymbolsProbabilities::default_regular());
+        params.scaffold = (Some(HashedInteraction::new(prefix)),None);
+        let mut state = GenerationState::new(&params, CountingRng::new(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,got_state| {
+            generated.push((i,got_state.last_attempt_rng.as_ref().unwrap().get_words()));
+        });
+        assert_eq!(generated.len(), 10);
+        // some attempts were rejected in between so that the rng is not only fast-forwarded to the end of the previous one
+        assert!(state.failed_tries() > 0);
+        for (i,words) in &generated {
+            assert_eq!(regenerate_interaction(&gen_ctx,&params,&mut CountingRng::resume(0,*words)), Ok(i.clone()));
+        }
+    }
+
+    #[test]
+    fn must_accept_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(5,4,1,100000,InteractionSymbolsProbabilities::default_regular());
+        params.must_accept = vec![multitrace_from_text(&gen_ctx,&"l1!m1.l2?m1".to_string()).unwrap(),
+                                  multitrace_from_text(&gen_ctx,&"l1!m1".to_string()).unwrap()];
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut generated = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,got_state| generated.push((i,got_state.last_accepted_traces)));
+        assert_eq!(generated.len(), 5);
+        assert!(state.trace_rejections > 0);
+        assert_eq!(state.partial_acceptances.iter().sum::<u32>(), state.trace_rejections);
+        for (i,num_accepted) in &generated {
+            assert_eq!(*num_accepted, Some(2));
+            for (co_localizations,multi_trace) in &params.must_accept {
+                assert_eq!(is_multi_trace_accepted(i,co_localizations,multi_trace), MembershipVerdict::Accepted);
+            }
+        }
+    }
+
+    #[test]
+    fn deadline_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(10,4,1,1000,InteractionSymbolsProbabilities::default_regular());
+        params.deadline = Some(Instant::now() + std::time::Duration::from_secs(3600));
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(generate_random_interactions(&gen_ctx,params.clone(),&mut rng).len(), 10);
+        // once the deadline is passed, nothing more is generated and no try is consumed
+        params.deadline = Some(Instant::now());
+        let mut state = GenerationState::new(&params, StdRng::seed_from_u64(0));
+        let mut ints = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,_| ints.push(i));
+        assert!(ints.is_empty());
+        assert_eq!(state.remaining_tries, params.num_tries);
+    }
+
+    #[test]
+    fn vacuous_loops_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        let probas = InteractionSymbolsProbabilities::from_map(HashMap::from([
+            (InteractionGenerationSymbol::LoopS, 0.3),
+            (InteractionGenerationSymbol::LoopW, 0.2),
+            (InteractionGenerationSymbol::Empty, 0.15),
+            (InteractionGenerationSymbol::Alt, 0.1),
+            (InteractionGenerationSymbol::Seq, 0.1),
+            (InteractionGenerationSymbol::Emission, 0.15)
+        ])).unwrap();
+        let mut params = GenerationParams::new(10,4,1,10000,probas);
+        let mut rng = StdRng::seed_from_u64(0);
+        let ints = generate_random_interactions(&gen_ctx,params.clone(),&mut rng);
+        assert_eq!(ints.len(), 10);
+        assert!(ints.iter().any(|i| i.total_loop_num() > 0));
+        assert!(ints.iter().all(|i| !i.has_vacuous_loop()));
+        // without the requirement, vacuous loops are kept
+        params.loop_requires_content = false;
+        let mut rng = StdRng::seed_from_u64(0);
+        let ints = generate_random_interactions(&gen_ctx,params,&mut rng);
+        assert!(ints.iter().any(|i| i.has_vacuous_loop()));
+    }
+
 }
@@ -17,5 +17,12 @@ limitations under the License.
 
 
 pub mod probas;
+pub mod weights;
 mod gen;
 pub mod interface;
+pub mod resume;
+pub mod histogram;
+pub mod repro;
+pub mod predicate;
+pub mod spec;
+pub mod stats;
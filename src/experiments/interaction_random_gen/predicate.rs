@@ -0,0 +1,270 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::metrics::InteractionMetrics;
+
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MetricPredicateError {
+    UnknownMetric(String,Vec<String>),
+    /// what was expected and the position (in characters) at which it was not found
+    MalformedExpression(String,usize)
+}
+
+impl std::fmt::Display for MetricPredicateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricPredicateError::UnknownMetric(name,known) => {
+                write!(f, "unknown metric '{:}', expected one of {:}", name, known.join(","))
+            },
+            MetricPredicateError::MalformedExpression(expected,position) => {
+                write!(f, "malformed predicate : expected {:} at position {:}", expected, position)
+            }
+        }
+    }
+}
+
+/// Values of the metrics (see 'InteractionMetrics::to_json') which predicates may refer to :
+/// its numeric and boolean (as 0 or 1) fields, their aliases without the 'num_' prefix
+/// and the occurrences of each symbol under its lowercase name (e.g. 'alt').
+pub fn get_interaction_metric_values(i : &Interaction) -> BTreeMap<String,f64> {
+    let metrics = InteractionMetrics::extract_from_interaction(i).to_json();
+    let mut values = BTreeMap::new();
+    for (key,value) in metrics.as_object().unwrap() {
+        let as_number = match value {
+            serde_json::Value::Bool(b) => Some(if *b {1.0} else {0.0}),
+            serde_json::Value::Number(n) => n.as_f64(),
+            _ => None
+        };
+        if let Some(x) = as_number {
+            values.insert(key.clone(), x);
+            if let Some(alias) = key.strip_prefix("num_") {
+                values.insert(alias.to_string(), x);
+            }
+        }
+    }
+    for (symbol,num_occ) in metrics["symbols"].as_object().unwrap() {
+        values.insert(symbol.to_lowercase(), num_occ.as_f64().unwrap());
+    }
+    values
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Lower,
+    LowerOrEqual,
+    Greater,
+    GreaterOrEqual
+}
+
+impl ComparisonOperator {
+    fn holds(&self, left : f64, right : f64) -> bool {
+        match self {
+            ComparisonOperator::Equal => left == right,
+            ComparisonOperator::NotEqual => left != right,
+            ComparisonOperator::Lower => left < right,
+            ComparisonOperator::LowerOrEqual => left <= right,
+            ComparisonOperator::Greater => left > right,
+            ComparisonOperator::GreaterOrEqual => left >= right
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MetricOperand {
+    Metric(String),
+    Constant(f64)
+}
+
+impl MetricOperand {
+    fn get_value(&self, values : &BTreeMap<String,f64>) -> f64 {
+        match self {
+            MetricOperand::Metric(name) => *values.get(name).unwrap(),
+            MetricOperand::Constant(x) => *x
+        }
+    }
+}
+
+/// Boolean expression over the metrics of an interaction, e.g. "depth>=4 && alt<=2 && lifelines==3",
+/// made of comparisons between metrics and constants combined with '&&', '||', '!' and parentheses
+/// ('&&' having precedence over '||').
+#[derive(Clone, PartialEq, Debug)]
+pub enum MetricPredicate {
+    Compare(MetricOperand,ComparisonOperator,MetricOperand),
+    Not(Box<MetricPredicate>),
+    And(Box<MetricPredicate>,Box<MetricPredicate>),
+    Or(Box<MetricPredicate>,Box<MetricPredicate>)
+}
+
+impl MetricPredicate {
+
+    /// Metric names are checked against those of 'get_interaction_metric_values'.
+    pub fn parse(expression : &str) -> Result<MetricPredicate,MetricPredicateError> {
+        let known : Vec<String> = get_interaction_metric_values(&Interaction::Empty).into_keys().collect();
+        let mut parser = PredicateParser{chars : expression.chars().collect(), position : 0, known};
+        let predicate = parser.parse_or()?;
+        parser.skip_whitespaces();
+        if parser.position < parser.chars.len() {
+            return Err(MetricPredicateError::MalformedExpression("'&&', '||' or the end of the expression".to_string(), parser.position));
+        }
+        Ok(predicate)
+    }
+
+    pub fn evaluate(&self, values : &BTreeMap<String,f64>) -> bool {
+        match self {
+            MetricPredicate::Compare(left,op,right) => op.holds(left.get_value(values),right.get_value(values)),
+            MetricPredicate::Not(p) => !p.evaluate(values),
+            MetricPredicate::And(p1,p2) => p1.evaluate(values) && p2.evaluate(values),
+            MetricPredicate::Or(p1,p2) => p1.evaluate(values) || p2.evaluate(values)
+        }
+    }
+
+    pub fn accepts(&self, i : &Interaction) -> bool {
+        self.evaluate(&get_interaction_metric_values(i))
+    }
+
+}
+
+struct PredicateParser {
+    chars : Vec<char>,
+    position : usize,
+    known : Vec<String>
+}
+
+impl PredicateParser {
+
+    fn skip_whitespaces(&mut self) {
+        while self.position < self.chars.len() && self.chars[self.position].is_whitespace() {
+            self.position += 1;
+        }
+    }
+
+    /// Consumes 'token' if the expression continues with it.
+    fn eat(&mut self, token : &str) -> bool {
+        self.skip_whitespaces();
+        let token_chars : Vec<char> = token.chars().collect();
+        if self.chars[self.position..].starts_with(&token_chars) {
+            self.position += token_chars.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<MetricPredicate,MetricPredicateError> {
+        let mut predicate = self.parse_and()?;
+        while self.eat("||") {
+            predicate = MetricPredicate::Or(Box::new(predicate),Box::new(self.parse_and()?));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_and(&mut self) -> Result<MetricPredicate,MetricPredicateError> {
+        let mut predicate = self.parse_unary()?;
+        while self.eat("&&") {
+            predicate = MetricPredicate::And(Box::new(predicate),Box::new(self.parse_unary()?));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_unary(&mut self) -> Result<MetricPredicate,MetricPredicateError> {
+        // '!=' only occurs after an operand
+        if self.eat("!") {
+            return Ok(MetricPredicate::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat("(") {
+            let predicate = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(MetricPredicateError::MalformedExpression("')'".to_string(), self.position));
+            }
+            return Ok(predicate);
+        }
+        let left = self.parse_operand()?;
+        // two-character operators are tried first
+        let operators = [("==",ComparisonOperator::Equal), ("!=",ComparisonOperator::NotEqual),
+                         ("<=",ComparisonOperator::LowerOrEqual), (">=",ComparisonOperator::GreaterOrEqual),
+                         ("<",ComparisonOperator::Lower), (">",ComparisonOperator::Greater)];
+        for (token,op) in operators {
+            if self.eat(token) {
+                let right = self.parse_operand()?;
+                return Ok(MetricPredicate::Compare(left,op,right));
+            }
+        }
+        Err(MetricPredicateError::MalformedExpression("a comparison operator".to_string(), self.position))
+    }
+
+    fn parse_operand(&mut self) -> Result<MetricOperand,MetricPredicateError> {
+        self.skip_whitespaces();
+        let start = self.position;
+        while self.position < self.chars.len()
+            && (self.chars[self.position].is_alphanumeric() || self.chars[self.position] == '_' || self.chars[self.position] == '.') {
+            self.position += 1;
+        }
+        let word : String = self.chars[start..self.position].iter().collect();
+        if word.is_empty() {
+            return Err(MetricPredicateError::MalformedExpression("a metric name or a number".to_string(), start));
+        }
+        if word.starts_with(|c : char| c.is_ascii_digit()) {
+            return match word.parse::<f64>() {
+                Ok( x ) => Ok(MetricOperand::Constant(x)),
+                Err(_) => Err(MetricPredicateError::MalformedExpression("a number".to_string(), start))
+            };
+        }
+        if !self.known.contains(&word) {
+            return Err(MetricPredicateError::UnknownMetric(word, self.known.clone()));
+        }
+        Ok(MetricOperand::Metric(word))
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn predicate_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let i = parse_hif_string(&gen_ctx,"seq(alt(a -- m -> b, o), loopS(c -- n ->|))".to_string()).unwrap();
+        let holds = |expression : &str| MetricPredicate::parse(expression).unwrap().accepts(&i);
+        assert!(holds("depth>=2 && alt<=2 && lifelines==3"));
+        assert!(holds("alt == 1 && loops == 1 && !(par > 0)"));
+        assert!(!holds("depth >= 4 || messages != 2"));
+        // '&&' has precedence over '||'
+        assert!(holds("depth > 9 && alt == 0 || symbols == 6"));
+        assert!(!holds("depth > 9 && (alt == 0 || symbols == 6)"));
+        assert!(holds("loop_free == 0 && 2.5 < num_symbols"));
+        // errors
+        assert!(matches!(MetricPredicate::parse("depht >= 1"), Err(MetricPredicateError::UnknownMetric(name,_)) if name == "depht"));
+        assert_eq!(MetricPredicate::parse("depth >= 1 &&"), Err(MetricPredicateError::MalformedExpression("a metric name or a number".to_string(), 13)));
+        assert_eq!(MetricPredicate::parse("depth 1"), Err(MetricPredicateError::MalformedExpression("a comparison operator".to_string(), 6)));
+        assert_eq!(MetricPredicate::parse("(depth > 1"), Err(MetricPredicateError::MalformedExpression("')'".to_string(), 10)));
+        assert_eq!(MetricPredicate::parse("depth > 1 alt"), Err(MetricPredicateError::MalformedExpression("'&&', '||' or the end of the expression".to_string(), 10)));
+    }
+
+}
@@ -17,9 +17,11 @@ limitations under the License.
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::Formatter;
+use std::fs;
 use std::hash::Hash;
 use rand::prelude::{SliceRandom, StdRng};
 use rand::Rng;
+use strum::IntoEnumIterator;
 use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, ReceptionAction};
 use crate::core::language::syntax::interaction::{Interaction, LoopKind};
@@ -31,6 +33,8 @@ use crate::core::language::syntax::metrics::SymbolKind;
 #[derive(IntoStaticStr, EnumIter, Clone, PartialEq, Eq, PartialOrd, Copy, Ord, Hash, Debug)]
 pub enum InteractionGenerationSymbol {
     Empty,
+    /// either an emission or a reception with equal probability
+    /// (deprecated in favor of 'Emission' and 'Reception')
     Action,
     Strict,
     Seq,
@@ -40,12 +44,19 @@ pub enum InteractionGenerationSymbol {
     LoopW,
     LoopP,
     Alt,
+    /// a sub-interaction drawn in place of the symbol with the fixed probabilities of 'default_basic'
+    /// (see 'InteractionSymbolsProbabilities::describe_basic')
     Basic,
     Transmission,
-    Broadcast
+    Broadcast,
+    Gate,
+    // pure emissions and receptions come last so that existing profiles keep drawing the same symbols
+    Emission,
+    Reception
 }
 
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct InteractionSymbolsProbabilities {
     pub ordered_symbols : Vec<InteractionGenerationSymbol>,
     pub ordered_bounds : Vec<f32>
@@ -53,12 +64,110 @@ pub struct InteractionSymbolsProbabilities {
 
 
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Copy, Ord, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum InteractionSymbolsProbabilitiesError {
     SymbolProbabilityMustBeBetweenOAnd1,
-    SumOfProbabilitiesMustBe1
+    SumOfProbabilitiesMustBe1,
+    // ***
+    ProfileFileError(String),
+    MissingSymbolProbability(String),
+    NegativeSymbolProbability(String),
+    // ***
+    UnknownSymbol(String),
+    NoTerminalSymbol,
+    // ***
+    InvalidLoopKinds(String)
 }
 
+impl std::fmt::Display for InteractionSymbolsProbabilitiesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InteractionSymbolsProbabilitiesError::SymbolProbabilityMustBeBetweenOAnd1 => {
+                write!(f, "symbol probabilities must be between 0 and 1")
+            },
+            InteractionSymbolsProbabilitiesError::SumOfProbabilitiesMustBe1 => {
+                write!(f, "symbol probabilities must sum to 1")
+            },
+            InteractionSymbolsProbabilitiesError::ProfileFileError(sub_e) => {
+                write!(f, "error while reading probability profile : {:}", sub_e)
+            },
+            InteractionSymbolsProbabilitiesError::MissingSymbolProbability(key) => {
+                write!(f, "missing probability for symbol '{:}' in probability profile", key)
+            },
+            InteractionSymbolsProbabilitiesError::NegativeSymbolProbability(key) => {
+                write!(f, "negative probability for symbol '{:}' in probability profile", key)
+            },
+            InteractionSymbolsProbabilitiesError::UnknownSymbol(key) => {
+                let keys : Vec<&str> = InteractionGenerationSymbol::iter().map(|s| s.profile_key()).collect();
+                write!(f, "unknown symbol '{:}', expected one of {:}", key, keys.join(","))
+            },
+            InteractionSymbolsProbabilitiesError::NoTerminalSymbol => {
+                write!(f, "either 'empty', 'action', 'emission' or 'reception' must have a non-zero probability for the generation to terminate")
+            },
+            InteractionSymbolsProbabilitiesError::InvalidLoopKinds(spec) => {
+                write!(f, "invalid loop kinds '{:}', expected non-negative weights of 'strong', 'weak' and 'perm' loops not all 0 (e.g. 'strong:0.5,weak:0.3,perm:0.2')", spec)
+            }
+        }
+    }
+}
+
+impl InteractionGenerationSymbol {
+
+    /// Kind of the root symbol of the interactions drawn for this symbol if it is always the same
+    /// (None for the patterns drawn for 'Basic', 'Transmission' and 'Broadcast').
+    pub fn get_symbol_kind(&self) -> Option<SymbolKind> {
+        match self {
+            InteractionGenerationSymbol::Empty => Some(SymbolKind::Empty),
+            InteractionGenerationSymbol::Action | InteractionGenerationSymbol::Gate
+                | InteractionGenerationSymbol::Emission | InteractionGenerationSymbol::Reception => Some(SymbolKind::Action),
+            InteractionGenerationSymbol::Strict => Some(SymbolKind::Strict),
+            InteractionGenerationSymbol::Seq => Some(SymbolKind::Seq),
+            InteractionGenerationSymbol::Coreg => Some(SymbolKind::CoReg),
+            InteractionGenerationSymbol::Par => Some(SymbolKind::Par),
+            InteractionGenerationSymbol::LoopS => Some(SymbolKind::LoopS),
+            InteractionGenerationSymbol::LoopW | InteractionGenerationSymbol::LoopP => Some(SymbolKind::LoopOther),
+            InteractionGenerationSymbol::Alt => Some(SymbolKind::Alt),
+            InteractionGenerationSymbol::Basic | InteractionGenerationSymbol::Transmission
+                | InteractionGenerationSymbol::Broadcast => None
+        }
+    }
+
+    /// Name under which the symbol is referred to in probability profiles.
+    pub fn profile_key(&self) -> &'static str {
+        match self {
+            InteractionGenerationSymbol::Empty => "empty",
+            InteractionGenerationSymbol::Action => "action",
+            InteractionGenerationSymbol::Strict => "strict",
+            InteractionGenerationSymbol::Seq => "seq",
+            InteractionGenerationSymbol::Coreg => "coreg",
+            InteractionGenerationSymbol::Par => "par",
+            InteractionGenerationSymbol::LoopS => "loopS",
+            InteractionGenerationSymbol::LoopW => "loopW",
+            InteractionGenerationSymbol::LoopP => "loopP",
+            InteractionGenerationSymbol::Alt => "alt",
+            InteractionGenerationSymbol::Basic => "basic",
+            InteractionGenerationSymbol::Transmission => "transmission",
+            InteractionGenerationSymbol::Broadcast => "broadcast",
+            InteractionGenerationSymbol::Gate => "gate",
+            InteractionGenerationSymbol::Emission => "emission",
+            InteractionGenerationSymbol::Reception => "reception"
+        }
+    }
+
+    /// Symbols that were introduced after the first probability profiles were written.
+    fn is_optional_in_profiles(&self) -> bool {
+        matches!(self, InteractionGenerationSymbol::Gate | InteractionGenerationSymbol::Emission | InteractionGenerationSymbol::Reception)
+    }
+
+    pub fn from_profile_key(key : &str) -> Option<Self> {
+        InteractionGenerationSymbol::iter().find(|s| s.profile_key() == key)
+    }
+}
+
+/// Profiles whose probabilities sum to 1.0 up to this deviation are rescaled
+/// instead of being rejected.
+pub const PROFILE_NORMALIZATION_TOLERANCE : f32 = 1e-2;
+
 
 impl InteractionSymbolsProbabilities {
 
@@ -66,7 +175,10 @@ impl InteractionSymbolsProbabilities {
         let mut ordered_symbols = vec![];
         let mut ordered_bounds = vec![0.0_f32];
         let mut sum = 0.0;
-        for (s,p) in map {
+        // symbols are ordered so that the same seed draws the same symbols across runs
+        let mut entries : Vec<(InteractionGenerationSymbol,f32)> = map.into_iter().collect();
+        entries.sort_by(|(s1,_),(s2,_)| s1.cmp(s2));
+        for (s,p) in entries {
             if p < 0.0 - 1e-6 || p > 1.0 + 1e-6 {
                 return Err(InteractionSymbolsProbabilitiesError::SymbolProbabilityMustBeBetweenOAnd1);
             }
@@ -196,6 +308,9 @@ impl InteractionSymbolsProbabilities {
         pbasic: f32,
         ptr: f32,
         pbc: f32,
+        pgate: f32,
+        pemission: f32,
+        preception: f32,
     ) -> InteractionSymbolsProbabilities {
         let map = hashmap!{
             InteractionGenerationSymbol::Empty => pempty,
@@ -211,11 +326,74 @@ impl InteractionSymbolsProbabilities {
             InteractionGenerationSymbol::Basic => pbasic,
             InteractionGenerationSymbol::Transmission => ptr,
             InteractionGenerationSymbol::Broadcast => pbc,
+            InteractionGenerationSymbol::Gate => pgate,
+            InteractionGenerationSymbol::Emission => pemission,
+            InteractionGenerationSymbol::Reception => preception,
         };
         Self::from_map(map).unwrap()
     }
 
 
+    /// Reads a probability profile from a TOML document in which each of the
+    /// generation symbols is given as a top-level key, e.g. 'loopS = 0.1'.
+    /// The 'gate', 'emission' and 'reception' keys may be omitted, in which case no such action is generated.
+    pub fn from_toml(toml_str : &str) -> Result<Self,InteractionSymbolsProbabilitiesError> {
+        let table = match toml_str.parse::<toml::Table>() {
+            Err(e) => {
+                return Err(InteractionSymbolsProbabilitiesError::ProfileFileError(e.to_string()));
+            },
+            Ok(got_table) => {
+                got_table
+            }
+        };
+        let mut map = HashMap::new();
+        let mut sum = 0.0_f32;
+        for symbol in InteractionGenerationSymbol::iter() {
+            let key = symbol.profile_key();
+            let proba = match table.get(key) {
+                None if symbol.is_optional_in_profiles() => {
+                    0.0
+                },
+                None => {
+                    return Err(InteractionSymbolsProbabilitiesError::MissingSymbolProbability(key.to_string()));
+                },
+                Some(toml::Value::Float(x)) => {
+                    *x as f32
+                },
+                Some(toml::Value::Integer(x)) => {
+                    *x as f32
+                },
+                Some(_) => {
+                    return Err(InteractionSymbolsProbabilitiesError::ProfileFileError(
+                        format!("probability for symbol '{:}' is not a number", key)));
+                }
+            };
+            if proba < 0.0 {
+                return Err(InteractionSymbolsProbabilitiesError::NegativeSymbolProbability(key.to_string()));
+            }
+            sum += proba;
+            map.insert(symbol, proba);
+        }
+        if (sum - 1.0).abs() > PROFILE_NORMALIZATION_TOLERANCE {
+            return Err(InteractionSymbolsProbabilitiesError::SumOfProbabilitiesMustBe1);
+        }
+        for proba in map.values_mut() {
+            *proba /= sum;
+        }
+        Self::from_map(map)
+    }
+
+    pub fn from_toml_file(file_path : &str) -> Result<Self,InteractionSymbolsProbabilitiesError> {
+        match fs::read_to_string(file_path) {
+            Err(e) => {
+                Err(InteractionSymbolsProbabilitiesError::ProfileFileError(e.to_string()))
+            },
+            Ok(toml_str) => {
+                Self::from_toml(&toml_str)
+            }
+        }
+    }
+
     pub fn default_basic() -> InteractionSymbolsProbabilities {
         let map = hashmap!{
             InteractionGenerationSymbol::Action     => 0.4,
@@ -228,7 +406,66 @@ impl InteractionSymbolsProbabilities {
         Self::from_map(map).unwrap()
     }
 
-    pub fn get_random_symbol(&self, rng : &mut StdRng) -> InteractionGenerationSymbol {
+    /// What the 'basic' symbol draws, e.g. "action 0.40, strict 0.40, seq 0.20".
+    /// It is no operator of its own : the sub-interaction drawn for it is only made of the symbols of 'default_basic',
+    /// under the same depth cap, and is written in this expanded form.
+    pub fn describe_basic() -> String {
+        let basic = Self::default_basic();
+        basic.ordered_symbols.iter()
+            .map(|symbol| format!("{:} {:.2}", symbol.profile_key(), basic.get_probability(symbol)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Returns the same probabilities except that the 'excluded' symbols are never selected,
+    /// the probabilities of the remaining symbols being rescaled so that they sum to 1.
+    /// Note that 'basic' is resolved with its own fixed probabilities (see 'default_basic').
+    pub fn without_symbols(&self, excluded : &[InteractionGenerationSymbol]) -> Result<Self,InteractionSymbolsProbabilitiesError> {
+        let mut map = HashMap::new();
+        let mut sum = 0.0_f32;
+        for symbol in &self.ordered_symbols {
+            if !excluded.contains(symbol) && !map.contains_key(symbol) {
+                let proba = self.get_probability(symbol);
+                sum += proba;
+                map.insert(*symbol, proba);
+            }
+        }
+        let is_terminal = |s : &InteractionGenerationSymbol| matches!(s,
+            InteractionGenerationSymbol::Empty | InteractionGenerationSymbol::Action | InteractionGenerationSymbol::Emission | InteractionGenerationSymbol::Reception);
+        if !map.iter().any(|(s,p)| is_terminal(s) && *p > 0.0) {
+            return Err(InteractionSymbolsProbabilitiesError::NoTerminalSymbol);
+        }
+        for proba in map.values_mut() {
+            *proba /= sum;
+        }
+        Self::from_map(map)
+    }
+
+    /// Returns the probability with which 'symbol' is selected (0.0 if it is not drawable).
+    pub fn get_probability(&self, symbol : &InteractionGenerationSymbol) -> f32 {
+        self.ordered_symbols.iter().enumerate()
+            .filter(|(_,s)| *s == symbol)
+            .fold(0.0_f32, |acc,(idx,_)| acc + self.ordered_bounds[idx+1] - self.ordered_bounds[idx])
+    }
+
+    /// Pairs (parent operator, child symbol) which may occur in the generated interactions
+    /// given the symbols with a non-zero probability.
+    /// The pairs introduced by patterns (see 'InteractionGenerationSymbol::get_symbol_kind') are not accounted for.
+    pub fn get_reachable_operator_pairs(&self) -> HashSet<(SymbolKind,SymbolKind)> {
+        let kinds : HashSet<SymbolKind> = InteractionGenerationSymbol::iter()
+            .filter(|symbol| self.get_probability(symbol) > 0.0)
+            .filter_map(|symbol| symbol.get_symbol_kind())
+            .collect();
+        let mut pairs = HashSet::new();
+        for parent in kinds.iter().filter(|kind| **kind != SymbolKind::Empty && **kind != SymbolKind::Action) {
+            for child in &kinds {
+                pairs.insert((*parent,*child));
+            }
+        }
+        pairs
+    }
+
+    pub fn get_random_symbol<R : Rng>(&self, rng : &mut R) -> InteractionGenerationSymbol {
         let got = rng.gen_range(0.0_f32..1.0_f32);
         for (idx,x) in self.ordered_bounds.iter().enumerate() {
             if got <= *x + 1e-6 {
@@ -250,6 +487,54 @@ impl std::fmt::Display for InteractionSymbolsProbabilities {
 }
 
 
+/// Distribution of the kind of a loop given that a loop is drawn,
+/// from which the 'loopS', 'loopW' and 'loopP' probabilities are derived given an overall loop probability.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LoopKindDistribution {
+    pub strong : f32,
+    pub weak : f32,
+    pub perm : f32
+}
+
+impl Default for LoopKindDistribution {
+    fn default() -> Self {
+        Self { strong : 1.0/3.0, weak : 1.0/3.0, perm : 1.0/3.0 }
+    }
+}
+
+impl LoopKindDistribution {
+
+    /// Parses a specification such as 'strong:0.5,weak:0.3,perm:0.2' in which omitted kinds have weight 0.
+    /// The weights are rescaled so that they sum to 1.
+    pub fn from_spec(spec : &str) -> Result<Self,InteractionSymbolsProbabilitiesError> {
+        let invalid = || InteractionSymbolsProbabilitiesError::InvalidLoopKinds(spec.to_string());
+        let mut got = Self { strong : 0.0, weak : 0.0, perm : 0.0 };
+        for entry in spec.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (kind,weight) = entry.split_once(':').ok_or_else(invalid)?;
+            let weight = weight.trim().parse::<f32>().ok().filter(|w| *w >= 0.0).ok_or_else(invalid)?;
+            match kind.trim() {
+                "strong" => got.strong = weight,
+                "weak" => got.weak = weight,
+                "perm" => got.perm = weight,
+                _ => {
+                    return Err(invalid());
+                }
+            }
+        }
+        let sum = got.strong + got.weak + got.perm;
+        if sum <= 0.0 {
+            return Err(invalid());
+        }
+        Ok( Self { strong : got.strong / sum, weak : got.weak / sum, perm : got.perm / sum } )
+    }
+
+    /// Probabilities of 'loopS', 'loopW' and 'loopP' for an overall loop probability 'ploop'.
+    pub fn expand(&self, ploop : f32) -> (f32,f32,f32) {
+        (ploop * self.strong, ploop * self.weak, ploop * self.perm)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,5 +545,77 @@ mod tests {
         println!("{:}", probas);
     }
 
+    #[test]
+    fn from_toml_test() {
+        let profile = "empty = 0.1\naction = 0.3\nstrict = 0.2\nseq = 0.1\ncoreg = 0\npar = 0.1\n\
+                       loopS = 0.1\nloopW = 0\nloopP = 0\nalt = 0.095\nbasic = 0\ntransmission = 0\nbroadcast = 0";
+        let probas = InteractionSymbolsProbabilities::from_toml(profile).unwrap();
+        assert!((probas.ordered_bounds.last().unwrap() - 1.0).abs() < 1e-6);
+        // ***
+        assert_eq!(probas.get_probability(&InteractionGenerationSymbol::Emission), 0.0);
+        let split = format!("{:}\nemission = 0.2\nreception = 0.1", profile.replace("action = 0.3","action = 0"));
+        let probas = InteractionSymbolsProbabilities::from_toml(&split).unwrap();
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Emission) - 0.2/0.995).abs() < 1e-5);
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Reception) - 0.1/0.995).abs() < 1e-5);
+        // ***
+        let missing = InteractionSymbolsProbabilities::from_toml("empty = 0.5\naction = 0.5");
+        assert_eq!(missing.err(), Some(InteractionSymbolsProbabilitiesError::MissingSymbolProbability("strict".to_string())));
+    }
+
+
+    #[test]
+    fn without_symbols_test() {
+        let probas = InteractionSymbolsProbabilities::default_regular();
+        let regular = probas.without_symbols(&[InteractionGenerationSymbol::Par]).unwrap();
+        assert_eq!(regular.get_probability(&InteractionGenerationSymbol::Par), 0.0);
+        assert!((regular.ordered_bounds.last().unwrap() - 1.0).abs() < 1e-6);
+        // par had probability 0.1, the others are scaled by 1/0.9
+        assert!((regular.get_probability(&InteractionGenerationSymbol::Seq) - 0.3/0.9).abs() < 1e-5);
+        // ***
+        let no_terminal = probas.without_symbols(&[InteractionGenerationSymbol::Empty,InteractionGenerationSymbol::Action]);
+        assert_eq!(no_terminal.err(), Some(InteractionSymbolsProbabilitiesError::NoTerminalSymbol));
+    }
+
+    #[test]
+    fn emission_reception_test() {
+        let probas = InteractionSymbolsProbabilities::custom(0.2,0.0,0.3,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.3,0.2);
+        assert_eq!(probas.get_probability(&InteractionGenerationSymbol::Action), 0.0);
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Emission) - 0.3).abs() < 1e-6);
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Reception) - 0.2).abs() < 1e-6);
+        // emissions and receptions are terminal symbols on their own
+        let only_receptions = probas.without_symbols(&[InteractionGenerationSymbol::Empty,InteractionGenerationSymbol::Emission]).unwrap();
+        assert!((only_receptions.get_probability(&InteractionGenerationSymbol::Reception) - 0.2/0.5).abs() < 1e-5);
+        assert!((only_receptions.get_probability(&InteractionGenerationSymbol::Strict) - 0.3/0.5).abs() < 1e-5);
+        assert!((only_receptions.ordered_bounds.last().unwrap() - 1.0).abs() < 1e-6);
+        let no_terminal = probas.without_symbols(&[InteractionGenerationSymbol::Empty,InteractionGenerationSymbol::Emission,InteractionGenerationSymbol::Reception]);
+        assert_eq!(no_terminal.err(), Some(InteractionSymbolsProbabilitiesError::NoTerminalSymbol));
+        // ***
+        // profiles are normalized when their sum is within the tolerance
+        let profile = "empty = 0\naction = 0\nstrict = 0.5\nseq = 0\ncoreg = 0\npar = 0\nloopS = 0\nloopW = 0\nloopP = 0\n\
+                       alt = 0\nbasic = 0\ntransmission = 0\nbroadcast = 0\nemission = 0.3\nreception = 0.204";
+        let probas = InteractionSymbolsProbabilities::from_toml(profile).unwrap();
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Emission) - 0.3/1.004).abs() < 1e-5);
+        assert!((probas.get_probability(&InteractionGenerationSymbol::Reception) - 0.204/1.004).abs() < 1e-5);
+        assert!((probas.ordered_bounds.last().unwrap() - 1.0).abs() < 1e-6);
+        let too_far = profile.replace("reception = 0.204", "reception = 0.3");
+        assert_eq!(InteractionSymbolsProbabilities::from_toml(&too_far).err(), Some(InteractionSymbolsProbabilitiesError::SumOfProbabilitiesMustBe1));
+    }
+
+    #[test]
+    fn loop_kinds_test() {
+        let kinds = LoopKindDistribution::from_spec("strong:0.5, weak:0.3,perm:0.2").unwrap();
+        let (ploops,ploopw,ploopp) = kinds.expand(0.1);
+        assert!((ploops - 0.05).abs() < 1e-6);
+        assert!((ploopw - 0.03).abs() < 1e-6);
+        assert!((ploopp - 0.02).abs() < 1e-6);
+        // weights are rescaled and omitted kinds are never drawn
+        assert_eq!(LoopKindDistribution::from_spec("weak:2,perm:2").unwrap(), LoopKindDistribution{strong:0.0,weak:0.5,perm:0.5});
+        for spec in ["strong:0", "strong:-1,weak:2", "head:1", "strong=1", ""] {
+            assert_eq!(LoopKindDistribution::from_spec(spec), Err(InteractionSymbolsProbabilitiesError::InvalidLoopKinds(spec.to_string())));
+        }
+        let probas = InteractionSymbolsProbabilities::custom(0.5,0.0,0.0,0.4,0.0,0.0,ploops,ploopw,ploopp,0.0,0.0,0.0,0.0,0.0,0.0,0.0);
+        assert!((probas.get_probability(&InteractionGenerationSymbol::LoopW) - 0.03).abs() < 1e-6);
+    }
+
 }
 
@@ -0,0 +1,121 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+
+/// Maximal number of bytes of the differing lines shown when describing a divergence.
+const DIVERGENCE_EXCERPT_LENGTH : usize = 40;
+
+/// Paths, relative to 'folder', of the files in 'folder' and its sub-folders.
+fn get_relative_file_paths(folder : &Path) -> std::io::Result<BTreeSet<PathBuf>> {
+    let mut paths = BTreeSet::new();
+    let mut to_visit = vec![folder.to_path_buf()];
+    while let Some(current) = to_visit.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                to_visit.push(path);
+            } else {
+                paths.insert(path.strip_prefix(folder).unwrap().to_path_buf());
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Describes the first difference between the contents of two folders (None if they have byte-identical files) :
+/// the first file, in the order of their relative paths, which is in only one of them or whose contents differ,
+/// with the position of its first differing byte.
+pub fn get_first_folder_divergence(left : &Path, right : &Path) -> std::io::Result<Option<String>> {
+    let left_paths = get_relative_file_paths(left)?;
+    let right_paths = get_relative_file_paths(right)?;
+    for path in left_paths.union(&right_paths) {
+        if !right_paths.contains(path) {
+            return Ok(Some(format!("file '{:}' is only in '{:}'", path.display(), left.display())));
+        }
+        if !left_paths.contains(path) {
+            return Ok(Some(format!("file '{:}' is only in '{:}'", path.display(), right.display())));
+        }
+        let left_content = std::fs::read(left.join(path))?;
+        let right_content = std::fs::read(right.join(path))?;
+        if left_content == right_content {
+            continue;
+        }
+        let left_lines : Vec<&[u8]> = left_content.split(|byte| *byte == b'\n').collect();
+        let right_lines : Vec<&[u8]> = right_content.split(|byte| *byte == b'\n').collect();
+        // since the contents differ, so do their lines at some index of either
+        let num_lines = left_lines.len().max(right_lines.len());
+        let line = (0..num_lines).find(|idx| left_lines.get(*idx) != right_lines.get(*idx)).unwrap();
+        let (left_line,right_line) = (left_lines.get(line), right_lines.get(line));
+        let column = match (left_line,right_line) {
+            (Some(got_left),Some(got_right)) => {
+                got_left.iter().zip(got_right.iter()).take_while(|(x,y)| x == y).count()
+            },
+            _ => 0
+        };
+        // only an excerpt from the first differing byte is shown as lines may be long (e.g. in JSON files)
+        let get_excerpt = |got_line : Option<&&[u8]>| match got_line {
+            None => "end of file".to_string(),
+            Some(got) => {
+                let excerpt = &got[column..got.len().min(column + DIVERGENCE_EXCERPT_LENGTH)];
+                let ellipsis = if got.len() > column + DIVERGENCE_EXCERPT_LENGTH { "..." } else { "" };
+                format!("'{:}{:}'", String::from_utf8_lossy(excerpt), ellipsis)
+            }
+        };
+        return Ok(Some(format!("file '{:}' differs at line {:}, column {:} : {:} instead of {:}",
+                               path.display(), line + 1, column + 1, get_excerpt(right_line), get_excerpt(left_line))));
+    }
+    Ok(None)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folder_divergence_test() {
+        let root = std::env::temp_dir().join(format!("hibou_repro_test_{:}", std::process::id()));
+        let left = root.join("left");
+        let right = root.join("right");
+        std::fs::create_dir_all(left.join("sub")).unwrap();
+        std::fs::create_dir_all(right.join("sub")).unwrap();
+        for folder in [&left,&right] {
+            std::fs::write(folder.join("i0.hif"), "seq(\n\ta -- m -> b\n)").unwrap();
+            std::fs::write(folder.join("sub").join("x.txt"), "x").unwrap();
+        }
+        assert_eq!(get_first_folder_divergence(&left,&right).unwrap(), None);
+        // ***
+        std::fs::write(right.join("i0.hif"), "seq(\n\ta -- n -> b\n)").unwrap();
+        assert_eq!(get_first_folder_divergence(&left,&right).unwrap(),
+                   Some("file 'i0.hif' differs at line 2, column 7 : 'n -> b' instead of 'm -> b'".to_string()));
+        std::fs::write(right.join("i0.hif"), "seq(\n\ta -- m -> b\n)\n").unwrap();
+        assert_eq!(get_first_folder_divergence(&left,&right).unwrap(),
+                   Some("file 'i0.hif' differs at line 4, column 1 : '' instead of end of file".to_string()));
+        // ***
+        std::fs::write(right.join("i0.hif"), "seq(\n\ta -- m -> b\n)").unwrap();
+        std::fs::write(left.join("i1.hif"), "o").unwrap();
+        assert_eq!(get_first_folder_divergence(&left,&right).unwrap(),
+                   Some(format!("file 'i1.hif' is only in '{:}'", left.display())));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+}
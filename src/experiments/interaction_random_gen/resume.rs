@@ -0,0 +1,249 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use std::collections::HashSet;
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use itertools::Itertools;
+use serde_json::json;
+use strum::IntoEnumIterator;
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::metrics::SymbolKind;
+use crate::experiments::interaction_random_gen::interface::{GenerationState, InteractionMemo};
+
+
+/// A 'StdRng' which counts the 32 bits words drawn from it.
+/// As 'StdRng' cannot be serialized, its state is captured by its seed and this count :
+/// the underlying generator consumes its stream of words contiguously whatever the calls
+/// (one word per 'next_u32', two per 'next_u64' and one per started group of 4 bytes when filling bytes)
+/// so that drawing as many words from a freshly seeded generator reconstructs the same state.
+#[derive(Clone, Debug)]
+pub struct CountingRng {
+    inner : StdRng,
+    seed : u64,
+    words : u64
+}
+
+impl CountingRng {
+
+    pub fn new(seed : u64) -> Self {
+        Self { inner : StdRng::seed_from_u64(seed), seed, words : 0 }
+    }
+
+    /// Reconstructs the generator seeded with 'seed' from which 'words' words have been drawn.
+    pub fn resume(seed : u64, words : u64) -> Self {
+        let mut rng = Self::new(seed);
+        for _ in 0..words {
+            rng.next_u32();
+        }
+        rng
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_words(&self) -> u64 {
+        self.words
+    }
+
+}
+
+impl RngCore for CountingRng {
+
+    fn next_u32(&mut self) -> u32 {
+        self.words += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.words += 2;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest : &mut [u8]) {
+        self.words += ((dest.len() + 3) / 4) as u64;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest : &mut [u8]) -> Result<(), rand::Error> {
+        self.words += ((dest.len() + 3) / 4) as u64;
+        self.inner.try_fill_bytes(dest)
+    }
+
+}
+
+
+/// What is saved in the checkpoint file of an interrupted generation to resume it :
+/// the generation state and the manifest entries of the files written so far.
+pub struct GenerationCheckpoint {
+    pub state : GenerationState<CountingRng>,
+    pub files : Vec<serde_json::Value>,
+    /// index of the first interaction file of the generation (non-zero if appended to a previous one)
+    pub first_index : usize
+}
+
+impl GenerationCheckpoint {
+
+    /// Writes the checkpoint as JSON, first to a temporary file which is then renamed
+    /// so that an interruption while checkpointing leaves the previous checkpoint intact.
+    pub fn save(&self, gen_ctx : &GeneralContext, path : &Path) -> std::io::Result<()> {
+        let checkpoint = json!({
+            "seed" : self.state.rng.get_seed(),
+            "words" : self.state.rng.get_words(),
+            "num_generated" : self.state.num_generated,
+            "remaining_tries" : self.state.remaining_tries,
+            "shallow_rejections" : self.state.shallow_rejections,
+            "infinite_rejections" : self.state.infinite_rejections,
+            "duplicate_rejections" : self.state.duplicate_rejections,
+            "few_symbols_rejections" : self.state.few_symbols_rejections,
+            "other_rejections" : self.state.other_rejections,
+            "redundant_rejections" : self.state.redundant_rejections,
+            "redundant_streak" : self.state.redundant_streak,
+            "nfa_rejections" : self.state.nfa_rejections,
+            "predicate_rejections" : self.state.predicate_rejections,
+            "trace_rejections" : self.state.trace_rejections,
+            "partial_acceptances" : self.state.partial_acceptances,
+            // sorted so that the same state is always saved the same way
+            "covered_pairs" : self.state.covered_pairs.iter().sorted()
+                .map(|(parent,child)| {
+                    let parent : &'static str = parent.into();
+                    let child : &'static str = child.into();
+                    json!([parent,child])
+                }).collect::<Vec<serde_json::Value>>(),
+            "memo" : self.state.memo.to_json(gen_ctx),
+            "files" : self.files,
+            "first_index" : self.first_index
+        });
+        let tmp_path = path.with_extension("state.tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(serde_json::to_string(&checkpoint).unwrap().as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, path)
+    }
+
+    pub fn load(gen_ctx : &GeneralContext, path : &Path) -> Result<Self,String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let value : serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        let ill_formed = || format!("ill-formed checkpoint file '{:}'", path.display());
+        let get_u64 = |key : &str| value.get(key).and_then(|x| x.as_u64()).ok_or_else(ill_formed);
+        let rng = CountingRng::resume(get_u64("seed")?, get_u64("words")?);
+        let memo = value.get("memo").and_then(|memo| InteractionMemo::from_json(gen_ctx,memo)).ok_or_else(ill_formed)?;
+        let files = value.get("files").and_then(|x| x.as_array()).ok_or_else(ill_formed)?.clone();
+        let get_count = |key : &str| value.get(key).and_then(|x| x.as_u64()).unwrap_or(0) as u32;
+        let get_kind = |x : &serde_json::Value| x.as_str().and_then(|name| SymbolKind::iter().find(|kind| {
+            let as_static_str : &'static str = kind.into();
+            as_static_str == name
+        }));
+        let mut covered_pairs = HashSet::new();
+        for pair in value.get("covered_pairs").and_then(|x| x.as_array()).unwrap_or(&vec![]) {
+            match (pair.get(0).and_then(get_kind), pair.get(1).and_then(get_kind)) {
+                (Some(parent),Some(child)) => {
+                    covered_pairs.insert((parent,child));
+                },
+                _ => {
+                    return Err(ill_formed());
+                }
+            }
+        }
+        let state = GenerationState {
+            rng,
+            memo,
+            num_generated : get_u64("num_generated")? as u32,
+            remaining_tries : get_u64("remaining_tries")? as u32,
+            // absent from the checkpoints written before these rejections were counted
+            shallow_rejections : get_count("shallow_rejections"),
+            infinite_rejections : get_count("infinite_rejections"),
+            duplicate_rejections : get_count("duplicate_rejections"),
+            few_symbols_rejections : get_count("few_symbols_rejections"),
+            other_rejections : get_count("other_rejections"),
+            redundant_rejections : get_count("redundant_rejections"),
+            redundant_streak : get_count("redundant_streak"),
+            covered_pairs,
+            nfa_rejections : get_count("nfa_rejections"),
+            predicate_rejections : get_count("predicate_rejections"),
+            trace_rejections : get_count("trace_rejections"),
+            partial_acceptances : value.get("partial_acceptances").and_then(|x| x.as_array()).unwrap_or(&vec![])
+                .iter().map(|x| x.as_u64().unwrap_or(0) as u32).collect(),
+            last_accepted_traces : None,
+            last_nfa_states : None,
+            last_basic_blocks : vec![],
+            last_attempt_rng : None
+        };
+        // absent from the checkpoints written before generations could be appended
+        let first_index = value.get("first_index").and_then(|x| x.as_u64()).unwrap_or(0) as usize;
+        Ok(Self { state, files, first_index })
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::experiments::interaction_random_gen::interface::{generate_random_interactions_streamed, GenerationParams};
+    use crate::experiments::interaction_random_gen::probas::InteractionSymbolsProbabilities;
+    use super::*;
+
+    #[test]
+    fn counting_rng_resume_test() {
+        let mut rng = CountingRng::new(7);
+        let _ : u64 = rng.gen();
+        let _ : f32 = rng.gen_range(0.0..1.0);
+        let mut bytes = [0_u8;7];
+        rng.fill_bytes(&mut bytes);
+        let mut resumed = CountingRng::resume(7, rng.get_words());
+        for _ in 0..100 {
+            assert_eq!(rng.next_u64(), resumed.next_u64());
+        }
+    }
+
+    #[test]
+    fn resume_generation_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let mut params = GenerationParams::new(6,3,1,1000,InteractionSymbolsProbabilities::default_regular());
+        params.dedup_window = Some(4);
+        // uninterrupted generation, keeping the state after the third interaction
+        let mut state = GenerationState::new(&params, CountingRng::new(3));
+        let mut uninterrupted = vec![];
+        let mut checkpoint = None;
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut state,&mut |i,got_state| {
+            uninterrupted.push(i);
+            if got_state.num_generated == 3 {
+                checkpoint = Some(GenerationCheckpoint{state : got_state.clone(), files : vec![], first_index : 0});
+            }
+        });
+        // resuming from the checkpoint saved to and reloaded from disk
+        let path = std::env::temp_dir().join(format!("hibou_resume_test_{:}.state", std::process::id()));
+        checkpoint.unwrap().save(&gen_ctx,&path).unwrap();
+        let mut reloaded = GenerationCheckpoint::load(&gen_ctx,&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut resumed = vec![];
+        generate_random_interactions_streamed(&gen_ctx,&params,&mut reloaded.state,&mut |i,_| resumed.push(i));
+        assert_eq!(resumed.as_slice(), &uninterrupted[3..]);
+    }
+
+}
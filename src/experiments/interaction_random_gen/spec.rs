@@ -0,0 +1,211 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+use std::fs;
+
+
+/// Keys of an experiment spec which are not arguments of the generation.
+const SPEC_CONFIG_KEY : &str = "config";
+const SPEC_NAME_KEY : &str = "name";
+/// The output folder of each configuration is its own sub-folder hence it cannot be given.
+const SPEC_RESERVED_KEY : &str = "folder";
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum GenerationSpecError {
+    SpecFileError(String),
+    NoConfiguration,
+    /// the index of the configuration in the spec
+    MissingName(usize),
+    InvalidName(String),
+    DuplicateName(String),
+    /// the name of the configuration and the key
+    ReservedKey(String,String),
+    InvalidValue(String,String)
+}
+
+impl std::fmt::Display for GenerationSpecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationSpecError::SpecFileError(e) => {
+                write!(f, "could not read experiment spec : {:}", e)
+            },
+            GenerationSpecError::NoConfiguration => {
+                write!(f, "experiment spec declares no [[{:}]] configuration", SPEC_CONFIG_KEY)
+            },
+            GenerationSpecError::MissingName(idx) => {
+                write!(f, "configuration #{:} has no '{:}'", idx + 1, SPEC_NAME_KEY)
+            },
+            GenerationSpecError::InvalidName(name) => {
+                write!(f, "configuration name '{:}' must be a non-empty folder name", name)
+            },
+            GenerationSpecError::DuplicateName(name) => {
+                write!(f, "several configurations are named '{:}'", name)
+            },
+            GenerationSpecError::ReservedKey(name,key) => {
+                write!(f, "configuration '{:}' cannot give '{:}' as each configuration is generated into its own sub-folder", name, key)
+            },
+            GenerationSpecError::InvalidValue(name,key) => {
+                write!(f, "value of '{:}' in configuration '{:}' must be a string, a number or a boolean", key, name)
+            }
+        }
+    }
+}
+
+
+/// Value of a generation argument : the value of an option or whether a flag is set.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GenerationSpecValue {
+    Value(String),
+    Flag(bool)
+}
+
+/// A named generation configuration, given as the arguments of 'rng_gen_raw_interactions' by their names.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GenerationSpecConfig {
+    pub name : String,
+    pub args : BTreeMap<String,GenerationSpecValue>
+}
+
+/// Configurations of an experiment, in the order in which they are declared.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GenerationSpec {
+    pub configs : Vec<GenerationSpecConfig>
+}
+
+impl GenerationSpec {
+
+    fn get_value(name : &str, key : &str, value : &toml::Value) -> Result<GenerationSpecValue,GenerationSpecError> {
+        match value {
+            toml::Value::String(x) => Ok(GenerationSpecValue::Value(x.clone())),
+            toml::Value::Integer(x) => Ok(GenerationSpecValue::Value(x.to_string())),
+            toml::Value::Float(x) => Ok(GenerationSpecValue::Value(x.to_string())),
+            toml::Value::Boolean(x) => Ok(GenerationSpecValue::Flag(*x)),
+            _ => Err(GenerationSpecError::InvalidValue(name.to_string(), key.to_string()))
+        }
+    }
+
+    /// Reads an experiment spec from a TOML document in which each configuration is a '[[config]]' table
+    /// with a 'name' and the arguments of 'rng_gen_raw_interactions' as keys, e.g. 'seed = 3' or 'diverse = true'.
+    /// Top-level keys are arguments shared by all the configurations, which may override them.
+    pub fn from_toml(toml_str : &str) -> Result<Self,GenerationSpecError> {
+        let table = match toml_str.parse::<toml::Table>() {
+            Err(e) => {
+                return Err(GenerationSpecError::SpecFileError(e.to_string()));
+            },
+            Ok(got_table) => {
+                got_table
+            }
+        };
+        let mut shared = BTreeMap::new();
+        for (key,value) in table.iter().filter(|(key,_)| key.as_str() != SPEC_CONFIG_KEY) {
+            if key == SPEC_RESERVED_KEY || key == SPEC_NAME_KEY {
+                return Err(GenerationSpecError::ReservedKey("*".to_string(), key.clone()));
+            }
+            shared.insert(key.clone(), Self::get_value("*",key,value)?);
+        }
+        let raw_configs = match table.get(SPEC_CONFIG_KEY) {
+            Some(toml::Value::Array(got)) if !got.is_empty() => {
+                got
+            },
+            _ => {
+                return Err(GenerationSpecError::NoConfiguration);
+            }
+        };
+        let mut configs : Vec<GenerationSpecConfig> = vec![];
+        for (idx,raw_config) in raw_configs.iter().enumerate() {
+            let raw_table = match raw_config {
+                toml::Value::Table(got) => got,
+                _ => {
+                    return Err(GenerationSpecError::SpecFileError(format!("configuration #{:} is not a table", idx + 1)));
+                }
+            };
+            let name = match raw_table.get(SPEC_NAME_KEY) {
+                Some(toml::Value::String(got)) => got.clone(),
+                _ => {
+                    return Err(GenerationSpecError::MissingName(idx));
+                }
+            };
+            if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c == '/' || c == '\\') || name == "." || name == ".." {
+                return Err(GenerationSpecError::InvalidName(name));
+            }
+            if configs.iter().any(|config| config.name == name) {
+                return Err(GenerationSpecError::DuplicateName(name));
+            }
+            let mut args = shared.clone();
+            for (key,value) in raw_table.iter().filter(|(key,_)| key.as_str() != SPEC_NAME_KEY) {
+                if key == SPEC_RESERVED_KEY {
+                    return Err(GenerationSpecError::ReservedKey(name, key.clone()));
+                }
+                args.insert(key.clone(), Self::get_value(&name,key,value)?);
+            }
+            configs.push(GenerationSpecConfig{ name, args });
+        }
+        Ok(GenerationSpec{ configs })
+    }
+
+    pub fn from_toml_file(file_path : &str) -> Result<Self,GenerationSpecError> {
+        match fs::read_to_string(file_path) {
+            Err(e) => {
+                Err(GenerationSpecError::SpecFileError(e.to_string()))
+            },
+            Ok(toml_str) => {
+                Self::from_toml(&toml_str)
+            }
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_test() {
+        let spec = GenerationSpec::from_toml("hsf = \"s.hsf\"\nprobas = \"custom\"\nseed = 1\n\
+            [[config]]\nname = \"small\"\nmax_depth = 3\n\
+            [[config]]\nname = \"large\"\nseed = 2\nmax_depth = 6\ndiverse = true\nploop = 0.25\n").unwrap();
+        assert_eq!(spec.configs.len(), 2);
+        // declaration order is kept and shared arguments are overridden
+        assert_eq!(spec.configs[0].name, "small");
+        assert_eq!(spec.configs[0].args.get("seed"), Some(&GenerationSpecValue::Value("1".to_string())));
+        assert_eq!(spec.configs[0].args.get("max_depth"), Some(&GenerationSpecValue::Value("3".to_string())));
+        assert_eq!(spec.configs[0].args.get("diverse"), None);
+        assert_eq!(spec.configs[1].name, "large");
+        assert_eq!(spec.configs[1].args.get("seed"), Some(&GenerationSpecValue::Value("2".to_string())));
+        assert_eq!(spec.configs[1].args.get("hsf"), Some(&GenerationSpecValue::Value("s.hsf".to_string())));
+        assert_eq!(spec.configs[1].args.get("diverse"), Some(&GenerationSpecValue::Flag(true)));
+        assert_eq!(spec.configs[1].args.get("ploop"), Some(&GenerationSpecValue::Value("0.25".to_string())));
+        // ***
+        assert_eq!(GenerationSpec::from_toml("hsf = \"s.hsf\""), Err(GenerationSpecError::NoConfiguration));
+        assert_eq!(GenerationSpec::from_toml("[[config]]\nseed = 1"), Err(GenerationSpecError::MissingName(0)));
+        assert_eq!(GenerationSpec::from_toml("[[config]]\nname = \"a\"\n[[config]]\nname = \"a\""),
+                   Err(GenerationSpecError::DuplicateName("a".to_string())));
+        assert_eq!(GenerationSpec::from_toml("[[config]]\nname = \"a/b\""),
+                   Err(GenerationSpecError::InvalidName("a/b".to_string())));
+        assert_eq!(GenerationSpec::from_toml("[[config]]\nname = \"a\"\nfolder = \"out\""),
+                   Err(GenerationSpecError::ReservedKey("a".to_string(),"folder".to_string())));
+        assert_eq!(GenerationSpec::from_toml("[[config]]\nname = \"a\"\nseed = [1,2]"),
+                   Err(GenerationSpecError::InvalidValue("a".to_string(),"seed".to_string())));
+        assert!(matches!(GenerationSpec::from_toml("[[config]\nname = \"a\""), Err(GenerationSpecError::SpecFileError(_))));
+    }
+}
@@ -0,0 +1,214 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use rand::Rng;
+use serde_json::json;
+
+use crate::core::language::syntax::metrics::SymbolCounts;
+use crate::experiments::interaction_random_gen::histogram::SizeHistogram;
+use crate::experiments::interaction_random_gen::interface::GenerationState;
+
+
+/// Statistics of the generations into an output folder, accumulated in its 'stats.json' file
+/// across invocations so that a corpus generated in several passes gets cumulative statistics.
+/// Every field is a count so that merging the statistics of two generations is a sum.
+#[derive(Clone, Debug, Default)]
+pub struct CumulativeGenerationStats {
+    /// number of merged generations
+    pub runs : u64,
+    pub num_interactions : u64,
+    /// number of interactions and of attempts of the generations which tracked their failed attempts
+    /// (i.e. which were not split among worker threads)
+    pub tracked_interactions : u64,
+    pub attempts : u64,
+    /// number of failed attempts per reason (as in 'GenerationState')
+    pub rejections : BTreeMap<String,u64>,
+    /// number of occurrences of each symbol
+    pub symbols : SymbolCounts,
+    /// number of interactions per size (i.e. number of symbols)
+    /// which amounts to a histogram with one bucket per size, that can be merged exactly
+    pub sizes : BTreeMap<u64,u64>
+}
+
+impl CumulativeGenerationStats {
+
+    /// Statistics of a single generation, which does not account for its interactions yet (see 'add_manifest_entry').
+    pub fn new_run() -> Self {
+        Self { runs : 1, ..Default::default() }
+    }
+
+    /// Accounts for the attempts of a generation carried on sequentially.
+    pub fn add_state<R : Rng>(&mut self, state : &GenerationState<R>) {
+        self.tracked_interactions += state.num_generated as u64;
+        self.attempts += (state.num_generated + state.failed_tries()) as u64;
+        for (reason,count) in [("shallow",state.shallow_rejections),
+                               ("infinite",state.infinite_rejections),
+                               ("duplicate",state.duplicate_rejections),
+                               ("few_symbols",state.few_symbols_rejections),
+                               ("other",state.other_rejections),
+                               ("redundant",state.redundant_rejections),
+                               ("nfa",state.nfa_rejections),
+                               ("predicate",state.predicate_rejections),
+                               ("trace",state.trace_rejections)] {
+            *self.rejections.entry(reason.to_string()).or_insert(0) += count as u64;
+        }
+    }
+
+    /// Accounts for an interaction from its entry in the manifest.
+    pub fn add_manifest_entry(&mut self, entry : &serde_json::Value) {
+        self.num_interactions += 1;
+        if let Some(symbols) = entry.get("symbols") {
+            self.symbols.add_json(symbols);
+        }
+        if let Some(size) = entry.get("num_symbols").and_then(|x| x.as_u64()) {
+            *self.sizes.entry(size).or_insert(0) += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other : &CumulativeGenerationStats) {
+        self.runs += other.runs;
+        self.num_interactions += other.num_interactions;
+        self.tracked_interactions += other.tracked_interactions;
+        self.attempts += other.attempts;
+        for (reason,count) in &other.rejections {
+            *self.rejections.entry(reason.clone()).or_insert(0) += *count;
+        }
+        self.symbols.add_json(&other.symbols.to_json());
+        for (size,count) in &other.sizes {
+            *self.sizes.entry(*size).or_insert(0) += *count;
+        }
+    }
+
+    /// Fraction of the tracked attempts which yielded an interaction, None if no attempt was tracked.
+    pub fn get_acceptance_rate(&self) -> Option<f64> {
+        if self.attempts == 0 {
+            None
+        } else {
+            Some(self.tracked_interactions as f64 / self.attempts as f64)
+        }
+    }
+
+    pub fn get_size_histogram(&self, num_buckets : u32) -> SizeHistogram {
+        let sizes : Vec<u64> = self.sizes.iter()
+            .flat_map(|(size,count)| std::iter::repeat(*size).take(*count as usize))
+            .collect();
+        SizeHistogram::from_sizes(&sizes, num_buckets)
+    }
+
+    /// The acceptance rate is only written for reading and is recomputed from the counts when merging.
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "runs" : self.runs,
+            "num_interactions" : self.num_interactions,
+            "tracked_interactions" : self.tracked_interactions,
+            "attempts" : self.attempts,
+            "acceptance_rate" : self.get_acceptance_rate(),
+            "rejections" : self.rejections,
+            "symbols" : self.symbols.to_json(),
+            "sizes" : self.sizes.iter()
+                .map(|(size,count)| (size.to_string(),json!(count)))
+                .collect::<serde_json::Map<String,serde_json::Value>>()
+        })
+    }
+
+    pub fn from_json(value : &serde_json::Value) -> Option<Self> {
+        let get_u64 = |key : &str| value.get(key).and_then(|x| x.as_u64());
+        let mut rejections = BTreeMap::new();
+        for (reason,count) in value.get("rejections")?.as_object()? {
+            rejections.insert(reason.clone(), count.as_u64()?);
+        }
+        let mut symbols = SymbolCounts::default();
+        symbols.add_json(value.get("symbols")?);
+        let mut sizes = BTreeMap::new();
+        for (size,count) in value.get("sizes")?.as_object()? {
+            sizes.insert(size.parse::<u64>().ok()?, count.as_u64()?);
+        }
+        Some(Self { runs : get_u64("runs")?,
+            num_interactions : get_u64("num_interactions")?,
+            tracked_interactions : get_u64("tracked_interactions")?,
+            attempts : get_u64("attempts")?,
+            rejections,
+            symbols,
+            sizes })
+    }
+
+    /// Merges the statistics into the 'stats.json' file of the folder (created if it does not exist)
+    /// and returns the merged statistics.
+    /// As with the manifest, the file is first written to a temporary file which is then renamed.
+    pub fn append_to_folder(&self, output_folder : &str) -> Result<CumulativeGenerationStats,String> {
+        let path : PathBuf = [output_folder, "stats.json"].iter().collect();
+        let mut merged = if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            let value : serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+            CumulativeGenerationStats::from_json(&value)
+                .ok_or_else(|| format!("ill-formed statistics file '{:}'", path.display()))?
+        } else {
+            CumulativeGenerationStats::default()
+        };
+        merged.merge(self);
+        let tmp_path = Path::new(output_folder).join("stats.json.tmp");
+        let mut file = File::create(&tmp_path).map_err(|e| e.to_string())?;
+        file.write_all(serde_json::to_string_pretty(&merged.to_json()).unwrap().as_bytes()).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
+        Ok(merged)
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(num_symbols : u64, num_actions : u64, num_seq : u64) -> serde_json::Value {
+        json!({"file" : "i0.hif", "num_symbols" : num_symbols, "symbols" : {"Action" : num_actions, "Seq" : num_seq}})
+    }
+
+    #[test]
+    fn merge_stats_test() {
+        let mut first = CumulativeGenerationStats::new_run();
+        first.add_manifest_entry(&entry(3,2,1));
+        first.add_manifest_entry(&entry(5,3,2));
+        first.tracked_interactions = 2;
+        first.attempts = 8;
+        first.rejections.insert("duplicate".to_string(), 6);
+        let mut second = CumulativeGenerationStats::new_run();
+        second.add_manifest_entry(&entry(5,3,2));
+        second.add_manifest_entry(&entry(12,7,4));
+        second.tracked_interactions = 2;
+        second.attempts = 2;
+        // the merge goes through the json written into 'stats.json'
+        let mut merged = CumulativeGenerationStats::from_json(&first.to_json()).unwrap();
+        merged.merge(&CumulativeGenerationStats::from_json(&second.to_json()).unwrap());
+        assert_eq!(merged.runs, 2);
+        assert_eq!(merged.num_interactions, 4);
+        assert_eq!(merged.rejections, btreemap!{"duplicate".to_string() => 6});
+        assert_eq!(merged.symbols.to_json()["Action"], json!(15));
+        assert_eq!(merged.symbols.to_json()["Seq"], json!(9));
+        assert_eq!(merged.sizes, btreemap!{3 => 1, 5 => 2, 12 => 1});
+        // the acceptance rate is that of all the attempts and not the average of the rates
+        assert_eq!(merged.get_acceptance_rate(), Some(0.4));
+        assert_eq!(merged.get_size_histogram(3).buckets, vec![(3,6,3),(7,10,0),(11,12,1)]);
+    }
+
+}
@@ -0,0 +1,313 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::HashMap;
+use std::fmt::Formatter;
+use std::fs;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+use crate::core::general_context::GeneralContext;
+
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum GenerationWeightsError {
+    WeightsFileError(String),
+    UnknownMessage(String),
+    UnknownLifeline(String),
+    /// the name of the message or lifeline
+    InvalidWeight(String)
+}
+
+impl std::fmt::Display for GenerationWeightsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationWeightsError::WeightsFileError(e) => {
+                write!(f, "could not read weights : {:}", e)
+            },
+            GenerationWeightsError::UnknownMessage(ms_name) => {
+                write!(f, "weight given for unknown message '{:}'", ms_name)
+            },
+            GenerationWeightsError::UnknownLifeline(lf_name) => {
+                write!(f, "weight given for unknown lifeline '{:}'", lf_name)
+            },
+            GenerationWeightsError::InvalidWeight(name) => {
+                write!(f, "weight for '{:}' must be a non-negative number", name)
+            }
+        }
+    }
+}
+
+
+
+/// Relative weights with which elements identified by their ids are drawn.
+/// Elements absent from the map have weight 1.
+#[derive(Clone, PartialEq, Debug, Default)]
+struct IdWeights {
+    weights : HashMap<usize,f64>
+}
+
+impl IdWeights {
+
+    fn get_weight(&self, id : usize) -> f64 {
+        *self.weights.get(&id).unwrap_or(&1.0)
+    }
+
+    fn is_uniform(&self) -> bool {
+        self.weights.values().all(|w| *w == 1.0)
+    }
+
+    /// Falls back to a uniform draw if all the candidates have weight 0.
+    fn choose<R : Rng>(&self, candidates : &[usize], rng : &mut R) -> usize {
+        match WeightedIndex::new(candidates.iter().map(|id| self.get_weight(*id))) {
+            Err(_) => {
+                *candidates.choose(rng).unwrap()
+            },
+            Ok(dist) => {
+                candidates[dist.sample(rng)]
+            }
+        }
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct MessageWeights {
+    weights : IdWeights
+}
+
+impl MessageWeights {
+
+    pub fn set_weight(&mut self, ms_id : usize, weight : f64) {
+        self.weights.weights.insert(ms_id,weight);
+    }
+
+    pub fn get_weight(&self, ms_id : usize) -> f64 {
+        self.weights.get_weight(ms_id)
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.weights.is_uniform()
+    }
+
+    /// Draws one of the messages of the signature.
+    /// Uniform weights draw exactly as 'rng.gen_range' so that seeds keep yielding the same interactions.
+    pub fn sample_message<R : Rng>(&self, signature : &GeneralContext, rng : &mut R) -> usize {
+        if self.is_uniform() {
+            rng.gen_range(0..signature.get_ms_num())
+        } else {
+            self.weights.choose(&(0..signature.get_ms_num()).collect::<Vec<usize>>(),rng)
+        }
+    }
+}
+
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LifelineWeights {
+    weights : IdWeights
+}
+
+impl LifelineWeights {
+
+    pub fn set_weight(&mut self, lf_id : usize, weight : f64) {
+        self.weights.weights.insert(lf_id,weight);
+    }
+
+    pub fn get_weight(&self, lf_id : usize) -> f64 {
+        self.weights.get_weight(lf_id)
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.weights.is_uniform()
+    }
+
+    /// Draws one of the candidate lifelines.
+    pub fn sample_lifeline<R : Rng>(&self, candidates : &[usize], rng : &mut R) -> usize {
+        if self.is_uniform() {
+            *candidates.choose(rng).unwrap()
+        } else {
+            self.weights.choose(candidates,rng)
+        }
+    }
+
+    /// Draws distinct candidate lifelines one after the other (i.e. without replacement).
+    pub fn sample_distinct_lifelines<R : Rng>(&self, candidates : &[usize], number : usize, rng : &mut R) -> Vec<usize> {
+        let mut remaining = candidates.to_vec();
+        let mut drawn = vec![];
+        while drawn.len() < number && !remaining.is_empty() {
+            let lf_id = self.sample_lifeline(&remaining,rng);
+            remaining.retain(|x| *x != lf_id);
+            drawn.push(lf_id);
+        }
+        drawn
+    }
+}
+
+
+/// Weights with which messages and lifelines are drawn when generating actions.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GenerationWeights {
+    pub messages : MessageWeights,
+    pub lifelines : LifelineWeights,
+    /// probability with which a generated emission towards the environment is directly followed
+    /// (under strict) by the reception of its message on another lifeline
+    pub handshake_bias : f64
+}
+
+impl GenerationWeights {
+
+    /// Reads weights from the optional 'messages' and 'lifelines' tables of a TOML document
+    /// (which may be the probability profile itself), e.g. '[messages]' followed by 'm1 = 5'.
+    pub fn from_toml(signature : &GeneralContext, toml_str : &str) -> Result<Self,GenerationWeightsError> {
+        let table = match toml_str.parse::<toml::Table>() {
+            Err(e) => {
+                return Err(GenerationWeightsError::WeightsFileError(e.to_string()));
+            },
+            Ok(got_table) => {
+                got_table
+            }
+        };
+        let mut weights = GenerationWeights::default();
+        for (table_key,is_message) in [("messages",true),("lifelines",false)] {
+            let sub_table = match table.get(table_key) {
+                None => {
+                    continue;
+                },
+                Some(toml::Value::Table(sub_table)) => {
+                    sub_table
+                },
+                Some(_) => {
+                    return Err(GenerationWeightsError::WeightsFileError(format!("'{:}' must be a table", table_key)));
+                }
+            };
+            for (name,value) in sub_table {
+                let weight = match value {
+                    toml::Value::Float(x) => *x,
+                    toml::Value::Integer(x) => *x as f64,
+                    _ => {
+                        return Err(GenerationWeightsError::InvalidWeight(name.clone()));
+                    }
+                };
+                if !(weight >= 0.0) {
+                    return Err(GenerationWeightsError::InvalidWeight(name.clone()));
+                }
+                if is_message {
+                    match signature.get_ms_id(name) {
+                        None => {
+                            return Err(GenerationWeightsError::UnknownMessage(name.clone()));
+                        },
+                        Some(ms_id) => {
+                            weights.messages.set_weight(ms_id,weight);
+                        }
+                    }
+                } else {
+                    match signature.get_lf_id(name) {
+                        None => {
+                            return Err(GenerationWeightsError::UnknownLifeline(name.clone()));
+                        },
+                        Some(lf_id) => {
+                            weights.lifelines.set_weight(lf_id,weight);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(weights)
+    }
+
+    pub fn from_toml_file(signature : &GeneralContext, file_path : &str) -> Result<Self,GenerationWeightsError> {
+        match fs::read_to_string(file_path) {
+            Err(e) => {
+                Err(GenerationWeightsError::WeightsFileError(e.to_string()))
+            },
+            Ok(toml_str) => {
+                Self::from_toml(signature,&toml_str)
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    fn get_signature() -> GeneralContext {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_lf("l3".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        gen_ctx.add_msg("m3".to_string());
+        gen_ctx
+    }
+
+    #[test]
+    fn weights_from_toml_test() {
+        let gen_ctx = get_signature();
+        let weights = GenerationWeights::from_toml(&gen_ctx,"seq = 1.0\n[messages]\nm1 = 5\nm2 = 0.5\n[lifelines]\nl3 = 0").unwrap();
+        assert_eq!(weights.messages.get_weight(0), 5.0);
+        assert_eq!(weights.messages.get_weight(1), 0.5);
+        assert_eq!(weights.messages.get_weight(2), 1.0);
+        assert_eq!(weights.lifelines.get_weight(2), 0.0);
+        assert!(weights.lifelines.get_weight(0) == 1.0 && !weights.lifelines.is_uniform());
+        assert!(GenerationWeights::from_toml(&gen_ctx,"").unwrap().messages.is_uniform());
+        assert_eq!(GenerationWeights::from_toml(&gen_ctx,"[messages]\nm4 = 1"),
+                   Err(GenerationWeightsError::UnknownMessage("m4".to_string())));
+        assert_eq!(GenerationWeights::from_toml(&gen_ctx,"[lifelines]\nl1 = -1"),
+                   Err(GenerationWeightsError::InvalidWeight("l1".to_string())));
+    }
+
+    #[test]
+    fn weighted_sampling_test() {
+        let gen_ctx = get_signature();
+        let mut weights = GenerationWeights::default();
+        weights.messages.set_weight(0,6.0);
+        weights.messages.set_weight(1,3.0);
+        weights.lifelines.set_weight(0,0.0);
+        let mut rng = StdRng::seed_from_u64(0);
+        let num_draws = 10000;
+        let mut ms_counts = vec![0_u32;3];
+        let mut lf_counts = vec![0_u32;3];
+        for _ in 0..num_draws {
+            ms_counts[weights.messages.sample_message(&gen_ctx,&mut rng)] += 1;
+            lf_counts[weights.lifelines.sample_lifeline(&[0,1,2],&mut rng)] += 1;
+        }
+        // m3 is absent from the map and has weight 1
+        for (ms_id,expected) in [(0,0.6),(1,0.3),(2,0.1)] {
+            let got = ms_counts[ms_id] as f64 / num_draws as f64;
+            assert!((got - expected).abs() < 0.02, "message {:} drawn with frequency {:}", ms_id, got);
+        }
+        assert_eq!(lf_counts[0], 0);
+        let got = lf_counts[1] as f64 / num_draws as f64;
+        assert!((got - 0.5).abs() < 0.02);
+        // distinct draws never repeat lifelines nor draw ones with weight 0 while others remain
+        for _ in 0..100 {
+            let drawn = weights.lifelines.sample_distinct_lifelines(&[0,1,2],2,&mut rng);
+            assert_eq!(drawn.len(), 2);
+            assert!(!drawn.contains(&0));
+        }
+    }
+
+}
@@ -0,0 +1,178 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use rand::prelude::StdRng;
+use rand::Rng;
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::semantics::execute::execute_interaction;
+use crate::core::execution::semantics::frontier::global_frontier;
+use crate::core::execution::semantics::membership::{is_multi_trace_accepted, MembershipVerdict};
+use crate::core::execution::trace::multitrace::Trace;
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// Samples a trace accepted by the interaction via a random walk on its operational semantics.
+/// At each step, either one of the immediately executable actions is executed or, if the
+/// remaining interaction accepts the empty trace, the walk may stop there.
+/// Each loop instantiation counts towards 'max_loop_depth' (as in the exploration process),
+/// and actions which would exceed this bound are not executable.
+/// Returns None if the walk gets stuck before reaching an accepting state.
+pub fn sample_accepted_trace(interaction : &Interaction,
+                             max_loop_depth : u32,
+                             rng : &mut StdRng) -> Option<Trace> {
+    let mut trace : Trace = vec![];
+    let mut current = interaction.clone();
+    let mut loop_depth = 0;
+    loop {
+        let executable : Vec<_> = global_frontier(&current,true).into_iter()
+            .filter(|frt_elt| loop_depth + frt_elt.max_loop_depth <= max_loop_depth)
+            .collect();
+        let can_stop = current.express_empty();
+        let num_choices = executable.len() + if can_stop {1} else {0};
+        if num_choices == 0 {
+            return None;
+        }
+        let choice = rng.gen_range(0..num_choices);
+        if choice == executable.len() {
+            return Some(trace);
+        }
+        let frt_elt = executable.get(choice).unwrap();
+        loop_depth += frt_elt.max_loop_depth;
+        trace.push(frt_elt.target_actions.clone());
+        current = execute_interaction(&current,
+                                      &frt_elt.position,
+                                      &frt_elt.target_lf_ids,
+                                      false).interaction;
+    }
+}
+
+/// Samples 'num_traces' accepted traces (possibly with repetitions).
+/// An interaction which only accepts the empty trace yields a single empty trace.
+/// Walks that get stuck are retried at most 'num_traces * 100' times in total.
+pub fn sample_accepted_traces(interaction : &Interaction,
+                              num_traces : u32,
+                              max_loop_depth : u32,
+                              rng : &mut StdRng) -> Vec<Trace> {
+    if interaction.express_empty() && global_frontier(interaction,true).is_empty() {
+        return vec![vec![]];
+    }
+    let mut traces = vec![];
+    let mut max_tries = num_traces.saturating_mul(100);
+    while (traces.len() as u32) < num_traces {
+        match sample_accepted_trace(interaction,max_loop_depth,rng) {
+            Some(trace) => {
+                traces.push(trace);
+            },
+            None => {
+                if max_tries == 0 {
+                    break;
+                }
+                max_tries -= 1;
+            }
+        }
+    }
+    traces
+}
+
+/// Derives up to 'num_traces' distinct traces rejected by the interaction from its accepted traces,
+/// each by either dropping one of the actions of an accepted trace or swapping two of its consecutive actions.
+/// Derived traces which happen to still be accepted are skipped.
+/// Derivations are tried at most 'num_traces * 100' times in total.
+pub fn derive_rejected_traces(interaction : &Interaction,
+                              co_localizations : &CoLocalizations,
+                              accepted : &[Trace],
+                              num_traces : u32,
+                              rng : &mut StdRng) -> Vec<Trace> {
+    let mutable : Vec<&Trace> = accepted.iter().filter(|trace| !trace.is_empty()).collect();
+    let mut traces : Vec<Trace> = vec![];
+    if mutable.is_empty() {
+        return traces;
+    }
+    let mut max_tries = num_traces.saturating_mul(100);
+    while (traces.len() as u32) < num_traces && max_tries > 0 {
+        max_tries -= 1;
+        let mut trace = mutable[rng.gen_range(0..mutable.len())].clone();
+        let idx = rng.gen_range(0..trace.len());
+        if trace.len() > 1 && rng.gen_bool(0.5) {
+            let other = if idx + 1 < trace.len() { idx + 1 } else { idx - 1 };
+            trace.swap(idx,other);
+        } else {
+            trace.remove(idx);
+        }
+        if traces.contains(&trace) {
+            continue;
+        }
+        if is_multi_trace_accepted(interaction,co_localizations,&vec![trace.clone()]) == MembershipVerdict::Accepted {
+            continue;
+        }
+        traces.push(trace);
+    }
+    traces
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    #[test]
+    fn sample_traces_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(sample_accepted_traces(&Interaction::Empty,5,1,&mut rng), vec![vec![]]);
+        // ***
+        let a = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b = Interaction::Emission(EmissionAction::new(0,1,CommunicationSynchronicity::Asynchronous,vec![]));
+        let i = Interaction::Strict(
+            Box::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(a))),
+            Box::new(b)
+        );
+        for max_loop_depth in 0..3 {
+            for trace in sample_accepted_traces(&i,20,max_loop_depth,&mut rng) {
+                // the loop is instantiated at most 'max_loop_depth' times and 'b' always ends the trace
+                assert!(trace.len() as u32 <= max_loop_depth + 1);
+                assert_eq!(trace.last().unwrap().iter().next().unwrap().ms_id, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn rejected_traces_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b = Interaction::Emission(EmissionAction::new(0,1,CommunicationSynchronicity::Asynchronous,vec![]));
+        let i = Interaction::Strict(Box::new(a.clone()),Box::new(b.clone()));
+        let co_localizations = CoLocalizations::get_trivial_partition(1);
+        let accepted = sample_accepted_traces(&i,3,1,&mut rng);
+        // 'a' then 'b' can only be mutated into 'a', 'b' and 'b' then 'a'
+        let rejected = derive_rejected_traces(&i,&co_localizations,&accepted,5,&mut rng);
+        assert_eq!(rejected.len(), 3);
+        for trace in &rejected {
+            assert_ne!(is_multi_trace_accepted(&i,&co_localizations,&vec![trace.clone()]), MembershipVerdict::Accepted);
+        }
+        // mutants of the traces of 'par(a,b)' which swap its actions are still accepted
+        let i = Interaction::Par(Box::new(a),Box::new(b));
+        let accepted = sample_accepted_traces(&i,3,1,&mut rng);
+        for trace in derive_rejected_traces(&i,&co_localizations,&accepted,5,&mut rng) {
+            assert_eq!(trace.len(), 1);
+        }
+    }
+
+}
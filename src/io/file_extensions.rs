@@ -25,4 +25,12 @@ pub static HIBOU_CONFIGURATION_FILE_EXTENSION : &'static str = "hcf";
 
 pub static HIBOU_INTERACTION_FILE_EXTENSION : &'static str = "hif";
 
-
+pub static HIBOU_SEXPR_INTERACTION_FILE_EXTENSION : &'static str = "hsx";
+
+/// Version of the .hif format written by this version of hibou
+/// (version 2 added message parameters, lifeline groups and co-regions over all lifelines).
+/// Files which do not carry a version tag are treated as version 1.
+pub static HIF_FORMAT_VERSION : u32 = 2;
+pub static HIF_VERSION_TAG : &'static str = "@hif-version";
+/// Tag of the comment recording where a generated interaction comes from (see 'HifProvenance').
+pub static HIF_PROVENANCE_TAG : &'static str = "@generated";
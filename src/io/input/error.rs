@@ -29,12 +29,14 @@ pub enum HibouParsingError {
     ProcessPriorityError(String),
     // ***
     MissingMessageDeclarationError(String),
+    MissingMessageParameterDeclarationError(String),
     MissingLifelineDeclarationError(String),
     MissingGateDeclarationError(String),
     MissingLifelineOrGateDeclarationError(String),
     // ***
     EmissionDefinitionError(String),
     OtherDefinitionError(String),
+    IncludeError(String),
     // ***
     NonDisjointTraceComponents,
     IllDefinedTraceComponents(String)
@@ -69,6 +71,9 @@ impl fmt::Display for HibouParsingError {
             HibouParsingError::MissingMessageDeclarationError(sub_e) => {
                 return write!(f, "{}", format!("error while parsing ; missing message declaration : {:}", sub_e));
             },
+            HibouParsingError::MissingMessageParameterDeclarationError(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; missing message parameter declaration : {:}", sub_e));
+            },
             HibouParsingError::MissingLifelineDeclarationError(sub_e) => {
                 return write!(f, "{}", format!("error while parsing ; missing lifeline declaration : {:}", sub_e));
             },
@@ -85,6 +90,9 @@ impl fmt::Display for HibouParsingError {
             HibouParsingError::OtherDefinitionError(sub_e) => {
                 return write!(f, "{}", format!("error while parsing ; other definition error : {:}", sub_e));
             },
+            HibouParsingError::IncludeError(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; include error : {:}", sub_e));
+            },
             // ***
             HibouParsingError::NonDisjointTraceComponents => {
                 return write!(f, "{}", format!("error while parsing ; non disjoint trace canals"));
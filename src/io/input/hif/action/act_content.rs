@@ -56,56 +56,63 @@ pub fn parse_comm_act_origin(gen_ctx : &GeneralContext, origin_pair : Pair<Rule>
     }
 }
 
-pub fn parse_comm_content(gen_ctx : &GeneralContext, comm_content_pair : Pair<Rule>) -> Result<(CommunicationSynchronicity,usize),HibouParsingError> {
+pub fn parse_comm_content(gen_ctx : &GeneralContext, comm_content_pair : Pair<Rule>) -> Result<(CommunicationSynchronicity,usize,Option<usize>),HibouParsingError> {
     let mut contents = comm_content_pair.into_inner();
-    let first_pair = contents.next().unwrap();
     let mut comm_type = CommunicationSynchronicity::Asynchronous;
-    let mut got_ms_id : Option<usize> = None;
-    match first_pair.as_rule() {
+    let mut got_ms_pair = contents.next().unwrap();
+    match got_ms_pair.as_rule() {
         Rule::COMM_ASYNCH => {
             comm_type = CommunicationSynchronicity::Asynchronous;
+            got_ms_pair = contents.next().unwrap();
         },
         Rule::COMM_SYNCH => {
             comm_type = CommunicationSynchronicity::Synchronous;
+            got_ms_pair = contents.next().unwrap();
         },
-        Rule::HIBOU_LABEL => {
-            let ms_name : String = first_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-            match gen_ctx.get_ms_id( &ms_name ) {
-                None => {
-                    return Err( HibouParsingError::MissingMessageDeclarationError( ms_name ) );
-                },
-                Some( ms_id ) => {
-                    got_ms_id = Some(ms_id);
-                }
-            }
-        },
+        Rule::HIBOU_LABEL => {},
         _ => {
-            panic!("what rule then ? : {:?}", first_pair.as_rule() );
+            panic!("what rule then ? : {:?}", got_ms_pair.as_rule() );
         }
     }
     // ***
-    match got_ms_id {
+    let ms_name : String = got_ms_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+    match gen_ctx.get_ms_id( &ms_name ) {
         None => {
-            let second_pair = contents.next().unwrap();
-            match second_pair.as_rule() {
-                Rule::HIBOU_LABEL => {
-                    let ms_name : String = second_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-                    match gen_ctx.get_ms_id( &ms_name ) {
-                        None => {
-                            return Err( HibouParsingError::MissingMessageDeclarationError( ms_name ) );
-                        },
-                        Some( ms_id ) => {
-                            return Ok( (comm_type,ms_id) );
-                        }
-                    }
+            return Err( HibouParsingError::MissingMessageDeclarationError( ms_name ) );
+        },
+        Some( ms_id ) => {
+            match parse_message_param(gen_ctx,&ms_name,ms_id,contents.next()) {
+                Err(e) => {
+                    return Err(e);
                 },
-                _ => {
-                    panic!("what rule then ? : {:?}", second_pair.as_rule() );
+                Ok( ms_param ) => {
+                    return Ok( (comm_type,ms_id,ms_param) );
                 }
             }
+        }
+    }
+}
+
+/// Parses the optional parameter 'x' of a message 'm(x)'.
+/// The value 'x' must be declared in the domain of the parameter of 'm' in the signature.
+pub fn parse_message_param(gen_ctx : &GeneralContext,
+                           ms_name : &str,
+                           ms_id : usize,
+                           param_pair : Option<Pair<Rule>>) -> Result<Option<usize>,HibouParsingError> {
+    match param_pair {
+        None => {
+            return Ok(None);
         },
-        Some(ms_id) => {
-            return Ok( (comm_type,ms_id) );
+        Some( param_pair ) => {
+            let param_name : String = param_pair.into_inner().next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            match gen_ctx.get_ms_param_id(ms_id, &param_name) {
+                None => {
+                    return Err( HibouParsingError::MissingMessageParameterDeclarationError( format!("{}({})", ms_name, param_name) ) );
+                },
+                Some( param_id ) => {
+                    return Ok( Some(param_id) );
+                }
+            }
         }
     }
-}
\ No newline at end of file
+}
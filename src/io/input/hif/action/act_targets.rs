@@ -71,7 +71,9 @@ pub fn parse_comm_act_targets_as_lifelines(gen_ctx : &GeneralContext, target_pai
     }
 }
 
-pub fn parse_comm_act_targets_as_generic_targets(gen_ctx : &GeneralContext, target_pair : Pair<Rule>) -> Result<Vec<EmissionTargetRef>,HibouParsingError> {
+/// Also returns the id of the multicast group if the targets are given as a single group name
+/// (which is then replaced by the lifelines of the group).
+pub fn parse_comm_act_targets_as_generic_targets(gen_ctx : &GeneralContext, target_pair : Pair<Rule>) -> Result<(Vec<EmissionTargetRef>,Option<usize>),HibouParsingError> {
     let inner_pair = target_pair.into_inner().next().unwrap();
     match inner_pair.as_rule() {
         Rule::HIBOU_LABEL => {
@@ -81,15 +83,24 @@ pub fn parse_comm_act_targets_as_generic_targets(gen_ctx : &GeneralContext, targ
                 None => {
                     match gen_ctx.get_gt_id(&target_name) {
                         None => {
-                            return Err( HibouParsingError::MissingLifelineOrGateDeclarationError( target_name ) );
+                            match gen_ctx.get_group_id(&target_name) {
+                                None => {
+                                    return Err( HibouParsingError::MissingLifelineOrGateDeclarationError( target_name ) );
+                                },
+                                Some( group_id ) => {
+                                    let targets = gen_ctx.get_group_lifelines(group_id).unwrap().iter()
+                                        .map(|lf_id| EmissionTargetRef::Lifeline(*lf_id)).collect();
+                                    return Ok( (targets, Some(group_id)) );
+                                }
+                            }
                         },
                         Some( tar_gt_id ) => {
-                            return Ok( vec![ EmissionTargetRef::Gate( tar_gt_id ) ] );
+                            return Ok( (vec![ EmissionTargetRef::Gate( tar_gt_id ) ], None) );
                         }
                     }
                 },
                 Some( tar_lf_id ) => {
-                    return Ok( vec![ EmissionTargetRef::Lifeline( tar_lf_id ) ] );
+                    return Ok( (vec![ EmissionTargetRef::Lifeline( tar_lf_id ) ], None) );
                 }
             }
             // ***
@@ -126,10 +137,10 @@ pub fn parse_comm_act_targets_as_generic_targets(gen_ctx : &GeneralContext, targ
                     }
                 }
             }
-            return Ok( target_refs );
+            return Ok( (target_refs, None) );
         },
         Rule::ENVIRONMENT_TARGET => {
-            return Ok( vec![] );
+            return Ok( (vec![], None) );
         },
         _ => {
             panic!("what rule then ? : {:?}", inner_pair.as_rule() );
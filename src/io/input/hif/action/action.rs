@@ -18,7 +18,7 @@ limitations under the License.
 use pest::iterators::{Pair, Pairs};
 
 use crate::core::general_context::GeneralContext;
-use crate::core::language::syntax::action::{EmissionAction, ReceptionAction};
+use crate::core::language::syntax::action::{EmissionAction, EmissionTargetRef, ReceptionAction};
 use crate::core::language::syntax::interaction::Interaction;
 use crate::io::input::hif::action::act_content::*;
 use crate::io::input::hif::action::act_targets::*;
@@ -64,7 +64,7 @@ pub fn parse_communication_action(gen_ctx : &GeneralContext, contents : &mut Pai
         Err(e) => {
             return Err(e);
         },
-        Ok( (comm_synchro, ms_id) ) => {
+        Ok( (comm_synchro, ms_id, ms_param) ) => {
             match origin_info {
                 None => {
                     match parse_comm_act_targets_as_lifelines(gen_ctx,comm_act_target_pair) {
@@ -72,7 +72,7 @@ pub fn parse_communication_action(gen_ctx : &GeneralContext, contents : &mut Pai
                             return Err(e);
                         },
                         Ok( tar_lf_ids) => {
-                            let rc_act = ReceptionAction::new(None,ms_id,comm_synchro,tar_lf_ids);
+                            let rc_act = ReceptionAction::new(None,ms_id,comm_synchro,tar_lf_ids).with_param(ms_param);
                             return Ok( Interaction::Reception(rc_act) );
                         }
                     }
@@ -85,7 +85,7 @@ pub fn parse_communication_action(gen_ctx : &GeneralContext, contents : &mut Pai
                                     return Err(e);
                                 },
                                 Ok( tar_lf_ids) => {
-                                    let rc_act = ReceptionAction::new(Some(gt_id),ms_id,comm_synchro,tar_lf_ids);
+                                    let rc_act = ReceptionAction::new(Some(gt_id),ms_id,comm_synchro,tar_lf_ids).with_param(ms_param);
                                     return Ok( Interaction::Reception(rc_act) );
                                 }
                             }
@@ -95,8 +95,9 @@ pub fn parse_communication_action(gen_ctx : &GeneralContext, contents : &mut Pai
                                 Err(e) => {
                                     return Err(e);
                                 },
-                                Ok( tar_refs) => {
-                                    let em_act = EmissionAction::new(lf_id,ms_id,comm_synchro,tar_refs);
+                                Ok( (tar_refs,target_group) ) => {
+                                    check_transmission_directions(gen_ctx,lf_id,ms_id,&tar_refs,target_group)?;
+                                    let em_act = EmissionAction::new(lf_id,ms_id,comm_synchro,tar_refs).with_param(ms_param).with_group(target_group);
                                     return Ok( Interaction::Emission(em_act) );
                                 }
                             }
@@ -106,4 +107,36 @@ pub fn parse_communication_action(gen_ctx : &GeneralContext, contents : &mut Pai
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Checks that an emission only targets lifelines towards which the message may be transmitted
+/// (see the '@direction' section of the signature), a group target being checked for each of its lifelines.
+/// Emissions towards gates or the environment and receptions from the environment are not constrained :
+/// a transmission split into an emission 'a -- m ->|' and a reception 'm -> b' is hence never rejected,
+/// even if 'm' may not be transmitted from 'a' to 'b'.
+fn check_transmission_directions(gen_ctx : &GeneralContext,
+                                 orig_lf_id : usize,
+                                 ms_id : usize,
+                                 tar_refs : &Vec<EmissionTargetRef>,
+                                 target_group : Option<usize>) -> Result<(),HibouParsingError> {
+    for tar_ref in tar_refs {
+        if let EmissionTargetRef::Lifeline(tar_lf_id) = tar_ref {
+            if !gen_ctx.is_transmission_allowed(ms_id,orig_lf_id,*tar_lf_id) {
+                let target_name = match target_group {
+                    None => {
+                        format!("'{}'", gen_ctx.get_lf_name(*tar_lf_id).unwrap())
+                    },
+                    Some( group_id ) => {
+                        format!("'{}' of group '{}'", gen_ctx.get_lf_name(*tar_lf_id).unwrap(), gen_ctx.get_group_name(group_id).unwrap())
+                    }
+                };
+                return Err( HibouParsingError::EmissionDefinitionError(
+                    format!("message '{}' may not be transmitted from '{}' to {} (see the '@direction' section of the signature)",
+                            gen_ctx.get_ms_name(ms_id).unwrap(),
+                            gen_ctx.get_lf_name(orig_lf_id).unwrap(),
+                            target_name) ) );
+            }
+        }
+    }
+    return Ok(());
+}
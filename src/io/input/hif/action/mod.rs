@@ -15,5 +15,5 @@ limitations under the License.
 */
 
 pub mod action;
-mod act_content;
+pub(crate) mod act_content;
 mod act_targets;
\ No newline at end of file
@@ -0,0 +1,173 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::interaction::Interaction;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::error::HibouParsingError;
+use crate::io::input::hif::interaction::parse_hif_string_with_includes;
+
+
+/// Keeps track of where '@include "path.hif"' directives are resolved from
+/// and of the files currently being included, so as to reject cyclic inclusions,
+/// as well as of whether commutative operands are sorted in the parsed interactions and the included ones.
+pub struct HifIncludeContext {
+    /// folder of the file being parsed (the current folder if None)
+    base_dir : Option<PathBuf>,
+    /// folders in which included files are looked for when they are not found
+    /// relatively to the including file (set via '--include-path')
+    search_dirs : Vec<PathBuf>,
+    /// canonical paths of the files being parsed, from the outermost one
+    stack : Vec<PathBuf>,
+    /// whether the operands of 'par' and 'alt' are sorted while parsing (set via '--canonicalize-on-parse')
+    /// so that interactions differing only by the order of such operands are parsed into equal terms
+    pub canonicalize : bool
+}
+
+impl Default for HifIncludeContext {
+    fn default() -> HifIncludeContext {
+        return HifIncludeContext::new(vec![], false);
+    }
+}
+
+impl HifIncludeContext {
+
+    /// Context for parsing a string not read from a file.
+    pub fn new(search_dirs : Vec<PathBuf>, canonicalize : bool) -> HifIncludeContext {
+        return HifIncludeContext{base_dir:None,search_dirs,stack:vec![],canonicalize};
+    }
+
+    /// Context for parsing the file at 'file_path', with the same search folders and canonicalization.
+    pub fn for_file(&self, file_path : &Path) -> HifIncludeContext {
+        let mut include_ctx = HifIncludeContext::new(self.search_dirs.clone(), self.canonicalize);
+        include_ctx.base_dir = file_path.parent().map(|dir| dir.to_path_buf());
+        if let Ok(canonical) = fs::canonicalize(file_path) {
+            include_ctx.stack.push(canonical);
+        }
+        return include_ctx;
+    }
+
+    fn resolve(&self, include_path : &str) -> Result<PathBuf,HibouParsingError> {
+        let path = Path::new(include_path);
+        let mut candidates : Vec<PathBuf> = vec![];
+        if path.is_absolute() {
+            candidates.push(path.to_path_buf());
+        } else {
+            match &self.base_dir {
+                None => {
+                    candidates.push(path.to_path_buf());
+                },
+                Some(dir) => {
+                    candidates.push(dir.join(path));
+                }
+            }
+            for dir in &self.search_dirs {
+                candidates.push(dir.join(path));
+            }
+        }
+        for candidate in candidates {
+            if candidate.is_file() {
+                return fs::canonicalize(&candidate).map_err(|e| HibouParsingError::FileError(e.to_string()));
+            }
+        }
+        return Err( HibouParsingError::IncludeError(format!("could not find included file '{:}'", include_path)) );
+    }
+
+}
+
+
+/// Parses the interaction of an included file with the signature of the including one.
+pub fn parse_included_hif_file(gen_ctx : &GeneralContext,
+                               include_ctx : &mut HifIncludeContext,
+                               include_path : &str) -> Result<Interaction,HibouParsingError> {
+    let path = include_ctx.resolve(include_path)?;
+    if path.extension().map_or(true, |ext| ext != HIBOU_INTERACTION_FILE_EXTENSION) {
+        return Err( HibouParsingError::IncludeError(format!("included file '{:}' is not a .{:} file", include_path, HIBOU_INTERACTION_FILE_EXTENSION)) );
+    }
+    if include_ctx.stack.contains(&path) {
+        let chain : Vec<String> = include_ctx.stack.iter().chain(std::iter::once(&path))
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect();
+        return Err( HibouParsingError::IncludeError(format!("cyclic inclusion : {:}", chain.join(" -> "))) );
+    }
+    let hif_str = fs::read_to_string(&path).map_err(|e| HibouParsingError::FileError(e.to_string()))?;
+    let parent_base_dir = include_ctx.base_dir.replace(path.parent().unwrap().to_path_buf());
+    include_ctx.stack.push(path);
+    let got = parse_hif_string_with_includes(gen_ctx, include_ctx, hif_str);
+    include_ctx.stack.pop();
+    include_ctx.base_dir = parent_base_dir;
+    return got.map_err(|e| match e {
+        HibouParsingError::IncludeError(_) => e,
+        _ => HibouParsingError::IncludeError(format!("in included file '{:}' : {:}", include_path, e))
+    });
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hif::interface::{parse_hif_file, parse_hif_file_with};
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn include_test() {
+        let dir = std::env::temp_dir().join(format!("hibou_include_test_{:}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("part.hif"), "seq(a -- m -> b, b -- n -> a)").unwrap();
+        fs::write(dir.join("main.hif"), "loopS(@include \"sub/part.hif\")").unwrap();
+        fs::write(dir.join("cycle1.hif"), "alt(o, @include \"cycle2.hif\")").unwrap();
+        fs::write(dir.join("cycle2.hif"), "seq(a -- m -> b, @include \"cycle1.hif\")").unwrap();
+        fs::write(dir.join("unknown.hif"), "seq(@include \"sub/part.hif\", c -- m -> a)").unwrap();
+        fs::write(dir.join("sub").join("bad.hif"), "a -- z -> b").unwrap();
+        fs::write(dir.join("bad.hif"), "@include \"sub/bad.hif\"").unwrap();
+        // ***
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let expected = parse_hif_string(&gen_ctx,"loopS(seq(a -- m -> b, b -- n -> a))".to_string()).unwrap();
+        assert_eq!(parse_hif_file(&gen_ctx, dir.join("main.hif").to_str().unwrap()).unwrap(), expected);
+        match parse_hif_file(&gen_ctx, dir.join("cycle1.hif").to_str().unwrap()) {
+            Err(HibouParsingError::IncludeError(msg)) => {
+                assert!(msg.contains("cyclic inclusion : cycle1.hif -> cycle2.hif -> cycle1.hif"));
+            },
+            other => panic!("expected a cyclic inclusion error, got {:?}", other.map(|_| ()).err().map(|e| e.to_string()))
+        }
+        assert!(parse_hif_file(&gen_ctx, dir.join("unknown.hif").to_str().unwrap()).is_err());
+        match parse_hif_file(&gen_ctx, dir.join("bad.hif").to_str().unwrap()) {
+            Err(HibouParsingError::IncludeError(msg)) => {
+                assert!(msg.starts_with("in included file 'sub/bad.hif'"));
+            },
+            _ => panic!("expected an include error")
+        }
+        // relative paths are resolved against the search folders when not found next to the including file
+        let mut include_ctx = HifIncludeContext::new(vec![dir.join("sub")], false);
+        let got = parse_hif_string_with_includes(&gen_ctx, &mut include_ctx, "@include \"part.hif\"".to_string()).unwrap();
+        assert_eq!(got, parse_hif_string(&gen_ctx,"seq(a -- m -> b, b -- n -> a)".to_string()).unwrap());
+        assert!(parse_hif_string(&gen_ctx, "@include \"part.hif\"".to_string()).is_err());
+        fs::write(dir.join("other.hif"), "@include \"part.hif\"").unwrap();
+        let other_path = dir.join("other.hif");
+        assert!(parse_hif_file(&gen_ctx, other_path.to_str().unwrap()).is_err());
+        let got = parse_hif_file_with(&gen_ctx, other_path.to_str().unwrap(), &include_ctx).unwrap();
+        assert_eq!(got, parse_hif_string(&gen_ctx,"seq(a -- m -> b, b -- n -> a)".to_string()).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
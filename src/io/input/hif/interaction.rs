@@ -23,22 +23,30 @@ use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::interaction::{Interaction, LoopKind};
 use crate::io::input::error::HibouParsingError;
 use crate::io::input::hif::action::action::parse_communication_action;
+use crate::io::input::hif::include::{HifIncludeContext, parse_included_hif_file};
 
 
 #[allow(unused_imports)]
 use pest::Parser;
 #[allow(unused_imports)]
 use crate::io::input::hif::parser::{HifParser,Rule};
-use crate::io::input::hif::trace::sync_acts_from_pair;
+use crate::io::input::hif::trace::{sync_acts_from_pair, trace_action_from_text};
 
 
 pub fn parse_hif_string(gen_ctx : &GeneralContext, hif_string : String) -> Result<Interaction,HibouParsingError> {
+    return parse_hif_string_with_includes(gen_ctx, &mut HifIncludeContext::default(), hif_string);
+}
+
+/// Parses a .hif string, resolving its '@include' directives with the given context.
+pub fn parse_hif_string_with_includes(gen_ctx : &GeneralContext,
+                                      include_ctx : &mut HifIncludeContext,
+                                      hif_string : String) -> Result<Interaction,HibouParsingError> {
     match HifParser::parse(Rule::HIF_PEST_FILE, &hif_string) {
         Ok( ref mut got_pair ) => {
             let int_pair = got_pair.next().unwrap();
             match int_pair.as_rule() {
                 Rule::SD_INTERACTION => {
-                    return parse_interaction(gen_ctx,int_pair);
+                    return parse_interaction(gen_ctx,include_ctx,int_pair);
                 },
                 _ => {
                     panic!("what rule then ? : {:?}", int_pair.as_rule() );
@@ -53,9 +61,13 @@ pub fn parse_hif_string(gen_ctx : &GeneralContext, hif_string : String) -> Resul
 
 
 
-fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -> Result<Interaction,HibouParsingError> {
+fn parse_interaction(gen_ctx : &GeneralContext, include_ctx : &mut HifIncludeContext, interaction_pair : Pair<Rule>) -> Result<Interaction,HibouParsingError> {
     let content_pair = interaction_pair.into_inner().next().unwrap();
     match content_pair.as_rule() {
+        Rule::SD_INCLUDE => {
+            let include_path = content_pair.into_inner().next().unwrap().as_str();
+            return parse_included_hif_file(gen_ctx, include_ctx, include_path);
+        },
         Rule::SD_EMPTY_INTERACTION => {
             return Ok( Interaction::Empty );
         },
@@ -63,7 +75,7 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
             return parse_communication_action(gen_ctx,&mut content_pair.into_inner());
         },
         Rule::SD_STRICT_INT => {
-            match get_nary_sub_interactions_from_pair(gen_ctx, content_pair) {
+            match get_nary_sub_interactions_from_pair(gen_ctx, include_ctx, content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -73,7 +85,7 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
             }
         },
         Rule::SD_SEQ_INT => {
-            match get_nary_sub_interactions_from_pair(gen_ctx, content_pair) {
+            match get_nary_sub_interactions_from_pair(gen_ctx, include_ctx, content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -92,7 +104,7 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
                     return Err(e);
                 },
                 Ok(sync_acts) => {
-                    match get_nary_sub_interactions(gen_ctx, content) {
+                    match get_nary_sub_interactions(gen_ctx, include_ctx, content) {
                         Err(e) => {
                             return Err(e);
                         },
@@ -108,9 +120,9 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
         Rule::SD_COREG_INT => {
             let mut content = content_pair.into_inner();
             content.next(); // get rid of the operator name
-            let coreg_lfs_pair = content.next().unwrap();
-            match coreg_lfs_pair.as_rule() {
+            match content.peek().unwrap().as_rule() {
                 Rule::HIBOU_LABEL_LIST => {
+                    let coreg_lfs_pair = content.next().unwrap();
                     let mut target_lfs : Vec<usize> = Vec::new();
                     let mut inner_contents = coreg_lfs_pair.into_inner();
                     for tar_lf_pair in inner_contents {
@@ -129,7 +141,7 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
                             }
                         }
                     }
-                    match get_nary_sub_interactions(gen_ctx, content) {
+                    match get_nary_sub_interactions(gen_ctx, include_ctx, content) {
                         Err(e) => {
                             return Err(e);
                         },
@@ -139,34 +151,42 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
                     }
                 },
                 _ => {
-                    panic!("what rule then ? : {:?}", coreg_lfs_pair.as_rule() );
+                    let all_lfs : Vec<usize> = (0..gen_ctx.get_lf_num()).collect();
+                    match get_nary_sub_interactions(gen_ctx, include_ctx, content) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( mut sub_ints ) => {
+                            return Ok( fold_interactions_in_binary_operator(&BinaryOperatorKind::CoReg(all_lfs),&mut sub_ints) );
+                        }
+                    }
                 }
             }
         },
         Rule::SD_ALT_INT => {
-            match get_nary_sub_interactions_from_pair(gen_ctx, content_pair) {
+            match get_nary_sub_interactions_from_pair(gen_ctx, include_ctx, content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
-                Ok( mut sub_ints ) => {
-                    return Ok( fold_interactions_in_binary_operator(&BinaryOperatorKind::Alt,&mut sub_ints) );
+                Ok( sub_ints ) => {
+                    return Ok( fold_commutative_interactions(include_ctx,&BinaryOperatorKind::Alt,sub_ints) );
                 }
             }
         },
         Rule::SD_PAR_INT => {
-            match get_nary_sub_interactions_from_pair(gen_ctx, content_pair) {
+            match get_nary_sub_interactions_from_pair(gen_ctx, include_ctx, content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
-                Ok( mut sub_ints ) => {
-                    return Ok( fold_interactions_in_binary_operator(&BinaryOperatorKind::Par,&mut sub_ints) );
+                Ok( sub_ints ) => {
+                    return Ok( fold_commutative_interactions(include_ctx,&BinaryOperatorKind::Par,sub_ints) );
                 }
             }
         },
         Rule::SD_LOOP_INT => {
             let mut loop_content = content_pair.into_inner();
             let loop_kind_pair = loop_content.next().unwrap().into_inner().next().unwrap();
-            match parse_interaction(gen_ctx,loop_content.next().unwrap()) {
+            match parse_interaction(gen_ctx,include_ctx,loop_content.next().unwrap()) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -192,7 +212,7 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
             }
         },
         Rule::SD_AND_INT => {
-            match get_nary_sub_interactions_from_pair(gen_ctx, content_pair) {
+            match get_nary_sub_interactions_from_pair(gen_ctx, include_ctx, content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -201,22 +221,28 @@ fn parse_interaction(gen_ctx : &GeneralContext, interaction_pair : Pair<Rule>) -
                 }
             }
         },
+        Rule::SD_NOT_INT => {
+            let mut content = content_pair.into_inner();
+            content.next(); // get rid of the operator name
+            let forbidden = trace_action_from_text(gen_ctx,content.next().unwrap())?;
+            return Ok( Interaction::Not(forbidden) );
+        },
         _ => {
             panic!("what rule then ? : {:?}", content_pair.as_rule());
         }
     }
 }
 
-fn get_nary_sub_interactions_from_pair(gen_ctx : &GeneralContext, sd_content_pair : Pair<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
+fn get_nary_sub_interactions_from_pair(gen_ctx : &GeneralContext, include_ctx : &mut HifIncludeContext, sd_content_pair : Pair<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
     let mut content = sd_content_pair.into_inner();
     content.next(); // get rid of the operator name
-    return get_nary_sub_interactions(gen_ctx, content);
+    return get_nary_sub_interactions(gen_ctx, include_ctx, content);
 }
 
-fn get_nary_sub_interactions(gen_ctx : &GeneralContext, content : Pairs<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
+fn get_nary_sub_interactions(gen_ctx : &GeneralContext, include_ctx : &mut HifIncludeContext, content : Pairs<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
     let mut sub_ints : Vec<Interaction> = Vec::new();
     for sub_interaction in content {
-        match parse_interaction(gen_ctx,sub_interaction) {
+        match parse_interaction(gen_ctx,include_ctx,sub_interaction) {
             Err(e) => {
                 return Err(e);
             },
@@ -238,6 +264,36 @@ enum BinaryOperatorKind {
     And
 }
 
+/// Operands of a (right-nested) chain of the commutative operator 'op_kind'.
+fn get_commutative_operands(op_kind : &BinaryOperatorKind, interaction : Interaction, operands : &mut Vec<Interaction>) {
+    match (op_kind,interaction) {
+        (BinaryOperatorKind::Par,Interaction::Par(i1,i2)) | (BinaryOperatorKind::Alt,Interaction::Alt(i1,i2)) => {
+            get_commutative_operands(op_kind, *i1, operands);
+            get_commutative_operands(op_kind, *i2, operands);
+        },
+        (_,other) => {
+            operands.push(other);
+        }
+    }
+}
+
+/// Folds the operands of 'par' or 'alt', which, with '--canonicalize-on-parse',
+/// are first flattened (with those of nested occurrences of the same operator) and sorted
+/// in the order in which they are printed (see 'interaction_as_hif_encoding').
+/// The operands of 'coreg' are not sorted as they are only commutative on the lifelines of the co-region.
+fn fold_commutative_interactions(include_ctx : &HifIncludeContext, op_kind : &BinaryOperatorKind, sub_ints : Vec<Interaction>) -> Interaction {
+    let mut operands = vec![];
+    if include_ctx.canonicalize {
+        for sub_int in sub_ints {
+            get_commutative_operands(op_kind, sub_int, &mut operands);
+        }
+        operands.sort();
+    } else {
+        operands = sub_ints;
+    }
+    return fold_interactions_in_binary_operator(op_kind,&mut operands);
+}
+
 fn fold_interactions_in_binary_operator(op_kind : &BinaryOperatorKind, sub_ints : &mut Vec<Interaction>) -> Interaction {
     assert!(sub_ints.len() > 0);
     if sub_ints.len() == 1 {
@@ -274,3 +330,122 @@ fn fold_interactions_in_binary_operator(op_kind : &BinaryOperatorKind, sub_ints
 
 
 
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef};
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::output::to_hfiles::interaction::model_action::emission_as_hif_encoding;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use super::*;
+
+    #[test]
+    fn message_param_test() {
+        let gen_ctx = parse_hsf_string("@message{m(x,y);n}\n@lifeline{a;b}".to_string()).unwrap();
+        assert_eq!(gen_ctx.get_ms_params_num(0), 2);
+        assert_eq!(gen_ctx.get_ms_params_num(1), 0);
+        assert_eq!(gen_ctx.get_ms_label(0,Some(1)).unwrap(), "m(y)");
+        // parameters are optional
+        let got = parse_hif_string(&gen_ctx,"seq(a -- m(y) -> b, a -- m -> b)".to_string()).unwrap();
+        let expected = Interaction::Seq(
+            Box::new(Interaction::Emission(
+                EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)]).with_param(Some(1))
+            )),
+            Box::new(Interaction::Emission(
+                EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+            ))
+        );
+        assert_eq!(got, expected);
+        // undeclared values of parameters
+        match parse_hif_string(&gen_ctx,"a -- m(z) -> b".to_string()) {
+            Err(HibouParsingError::MissingMessageParameterDeclarationError(got)) => {
+                assert_eq!(got, "m(z)");
+            },
+            _ => {
+                panic!("expected an undeclared parameter error");
+            }
+        }
+        assert!(parse_hif_string(&gen_ctx,"a -- n(x) -> b".to_string()).is_err());
+        // 'm', 'm(x)' and 'm(y)' and 'n' for emissions and receptions on both lifelines
+        assert_eq!(get_alphabet_from_gen_ctx(&gen_ctx).len(), 2*4*2);
+    }
+
+    #[test]
+    fn group_test() {
+        // groups may be declared before the lifelines they contain
+        let gen_ctx = parse_hsf_string("@message{m}\n@group{servers = {s2,s1}}\n@lifeline{c;s1;s2}".to_string()).unwrap();
+        assert_eq!(gen_ctx.get_group_lifelines(0).unwrap(), &vec![1,2]);
+        let got = parse_hif_string(&gen_ctx,"c -- m -> servers".to_string()).unwrap();
+        let em_act = EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,
+                                         vec![EmissionTargetRef::Lifeline(1),EmissionTargetRef::Lifeline(2)]).with_group(Some(0));
+        assert_eq!(got, Interaction::Emission(em_act.clone()));
+        assert_eq!(emission_as_hif_encoding(&gen_ctx,&em_act), "c -- m -> servers");
+        // ***
+        assert!(parse_hsf_string("@message{m}\n@lifeline{c}\n@group{servers = {s1}}".to_string()).is_err());
+        assert!(parse_hsf_string("@message{m}\n@lifeline{c}\n@group{c = {c}}".to_string()).is_err());
+    }
+
+    #[test]
+    fn direction_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}\n@direction{a -- m -> b}".to_string()).unwrap();
+        assert!(parse_hif_string(&gen_ctx,"seq(a -- m -> b, b -- n -> a, b -- m ->|)".to_string()).is_ok());
+        assert!(parse_hif_string(&gen_ctx,"b -- m -> a".to_string()).is_err());
+        // directions must refer to declared lifelines and messages
+        assert!(parse_hsf_string("@message{m}\n@lifeline{a;b}\n@direction{a -- m -> c}".to_string()).is_err());
+        assert!(parse_hsf_string("@direction{a -- m -> b}\n@message{m}\n@lifeline{a;b}".to_string()).is_ok());
+        assert!(parse_hsf_string("@message{m}\n@lifeline{a;b}\n@direction{a -- m -> a}".to_string()).is_err());
+        // the lifelines of a group target are each checked
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b;c}\n@group{g = {b,c}}\n@direction{a -- m -> b;a -- m -> c;b -- m -> a}".to_string()).unwrap();
+        assert!(parse_hif_string(&gen_ctx,"a -- m -> g".to_string()).is_ok());
+        assert!(parse_hif_string(&gen_ctx,"a -- m -> (b,c)".to_string()).is_ok());
+        assert!(parse_hif_string(&gen_ctx,"c -- m -> a".to_string()).is_err());
+        assert!(parse_hif_string(&gen_ctx,"c -- m -> (a,b)".to_string()).is_err());
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b;c}\n@group{g = {b,c}}\n@direction{a -- m -> b}".to_string()).unwrap();
+        assert!(parse_hif_string(&gen_ctx,"a -- m -> g".to_string()).is_err());
+        // emissions towards and receptions from the environment are not constrained,
+        // hence a transmission split between them is not checked
+        assert!(parse_hif_string(&gen_ctx,"seq(b -- m ->|, m -> a)".to_string()).is_ok());
+    }
+
+    #[test]
+    fn coreg_test() {
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let a_m = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let b_m = Interaction::Emission(EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let got = parse_hif_string(&gen_ctx,"coreg(c,a)(a -- m ->|, b -- m ->|)".to_string()).unwrap();
+        assert_eq!(got, Interaction::CoReg(vec![2,0],Box::new(a_m.clone()),Box::new(b_m.clone())));
+        // without a list of lifelines, the co-region concerns all the lifelines
+        let got = parse_hif_string(&gen_ctx,"coreg(a -- m ->|, b -- m ->|)".to_string()).unwrap();
+        assert_eq!(got, Interaction::CoReg(vec![0,1,2],Box::new(a_m),Box::new(b_m)));
+        assert!(parse_hif_string(&gen_ctx,"coreg(a,a)(a -- m ->|, b -- m ->|)".to_string()).is_err());
+    }
+
+
+    #[test]
+    fn canonicalize_on_parse_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let parse = |hif_str : &str, canonicalize : bool| {
+            let mut include_ctx = HifIncludeContext::new(vec![], canonicalize);
+            parse_hif_string_with_includes(&gen_ctx, &mut include_ctx, hif_str.to_string()).unwrap()
+        };
+        let variants = ["par(a -- m -> b, seq(b -- n -> a, a -- m ->|), alt(b -- m -> a, o))",
+                        "par(alt(o, b -- m -> a), a -- m -> b, seq(b -- n -> a, a -- m ->|))",
+                        "par(par(seq(b -- n -> a, a -- m ->|), alt(o, b -- m -> a)), a -- m -> b)"];
+        let canonical = parse(variants[0], true);
+        for hif_str in variants {
+            assert_eq!(parse(hif_str, true), canonical);
+        }
+        // without the option, the order of the operands is kept
+        assert_ne!(parse(variants[0], false), parse(variants[1], false));
+        // the order of the operands of other operators is kept
+        assert_ne!(parse("seq(a -- m -> b, b -- n -> a)", true), parse("seq(b -- n -> a, a -- m -> b)", true));
+        assert_ne!(parse("coreg(a)(a -- m -> b, b -- n ->|)", true), parse("coreg(a)(b -- n ->|, a -- m -> b)", true));
+        // the canonical interaction is written in the same order
+        let path = std::env::temp_dir().join(format!("hibou_canonicalize_test_{:}.hif", std::process::id()));
+        crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif(&path, &gen_ctx, &canonical);
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(parse(&written, false), canonical);
+    }
+}
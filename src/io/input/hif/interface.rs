@@ -17,18 +17,137 @@ limitations under the License.
 
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 
 use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::interaction::Interaction;
 use crate::io::input::error::HibouParsingError;
-use crate::io::input::hif::interaction::parse_hif_string;
+use crate::io::input::hif::include::HifIncludeContext;
+use crate::io::input::hif::interaction::parse_hif_string_with_includes;
 
 use crate::io::file_extensions::{HIBOU_INTERACTION_FILE_EXTENSION};
+use crate::io::file_extensions::{HIF_FORMAT_VERSION, HIF_VERSION_TAG};
+use crate::io::output::to_hfiles::interaction::to_hif::HifProvenance;
 
 
+/// Returns the format version declared by the leading '/* @hif-version N */' comment of a .hif string.
+/// Strings without such a tag are treated as version 1.
+pub fn get_hif_format_version(hif_str : &str) -> u32 {
+    let trimmed = hif_str.trim_start();
+    if let Some(after_open) = trimmed.strip_prefix("/*") {
+        if let Some(end) = after_open.find("*/") {
+            if let Some(version_str) = after_open[..end].trim().strip_prefix(HIF_VERSION_TAG) {
+                if let Ok(version) = version_str.trim().parse::<u32>() {
+                    return version;
+                }
+            }
+        }
+    }
+    return 1;
+}
+
+
+/// Returns the provenance recorded in the leading comments of a generated .hif string, if any.
+pub fn get_hif_provenance(hif_str : &str) -> Option<HifProvenance> {
+    let mut rest = hif_str.trim_start();
+    while let Some(after_open) = rest.strip_prefix("/*") {
+        let end = after_open.find("*/")?;
+        if let Some(provenance) = HifProvenance::from_comment_content(&after_open[..end]) {
+            return Some(provenance);
+        }
+        rest = after_open[end+2..].trim_start();
+    }
+    return None;
+}
+
+
+/// Returns the paths of the .hif files of a folder, ordered by the number ending their name
+/// (so that i2.hif comes before i10.hif) and then alphabetically.
+pub fn get_hif_files_in_folder(folder : &str) -> Result<Vec<PathBuf>,String> {
+    let mut hif_paths : Vec<PathBuf> = vec![];
+    match fs::read_dir(folder) {
+        Err(e) => {
+            return Err( format!("could not read folder '{:}' : {:}", folder, e) );
+        },
+        Ok( entries ) => {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() && path.extension().map_or(false, |ext| ext == HIBOU_INTERACTION_FILE_EXTENSION) {
+                    hif_paths.push(path);
+                }
+            }
+        }
+    }
+    hif_paths.sort_by_key(|path| {
+        let stem = path.file_stem().unwrap().to_str().unwrap().to_string();
+        let prefix = stem.trim_end_matches(|c : char| c.is_ascii_digit()).to_string();
+        let number = stem[prefix.len()..].parse::<u64>().ok();
+        (prefix, number, stem)
+    });
+    return Ok( hif_paths );
+}
+
+/// Whether a file name matches a pattern in which '*' stands for any sequence of characters and '?' for any single character.
+fn wildcard_matches(pattern : &[char], name : &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*',rest)) => (0..=name.len()).any(|skipped| wildcard_matches(rest, &name[skipped..])),
+        Some((c,rest)) => match name.split_first() {
+            Some((n,name_rest)) if *c == '?' || c == n => wildcard_matches(rest, name_rest),
+            _ => false
+        }
+    }
+}
+
+/// Returns the paths of the files matched by a pattern such as 'models/*.hif'.
+/// Wildcards may only occur in the file name and not in the folders leading to it.
+/// A pattern without wildcards designates a single file, or all the .hif files of a folder
+/// (as 'get_hif_files_in_folder').
+pub fn get_files_matching_pattern(pattern : &str) -> Result<Vec<PathBuf>,String> {
+    let path_object = Path::new(pattern);
+    if !pattern.contains(|c| c == '*' || c == '?') {
+        if path_object.is_dir() {
+            return get_hif_files_in_folder(pattern);
+        }
+        return Ok( vec![path_object.to_path_buf()] );
+    }
+    let folder = match path_object.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new(".")
+    };
+    if folder.to_string_lossy().contains(|c| c == '*' || c == '?') {
+        return Err( format!("wildcards are only allowed in the file name of pattern '{:}'", pattern) );
+    }
+    let name_pattern : Vec<char> = path_object.file_name().unwrap().to_string_lossy().chars().collect();
+    let mut paths = vec![];
+    match fs::read_dir(folder) {
+        Err(e) => {
+            return Err( format!("could not read folder '{:}' : {:}", folder.display(), e) );
+        },
+        Ok( entries ) => {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name : Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+                if path.is_file() && wildcard_matches(&name_pattern, &name) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+    paths.sort();
+    return Ok( paths );
+}
+
 pub fn parse_hif_file(gen_ctx : &GeneralContext, file_path : &str) -> Result<Interaction,HibouParsingError> {
+    return parse_hif_file_with(gen_ctx, file_path, &HifIncludeContext::default());
+}
+
+/// Parses a .hif file, its '@include' directives being resolved with the search folders
+/// and the canonicalization of the given context.
+pub fn parse_hif_file_with(gen_ctx : &GeneralContext,
+                           file_path : &str,
+                           include_ctx : &HifIncludeContext) -> Result<Interaction,HibouParsingError> {
     let path_object = Path::new(file_path);
     let file_extension : &str = path_object.extension().unwrap().to_str().unwrap();
     if file_extension != HIBOU_INTERACTION_FILE_EXTENSION {
@@ -36,7 +155,11 @@ pub fn parse_hif_file(gen_ctx : &GeneralContext, file_path : &str) -> Result<Int
     }
     match fs::read_to_string(file_path) {
         Ok( unparsed_hif_str ) => {
-            return parse_hif_string(gen_ctx,unparsed_hif_str);
+            let version = get_hif_format_version(&unparsed_hif_str);
+            if version > HIF_FORMAT_VERSION {
+                warn!("file '{:}' uses version {:} of the hif format whereas this version of hibou only supports up to version {:}", file_path, version, HIF_FORMAT_VERSION);
+            }
+            return parse_hif_string_with_includes(gen_ctx,&mut include_ctx.for_file(path_object),unparsed_hif_str);
         },
         Err(e) => {
             return Err( HibouParsingError::FileError(e.to_string()) );
@@ -45,3 +168,61 @@ pub fn parse_hif_file(gen_ctx : &GeneralContext, file_path : &str) -> Result<Int
 }
 
 
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif_with_provenance;
+    use super::*;
+
+    #[test]
+    fn format_version_test() {
+        assert_eq!(get_hif_format_version("seq(a -- m -> b, o)"), 1);
+        assert_eq!(get_hif_format_version("/* some comment */\nseq(a -- m -> b, o)"), 1);
+        assert_eq!(get_hif_format_version("/* @hif-version 2 */\nseq(a -- m -> b, o)"), 2);
+        assert_eq!(get_hif_format_version("\n  /*@hif-version 17*/ o"), 17);
+        assert_eq!(get_hif_format_version("/* @hif-version two */ o"), 1);
+    }
+
+    #[test]
+    fn provenance_test() {
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b}".to_string()).unwrap();
+        let int = parse_hif_string(&gen_ctx,"seq(a -- m -> b, loopS(b -- m -> a))".to_string()).unwrap();
+        let provenance = HifProvenance::new(&gen_ctx, 7, 3, &int);
+        assert_eq!(provenance.num_symbols, 4);
+        let path = std::env::temp_dir().join(format!("hibou_provenance_test_{:}.hif", std::process::id()));
+        interaction_to_hif_with_provenance(&path, &gen_ctx, &int, Some(&provenance));
+        let content = fs::read_to_string(&path).unwrap();
+        // the provenance comment does not prevent reading the file nor its version
+        let reparsed = parse_hif_file(&gen_ctx, path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(get_hif_format_version(&content), HIF_FORMAT_VERSION);
+        assert_eq!(get_hif_provenance(&content), Some(provenance.clone()));
+        assert!(provenance.matches(&gen_ctx, &reparsed));
+        let modified = parse_hif_string(&gen_ctx,"seq(a -- m -> b, loopS(b -- m -> a), o)".to_string()).unwrap();
+        assert!(!provenance.matches(&gen_ctx, &modified));
+        assert_eq!(get_hif_provenance("/* @hif-version 2 */\no"), None);
+    }
+
+    #[test]
+    fn files_matching_pattern_test() {
+        let pattern : Vec<char> = "i*_?.hif".chars().collect();
+        for (name,expected) in [("i_a.hif",true),("i12_b.hif",true),("i12_bc.hif",false),("j1_a.hif",false),("i1_a.hsf",false)] {
+            assert_eq!(wildcard_matches(&pattern, &name.chars().collect::<Vec<char>>()), expected);
+        }
+        let folder = std::env::temp_dir().join(format!("hibou_pattern_test_{:}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        for name in ["b.hif","a.hif","c.htf"] {
+            fs::write(folder.join(name), "o").unwrap();
+        }
+        let got = get_files_matching_pattern(&format!("{:}/*.hif", folder.display())).unwrap();
+        let whole = get_files_matching_pattern(&folder.to_string_lossy()).unwrap();
+        fs::remove_dir_all(&folder).unwrap();
+        assert_eq!(got, vec![folder.join("a.hif"),folder.join("b.hif")]);
+        assert_eq!(whole, got);
+        assert!(get_files_matching_pattern("*/a.hif").is_err());
+    }
+}
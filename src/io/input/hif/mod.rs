@@ -17,5 +17,6 @@ limitations under the License.
 mod parser;
 mod action;
 mod trace;
-mod interaction;
+pub(crate) mod interaction;
+pub mod include;
 pub mod interface;
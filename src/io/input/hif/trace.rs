@@ -19,6 +19,7 @@ use pest::iterators::Pair;
 use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
 use crate::core::general_context::GeneralContext;
 use crate::io::input::error::HibouParsingError;
+use crate::io::input::hif::action::act_content::parse_message_param;
 
 #[allow(unused_imports)]
 use pest::Parser;
@@ -67,7 +68,7 @@ fn get_trace_multi_action(gen_ctx : &GeneralContext,
 }
 
 
-fn trace_action_from_text(gen_ctx : &GeneralContext,
+pub fn trace_action_from_text(gen_ctx : &GeneralContext,
                           action_pair : Pair<Rule>) -> Result<TraceAction,HibouParsingError> {
     let mut contents = action_pair.into_inner();
     // ***
@@ -98,8 +99,8 @@ fn trace_action_from_text(gen_ctx : &GeneralContext,
         }
     }
     // ***
-    let ms_pair : Pair<Rule> = contents.next().unwrap();
-    let ms_name : String  = ms_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+    let mut ms_contents = contents.next().unwrap().into_inner();
+    let ms_name : String  = ms_contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
     // ***
     let got_ms_id : usize;
     match gen_ctx.get_ms_id(&ms_name) {
@@ -111,5 +112,12 @@ fn trace_action_from_text(gen_ctx : &GeneralContext,
         }
     }
     // ***
-    return Ok( TraceAction{lf_id:got_lf_id,act_kind,ms_id:got_ms_id} );
+    match parse_message_param(gen_ctx,&ms_name,got_ms_id,ms_contents.next()) {
+        Err(e) => {
+            return Err(e);
+        },
+        Ok( ms_param ) => {
+            return Ok( TraceAction::new(got_lf_id,act_kind,got_ms_id).with_param(ms_param) );
+        }
+    }
 }
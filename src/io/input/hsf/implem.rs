@@ -52,6 +52,9 @@ fn parse_signature(signature_pair : Pair<Rule>) -> Result<GeneralContext,HibouPa
     let mut got_section_messages  : bool = false;
     let mut got_section_lifelines : bool = false;
     let mut got_section_gates : bool = false;
+    // groups and directions refer to lifelines hence they are parsed once all the other sections are
+    let mut group_decl_pair : Option<Pair<Rule>> = None;
+    let mut direction_decl_pair : Option<Pair<Rule>> = None;
     // ***
     let mut contents = signature_pair.into_inner();
     // ***
@@ -80,20 +83,48 @@ fn parse_signature(signature_pair : Pair<Rule>) -> Result<GeneralContext,HibouPa
                 got_section_gates = true;
                 parse_gate_decl(current_pair,&mut gen_ctx);
             },
+            Rule::HIBOU_SIG_GROUP_DECL => {
+                if group_decl_pair.is_some() {
+                    return Err( HibouParsingError::HsfSetupError("several '@group' sections declared".to_string()));
+                }
+                group_decl_pair = Some(current_pair);
+            },
+            Rule::HIBOU_SIG_DIRECTION_DECL => {
+                if direction_decl_pair.is_some() {
+                    return Err( HibouParsingError::HsfSetupError("several '@direction' sections declared".to_string()));
+                }
+                direction_decl_pair = Some(current_pair);
+            },
             _ => {
                 panic!("what rule then ? : {:?}", current_pair.as_rule() );
             }
         }
     }
     // ***
+    if let Some(group_decl_pair) = group_decl_pair {
+        parse_group_decl(group_decl_pair,&mut gen_ctx)?;
+    }
+    if let Some(direction_decl_pair) = direction_decl_pair {
+        parse_direction_decl(direction_decl_pair,&mut gen_ctx)?;
+    }
+    // ***
     return Ok(gen_ctx);
 }
 
 
 fn parse_message_decl(ms_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) {
     for ms_pair in ms_decl_pair.into_inner() {
-        let ms_name : String = ms_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-        gen_ctx.add_msg(ms_name);
+        let mut ms_contents = ms_pair.into_inner();
+        let ms_name_pair = ms_contents.next().unwrap();
+        let ms_name : String = ms_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        let ms_id = gen_ctx.add_msg(ms_name);
+        // the optional domain of the parameter of the message e.g. 'm(x,y)'
+        if let Some(params_pair) = ms_contents.next() {
+            for param_pair in params_pair.into_inner() {
+                let param_name : String = param_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                gen_ctx.add_ms_param(ms_id,param_name);
+            }
+        }
     }
 }
 
@@ -109,4 +140,48 @@ fn parse_gate_decl(gt_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) {
         let gt_name : String = gt_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
         gen_ctx.add_gt(gt_name);
     }
-}
\ No newline at end of file
+}
+
+fn parse_group_decl(group_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) -> Result<(),HibouParsingError> {
+    for group_pair in group_decl_pair.into_inner() {
+        let mut group_contents = group_pair.into_inner();
+        let group_name : String = group_contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        if gen_ctx.get_group_id(&group_name).is_some() {
+            return Err( HibouParsingError::HsfSetupError(format!("group '{}' declared several times", group_name)));
+        }
+        if gen_ctx.get_lf_id(&group_name).is_some() || gen_ctx.get_gt_id(&group_name).is_some() {
+            return Err( HibouParsingError::HsfSetupError(format!("group '{}' has the same name as a lifeline or a gate", group_name)));
+        }
+        let mut lf_ids = vec![];
+        for lf_pair in group_contents {
+            let lf_name : String = lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            match gen_ctx.get_lf_id(&lf_name) {
+                None => {
+                    return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name) );
+                },
+                Some( lf_id ) => {
+                    lf_ids.push(lf_id);
+                }
+            }
+        }
+        gen_ctx.add_group(group_name,lf_ids);
+    }
+    return Ok(());
+}
+
+/// Directions are declared as transmissions e.g. 'l1 -- m -> l2'.
+fn parse_direction_decl(direction_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) -> Result<(),HibouParsingError> {
+    for direction_pair in direction_decl_pair.into_inner() {
+        let names : Vec<String> = direction_pair.into_inner()
+            .map(|pair| pair.as_str().chars().filter(|c| !c.is_whitespace()).collect())
+            .collect();
+        let orig_lf_id = gen_ctx.get_lf_id(&names[0]).ok_or( HibouParsingError::MissingLifelineDeclarationError(names[0].clone()) )?;
+        let ms_id = gen_ctx.get_ms_id(&names[1]).ok_or( HibouParsingError::MissingMessageDeclarationError(names[1].clone()) )?;
+        let tar_lf_id = gen_ctx.get_lf_id(&names[2]).ok_or( HibouParsingError::MissingLifelineDeclarationError(names[2].clone()) )?;
+        if orig_lf_id == tar_lf_id {
+            return Err( HibouParsingError::HsfSetupError(format!("message '{}' cannot be transmitted from lifeline '{}' to itself", names[1], names[0])));
+        }
+        gen_ctx.add_ms_direction(ms_id,orig_lf_id,tar_lf_id);
+    }
+    return Ok(());
+}
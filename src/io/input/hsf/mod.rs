@@ -16,5 +16,5 @@ limitations under the License.
 
 
 mod parser;
-mod implem;
+pub(crate) mod implem;
 pub mod interface;
\ No newline at end of file
@@ -129,6 +129,8 @@ fn get_trace_action(gen_ctx : &GeneralContext,
 
 fn trace_action_from_text(gen_ctx : &GeneralContext,
                           action_pair : Pair<Rule>) -> Result<TraceAction,HibouParsingError> {
+    // line on which the action occurs in the trace file, to locate undeclared names
+    let (line,_) = action_pair.as_span().start_pos().line_col();
     let mut contents = action_pair.into_inner();
     // ***
     let lf_pair : Pair<Rule> = contents.next().unwrap();
@@ -137,7 +139,7 @@ fn trace_action_from_text(gen_ctx : &GeneralContext,
     let got_lf_id : usize;
     match gen_ctx.get_lf_id(&lf_name) {
         None => {
-            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name) );
+            return Err( HibouParsingError::MissingLifelineDeclarationError(format!("{:} (line {:})", lf_name, line)) );
         },
         Some( lf_id ) => {
             got_lf_id = lf_id;
@@ -158,18 +160,62 @@ fn trace_action_from_text(gen_ctx : &GeneralContext,
         }
     }
     // ***
-    let ms_pair : Pair<Rule> = contents.next().unwrap();
-    let ms_name : String  = ms_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+    let mut ms_contents = contents.next().unwrap().into_inner();
+    let ms_name : String  = ms_contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
     // ***
     let got_ms_id : usize;
     match gen_ctx.get_ms_id(&ms_name) {
         None => {
-            return Err( HibouParsingError::MissingMessageDeclarationError(ms_name) );
+            return Err( HibouParsingError::MissingMessageDeclarationError(format!("{:} (line {:})", ms_name, line)) );
         },
         Some( ms_id ) => {
             got_ms_id = ms_id;
         }
     }
     // ***
-    return Ok( TraceAction{lf_id:got_lf_id,act_kind,ms_id:got_ms_id} );
+    let mut got_ms_param : Option<usize> = None;
+    if let Some(param_pair) = ms_contents.next() {
+        let param_name : String = param_pair.into_inner().next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        match gen_ctx.get_ms_param_id(got_ms_id,&param_name) {
+            None => {
+                return Err( HibouParsingError::MissingMessageParameterDeclarationError(format!("{:}({:}) (line {:})", ms_name, param_name, line)) );
+            },
+            Some( param_id ) => {
+                got_ms_param = Some(param_id);
+            }
+        }
+    }
+    // ***
+    return Ok( TraceAction::new(got_lf_id,act_kind,got_ms_id).with_param(got_ms_param) );
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::htf::implem::multitrace_from_text;
+    use super::*;
+
+    #[test]
+    fn htf_parsing_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("a".to_string());
+        gen_ctx.add_lf("b".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let (colocs,multi_trace) = multitrace_from_text(&gen_ctx,&"{\n[a] a!m.a!m;\n[b] b?m\n}".to_string()).unwrap();
+        assert_eq!(colocs.num_colocs(), 2);
+        assert_eq!(multi_trace, vec![
+            vec![btreeset!{TraceAction::new(0,TraceActionKind::Emission,0)},btreeset!{TraceAction::new(0,TraceActionKind::Emission,0)}],
+            vec![btreeset!{TraceAction::new(1,TraceActionKind::Reception,0)}]
+        ]);
+        // undeclared message on the third line
+        match multitrace_from_text(&gen_ctx,&"{\n[a] a!m;\n[b] b?z\n}".to_string()) {
+            Err(HibouParsingError::MissingMessageDeclarationError(got)) => {
+                assert_eq!(got, "z (line 3)");
+            },
+            _ => {
+                panic!("expected an undeclared message error");
+            }
+        }
+    }
+
 }
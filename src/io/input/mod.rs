@@ -19,6 +19,7 @@ pub mod hsf;
 pub mod hcf;
 pub mod htf;
 pub mod hif;
+pub mod sexpr;
 
 
 
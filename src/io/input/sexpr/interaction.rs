@@ -0,0 +1,449 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fmt;
+
+use pest::iterators::Pair;
+
+use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+use crate::io::input::error::HibouParsingError;
+use crate::io::textual_convention::{SEXPR_ACTION, SEXPR_EMISSION, SEXPR_EMPTY, SEXPR_GATE_ORIGIN, SEXPR_GROUP, SEXPR_PARAM, SEXPR_RECEPTION, SEXPR_SYNCHRONOUS, SEXPR_TARGETS, SYNTAX_ALT, SYNTAX_AND, SYNTAX_COREG, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_NOT, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT, SYNTAX_SYNC};
+
+
+#[allow(unused_imports)]
+use pest::Parser;
+#[allow(unused_imports)]
+use crate::io::input::sexpr::parser::{SexprParser,Rule};
+
+
+/// Generic S-expression tree, interpreted as an interaction in a second step.
+enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>)
+}
+
+impl fmt::Display for SExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SExpr::Atom(atom) => {
+                return write!(f, "{}", atom);
+            },
+            SExpr::List(sub_exprs) => {
+                let sub_strs : Vec<String> = sub_exprs.iter().map(|sub| sub.to_string()).collect();
+                return write!(f, "({})", sub_strs.join(" "));
+            }
+        }
+    }
+}
+
+fn sexpr_from_pair(pair : Pair<Rule>) -> SExpr {
+    match pair.as_rule() {
+        Rule::SEXPR_ATOM => {
+            return SExpr::Atom(pair.as_str().to_string());
+        },
+        Rule::SEXPR_LIST => {
+            return SExpr::List(pair.into_inner().map(sexpr_from_pair).collect());
+        },
+        _ => {
+            panic!("what rule then ? : {:?}", pair.as_rule() );
+        }
+    }
+}
+
+pub fn parse_sexpr(gen_ctx : &GeneralContext, sexpr_string : &str) -> Result<Interaction,HibouParsingError> {
+    match SexprParser::parse(Rule::SEXPR_PEST_FILE, sexpr_string) {
+        Ok( ref mut got_pairs ) => {
+            let sexpr = sexpr_from_pair(got_pairs.next().unwrap());
+            return parse_interaction(gen_ctx,&sexpr);
+        },
+        Err(e) => {
+            return Err( HibouParsingError::MatchError(e.to_string()) );
+        }
+    }
+}
+
+fn ill_formed(sexpr : &SExpr, reason : &str) -> HibouParsingError {
+    return HibouParsingError::OtherDefinitionError( format!("{} in '{}'", reason, sexpr) );
+}
+
+fn parse_interaction(gen_ctx : &GeneralContext, sexpr : &SExpr) -> Result<Interaction,HibouParsingError> {
+    let sub_exprs : &Vec<SExpr>;
+    match sexpr {
+        SExpr::Atom(atom) => {
+            if atom == SEXPR_EMPTY {
+                return Ok( Interaction::Empty );
+            }
+            return Err( ill_formed(sexpr,"unknown interaction") );
+        },
+        SExpr::List(got) => {
+            sub_exprs = got;
+        }
+    }
+    let head : &str;
+    match sub_exprs.first() {
+        Some(SExpr::Atom(atom)) => {
+            head = atom;
+        },
+        _ => {
+            return Err( ill_formed(sexpr,"expected an operator name") );
+        }
+    }
+    let args = &sub_exprs[1..];
+    if head == SEXPR_ACTION {
+        return parse_action(gen_ctx,sexpr,args);
+    }
+    for (lk_text,lk) in [(SYNTAX_LOOP_S,LoopKind::SStrictSeq),
+                         (SYNTAX_LOOP_H,LoopKind::HHeadFirstWS),
+                         (SYNTAX_LOOP_W,LoopKind::WWeakSeq),
+                         (SYNTAX_LOOP_P,LoopKind::PInterleaving)] {
+        if head == lk_text {
+            if args.len() != 1 {
+                return Err( ill_formed(sexpr,"expected exactly one operand") );
+            }
+            let i1 = parse_interaction(gen_ctx,&args[0])?;
+            return Ok( Interaction::Loop(lk,Box::new(i1)) );
+        }
+    }
+    if head == SYNTAX_NOT {
+        if args.len() != 1 {
+            return Err( ill_formed(sexpr,"expected exactly one action") );
+        }
+        return Ok( Interaction::Not(parse_trace_action(gen_ctx,&args[0])?) );
+    }
+    // ***
+    let operands : &[SExpr];
+    if head == SYNTAX_COREG || head == SYNTAX_SYNC {
+        if args.len() != 3 {
+            return Err( ill_formed(sexpr,"expected a list followed by exactly two operands") );
+        }
+        operands = &args[1..];
+    } else {
+        if args.len() != 2 {
+            return Err( ill_formed(sexpr,"expected exactly two operands") );
+        }
+        operands = args;
+    }
+    let i1 = Box::new(parse_interaction(gen_ctx,&operands[0])?);
+    let i2 = Box::new(parse_interaction(gen_ctx,&operands[1])?);
+    if head == SYNTAX_STRICT {
+        return Ok( Interaction::Strict(i1,i2) );
+    } else if head == SYNTAX_SEQ {
+        return Ok( Interaction::Seq(i1,i2) );
+    } else if head == SYNTAX_PAR {
+        return Ok( Interaction::Par(i1,i2) );
+    } else if head == SYNTAX_ALT {
+        return Ok( Interaction::Alt(i1,i2) );
+    } else if head == SYNTAX_AND {
+        return Ok( Interaction::And(i1,i2) );
+    } else if head == SYNTAX_COREG {
+        let cr = parse_lifelines(gen_ctx,&args[0])?;
+        return Ok( Interaction::CoReg(cr,i1,i2) );
+    } else if head == SYNTAX_SYNC {
+        let mut sync_acts = vec![];
+        match &args[0] {
+            SExpr::List(acts_exprs) => {
+                for act_expr in acts_exprs {
+                    sync_acts.push( parse_trace_action(gen_ctx,act_expr)? );
+                }
+            },
+            SExpr::Atom(_) => {
+                return Err( ill_formed(sexpr,"expected a list of actions") );
+            }
+        }
+        return Ok( Interaction::Sync(sync_acts,i1,i2) );
+    }
+    return Err( ill_formed(sexpr,"unknown operator") );
+}
+
+fn parse_lifeline(gen_ctx : &GeneralContext, lf_name : &str) -> Result<usize,HibouParsingError> {
+    match gen_ctx.get_lf_id(lf_name) {
+        None => {
+            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name.to_string()) );
+        },
+        Some( lf_id ) => {
+            return Ok( lf_id );
+        }
+    }
+}
+
+/// Either a single lifeline name or a list of lifeline names.
+fn parse_lifelines(gen_ctx : &GeneralContext, sexpr : &SExpr) -> Result<Vec<usize>,HibouParsingError> {
+    match sexpr {
+        SExpr::Atom(lf_name) => {
+            return Ok( vec![parse_lifeline(gen_ctx,lf_name)?] );
+        },
+        SExpr::List(sub_exprs) => {
+            let mut lf_ids = vec![];
+            for sub in sub_exprs {
+                match sub {
+                    SExpr::Atom(lf_name) => {
+                        lf_ids.push( parse_lifeline(gen_ctx,lf_name)? );
+                    },
+                    SExpr::List(_) => {
+                        return Err( ill_formed(sexpr,"expected a lifeline name") );
+                    }
+                }
+            }
+            return Ok( lf_ids );
+        }
+    }
+}
+
+fn parse_message(gen_ctx : &GeneralContext, sexpr : &SExpr) -> Result<usize,HibouParsingError> {
+    match sexpr {
+        SExpr::Atom(ms_name) => {
+            match gen_ctx.get_ms_id(ms_name) {
+                None => {
+                    return Err( HibouParsingError::MissingMessageDeclarationError(ms_name.to_string()) );
+                },
+                Some( ms_id ) => {
+                    return Ok( ms_id );
+                }
+            }
+        },
+        SExpr::List(_) => {
+            return Err( ill_formed(sexpr,"expected a message name") );
+        }
+    }
+}
+
+/// Modifiers shared by all the kinds of actions ; the list-shaped modifiers which are not
+/// about the message are returned as (key,values) for the caller to interpret.
+struct ContentModifiers<'a> {
+    ms_param : Option<usize>,
+    synchronicity : CommunicationSynchronicity,
+    others : Vec<(&'a str,Vec<&'a str>)>
+}
+
+fn parse_modifiers<'a>(gen_ctx : &GeneralContext,
+                       ms_id : usize,
+                       whole : &SExpr,
+                       modifiers : &'a [SExpr]) -> Result<ContentModifiers<'a>,HibouParsingError> {
+    let mut got = ContentModifiers{ms_param:None,synchronicity:CommunicationSynchronicity::Asynchronous,others:vec![]};
+    for modifier in modifiers {
+        match modifier {
+            SExpr::Atom(atom) => {
+                if atom == SEXPR_SYNCHRONOUS {
+                    got.synchronicity = CommunicationSynchronicity::Synchronous;
+                } else {
+                    return Err( ill_formed(whole,"unknown action modifier") );
+                }
+            },
+            SExpr::List(sub_exprs) => {
+                let mut names : Vec<&str> = vec![];
+                for sub in sub_exprs {
+                    match sub {
+                        SExpr::Atom(name) => {
+                            names.push(name);
+                        },
+                        SExpr::List(_) => {
+                            return Err( ill_formed(whole,"nested list in action modifier") );
+                        }
+                    }
+                }
+                if names.is_empty() {
+                    return Err( ill_formed(whole,"empty action modifier") );
+                }
+                let key = names.remove(0);
+                if key == SEXPR_PARAM {
+                    if names.len() != 1 {
+                        return Err( ill_formed(whole,"expected exactly one message parameter") );
+                    }
+                    match gen_ctx.get_ms_param_id(ms_id,names[0]) {
+                        None => {
+                            return Err( HibouParsingError::MissingMessageParameterDeclarationError(
+                                format!("{}({})", gen_ctx.get_ms_name(ms_id).unwrap(), names[0])) );
+                        },
+                        Some( param_id ) => {
+                            got.ms_param = Some(param_id);
+                        }
+                    }
+                } else {
+                    got.others.push( (key,names) );
+                }
+            }
+        }
+    }
+    return Ok( got );
+}
+
+fn parse_action(gen_ctx : &GeneralContext, whole : &SExpr, args : &[SExpr]) -> Result<Interaction,HibouParsingError> {
+    if args.len() < 3 {
+        return Err( ill_formed(whole,"expected a subject, an action kind and a message") );
+    }
+    let ms_id = parse_message(gen_ctx,&args[2])?;
+    let modifiers = parse_modifiers(gen_ctx,ms_id,whole,&args[3..])?;
+    match &args[1] {
+        SExpr::Atom(kind) if kind == SEXPR_EMISSION => {
+            let origin_lf_id : usize;
+            match &args[0] {
+                SExpr::Atom(lf_name) => {
+                    origin_lf_id = parse_lifeline(gen_ctx,lf_name)?;
+                },
+                SExpr::List(_) => {
+                    return Err( HibouParsingError::EmissionDefinitionError(format!("a single emitting lifeline is expected in '{}'", whole)) );
+                }
+            }
+            let mut targets : Vec<EmissionTargetRef> = vec![];
+            let mut target_group : Option<usize> = None;
+            for (key,names) in modifiers.others {
+                if !targets.is_empty() || target_group.is_some() {
+                    return Err( HibouParsingError::EmissionDefinitionError(format!("targets given more than once in '{}'", whole)) );
+                }
+                if key == SEXPR_TARGETS {
+                    for target_name in names {
+                        let new_ref : EmissionTargetRef;
+                        match (gen_ctx.get_lf_id(target_name),gen_ctx.get_gt_id(target_name)) {
+                            (Some(tar_lf_id),_) => {
+                                new_ref = EmissionTargetRef::Lifeline(tar_lf_id);
+                            },
+                            (None,Some(tar_gt_id)) => {
+                                new_ref = EmissionTargetRef::Gate(tar_gt_id);
+                            },
+                            (None,None) => {
+                                return Err( HibouParsingError::MissingLifelineOrGateDeclarationError(target_name.to_string()) );
+                            }
+                        }
+                        if targets.contains(&new_ref) {
+                            return Err( HibouParsingError::EmissionDefinitionError(format!("duplicate target {:}",target_name)) );
+                        }
+                        targets.push(new_ref);
+                    }
+                } else if key == SEXPR_GROUP && names.len() == 1 {
+                    match gen_ctx.get_group_id(names[0]) {
+                        None => {
+                            return Err( HibouParsingError::EmissionDefinitionError(format!("unknown multicast group {:}",names[0])) );
+                        },
+                        Some( group_id ) => {
+                            targets = gen_ctx.get_group_lifelines(group_id).unwrap().iter()
+                                .map(|lf_id| EmissionTargetRef::Lifeline(*lf_id)).collect();
+                            target_group = Some(group_id);
+                        }
+                    }
+                } else {
+                    return Err( ill_formed(whole,"unknown emission modifier") );
+                }
+            }
+            let em_act = EmissionAction::new(origin_lf_id,ms_id,modifiers.synchronicity,targets)
+                .with_param(modifiers.ms_param)
+                .with_group(target_group);
+            return Ok( Interaction::Emission(em_act) );
+        },
+        SExpr::Atom(kind) if kind == SEXPR_RECEPTION => {
+            let recipients = parse_lifelines(gen_ctx,&args[0])?;
+            if recipients.is_empty() {
+                return Err( ill_formed(whole,"expected at least one recipient") );
+            }
+            let mut origin_gt_id : Option<usize> = None;
+            for (key,names) in modifiers.others {
+                if key == SEXPR_GATE_ORIGIN && names.len() == 1 && origin_gt_id.is_none() {
+                    match gen_ctx.get_gt_id(names[0]) {
+                        None => {
+                            return Err( HibouParsingError::MissingGateDeclarationError(names[0].to_string()) );
+                        },
+                        Some( gt_id ) => {
+                            origin_gt_id = Some(gt_id);
+                        }
+                    }
+                } else {
+                    return Err( ill_formed(whole,"unknown reception modifier") );
+                }
+            }
+            let rc_act = ReceptionAction::new(origin_gt_id,ms_id,modifiers.synchronicity,recipients)
+                .with_param(modifiers.ms_param);
+            return Ok( Interaction::Reception(rc_act) );
+        },
+        _ => {
+            return Err( ill_formed(whole,"expected either 'emit' or 'recv'") );
+        }
+    }
+}
+
+fn parse_trace_action(gen_ctx : &GeneralContext, sexpr : &SExpr) -> Result<TraceAction,HibouParsingError> {
+    match sexpr {
+        SExpr::List(args) if args.len() >= 3 => {
+            let lf_id : usize;
+            match &args[0] {
+                SExpr::Atom(lf_name) => {
+                    lf_id = parse_lifeline(gen_ctx,lf_name)?;
+                },
+                SExpr::List(_) => {
+                    return Err( ill_formed(sexpr,"expected a lifeline name") );
+                }
+            }
+            let act_kind : TraceActionKind;
+            match &args[1] {
+                SExpr::Atom(kind) if kind == SEXPR_EMISSION => {
+                    act_kind = TraceActionKind::Emission;
+                },
+                SExpr::Atom(kind) if kind == SEXPR_RECEPTION => {
+                    act_kind = TraceActionKind::Reception;
+                },
+                _ => {
+                    return Err( ill_formed(sexpr,"expected either 'emit' or 'recv'") );
+                }
+            }
+            let ms_id = parse_message(gen_ctx,&args[2])?;
+            let modifiers = parse_modifiers(gen_ctx,ms_id,sexpr,&args[3..])?;
+            if !modifiers.others.is_empty() || modifiers.synchronicity == CommunicationSynchronicity::Synchronous {
+                return Err( ill_formed(sexpr,"only a message parameter can be given for a synchronized action") );
+            }
+            return Ok( TraceAction::new(lf_id,act_kind,ms_id).with_param(modifiers.ms_param) );
+        },
+        _ => {
+            return Err( ill_formed(sexpr,"expected an action of the form (lifeline emit|recv message)") );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::output::to_hfiles::interaction::to_sexpr::interaction_to_sexpr;
+    use super::*;
+
+    #[test]
+    fn sexpr_round_trip_test() {
+        let gen_ctx = parse_hsf_string("@message{m1;m2}\n@lifeline{a;b;c}\n@gate{g}".to_string()).unwrap();
+        let hif = "alt(par(c -- m2 -> (c,g), loopW(a -- <synch>m1 ->|)), seq(g -- m1 -> a, coreg(b,a)(strict(a -- m1 ->|, ∅), m2 -> (b,a))))";
+        let parsed = parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let sexpr = interaction_to_sexpr(&gen_ctx,&parsed);
+        assert!(sexpr.contains("(act c emit m2 (to c g))"));
+        assert!(sexpr.contains("(act a emit m1 synch)"));
+        assert!(sexpr.contains("(act a recv m1 (from g))"));
+        assert!(sexpr.contains("(coreg (b a) "));
+        assert!(sexpr.contains("(act (b a) recv m2)"));
+        let reparsed = parse_sexpr(&gen_ctx,&sexpr).unwrap();
+        // the structure is preserved exactly, including the order of the operands and targets
+        assert_eq!(reparsed, parsed);
+        // line breaks and comments are ignored
+        let commented = "; a commented interaction\n(seq (act a emit m1 (to b))\n     (act b recv m1))";
+        let expected = parse_hif_string(&gen_ctx,"seq(a -- m1 -> b, m1 -> b)".to_string()).unwrap();
+        assert_eq!(parse_sexpr(&gen_ctx,commented).unwrap(), expected);
+        // ill-formed terms are rejected
+        assert!(parse_sexpr(&gen_ctx,"(seq (act a emit m1))").is_err());
+        assert!(parse_sexpr(&gen_ctx,"(act d emit m1)").is_err());
+        assert!(parse_sexpr(&gen_ctx,"(act a emit m1 (from g))").is_err());
+        assert!(parse_sexpr(&gen_ctx,"(act a emit m1").is_err());
+    }
+
+}
@@ -28,7 +28,7 @@ use imageproc::rect::Rect;
 use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::action::{EmissionAction, EmissionTargetRef};
 use crate::io::output::draw_commons::font::{get_hibou_font, HIBOU_FONT_SCALE};
-use crate::io::output::draw_commons::hibou_color_palette::{HC_Message, HCP_Black};
+use crate::io::output::draw_commons::hibou_color_palette::{HC_Lifeline, HC_Message, HCP_Black};
 use crate::io::output::draw_commons::sd_drawing_conf::*;
 use crate::io::output::draw_interactions::as_sd::action_repr::common::draw_line_for_message_exchange;
 use crate::io::output::draw_interactions::as_sd::util::arrow_heads::{draw_arrowhead_leftward, draw_arrowhead_rightward};
@@ -48,8 +48,13 @@ pub fn draw_emission( image : &mut RgbImage,
     // ***
     let msg_to_print : Vec<TextToPrint>;
     {
-        let msg_label = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
-        msg_to_print = vec![TextToPrint::new(msg_label,Rgb(HC_Message))];
+        let msg_label = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
+        let mut to_print = vec![TextToPrint::new(msg_label,Rgb(HC_Message))];
+        // the multicast group the message is sent to
+        if let Some(group_id) = em_act.target_group {
+            to_print.push( TextToPrint::new(format!(" @{}", gen_ctx.get_group_name(group_id).unwrap()),Rgb(HC_Lifeline)) );
+        }
+        msg_to_print = to_print;
     }
     // ***
     let text_y_pos = get_y_pos_from_yshift(yshift) + VERTICAL_SIZE/2.0;
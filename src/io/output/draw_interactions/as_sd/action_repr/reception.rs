@@ -47,7 +47,7 @@ pub fn draw_reception( image : &mut RgbImage,
     // ***
     let msg_to_print : Vec<TextToPrint>;
     {
-        let msg_label = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
+        let msg_label = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
         msg_to_print = vec![TextToPrint::new(msg_label,Rgb(HC_Message))];
     }
     // ***
@@ -33,7 +33,7 @@ use crate::io::output::draw_interactions::as_sd::action_repr::emission::draw_emi
 use crate::io::output::draw_interactions::as_sd::action_repr::reception::draw_reception;
 use crate::io::output::draw_interactions::as_sd::util::dimensions_tools::get_y_pos_from_yshift;
 use crate::io::output::draw_interactions::as_sd::util::lf_coords::DrawingLifelineCoords;
-use crate::io::output::draw_traces::implem::trace_action::diagram_repr_trace_actions;
+use crate::io::output::draw_traces::implem::trace_action::{diagram_repr_trace_action, diagram_repr_trace_actions};
 use crate::io::textual_convention::*;
 
 
@@ -61,6 +61,22 @@ pub fn draw_interaction_rec(    image : &mut RgbImage,
             *yshift = *yshift + 3;
             return lr_bounds;
         },
+        &Interaction::Not(ref act) => {
+            // the forbidden action is written over its lifeline
+            let lf_coords = lf_x_widths.get(&act.lf_id).unwrap();
+            let mut label = vec![TextToPrint::new(SYNTAX_NOT.to_string(),Rgb(HCP_Black))];
+            label.push(TextToPrint::new("(".to_string(),Rgb(HCP_Black)));
+            label.append(&mut diagram_repr_trace_action(act,gen_ctx));
+            label.push(TextToPrint::new(")".to_string(),Rgb(HCP_Black)));
+            draw_line_of_colored_text(image,
+                                      &DrawCoord::CenteredAround(lf_coords.x_middle),
+                                      &DrawCoord::CenteredAround(get_y_pos_from_yshift(*yshift) + VERTICAL_SIZE),
+                                      &label,
+                                      &get_hibou_font(),
+                                      &HIBOU_FONT_SCALE);
+            *yshift = *yshift + 2;
+            return [act.lf_id,act.lf_id];
+        },
         &Interaction::Seq(ref i1,ref i2) => {
             let wr1 : [usize;2] = draw_interaction_rec(image, gen_ctx,i1, lf_x_widths,  lf_num,nest_shift, yshift);
             *yshift = *yshift +1;
@@ -19,16 +19,26 @@ use std::path::PathBuf;
 use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::interaction::Interaction;
 use crate::io::output::draw_interactions::as_sd::interaction_repr::interaction::make_interaction_image;
+use crate::io::output::draw_interactions::as_sd::util::lf_order::relabel_lifelines_in_order;
 
 
 pub fn draw_int_as_sd(gen_ctx : &GeneralContext,
                       interaction : &Interaction,
+                      lifeline_order : &Option<Vec<usize>>,
                       parent_folder : &String,
                       output_file_name : &String) {
     // ***
     let output_file_name = format!("{:}.png", output_file_name);
     let output_path : PathBuf = [parent_folder, &output_file_name].iter().collect();
-    let image = make_interaction_image(gen_ctx,interaction);
+    let image = match lifeline_order {
+        None => {
+            make_interaction_image(gen_ctx,interaction)
+        },
+        Some( order ) => {
+            let (ordered_ctx,ordered_int) = relabel_lifelines_in_order(gen_ctx,interaction,order);
+            make_interaction_image(&ordered_ctx,&ordered_int)
+        }
+    };
     image.save(output_path.as_path());
 }
 
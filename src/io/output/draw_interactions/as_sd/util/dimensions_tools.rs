@@ -37,6 +37,9 @@ fn get_interaction_depth(interaction : &Interaction ) -> usize {
         &Interaction::Reception(_) => {
             return 3;
         },
+        &Interaction::Not(_) => {
+            return 2;
+        },
         &Interaction::Strict(ref i1, ref i2) => {
             let mut frags = get_recursive_strict_frags(i1);
             frags.extend( get_recursive_strict_frags(i2) );
@@ -0,0 +1,148 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::Interaction;
+
+
+/// The drawing places lifelines from left to right in increasing order of their ids.
+/// To draw them in another order, the lifelines are relabelled so that the id of each lifeline
+/// is its position in that order.
+/// The lifelines in 'lifeline_order' come first, the others follow in declaration order.
+pub fn relabel_lifelines_in_order(gen_ctx : &GeneralContext,
+                                  interaction : &Interaction,
+                                  lifeline_order : &[usize]) -> (GeneralContext,Interaction) {
+    let mut ordered : Vec<usize> = vec![];
+    for lf_id in lifeline_order.iter().cloned().chain(0..gen_ctx.get_lf_num()) {
+        if lf_id < gen_ctx.get_lf_num() && !ordered.contains(&lf_id) {
+            ordered.push(lf_id);
+        }
+    }
+    let mut new_ids : Vec<usize> = vec![0; gen_ctx.get_lf_num()];
+    for (new_id,old_id) in ordered.iter().enumerate() {
+        new_ids[*old_id] = new_id;
+    }
+    // ***
+    let mut new_ctx = GeneralContext::new();
+    for old_id in &ordered {
+        new_ctx.add_lf(gen_ctx.get_lf_name(*old_id).unwrap());
+    }
+    for ms_id in 0..gen_ctx.get_ms_num() {
+        new_ctx.add_msg(gen_ctx.get_ms_name(ms_id).unwrap());
+        for param_id in 0..gen_ctx.get_ms_params_num(ms_id) {
+            new_ctx.add_ms_param(ms_id, gen_ctx.get_ms_param_name(ms_id,param_id).unwrap());
+        }
+        for (orig_lf_id,tar_lf_id) in gen_ctx.get_ms_directions(ms_id) {
+            new_ctx.add_ms_direction(ms_id, new_ids[*orig_lf_id], new_ids[*tar_lf_id]);
+        }
+    }
+    for gt_id in 0..gen_ctx.get_gt_num() {
+        new_ctx.add_gt(gen_ctx.get_gt_name(gt_id).unwrap());
+    }
+    for group_id in 0..gen_ctx.get_group_num() {
+        let group_lfs = gen_ctx.get_group_lifelines(group_id).unwrap().iter().map(|lf_id| new_ids[*lf_id]).collect();
+        new_ctx.add_group(gen_ctx.get_group_name(group_id).unwrap(), group_lfs);
+    }
+    return (new_ctx, relabel_interaction(interaction,&new_ids));
+}
+
+fn relabel_interaction(interaction : &Interaction, new_ids : &Vec<usize>) -> Interaction {
+    let relabel = |i : &Interaction| Box::new(relabel_interaction(i,new_ids));
+    match interaction {
+        Interaction::Empty => {
+            Interaction::Empty
+        },
+        Interaction::Emission(em_act) => {
+            let targets = em_act.targets.iter().map(|targ_ref|
+                match targ_ref {
+                    EmissionTargetRef::Lifeline(tar_lf_id) => {
+                        EmissionTargetRef::Lifeline(new_ids[*tar_lf_id])
+                    },
+                    EmissionTargetRef::Gate(tar_gt_id) => {
+                        EmissionTargetRef::Gate(*tar_gt_id)
+                    }
+                }).collect();
+            let new_act = EmissionAction{origin_lf_id:new_ids[em_act.origin_lf_id],targets,..em_act.clone()};
+            Interaction::Emission(new_act)
+        },
+        Interaction::Reception(rc_act) => {
+            let recipients = rc_act.recipients.iter().map(|lf_id| new_ids[*lf_id]).collect();
+            Interaction::Reception(ReceptionAction{recipients,..rc_act.clone()})
+        },
+        Interaction::Strict(i1, i2) => {
+            Interaction::Strict(relabel(i1),relabel(i2))
+        },
+        Interaction::Seq(i1, i2) => {
+            Interaction::Seq(relabel(i1),relabel(i2))
+        },
+        Interaction::CoReg(cr, i1, i2) => {
+            let new_cr = cr.iter().map(|lf_id| new_ids[*lf_id]).collect();
+            Interaction::CoReg(new_cr,relabel(i1),relabel(i2))
+        },
+        Interaction::Alt(i1, i2) => {
+            Interaction::Alt(relabel(i1),relabel(i2))
+        },
+        Interaction::Par(i1, i2) => {
+            Interaction::Par(relabel(i1),relabel(i2))
+        },
+        Interaction::Loop(lk, i1) => {
+            Interaction::Loop(lk.clone(),relabel(i1))
+        },
+        Interaction::And(i1, i2) => {
+            Interaction::And(relabel(i1),relabel(i2))
+        },
+        Interaction::Sync(sync_acts, i1, i2) => {
+            let mut new_acts = sync_acts.clone();
+            for tract in new_acts.iter_mut() {
+                tract.lf_id = new_ids[tract.lf_id];
+            }
+            Interaction::Sync(new_acts,relabel(i1),relabel(i2))
+        },
+        Interaction::Not(act) => {
+            let mut new_act = act.clone();
+            new_act.lf_id = new_ids[act.lf_id];
+            Interaction::Not(new_act)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::output::to_hfiles::interaction::to_sexpr::interaction_to_sexpr;
+    use super::*;
+
+    #[test]
+    fn relabel_lifelines_test() {
+        let gen_ctx = parse_hsf_string("@message{m1;m2}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let int = parse_hif_string(&gen_ctx,"seq(a -- m1 -> (b,c), coreg(c)(b -- m2 -> c, m1 -> a))".to_string()).unwrap();
+        let (new_ctx,new_int) = relabel_lifelines_in_order(&gen_ctx,&int,&[2,0]);
+        // c, then a, then the unmentioned b
+        assert_eq!(new_ctx.get_lf_id("c"), Some(0));
+        assert_eq!(new_ctx.get_lf_id("a"), Some(1));
+        assert_eq!(new_ctx.get_lf_id("b"), Some(2));
+        // the interaction is unchanged up to the renaming
+        assert_eq!(interaction_to_sexpr(&new_ctx,&new_int), interaction_to_sexpr(&gen_ctx,&int));
+        let (same_ctx,same_int) = relabel_lifelines_in_order(&gen_ctx,&int,&[]);
+        assert_eq!(same_ctx, gen_ctx);
+        assert_eq!(same_int, int);
+    }
+
+}
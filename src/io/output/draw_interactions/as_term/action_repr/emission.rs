@@ -23,17 +23,21 @@ use crate::io::textual_convention::{SYNTAX_EMISSION, SYNTAX_EMISSION_SYNCHRONOUS
 pub fn emission_as_gv_label(gen_ctx : &GeneralContext,
                     em_act : &EmissionAction) -> String {
     // ***
-    let ms_name = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
     let lf_name = gen_ctx.get_lf_name(em_act.origin_lf_id).unwrap();
     // ***
     let mut targ_names : Vec<String> = Vec::new();
-    for targ_ref in &em_act.targets {
-        match targ_ref {
-            EmissionTargetRef::Lifeline(tar_lf_id) => {
-                targ_names.push( gen_ctx.get_lf_name(*tar_lf_id).unwrap() );
-            },
-            EmissionTargetRef::Gate(tar_gt_id) => {
-                targ_names.push( gen_ctx.get_gt_name(*tar_gt_id).unwrap() );
+    if let Some(group_id) = em_act.target_group {
+        targ_names.push( gen_ctx.get_group_name(group_id).unwrap() );
+    } else {
+        for targ_ref in &em_act.targets {
+            match targ_ref {
+                EmissionTargetRef::Lifeline(tar_lf_id) => {
+                    targ_names.push( gen_ctx.get_lf_name(*tar_lf_id).unwrap() );
+                },
+                EmissionTargetRef::Gate(tar_gt_id) => {
+                    targ_names.push( gen_ctx.get_gt_name(*tar_gt_id).unwrap() );
+                }
             }
         }
     }
@@ -23,7 +23,7 @@ use crate::io::textual_convention::{SYNTAX_RECEPTION, SYNTAX_RECEPTION_SYNCHRONO
 pub fn reception_as_gv_label (gen_ctx : &GeneralContext,
                     rc_act : &ReceptionAction) -> String {
     // ***
-    let ms_name = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
     // ***
     let mut targ_names : Vec<String> = Vec::new();
     for rcp_lf_id in &rc_act.recipients {
@@ -33,7 +33,7 @@ pub fn trace_action_as_gv_label (gen_ctx : &GeneralContext,
         }
     }
     // ***
-    let ms_name = gen_ctx.get_ms_name(tr_act.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(tr_act.ms_id,tr_act.ms_param).unwrap();
     // ***
     return format!("{}{}{}",lf_name,act_kind_label,ms_name);
 }
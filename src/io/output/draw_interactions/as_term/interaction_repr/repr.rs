@@ -14,6 +14,9 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::HashSet;
+
+use graphviz_dot_builder::colors::GraphvizColor;
 use graphviz_dot_builder::edge::edge::GraphVizEdge;
 use graphviz_dot_builder::edge::style::{GraphvizEdgeStyleItem, GvArrowHeadStyle};
 use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
@@ -26,21 +29,49 @@ use crate::core::language::syntax::interaction::{Interaction, LoopKind};
 use crate::core::language::position::position::Position;
 use crate::io::output::draw_interactions::as_term::action_repr::emission::{emission_as_gv_label};
 use crate::io::output::draw_interactions::as_term::action_repr::reception::{reception_as_gv_label};
-use crate::io::output::draw_interactions::as_term::action_repr::trace_action::trace_actions_as_gv_label;
-use crate::io::textual_convention::{SYNTAX_ALT, SYNTAX_COREG, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT, SYNTAX_SYNC, SYNTAX_AND};
+use crate::io::output::draw_interactions::as_term::action_repr::trace_action::{trace_action_as_gv_label, trace_actions_as_gv_label};
+use crate::io::textual_convention::{SYNTAX_ALT, SYNTAX_COREG, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT, SYNTAX_SYNC, SYNTAX_AND, SYNTAX_NOT};
 
 
 pub fn interaction_gv_repr(gen_ctx : &GeneralContext,
                         interaction : &Interaction) -> GraphVizDiGraph {
     let mut digraph = GraphVizDiGraph::new(vec![]);
-    interaction_gv_repr_rec(gen_ctx, interaction,Position::Epsilon(None), &mut digraph);
+    interaction_gv_repr_rec(gen_ctx, interaction,Position::Epsilon(None), None, &mut digraph);
+    return digraph;
+}
+
+/// Same as 'interaction_gv_repr' but with the nodes at the covered positions filled in green and the others in grey.
+pub fn interaction_coverage_gv_repr(gen_ctx : &GeneralContext,
+                                    interaction : &Interaction,
+                                    covered : &HashSet<Position>) -> GraphVizDiGraph {
+    let mut digraph = GraphVizDiGraph::new(vec![]);
+    interaction_gv_repr_rec(gen_ctx, interaction,Position::Epsilon(None), Some(covered), &mut digraph);
     return digraph;
 }
 
+fn add_term_node(gv_graph : &mut GraphVizDiGraph,
+                 node_name : String,
+                 mut node_gv_options : GraphvizNodeStyle,
+                 current_pos : &Position,
+                 covered : Option<&HashSet<Position>>) {
+    if let Some(covered) = covered {
+        node_gv_options.retain(|item| !matches!(item, GraphvizNodeStyleItem::Shape(_)));
+        node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle) );
+        if covered.contains(current_pos) {
+            // the fill color item also sets the filled style
+            node_gv_options.push( GraphvizNodeStyleItem::FillColor(GraphvizColor::palegreen) );
+        } else {
+            node_gv_options.push( GraphvizNodeStyleItem::FillColor(GraphvizColor::lightgrey) );
+        }
+    }
+    gv_graph.add_node( GraphVizNode::new(node_name, node_gv_options) );
+}
+
 
 fn interaction_gv_repr_rec(gen_ctx : &GeneralContext,
                         interaction : &Interaction,
                         current_pos : Position,
+                           covered : Option<&HashSet<Position>>,
                            gv_graph : &mut GraphVizDiGraph) -> String {
     let node_name = format!("p{}",current_pos.to_string());
     match interaction {
@@ -48,41 +79,48 @@ fn interaction_gv_repr_rec(gen_ctx : &GeneralContext,
             let mut node_gv_options : GraphvizNodeStyle = Vec::new();
             node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::PlainText) );
             node_gv_options.push( GraphvizNodeStyleItem::Label( "o".to_string() ) );
-            gv_graph.add_node( GraphVizNode::new(node_name.clone(), node_gv_options) );
+            add_term_node(gv_graph, node_name.clone(), node_gv_options, &current_pos, covered);
         },
         &Interaction::Emission(ref em_act) => {
             let mut node_gv_options : GraphvizNodeStyle = Vec::new();
             node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::PlainText) );
             node_gv_options.push( GraphvizNodeStyleItem::Label( emission_as_gv_label(gen_ctx,em_act) ) );
-            gv_graph.add_node( GraphVizNode::new(node_name.clone(), node_gv_options) );
+            add_term_node(gv_graph, node_name.clone(), node_gv_options, &current_pos, covered);
         },
         &Interaction::Reception(ref rc_act) => {
             let mut node_gv_options : GraphvizNodeStyle = Vec::new();
             node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::PlainText) );
             node_gv_options.push( GraphvizNodeStyleItem::Label( reception_as_gv_label(gen_ctx,rc_act) ) );
-            gv_graph.add_node( GraphVizNode::new(node_name.clone(), node_gv_options) );
+            add_term_node(gv_graph, node_name.clone(), node_gv_options, &current_pos, covered);
+        },
+        &Interaction::Not(ref act) => {
+            let mut node_gv_options : GraphvizNodeStyle = Vec::new();
+            node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::PlainText) );
+            let label = format!("{}({})", SYNTAX_NOT, trace_action_as_gv_label(gen_ctx,act));
+            node_gv_options.push( GraphvizNodeStyleItem::Label( label ) );
+            add_term_node(gv_graph, node_name.clone(), node_gv_options, &current_pos, covered);
         },
         &Interaction::Strict(ref i1, ref i2) => {
-            repr_binary_operator(gen_ctx,i1, i2, SYNTAX_STRICT, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx,i1, i2, SYNTAX_STRICT, current_pos, covered, gv_graph);
         },
         &Interaction::Seq(ref i1, ref i2) => {
-            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_SEQ, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_SEQ, current_pos, covered, gv_graph);
         },
         &Interaction::CoReg(ref cr, ref i1, ref i2) => {
             let co_localised_lf_names : Vec<String> = cr.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
             let op_label = format!("{}({})", SYNTAX_COREG, co_localised_lf_names.join(","));
-            repr_binary_operator(gen_ctx, i1, i2, &op_label, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, &op_label, current_pos, covered, gv_graph);
         },
         &Interaction::Sync(ref sync_acts, ref i1, ref i2) => {
             let acts_as_str = trace_actions_as_gv_label(gen_ctx,sync_acts.iter());
             let op_label = format!("{}{}", SYNTAX_SYNC,acts_as_str);
-            repr_binary_operator(gen_ctx, i1, i2, &op_label, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, &op_label, current_pos, covered, gv_graph);
         },
         &Interaction::Par(ref i1, ref i2) => {
-            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_PAR, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_PAR, current_pos, covered, gv_graph);
         },
         &Interaction::Alt(ref i1, ref i2) => {
-            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_ALT, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_ALT, current_pos, covered, gv_graph);
         },
         &Interaction::Loop(ref lp_kind, ref i1) => {
             // the parent loop node
@@ -103,12 +141,12 @@ fn interaction_gv_repr_rec(gen_ctx : &GeneralContext,
                         node_gv_options.push( GraphvizNodeStyleItem::Label( SYNTAX_LOOP_P.to_string() ) );
                     }
                 }
-                gv_graph.add_node( GraphVizNode::new(node_name.clone(), node_gv_options) );
+                add_term_node(gv_graph, node_name.clone(), node_gv_options, &current_pos, covered);
             }
             // then the left sub-interaction
             {
                 let left_position = Position::Left(Box::new(current_pos.clone()));
-                let child_node_name = interaction_gv_repr_rec(gen_ctx,i1,left_position, gv_graph);
+                let child_node_name = interaction_gv_repr_rec(gen_ctx,i1,left_position, covered, gv_graph);
                 let gv_edge = GraphVizEdge::new(node_name.clone(),
                                                 None,
                                                 child_node_name,
@@ -118,7 +156,7 @@ fn interaction_gv_repr_rec(gen_ctx : &GeneralContext,
             }
         },
         &Interaction::And(ref i1, ref i2) => {
-            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_AND, current_pos, gv_graph);
+            repr_binary_operator(gen_ctx, i1, i2, SYNTAX_AND, current_pos, covered, gv_graph);
         }
     }
     return node_name;
@@ -129,6 +167,7 @@ fn repr_binary_operator(gen_ctx : &GeneralContext,
                         i2 : &Interaction,
                         operator_label : &str,
                         current_pos : Position,
+                        covered : Option<&HashSet<Position>>,
                         gv_graph : &mut GraphVizDiGraph) {
     let node_name = format!("p{}",current_pos.to_string());
     // the parent node
@@ -136,12 +175,12 @@ fn repr_binary_operator(gen_ctx : &GeneralContext,
         let mut parent_node_gv_options : GraphvizNodeStyle = Vec::new();
         parent_node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::PlainText) );
         parent_node_gv_options.push( GraphvizNodeStyleItem::Label( operator_label.to_string() ) );
-        gv_graph.add_node( GraphVizNode::new(node_name.clone(), parent_node_gv_options) );
+        add_term_node(gv_graph, node_name.clone(), parent_node_gv_options, &current_pos, covered);
     }
     // then the left sub-interaction
     {
         let left_position = Position::Left(Box::new(current_pos.clone()));
-        let child_node_name = interaction_gv_repr_rec(gen_ctx,i1,left_position.clone(), gv_graph);
+        let child_node_name = interaction_gv_repr_rec(gen_ctx,i1,left_position.clone(), covered, gv_graph);
         let gv_edge = GraphVizEdge::new(node_name.clone(),
                                         None,
                                         child_node_name,
@@ -152,7 +191,7 @@ fn repr_binary_operator(gen_ctx : &GeneralContext,
     // then the right sub-interaction
     {
         let right_position = Position::Right(Box::new(current_pos.clone()));
-        let child_node_name = interaction_gv_repr_rec(gen_ctx,i2,right_position.clone(), gv_graph);
+        let child_node_name = interaction_gv_repr_rec(gen_ctx,i2,right_position.clone(), covered, gv_graph);
         let gv_edge = GraphVizEdge::new(node_name,
                                         None,
                                         child_node_name,
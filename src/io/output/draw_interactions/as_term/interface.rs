@@ -15,16 +15,21 @@ limitations under the License.
 */
 
 
+use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{ErrorKind, Write};
 use std::path::PathBuf;
 use std::process::Command;
-use graphviz_dot_builder::traits::DotTranslatable;
+use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
+use graphviz_dot_builder::item::cluster::GraphVizCluster;
+use graphviz_dot_builder::item::node::style::GraphvizNodeStyleItem;
+use graphviz_dot_builder::traits::{DotBuildable, DotTranslatable, GraphVizOutputFormat};
 
 use crate::core::general_context::GeneralContext;
+use crate::core::language::position::position::Position;
 use crate::core::language::syntax::interaction::Interaction;
-use crate::io::output::draw_interactions::as_term::interaction_repr::repr::interaction_gv_repr;
+use crate::io::output::draw_interactions::as_term::interaction_repr::repr::{interaction_coverage_gv_repr, interaction_gv_repr};
 
 
 
@@ -34,6 +39,79 @@ pub fn draw_int_as_term(gen_ctx : &GeneralContext,
                     temp_folder : &String,
                     parent_folder : &String,
                     output_file_name : &String) {
+    let _ = draw_int_as_term_with_format(gen_ctx,
+                                         interaction,
+                                         &GraphVizOutputFormat::png,
+                                         temp_folder,
+                                         parent_folder,
+                                         output_file_name);
+}
+
+/// Writes the term tree in a temporary .dot file and renders it with the graphviz 'dot' executable.
+/// Returns an error message if 'dot' cannot be run or fails.
+pub fn draw_int_as_term_with_format(gen_ctx : &GeneralContext,
+                                    interaction : &Interaction,
+                                    format : &GraphVizOutputFormat,
+                                    temp_folder : &String,
+                                    parent_folder : &String,
+                                    output_file_name : &String) -> Result<(),String> {
+    render_term_graph(&interaction_gv_repr(gen_ctx,interaction),
+                      format,
+                      temp_folder,
+                      parent_folder,
+                      output_file_name)
+}
+
+/// Draws the term tree with the nodes at the covered positions filled in green and the others in grey.
+pub fn draw_int_coverage_as_term(gen_ctx : &GeneralContext,
+                                 interaction : &Interaction,
+                                 covered : &HashSet<Position>,
+                                 format : &GraphVizOutputFormat,
+                                 temp_folder : &String,
+                                 parent_folder : &String,
+                                 output_file_name : &String) -> Result<(),String> {
+    render_term_graph(&interaction_coverage_gv_repr(gen_ctx,interaction,covered),
+                      format,
+                      temp_folder,
+                      parent_folder,
+                      output_file_name)
+}
+
+/// Places the term tree of the interaction and another graph (e.g. the NFA of the interaction)
+/// side by side, each in its own labelled cluster, both clusters being enclosed in a cluster
+/// labelled with 'figure_label'.
+/// Node identifiers are prefixed so that the two graphs cannot clash.
+pub fn interaction_beside_graph_gv_repr(gen_ctx : &GeneralContext,
+                                        interaction : &Interaction,
+                                        interaction_label : &str,
+                                        other : &GraphVizDiGraph,
+                                        other_label : &str,
+                                        figure_label : &str) -> GraphVizDiGraph {
+    let int_cluster = interaction_gv_repr(gen_ctx,interaction).as_cluster(
+        "interaction".to_string(),
+        vec![GraphvizNodeStyleItem::Label(interaction_label.to_string())],
+        Some("i_".to_string()));
+    let other_cluster = other.as_cluster(
+        "other".to_string(),
+        vec![GraphvizNodeStyleItem::Label(other_label.to_string())],
+        Some("o_".to_string()));
+    let mut figure = GraphVizCluster::new("figure".to_string(),
+                                          vec![GraphvizNodeStyleItem::Label(figure_label.to_string())],
+                                          vec![],
+                                          vec![]);
+    figure.add_cluster(int_cluster);
+    figure.add_cluster(other_cluster);
+    // ***
+    let mut digraph = GraphVizDiGraph::new(vec![]);
+    digraph.add_cluster(figure);
+    digraph
+}
+
+fn render_term_graph(digraph : &GraphVizDiGraph,
+                     format : &GraphVizOutputFormat,
+                     temp_folder : &String,
+                     parent_folder : &String,
+                     output_file_name : &String) -> Result<(),String> {
     // ***
     // creates directories if not exist
     fs::create_dir_all(&temp_folder).unwrap();
@@ -41,21 +119,61 @@ pub fn draw_int_as_term(gen_ctx : &GeneralContext,
     let temp_file_name = format!("{:}.dot", output_file_name);
     let temp_path : PathBuf = [temp_folder, &temp_file_name].iter().collect();
     let mut file = File::create(temp_path.as_path()).unwrap();
-    file.write( interaction_gv_repr(gen_ctx,interaction).to_dot_string().as_bytes() );
+    file.write( digraph.to_dot_string().as_bytes() );
     // ***
-    let output_file_name = format!("{:}.png", output_file_name);
+    let output_file_name = format!("{:}.{:}", output_file_name, format);
     let output_path : PathBuf = [parent_folder, &output_file_name].iter().collect();
     // ***
     let status = Command::new("dot")
-        .arg("-Tpng")
+        .arg(format!("-T{:}", format))
         .arg(temp_path.as_path())
         .arg("-o")
         .arg(output_path.as_path())
         .output();
+    match status {
+        Err(e) => {
+            if e.kind() == ErrorKind::NotFound {
+                Err("could not find the graphviz 'dot' executable : install graphviz (https://graphviz.org/download/) and make sure 'dot' is on the PATH".to_string())
+            } else {
+                Err(format!("could not run the graphviz 'dot' executable : {:}", e))
+            }
+        },
+        Ok( output ) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(format!("graphviz 'dot' failed to render '{:?}' : {:}",
+                            output_path.as_path(),
+                            String::from_utf8_lossy(&output.stderr).trim()))
+            }
+        }
+    }
 }
 
 
 
+#[cfg(test)]
+mod tests {
+    use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
+    use graphviz_dot_builder::item::node::node::GraphVizNode;
+    use graphviz_dot_builder::traits::{DotBuildable, DotTranslatable};
 
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
 
+    use super::interaction_beside_graph_gv_repr;
 
+    #[test]
+    fn side_by_side_test() {
+        let gen_ctx = parse_hsf_string("@lifeline{a;b} @message{m}".to_string()).unwrap();
+        let int = parse_hif_string(&gen_ctx,"a -- m -> b".to_string()).unwrap();
+        let mut other = GraphVizDiGraph::new(vec![]);
+        other.add_node(GraphVizNode::new("s0".to_string(),vec![]));
+        let dot = interaction_beside_graph_gv_repr(&gen_ctx,&int,"int",&other,"nfa","fig").to_dot_string();
+        assert!(dot.contains("subgraph cluster_figure"));
+        assert!(dot.contains("subgraph cluster_interaction"));
+        assert!(dot.contains("subgraph cluster_other"));
+        assert!(dot.contains("o_s0"));
+        assert!(dot.find("cluster_interaction").unwrap() < dot.find("cluster_other").unwrap());
+    }
+}
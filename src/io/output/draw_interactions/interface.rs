@@ -16,10 +16,17 @@ limitations under the License.
 
 
 
+use std::collections::HashSet;
+
+use graphviz_dot_builder::traits::GraphVizOutputFormat;
+
 use crate::core::general_context::GeneralContext;
+use crate::core::language::position::position::Position;
 use crate::core::language::syntax::interaction::Interaction;
 use crate::io::output::draw_interactions::as_sd::interface::draw_int_as_sd;
-use crate::io::output::draw_interactions::as_term::interface::draw_int_as_term;
+use crate::io::output::draw_interactions::as_term::interface::{draw_int_as_term, draw_int_as_term_with_format, draw_int_coverage_as_term};
+
+pub use crate::io::output::draw_interactions::as_term::interface::interaction_beside_graph_gv_repr;
 
 
 
@@ -29,18 +36,58 @@ pub enum InteractionGraphicalRepresentation {
     AsTerm
 }
 
+/// If given, 'lifeline_order' is the left-to-right order of the lifelines on sequence diagrams,
+/// lifelines which are not mentioned coming after, in declaration order (by default lifelines
+/// are drawn in declaration order). It has no effect on term trees.
 pub fn draw_interaction(gen_ctx : &GeneralContext,
                         int : &Interaction,
                         repr : &InteractionGraphicalRepresentation,
+                        lifeline_order : &Option<Vec<usize>>,
                         temp_folder : &String,
                         parent_folder : &String,
                         output_file_name : &String) {
     match repr {
         InteractionGraphicalRepresentation::AsSequenceDiagram => {
-            draw_int_as_sd(gen_ctx,int,parent_folder,output_file_name);
+            draw_int_as_sd(gen_ctx,int,lifeline_order,parent_folder,output_file_name);
         },
         InteractionGraphicalRepresentation::AsTerm => {
             draw_int_as_term(gen_ctx,int,temp_folder,parent_folder,output_file_name);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Same as 'draw_interaction' but with a choice of output format and reporting rendering errors.
+/// Sequence diagrams are drawn directly as png images, only term trees (rendered with graphviz) can be output as svg.
+pub fn draw_interaction_with_format(gen_ctx : &GeneralContext,
+                                    int : &Interaction,
+                                    repr : &InteractionGraphicalRepresentation,
+                                    lifeline_order : &Option<Vec<usize>>,
+                                    format : &GraphVizOutputFormat,
+                                    temp_folder : &String,
+                                    parent_folder : &String,
+                                    output_file_name : &String) -> Result<(),String> {
+    match (repr,format) {
+        (InteractionGraphicalRepresentation::AsSequenceDiagram, GraphVizOutputFormat::png) => {
+            draw_int_as_sd(gen_ctx,int,lifeline_order,parent_folder,output_file_name);
+            Ok(())
+        },
+        (InteractionGraphicalRepresentation::AsSequenceDiagram, _) => {
+            Err(format!("sequence diagrams can only be drawn as png, use the term tree representation for {:}", format))
+        },
+        (InteractionGraphicalRepresentation::AsTerm, _) => {
+            draw_int_as_term_with_format(gen_ctx,int,format,temp_folder,parent_folder,output_file_name)
+        }
+    }
+}
+
+/// Draws the coverage of an interaction (see 'get_multi_trace_coverage').
+/// Positions only make sense on a term tree so that the coverage is always drawn as such.
+pub fn draw_interaction_coverage(gen_ctx : &GeneralContext,
+                                 int : &Interaction,
+                                 covered : &HashSet<Position>,
+                                 format : &GraphVizOutputFormat,
+                                 temp_folder : &String,
+                                 parent_folder : &String,
+                                 output_file_name : &String) -> Result<(),String> {
+    draw_int_coverage_as_term(gen_ctx,int,covered,format,temp_folder,parent_folder,output_file_name)
+}
@@ -45,7 +45,7 @@ pub fn diagram_repr_trace_action(action : &TraceAction, gen_ctx : &GeneralContex
     }
     // ***
     {
-        let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+        let ms_name = gen_ctx.get_ms_label(action.ms_id,action.ms_param).unwrap();
         to_print.push( TextToPrint::new(ms_name,Rgb(HC_Message)) );
     }
     // ***
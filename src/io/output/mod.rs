@@ -22,5 +22,6 @@ pub mod draw_traces;
 pub mod draw_transitions;
 
 pub mod to_hfiles;
+pub mod to_text;
 
 
@@ -24,9 +24,13 @@ use crate::core::language::syntax::util::get_recursive_frag::{
     get_recursive_coreg_frags,
     get_recursive_strict_frags};
 use crate::io::output::to_hfiles::interaction::model_action::{emission_as_hif_encoding, reception_as_hif_encoding};
-use crate::io::textual_convention::{SYNTAX_ALT, SYNTAX_COREG, SYNTAX_EMPTY, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT};
+use crate::io::output::to_hfiles::trace::trace_action::trace_action_as_htf_encoding;
+use crate::io::textual_convention::{SYNTAX_ALT, SYNTAX_COREG, SYNTAX_EMPTY, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_NOT, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT};
 
 
+/// The encoding is deterministic : references to lifelines and gates are sorted by declaration index
+/// and the operands of the commutative 'par' and 'alt' operators are sorted (see 'Ord for Interaction')
+/// so that equivalent terms up to the order of these operands are printed identically.
 pub fn interaction_as_hif_encoding(gen_ctx : &GeneralContext,
                            interaction : &Interaction) -> String {
     return interaction_as_hif_encoding_inner(gen_ctx,0, interaction);
@@ -70,6 +74,9 @@ fn interaction_as_hif_encoding_inner(gen_ctx : &GeneralContext,
         &Interaction::Reception(ref rc_act) => {
             return format!("{}{}", "\t".repeat(depth), reception_as_hif_encoding(gen_ctx,rc_act));
         },
+        &Interaction::Not(ref act) => {
+            return format!("{}{}({})", "\t".repeat(depth), SYNTAX_NOT, trace_action_as_htf_encoding(gen_ctx,act));
+        },
         &Interaction::Strict(ref i1, ref i2) => {
             let mut strict_frags = get_recursive_strict_frags(i1);
             strict_frags.extend_from_slice(&mut get_recursive_strict_frags(i2));
@@ -83,18 +90,22 @@ fn interaction_as_hif_encoding_inner(gen_ctx : &GeneralContext,
         &Interaction::CoReg(ref cr, ref i1, ref i2) => {
             let mut cr_frags = get_recursive_coreg_frags(cr,i1);
             cr_frags.extend_from_slice(&mut get_recursive_coreg_frags(cr,i2));
-            let cr_lfs : Vec<String> = cr.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+            let mut cr_lf_ids = cr.clone();
+            cr_lf_ids.sort();
+            let cr_lfs : Vec<String> = cr_lf_ids.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
             let operator_name = format!("{:}({:})", SYNTAX_COREG, cr_lfs.join(","));
             return op_as_hif_encoding(gen_ctx,depth,&operator_name,cr_frags);
         },
         &Interaction::Par(ref i1, ref i2) => {
             let mut par_frags = get_recursive_par_frags(i1);
             par_frags.extend_from_slice(&mut get_recursive_par_frags(i2));
+            par_frags.sort();
             return op_as_hif_encoding(gen_ctx,depth,SYNTAX_PAR,par_frags);
         },
         &Interaction::Alt(ref i1, ref i2) => {
             let mut alt_frags = get_recursive_alt_frags(i1);
             alt_frags.extend_from_slice(&mut get_recursive_alt_frags(i2));
+            alt_frags.sort();
             return op_as_hif_encoding(gen_ctx,depth,SYNTAX_ALT,alt_frags);
         },
         &Interaction::Loop(ref lk, ref i1) => {
@@ -106,4 +117,37 @@ fn interaction_as_hif_encoding_inner(gen_ctx : &GeneralContext,
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn hif_round_trip_test() {
+        let gen_ctx = parse_hsf_string("@message{m1;m2}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let hif = "alt(par(c -- m2 -> (c,a), loopW(a -- m1 ->|)), seq(b -- m1 -> a, coreg(b,a)(a -- m1 ->|, m2 -> (b,a))))";
+        let parsed = parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let printed = interaction_as_hif_encoding(&gen_ctx,&parsed);
+        assert!(printed.contains("c -- m2 -> (a,c)"));
+        assert!(printed.contains("coreg(a,b)("));
+        assert!(printed.contains("m2 -> (a,b)"));
+        // printing is stable through parsing
+        let reparsed = parse_hif_string(&gen_ctx,printed.clone()).unwrap();
+        let reprinted = interaction_as_hif_encoding(&gen_ctx,&reparsed);
+        assert_eq!(printed, reprinted);
+        assert_eq!(parse_hif_string(&gen_ctx,reprinted).unwrap(), reparsed);
+        // the order of the operands of alt and par does not matter
+        let swapped = "alt(seq(b -- m1 -> a, coreg(a,b)(a -- m1 ->|, m2 -> (a,b))), par(loopW(a -- m1 ->|), c -- m2 -> (a,c)))";
+        let swapped = parse_hif_string(&gen_ctx,swapped.to_string()).unwrap();
+        assert_eq!(interaction_as_hif_encoding(&gen_ctx,&swapped), printed);
+        // forbidden actions
+        let forbidding = parse_hif_string(&gen_ctx,"seq(not(b?m1), m1 -> b)".to_string()).unwrap();
+        let printed = interaction_as_hif_encoding(&gen_ctx,&forbidding);
+        assert!(printed.contains("not(b?m1)"));
+        assert_eq!(parse_hif_string(&gen_ctx,printed).unwrap(), forbidding);
+    }
+
+}
@@ -14,8 +14,9 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-mod model_action;
+pub(crate) mod model_action;
 mod interaction;
 pub mod to_hif;
+pub mod to_sexpr;
 
 
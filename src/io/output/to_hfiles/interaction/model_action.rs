@@ -22,8 +22,10 @@ use crate::core::language::syntax::action::{CommunicationSynchronicity, Emission
 
 pub fn emission_as_hif_encoding(gen_ctx : &GeneralContext,
                                 em_act : &EmissionAction) -> String {
+    let mut targets = em_act.targets.clone();
+    targets.sort();
     let mut targ_names : Vec<String> = Vec::new();
-    for targ_ref in &em_act.targets {
+    for targ_ref in &targets {
         match targ_ref {
             EmissionTargetRef::Lifeline(tar_lf_id) => {
                 targ_names.push( gen_ctx.get_lf_name(*tar_lf_id).unwrap() );
@@ -45,7 +47,10 @@ pub fn emission_as_hif_encoding(gen_ctx : &GeneralContext,
     }
     // ***
     let lf_name = gen_ctx.get_lf_name(em_act.origin_lf_id).unwrap();
-    let ms_name = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
+    if let Some(group_id) = em_act.target_group {
+        return format!("{} -- {}{} -> {}", &lf_name, synch_key, &ms_name, gen_ctx.get_group_name(group_id).unwrap());
+    }
     let rcp_num = targ_names.len();
     if rcp_num == 0 {
         return format!("{} -- {}{} ->|", &lf_name, synch_key, &ms_name);
@@ -59,8 +64,10 @@ pub fn emission_as_hif_encoding(gen_ctx : &GeneralContext,
 
 pub fn reception_as_hif_encoding(gen_ctx : &GeneralContext,
                                  rc_act : &ReceptionAction) -> String {
+    let mut recipients = rc_act.recipients.clone();
+    recipients.sort();
     let mut targ_names : Vec<String> = Vec::new();
-    for rcp_lf_id in &rc_act.recipients {
+    for rcp_lf_id in &recipients {
         targ_names.push( gen_ctx.get_lf_name(*rcp_lf_id).unwrap() );
     }
     // ***
@@ -84,7 +91,7 @@ pub fn reception_as_hif_encoding(gen_ctx : &GeneralContext,
         }
     }
     // ***
-    let ms_name = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
     let rcp_num = targ_names.len();
     if rcp_num == 1 {
         return format!("{}{}{} -> {}", gate_str, synch_key, &ms_name, targ_names.get(0).unwrap());
@@ -17,22 +17,88 @@ limitations under the License.
 
 
 use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::path::Path;
 
+use crate::core::language::hash::canonical::StableHasher;
 use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::metrics::InteractionMetrics;
 
 use crate::core::general_context::GeneralContext;
 
 
 
 use crate::io::output::to_hfiles::interaction::interaction::interaction_as_hif_encoding;
+use crate::io::file_extensions::{HIF_FORMAT_VERSION, HIF_PROVENANCE_TAG, HIF_VERSION_TAG};
 
 
 
+/// Where a generated interaction comes from, written as a comment after the version tag
+/// e.g. '/* @generated seed 0 id 3 hash 5f0c... symbols 12 */'.
+/// The hash is that of the .hif encoding of the interaction, so that changes made after its generation can be detected
+/// (its 'canonical_hash' would not do as reading the file back may nest associative operators differently).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HifProvenance {
+    pub seed : u64,
+    /// index of the interaction within its generation
+    pub id : usize,
+    pub hash : u64,
+    pub num_symbols : u32
+}
+
+impl HifProvenance {
+    pub fn new(gen_ctx : &GeneralContext, seed : u64, id : usize, interaction : &Interaction) -> Self {
+        let num_symbols = InteractionMetrics::extract_from_interaction(interaction).get_num_symbols();
+        Self { seed, id, hash : Self::get_hash(gen_ctx,interaction), num_symbols }
+    }
+
+    fn get_hash(gen_ctx : &GeneralContext, interaction : &Interaction) -> u64 {
+        let mut hasher = StableHasher::default();
+        interaction_as_hif_encoding(gen_ctx,interaction).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn as_comment(&self) -> String {
+        format!("/* {:} seed {:} id {:} hash {:016x} symbols {:} */",
+                HIF_PROVENANCE_TAG, self.seed, self.id, self.hash, self.num_symbols)
+    }
+
+    /// Reads back the provenance from the content of a comment written by 'as_comment'.
+    pub fn from_comment_content(content : &str) -> Option<Self> {
+        let fields : Vec<&str> = content.trim().strip_prefix(HIF_PROVENANCE_TAG)?.split_whitespace().collect();
+        match fields.as_slice() {
+            ["seed", seed, "id", id, "hash", hash, "symbols", num_symbols] => {
+                Some( Self { seed : seed.parse().ok()?,
+                             id : id.parse().ok()?,
+                             hash : u64::from_str_radix(hash, 16).ok()?,
+                             num_symbols : num_symbols.parse().ok()? } )
+            },
+            _ => None
+        }
+    }
+
+    /// Whether the interaction is still the one that was generated.
+    pub fn matches(&self, gen_ctx : &GeneralContext, interaction : &Interaction) -> bool {
+        Self::get_hash(gen_ctx,interaction) == self.hash
+    }
+}
+
 pub fn interaction_to_hif(file_path : &Path,
                           gen_ctx : &GeneralContext,
                           interaction : &Interaction) {
+    interaction_to_hif_with_provenance(file_path,gen_ctx,interaction,None);
+}
+
+pub fn interaction_to_hif_with_provenance(file_path : &Path,
+                                          gen_ctx : &GeneralContext,
+                                          interaction : &Interaction,
+                                          provenance : Option<&HifProvenance>) {
     let mut file = File::create(file_path).unwrap();
+    // the version tag is written as a comment so that parsers which ignore it still read the file
+    file.write(format!("/* {:} {:} */\n", HIF_VERSION_TAG, HIF_FORMAT_VERSION).as_bytes() );
+    if let Some(provenance) = provenance {
+        file.write(format!("{:}\n", provenance.as_comment()).as_bytes() );
+    }
     file.write(interaction_as_hif_encoding(gen_ctx,&interaction).as_bytes() );
 }
\ No newline at end of file
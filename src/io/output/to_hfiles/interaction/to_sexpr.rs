@@ -0,0 +1,176 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+use crate::io::textual_convention::{SEXPR_ACTION, SEXPR_EMISSION, SEXPR_EMPTY, SEXPR_GATE_ORIGIN, SEXPR_GROUP, SEXPR_PARAM, SEXPR_RECEPTION, SEXPR_SYNCHRONOUS, SEXPR_TARGETS, SYNTAX_ALT, SYNTAX_AND, SYNTAX_COREG, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_NOT, SYNTAX_PAR, SYNTAX_SEQ, SYNTAX_STRICT, SYNTAX_SYNC};
+
+
+/// Contrary to the HIF encoding, the S-expression encoding is a faithful image of the term :
+/// operators stay binary and nothing is reordered, so that parsing it back (see 'parse_sexpr')
+/// yields the exact same interaction.
+pub fn interaction_to_sexpr(gen_ctx : &GeneralContext,
+                            interaction : &Interaction) -> String {
+    match interaction {
+        &Interaction::Empty => {
+            return SEXPR_EMPTY.to_string();
+        },
+        &Interaction::Emission(ref em_act) => {
+            return emission_as_sexpr(gen_ctx,em_act);
+        },
+        &Interaction::Reception(ref rc_act) => {
+            return reception_as_sexpr(gen_ctx,rc_act);
+        },
+        &Interaction::Not(ref act) => {
+            return format!("({} {})", SYNTAX_NOT, trace_action_as_sexpr(gen_ctx,act));
+        },
+        &Interaction::Strict(ref i1, ref i2) => {
+            return binary_op_as_sexpr(gen_ctx,SYNTAX_STRICT,i1,i2);
+        },
+        &Interaction::Seq(ref i1, ref i2) => {
+            return binary_op_as_sexpr(gen_ctx,SYNTAX_SEQ,i1,i2);
+        },
+        &Interaction::Par(ref i1, ref i2) => {
+            return binary_op_as_sexpr(gen_ctx,SYNTAX_PAR,i1,i2);
+        },
+        &Interaction::Alt(ref i1, ref i2) => {
+            return binary_op_as_sexpr(gen_ctx,SYNTAX_ALT,i1,i2);
+        },
+        &Interaction::And(ref i1, ref i2) => {
+            return binary_op_as_sexpr(gen_ctx,SYNTAX_AND,i1,i2);
+        },
+        &Interaction::CoReg(ref cr, ref i1, ref i2) => {
+            let cr_lfs : Vec<String> = cr.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+            let operator_name = format!("{} ({})", SYNTAX_COREG, cr_lfs.join(" "));
+            return binary_op_as_sexpr(gen_ctx,&operator_name,i1,i2);
+        },
+        &Interaction::Sync(ref sync_acts, ref i1, ref i2) => {
+            let acts_strs : Vec<String> = sync_acts.iter().map(|tract| trace_action_as_sexpr(gen_ctx,tract)).collect();
+            let operator_name = format!("{} ({})", SYNTAX_SYNC, acts_strs.join(" "));
+            return binary_op_as_sexpr(gen_ctx,&operator_name,i1,i2);
+        },
+        &Interaction::Loop(ref lk, ref i1) => {
+            let lk_text : &str;
+            match lk {
+                LoopKind::SStrictSeq => {
+                    lk_text = SYNTAX_LOOP_S;
+                },
+                LoopKind::HHeadFirstWS => {
+                    lk_text = SYNTAX_LOOP_H;
+                },
+                LoopKind::WWeakSeq => {
+                    lk_text = SYNTAX_LOOP_W;
+                },
+                LoopKind::PInterleaving => {
+                    lk_text = SYNTAX_LOOP_P;
+                }
+            }
+            return format!("({} {})", lk_text, interaction_to_sexpr(gen_ctx,i1));
+        }
+    }
+}
+
+fn binary_op_as_sexpr(gen_ctx : &GeneralContext,
+                      op_text : &str,
+                      i1 : &Interaction,
+                      i2 : &Interaction) -> String {
+    return format!("({} {} {})", op_text, interaction_to_sexpr(gen_ctx,i1), interaction_to_sexpr(gen_ctx,i2));
+}
+
+fn content_modifiers_as_sexpr(gen_ctx : &GeneralContext,
+                              ms_id : usize,
+                              ms_param : Option<usize>,
+                              synchronicity : &CommunicationSynchronicity) -> String {
+    let mut modifiers = String::new();
+    if let Some(param_id) = ms_param {
+        modifiers.push_str(&format!(" ({} {})", SEXPR_PARAM, gen_ctx.get_ms_param_name(ms_id,param_id).unwrap()));
+    }
+    if let CommunicationSynchronicity::Synchronous = synchronicity {
+        modifiers.push_str(&format!(" {}", SEXPR_SYNCHRONOUS));
+    }
+    return modifiers;
+}
+
+fn emission_as_sexpr(gen_ctx : &GeneralContext,
+                     em_act : &EmissionAction) -> String {
+    let lf_name = gen_ctx.get_lf_name(em_act.origin_lf_id).unwrap();
+    let ms_name = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
+    let modifiers = content_modifiers_as_sexpr(gen_ctx,em_act.ms_id,em_act.ms_param,&em_act.synchronicity);
+    let targets_str : String;
+    if let Some(group_id) = em_act.target_group {
+        targets_str = format!(" ({} {})", SEXPR_GROUP, gen_ctx.get_group_name(group_id).unwrap());
+    } else if em_act.targets.is_empty() {
+        targets_str = "".to_string();
+    } else {
+        let targ_names : Vec<String> = em_act.targets.iter().map(|targ_ref|
+            match targ_ref {
+                EmissionTargetRef::Lifeline(tar_lf_id) => {
+                    gen_ctx.get_lf_name(*tar_lf_id).unwrap()
+                },
+                EmissionTargetRef::Gate(tar_gt_id) => {
+                    gen_ctx.get_gt_name(*tar_gt_id).unwrap()
+                }
+            }).collect();
+        targets_str = format!(" ({} {})", SEXPR_TARGETS, targ_names.join(" "));
+    }
+    return format!("({} {} {} {}{}{})", SEXPR_ACTION, lf_name, SEXPR_EMISSION, ms_name, modifiers, targets_str);
+}
+
+fn reception_as_sexpr(gen_ctx : &GeneralContext,
+                      rc_act : &ReceptionAction) -> String {
+    let rcp_names : Vec<String> = rc_act.recipients.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+    let recipients_str : String;
+    if rcp_names.len() == 1 {
+        recipients_str = rcp_names.get(0).unwrap().clone();
+    } else {
+        recipients_str = format!("({})", rcp_names.join(" "));
+    }
+    let ms_name = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
+    let modifiers = content_modifiers_as_sexpr(gen_ctx,rc_act.ms_id,rc_act.ms_param,&rc_act.synchronicity);
+    let origin_str : String;
+    match rc_act.origin_gt_id {
+        None => {
+            origin_str = "".to_string();
+        },
+        Some(orig_gt_id) => {
+            origin_str = format!(" ({} {})", SEXPR_GATE_ORIGIN, gen_ctx.get_gt_name(orig_gt_id).unwrap());
+        }
+    }
+    return format!("({} {} {} {}{}{})", SEXPR_ACTION, recipients_str, SEXPR_RECEPTION, ms_name, modifiers, origin_str);
+}
+
+fn trace_action_as_sexpr(gen_ctx : &GeneralContext,
+                         tract : &TraceAction) -> String {
+    let kind_text : &str;
+    match tract.act_kind {
+        TraceActionKind::Emission => {
+            kind_text = SEXPR_EMISSION;
+        },
+        TraceActionKind::Reception => {
+            kind_text = SEXPR_RECEPTION;
+        }
+    }
+    let modifiers = content_modifiers_as_sexpr(gen_ctx,tract.ms_id,tract.ms_param,&CommunicationSynchronicity::Asynchronous);
+    return format!("({} {} {}{})",
+                   gen_ctx.get_lf_name(tract.lf_id).unwrap(),
+                   kind_text,
+                   gen_ctx.get_ms_name(tract.ms_id).unwrap(),
+                   modifiers);
+}
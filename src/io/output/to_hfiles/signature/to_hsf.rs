@@ -0,0 +1,139 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::core::general_context::GeneralContext;
+
+
+/// Writes the signature as a .hsf string with one line per non-empty section
+/// e.g. '@message{m(x,y);n}' then '@lifeline{a;b}'.
+pub fn signature_as_hsf_encoding(gen_ctx : &GeneralContext) -> String {
+    let mut sections : Vec<String> = vec![];
+    let messages : Vec<String> = (0..gen_ctx.get_ms_num()).map(|ms_id| {
+        let ms_name = gen_ctx.get_ms_name(ms_id).unwrap();
+        match gen_ctx.get_ms_params_num(ms_id) {
+            0 => ms_name,
+            params_num => {
+                let params : Vec<String> = (0..params_num).map(|param_id| gen_ctx.get_ms_param_name(ms_id,param_id).unwrap()).collect();
+                format!("{}({})", ms_name, params.join(","))
+            }
+        }
+    }).collect();
+    let lifelines : Vec<String> = (0..gen_ctx.get_lf_num()).map(|lf_id| gen_ctx.get_lf_name(lf_id).unwrap()).collect();
+    let gates : Vec<String> = (0..gen_ctx.get_gt_num()).map(|gt_id| gen_ctx.get_gt_name(gt_id).unwrap()).collect();
+    let groups : Vec<String> = (0..gen_ctx.get_group_num()).map(|group_id| {
+        let lf_names : Vec<String> = gen_ctx.get_group_lifelines(group_id).unwrap().iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+        format!("{} = {{{}}}", gen_ctx.get_group_name(group_id).unwrap(), lf_names.join(","))
+    }).collect();
+    let mut directions : Vec<String> = vec![];
+    for ms_id in 0..gen_ctx.get_ms_num() {
+        for (orig_lf_id,tar_lf_id) in gen_ctx.get_ms_directions(ms_id) {
+            directions.push(format!("{} -- {} -> {}",
+                                    gen_ctx.get_lf_name(*orig_lf_id).unwrap(),
+                                    gen_ctx.get_ms_name(ms_id).unwrap(),
+                                    gen_ctx.get_lf_name(*tar_lf_id).unwrap()));
+        }
+    }
+    for (keyword,decls) in [("message",messages),("lifeline",lifelines),("gate",gates),("group",groups),("direction",directions)] {
+        if !decls.is_empty() {
+            sections.push(format!("@{}{{{}}}", keyword, decls.join(";")));
+        }
+    }
+    return sections.join("\n") + "\n";
+}
+
+/// Writes the signature into a temporary file which is then renamed so that,
+/// when rewriting a signature in place, the original file is never left truncated.
+pub fn signature_to_hsf(file_path : &Path, gen_ctx : &GeneralContext) -> std::io::Result<()> {
+    let mut tmp_path = file_path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(signature_as_hsf_encoding(gen_ctx).as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, file_path)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::signature::{get_normalized_signature, get_signature_declarations_num, get_used_signature};
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+    use super::*;
+
+    #[test]
+    fn used_signature_test() {
+        let hsf = "@message{m(x,y);n;p}\n@lifeline{a;b;c;d}\n@gate{g;h}\n@group{all = {a,b,c};bd = {b,d}}\n@direction{a -- m -> b;c -- m -> a;a -- n -> d}\n";
+        let gen_ctx = parse_hsf_string(hsf.to_string()).unwrap();
+        assert_eq!(parse_hsf_string(signature_as_hsf_encoding(&gen_ctx)).unwrap(), gen_ctx);
+        assert_eq!(get_signature_declarations_num(&gen_ctx), 14);
+        // ***
+        let hif = "seq(a -- m(y) -> b, a -- p -> bd, coreg(c)(p -> a, a -- p -> g))";
+        let int = parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let used_ctx = get_used_signature(&gen_ctx,&int);
+        assert_eq!(signature_as_hsf_encoding(&used_ctx),
+                   "@message{m(x,y);p}\n@lifeline{a;b;c;d}\n@gate{g}\n@group{bd = {b,d}}\n@direction{a -- m -> b;c -- m -> a}\n");
+        assert_eq!(get_signature_declarations_num(&used_ctx), 10);
+        // the interaction is still well-defined over the trimmed signature
+        assert!(parse_hif_string(&used_ctx,hif.to_string()).is_ok());
+    }
+
+    #[test]
+    fn normalized_signature_test() {
+        let hsf = "@message{p;m(y,x);n}\n@lifeline{d;b;a;c}\n@gate{h;g}\n@group{bd = {d,b};all = {c,a,b}}\n@direction{c -- m -> a;a -- n -> d;a -- m -> b}\n";
+        let gen_ctx = parse_hsf_string(hsf.to_string()).unwrap();
+        let normalized = get_normalized_signature(&gen_ctx);
+        assert_eq!(signature_as_hsf_encoding(&normalized),
+                   "@message{m(y,x);n;p}\n@lifeline{a;b;c;d}\n@gate{g;h}\n@group{all = {a,b,c};bd = {b,d}}\n@direction{a -- m -> b;c -- m -> a;a -- n -> d}\n");
+        assert_eq!(get_normalized_signature(&normalized), normalized);
+        // interactions are written with names hence their encoding does not depend on the order of declarations
+        let hif = "seq(a -- m(x) -> b, d -- p -> bd, coreg(a)(n -> a, c -- p -> g))";
+        let int = parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let path = std::env::temp_dir().join(format!("hibou_normalized_signature_test_{:}.hif", std::process::id()));
+        interaction_to_hif(&path,&gen_ctx,&int);
+        let encoded = std::fs::read_to_string(&path).unwrap();
+        let reparsed = parse_hif_string(&normalized,encoded.clone()).unwrap();
+        interaction_to_hif(&path,&normalized,&reparsed);
+        let reencoded = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reencoded, encoded);
+    }
+
+    #[test]
+    fn signature_to_hsf_test() {
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b}\n".to_string()).unwrap();
+        let dir = std::env::temp_dir().join(format!("hibou_signature_to_hsf_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sig.hsf");
+        std::fs::write(&path, "@lifeline{z}\n").unwrap();
+        signature_to_hsf(&path, &gen_ctx).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), signature_as_hsf_encoding(&gen_ctx));
+        // the temporary file is renamed over the original one
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        // a failure is reported instead of panicking
+        assert!(signature_to_hsf(&dir.join("missing").join("sig.hsf"), &gen_ctx).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -36,9 +36,9 @@ pub fn trace_actions_as_htf_encoding(gen_ctx : &GeneralContext, actions : &BTree
 }
 
 
-fn trace_action_as_htf_encoding(gen_ctx : &GeneralContext, action : &TraceAction) -> String {
+pub fn trace_action_as_htf_encoding(gen_ctx : &GeneralContext, action : &TraceAction) -> String {
     let lf_name = gen_ctx.get_lf_name(action.lf_id).unwrap();
-    let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(action.ms_id,action.ms_param).unwrap();
     // ***
     match &action.act_kind {
         &TraceActionKind::Reception => {
@@ -0,0 +1,254 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef, ReceptionAction};
+use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+use crate::core::language::syntax::util::get_recursive_frag::{
+    get_recursive_alt_frags,
+    get_recursive_par_frags,
+    get_recursive_seq_frags,
+    get_recursive_coreg_frags,
+    get_recursive_strict_frags};
+use crate::io::output::to_hfiles::trace::trace_action::trace_actions_as_htf_encoding;
+use crate::io::textual_convention::{SYNTAX_ALT, SYNTAX_AND, SYNTAX_COREG, SYNTAX_EMISSION, SYNTAX_EMISSION_SYNCHRONOUS, SYNTAX_EMPTY, SYNTAX_LOOP_H, SYNTAX_LOOP_P, SYNTAX_LOOP_S, SYNTAX_LOOP_W, SYNTAX_NOT, SYNTAX_PAR, SYNTAX_RECEPTION, SYNTAX_RECEPTION_SYNCHRONOUS, SYNTAX_SEQ, SYNTAX_SKELETON_LEAF, SYNTAX_STRICT, SYNTAX_SYNC};
+
+
+/// Human readable rendering of an interaction as a tree with one operator or action per line,
+/// the operands of an operator being indented below it.
+/// Contrary to the .hif encoding, operands are kept in their order of appearance in the term.
+pub fn interaction_as_indented_text(gen_ctx : &GeneralContext,
+                                    interaction : &Interaction) -> String {
+    let mut lines = vec![];
+    interaction_as_indented_text_inner(Some(gen_ctx),0,interaction,&mut lines);
+    return lines.join("\n");
+}
+
+/// Same rendering as 'interaction_as_indented_text' with operands given between parentheses on a single line.
+pub fn interaction_as_oneline_text(gen_ctx : &GeneralContext,
+                                   interaction : &Interaction) -> String {
+    return interaction_as_oneline_text_inner(Some(gen_ctx),interaction);
+}
+
+/// Same rendering as 'interaction_as_indented_text' for the skeleton of the interaction (see 'Interaction::get_skeleton'),
+/// actions being printed as a placeholder and the parameters of the 'coreg' and 'sync' operators omitted.
+pub fn skeleton_as_indented_text(interaction : &Interaction) -> String {
+    let mut lines = vec![];
+    interaction_as_indented_text_inner(None,0,&interaction.get_skeleton(),&mut lines);
+    return lines.join("\n");
+}
+
+/// Same rendering as 'skeleton_as_indented_text' on a single line.
+/// Interactions with the same shape (up to the nesting of the associative operators) have the same rendering.
+pub fn skeleton_as_oneline_text(interaction : &Interaction) -> String {
+    return interaction_as_oneline_text_inner(None,&interaction.get_skeleton());
+}
+
+fn interaction_as_oneline_text_inner(gen_ctx : Option<&GeneralContext>,
+                                     interaction : &Interaction) -> String {
+    let (label,sub_ints) = get_node(gen_ctx,interaction);
+    if sub_ints.is_empty() {
+        return label;
+    }
+    let sub_strs : Vec<String> = sub_ints.iter().map(|i| interaction_as_oneline_text_inner(gen_ctx,i)).collect();
+    return format!("{}({})", label, sub_strs.join(", "));
+}
+
+fn interaction_as_indented_text_inner(gen_ctx : Option<&GeneralContext>,
+                                      depth : usize,
+                                      interaction : &Interaction,
+                                      lines : &mut Vec<String>) {
+    let (label,sub_ints) = get_node(gen_ctx,interaction);
+    lines.push(format!("{}{}", "  ".repeat(depth), label));
+    for sub_int in sub_ints {
+        interaction_as_indented_text_inner(gen_ctx,depth+1,sub_int,lines);
+    }
+}
+
+fn names_as_text(names : Vec<String>) -> String {
+    if names.len() == 1 {
+        return names[0].clone();
+    } else {
+        return format!("({})", names.join(","));
+    }
+}
+
+fn emission_as_text(gen_ctx : &GeneralContext, em_act : &EmissionAction) -> String {
+    let lf_name = gen_ctx.get_lf_name(em_act.origin_lf_id).unwrap();
+    let ms_name = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
+    if em_act.targets.is_empty() {
+        return match em_act.synchronicity {
+            CommunicationSynchronicity::Asynchronous => format!("{}{}{}", lf_name, SYNTAX_EMISSION, ms_name),
+            CommunicationSynchronicity::Synchronous => format!("{}{}{}", lf_name, SYNTAX_EMISSION_SYNCHRONOUS, ms_name)
+        };
+    }
+    let targets = match em_act.target_group {
+        Some(group_id) => {
+            gen_ctx.get_group_name(group_id).unwrap()
+        },
+        None => {
+            let targ_names = em_act.targets.iter().map(|targ_ref| match targ_ref {
+                EmissionTargetRef::Lifeline(tar_lf_id) => gen_ctx.get_lf_name(*tar_lf_id).unwrap(),
+                EmissionTargetRef::Gate(tar_gt_id) => gen_ctx.get_gt_name(*tar_gt_id).unwrap()
+            }).collect();
+            names_as_text(targ_names)
+        }
+    };
+    let synch_key = match em_act.synchronicity {
+        CommunicationSynchronicity::Asynchronous => "",
+        CommunicationSynchronicity::Synchronous => "<synch>"
+    };
+    return format!("{}--{}{}-->{}", lf_name, synch_key, ms_name, targets);
+}
+
+fn reception_as_text(gen_ctx : &GeneralContext, rc_act : &ReceptionAction) -> String {
+    let ms_name = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
+    let rcp_names = names_as_text(rc_act.recipients.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect());
+    let synch_key = match rc_act.synchronicity {
+        CommunicationSynchronicity::Asynchronous => "",
+        CommunicationSynchronicity::Synchronous => "<synch>"
+    };
+    match rc_act.origin_gt_id {
+        Some(orig_gt_id) => {
+            return format!("{}--{}{}-->{}", gen_ctx.get_gt_name(orig_gt_id).unwrap(), synch_key, ms_name, rcp_names);
+        },
+        None => {
+            return match rc_act.synchronicity {
+                CommunicationSynchronicity::Asynchronous => format!("{}{}{}", rcp_names, SYNTAX_RECEPTION, ms_name),
+                CommunicationSynchronicity::Synchronous => format!("{}{}{}", rcp_names, SYNTAX_RECEPTION_SYNCHRONOUS, ms_name)
+            };
+        }
+    }
+}
+
+/// Label of the root of the term together with its operands (flattened for the associative operators).
+/// Without a context, the term is rendered as a skeleton.
+fn get_node<'lifetime>(gen_ctx : Option<&GeneralContext>,
+                       interaction : &'lifetime Interaction) -> (String,Vec<&'lifetime Interaction>) {
+    match (interaction,gen_ctx) {
+        (Interaction::Empty,_) => {
+            return (SYNTAX_EMPTY.to_string(),vec![]);
+        },
+        (Interaction::Emission(_),None) | (Interaction::Reception(_),None) => {
+            return (SYNTAX_SKELETON_LEAF.to_string(),vec![]);
+        },
+        (Interaction::Emission(em_act),Some(gen_ctx)) => {
+            return (emission_as_text(gen_ctx,em_act),vec![]);
+        },
+        (Interaction::Reception(rc_act),Some(gen_ctx)) => {
+            return (reception_as_text(gen_ctx,rc_act),vec![]);
+        },
+        (Interaction::Not(_),None) => {
+            return (format!("{}({})", SYNTAX_NOT, SYNTAX_SKELETON_LEAF),vec![]);
+        },
+        (Interaction::Not(act),Some(gen_ctx)) => {
+            return (format!("{}({})", SYNTAX_NOT, trace_actions_as_htf_encoding(gen_ctx,&btreeset!{act.clone()})),vec![]);
+        },
+        (Interaction::Strict(i1,i2),_) => {
+            let mut frags = get_recursive_strict_frags(i1);
+            frags.extend(get_recursive_strict_frags(i2));
+            return (SYNTAX_STRICT.to_string(),frags);
+        },
+        (Interaction::Seq(i1,i2),_) => {
+            let mut frags = get_recursive_seq_frags(i1);
+            frags.extend(get_recursive_seq_frags(i2));
+            return (SYNTAX_SEQ.to_string(),frags);
+        },
+        (Interaction::CoReg(cr,i1,i2),_) => {
+            let mut frags = get_recursive_coreg_frags(cr,i1);
+            frags.extend(get_recursive_coreg_frags(cr,i2));
+            return match gen_ctx {
+                None => (SYNTAX_COREG.to_string(),frags),
+                Some(gen_ctx) => {
+                    let cr_lfs : Vec<String> = cr.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+                    (format!("{}({})", SYNTAX_COREG, cr_lfs.join(",")),frags)
+                }
+            };
+        },
+        (Interaction::Par(i1,i2),_) => {
+            let mut frags = get_recursive_par_frags(i1);
+            frags.extend(get_recursive_par_frags(i2));
+            return (SYNTAX_PAR.to_string(),frags);
+        },
+        (Interaction::Alt(i1,i2),_) => {
+            let mut frags = get_recursive_alt_frags(i1);
+            frags.extend(get_recursive_alt_frags(i2));
+            return (SYNTAX_ALT.to_string(),frags);
+        },
+        (Interaction::Loop(lk,i1),_) => {
+            let loop_text = match lk {
+                LoopKind::SStrictSeq => SYNTAX_LOOP_S,
+                LoopKind::HHeadFirstWS => SYNTAX_LOOP_H,
+                LoopKind::WWeakSeq => SYNTAX_LOOP_W,
+                LoopKind::PInterleaving => SYNTAX_LOOP_P
+            };
+            return (loop_text.to_string(),vec![i1]);
+        },
+        (Interaction::And(i1,i2),_) => {
+            return (SYNTAX_AND.to_string(),vec![i1,i2]);
+        },
+        (Interaction::Sync(sync_acts,i1,i2),_) => {
+            return match gen_ctx {
+                None => (SYNTAX_SYNC.to_string(),vec![i1,i2]),
+                Some(gen_ctx) => {
+                    let acts_strs : Vec<String> = sync_acts.iter()
+                        .map(|act| trace_actions_as_htf_encoding(gen_ctx,&btreeset!{act.clone()}))
+                        .collect();
+                    (format!("{}{{{}}}", SYNTAX_SYNC, acts_strs.join(",")),vec![i1,i2])
+                }
+            };
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn show_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n(x)}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let hif = "seq(alt(b -- m -> (a,c), a -- n(x) ->|, m -> b), loopS(coreg(a,b)(o, c -- m -> a)), b -- m -> a)";
+        let int = parse_hif_string(&gen_ctx,hif.to_string()).unwrap();
+        let expected = vec![
+            "seq",
+            "  alt",
+            "    b--m-->(a,c)",
+            "    a!n(x)",
+            "    b?m",
+            "  loopS",
+            "    coreg(a,b)",
+            "      ∅",
+            "      c--m-->a",
+            "  b--m-->a"
+        ];
+        assert_eq!(interaction_as_indented_text(&gen_ctx,&int), expected.join("\n"));
+        assert_eq!(interaction_as_oneline_text(&gen_ctx,&int),
+                   "seq(alt(b--m-->(a,c), a!n(x), b?m), loopS(coreg(a,b)(∅, c--m-->a)), b--m-->a)");
+        assert_eq!(skeleton_as_indented_text(&int).lines().take(3).collect::<Vec<&str>>(), vec!["seq","  alt","    •"]);
+        assert_eq!(skeleton_as_oneline_text(&int), "seq(alt(•, •, •), loopS(coreg(∅, •)), •)");
+        // the skeleton does not depend on the choice of messages and lifelines
+        let other = parse_hif_string(&gen_ctx,"seq(seq(alt(a -- n(x) ->|, m -> c, c -- m ->|), loopS(coreg(c)(o, a -- m -> b))), n(x) -> a)".to_string()).unwrap();
+        assert_eq!(skeleton_as_oneline_text(&other), skeleton_as_oneline_text(&int));
+    }
+
+}
@@ -24,6 +24,7 @@ pub static SYNTAX_SYNC: &'static str = "sync";
 pub static SYNTAX_COREG: &'static str = "coreg";
 
 pub static SYNTAX_AND: &'static str = "and";
+pub static SYNTAX_NOT: &'static str = "not";
 
 pub static SYNTAX_LOOP_S: &'static str = "loopS";
 pub static SYNTAX_LOOP_H: &'static str = "loopH";
@@ -34,6 +35,7 @@ pub static SYNTAX_EMISSION: &'static str = "!";
 pub static SYNTAX_EMISSION_SYNCHRONOUS: &'static str = "|!|";
 pub static SYNTAX_RECEPTION: &'static str = "?";
 pub static SYNTAX_RECEPTION_SYNCHRONOUS: &'static str = "|?|";
+pub static SYNTAX_SKELETON_LEAF: &'static str = "•";
 
 pub static SYNTAX_POSITION_LEFT: &'static str = "1";
 pub static SYNTAX_POSITION_RIGHT: &'static str = "2";
@@ -42,3 +44,13 @@ pub static SYNTAX_POSITION_EPSILON: &'static str = "ε";
 
 
 
+
+pub static SEXPR_EMPTY: &'static str = "empty";
+pub static SEXPR_ACTION: &'static str = "act";
+pub static SEXPR_EMISSION: &'static str = "emit";
+pub static SEXPR_RECEPTION: &'static str = "recv";
+pub static SEXPR_TARGETS: &'static str = "to";
+pub static SEXPR_GROUP: &'static str = "group";
+pub static SEXPR_GATE_ORIGIN: &'static str = "from";
+pub static SEXPR_PARAM: &'static str = "param";
+pub static SEXPR_SYNCHRONOUS: &'static str = "synch";
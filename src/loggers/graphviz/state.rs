@@ -109,6 +109,7 @@ impl InteractionProcessDrawer {
             draw_interaction(gen_ctx,
                              interaction,
                              &InteractionGraphicalRepresentation::AsSequenceDiagram,
+                             &None,
                              &"temp".to_string(),
                              &self.temp_folder,
                              &name);
@@ -135,6 +136,7 @@ impl InteractionProcessDrawer {
             draw_interaction(gen_ctx,
                              interaction,
                              &InteractionGraphicalRepresentation::AsTerm,
+                             &None,
                              &"temp".to_string(),
                              &self.temp_folder,
                              &name);
@@ -26,6 +26,8 @@ extern crate maplit;
 
 #[macro_use]
 extern crate strum_macros;
+#[macro_use]
+extern crate log;
 
 // **********
 
@@ -49,5 +51,5 @@ use crate::io::output::draw_interactions::interface::{draw_interaction, Interact
 use crate::ui::hibou_cli::hibou_cli;
 
 fn main() {
-    hibou_cli();
+    std::process::exit(hibou_cli());
 }
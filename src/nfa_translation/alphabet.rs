@@ -16,16 +16,113 @@ limitations under the License.
 
 
 use std::collections::BTreeSet;
+use std::path::Path;
+use serde_json::json;
 use crate::core::general_context::GeneralContext;
 use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
+use crate::io::output::to_hfiles::trace::trace_action::trace_action_as_htf_encoding;
 
+
+/// Version of the JSON encoding of alphabets written by 'get_alphabet_as_json',
+/// to be incremented whenever the encoding changes.
+pub const ALPHABET_ENCODING_VERSION : u32 = 1;
+
+/// Letters of the NFAs translated from interactions over the signature.
+/// Each letter is a single observable action and fully encodes its lifeline, its kind (emission or reception),
+/// its message and the value of its parameter, so that e.g. 'l!m' and 'l?m' are always distinct letters.
 pub fn get_alphabet_from_gen_ctx(gen_ctx : &GeneralContext) -> Vec<BTreeSet<TraceAction>> {
     let mut alphabet = vec![];
     for lf in 0..gen_ctx.get_lf_num() {
         for ms in 0..gen_ctx.get_ms_num() {
-            alphabet.push(btreeset!{TraceAction::new(lf,TraceActionKind::Emission,ms)});
-            alphabet.push(btreeset!{TraceAction::new(lf,TraceActionKind::Reception,ms)});
+            // a message 'm' with a parameter gives one letter 'm(x)' per value 'x' of its parameter
+            // in addition to the letter 'm' for occurrences of the message without parameter
+            let params = std::iter::once(None).chain((0..gen_ctx.get_ms_params_num(ms)).map(Some));
+            for ms_param in params {
+                alphabet.push(btreeset!{TraceAction::new(lf,TraceActionKind::Emission,ms).with_param(ms_param)});
+                alphabet.push(btreeset!{TraceAction::new(lf,TraceActionKind::Reception,ms).with_param(ms_param)});
+            }
         }
     }
     alphabet
-}
\ No newline at end of file
+}
+
+/// Mapping from the index of each letter of the alphabet (i.e. the letters of the NFAs)
+/// to the actions it stands for, with the names of their lifeline, message and parameter
+/// and their textual representation as in .htf files.
+pub fn get_alphabet_as_json(gen_ctx : &GeneralContext, alphabet : &[BTreeSet<TraceAction>]) -> serde_json::Value {
+    let letters : Vec<serde_json::Value> = alphabet.iter().enumerate().map(|(letter,actions)| {
+        let actions : Vec<serde_json::Value> = actions.iter().map(|action| {
+            let kind = match action.act_kind {
+                TraceActionKind::Emission => "emission",
+                TraceActionKind::Reception => "reception"
+            };
+            json!({
+                "lifeline" : gen_ctx.get_lf_name(action.lf_id).unwrap(),
+                "kind" : kind,
+                "message" : gen_ctx.get_ms_name(action.ms_id).unwrap(),
+                "parameter" : action.ms_param.map(|param| gen_ctx.get_ms_param_name(action.ms_id,param).unwrap()),
+                "label" : trace_action_as_htf_encoding(gen_ctx,action)
+            })
+        }).collect();
+        json!({"letter" : letter, "actions" : actions})
+    }).collect();
+    json!({
+        "version" : ALPHABET_ENCODING_VERSION,
+        "letters" : letters
+    })
+}
+
+/// Writes 'get_alphabet_as_json' into '{name}.alphabet.json' and returns the name of this file.
+pub fn write_alphabet_file(gen_ctx : &GeneralContext, alphabet : &[BTreeSet<TraceAction>], name : &str) -> std::io::Result<String> {
+    let file_name = format!("{:}.alphabet.json", name);
+    std::fs::write(Path::new(&file_name), serde_json::to_string_pretty(&get_alphabet_as_json(gen_ctx,alphabet)).unwrap())?;
+    Ok(file_name)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn alphabet_test() {
+        let gen_ctx = parse_hsf_string("@message{m(x,y);n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        // 2 lifelines, 2 kinds and 3 + 1 (message, parameter) pairs
+        assert_eq!(alphabet.len(), 2 * 2 * 4);
+        assert_eq!(alphabet.iter().collect::<HashSet<_>>().len(), alphabet.len());
+        let letter = |act : TraceAction| alphabet.iter().position(|x| *x == btreeset!{act}).unwrap();
+        let emission = letter(TraceAction::new(0,TraceActionKind::Emission,0));
+        let reception = letter(TraceAction::new(0,TraceActionKind::Reception,0));
+        assert_ne!(emission, reception);
+        // the emission and the reception on the same lifeline are told apart in the NFA
+        let i = parse_hif_string(&gen_ctx,"seq(a -- m ->|, m -> a)".to_string()).unwrap();
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+        assert_eq!(nfa.transitions.len(), 3);
+        let letters : HashSet<usize> = nfa.transitions.iter().flat_map(|x| x.keys().cloned()).collect();
+        assert_eq!(letters, HashSet::from([emission,reception]));
+    }
+
+    #[test]
+    fn alphabet_as_json_test() {
+        let gen_ctx = parse_hsf_string("@message{m(x);n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let as_json = get_alphabet_as_json(&gen_ctx,&alphabet);
+        assert_eq!(as_json["version"], json!(ALPHABET_ENCODING_VERSION));
+        let letters = as_json["letters"].as_array().unwrap();
+        assert_eq!(letters.len(), alphabet.len());
+        // letters are listed by index and each one gives the action it stands for
+        for (idx,letter) in letters.iter().enumerate() {
+            assert_eq!(letter["letter"], json!(idx));
+            let action = alphabet[idx].iter().next().unwrap();
+            assert_eq!(letter["actions"][0]["label"], json!(trace_action_as_htf_encoding(&gen_ctx,action)));
+        }
+        let reception = alphabet.iter().position(|x| *x == btreeset!{TraceAction::new(1,TraceActionKind::Reception,0).with_param(Some(0))}).unwrap();
+        assert_eq!(letters[reception]["actions"][0],
+                   json!({"lifeline" : "b", "kind" : "reception", "message" : "m", "parameter" : "x", "label" : trace_action_as_htf_encoding(&gen_ctx,alphabet[reception].iter().next().unwrap())}));
+    }
+}
@@ -15,7 +15,9 @@ limitations under the License.
 */
 
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use autour_core::nfa::nfa::AutNFA;
 use autour_core::traits::access::AutAccessible;
@@ -24,8 +26,9 @@ use autour_core::traits::transform::AutTransformable;
 
 use crate::core::execution::trace::trace::{TraceAction, TraceActionKind};
 use crate::core::general_context::GeneralContext;
+use crate::core::language::hash::canonical::HashedInteraction;
 use crate::core::language::syntax::interaction::{Interaction, LoopKind};
-use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::get_nfa_from_logger::{get_nfa_from_interaction_exploration_with_budget, TranslationError};
 use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
 
 pub fn interaction_has_only_strict_and_seq(int : &Interaction) -> bool {
@@ -44,71 +47,116 @@ pub fn interaction_has_only_strict_and_seq(int : &Interaction) -> bool {
 }
 
 
+/// Numbers of sub-interactions whose NFA was found in (hits) or added to (misses) the cache
+/// during a translation via composition.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct NfaTranslationCacheStats {
+    pub hits : u64,
+    pub misses : u64
+}
+
+/// NFAs of the sub-interactions already translated, so that identical sub-interactions are translated once.
+/// All these NFAs are built on the same alphabet, hence a cache must not outlive the translation it was created for.
+/// It also carries the cancellation flag of the translation, checked before each sub-interaction is translated.
+struct NfaTranslationCache {
+    memo : HashMap<HashedInteraction,AutNFA<usize>>,
+    stats : NfaTranslationCacheStats,
+    cancel : Option<Arc<AtomicBool>>
+}
+
+impl NfaTranslationCache {
+    fn new(cancel : Option<Arc<AtomicBool>>) -> Self {
+        Self{memo:HashMap::new(),stats:NfaTranslationCacheStats::default(),cancel}
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().map_or(false, |cancelled| cancelled.load(Ordering::Relaxed))
+    }
+}
+
+
 pub fn get_nfa_from_interaction_via_composition(gen_ctx : &GeneralContext,
                                             int : &Interaction,
                                                 base_alphabet : Vec<BTreeSet<TraceAction>>) -> (AutNFA<usize>,Duration) {
+    let (nfa,elapsed_get_nfa,_) = get_nfa_from_interaction_via_composition_with_cache_stats(gen_ctx,int,base_alphabet);
+    (nfa,elapsed_get_nfa)
+}
+
+/// Same as 'get_nfa_from_interaction_via_composition' but also returns how often the cache of sub-interactions was used.
+/// The cache is created anew for each call so that the elapsed time only accounts for this translation.
+pub fn get_nfa_from_interaction_via_composition_with_cache_stats(gen_ctx : &GeneralContext,
+                                                                 int : &Interaction,
+                                                                 base_alphabet : Vec<BTreeSet<TraceAction>>) -> (AutNFA<usize>,Duration,NfaTranslationCacheStats) {
+    get_nfa_from_interaction_via_composition_cancellable(gen_ctx,int,base_alphabet,None).unwrap()
+}
+
+/// Same as 'get_nfa_from_interaction_via_composition_with_cache_stats' but aborts
+/// as soon as the 'cancel' flag is set (e.g. from another thread).
+pub fn get_nfa_from_interaction_via_composition_cancellable(gen_ctx : &GeneralContext,
+                                                            int : &Interaction,
+                                                            base_alphabet : Vec<BTreeSet<TraceAction>>,
+                                                            cancel : Option<Arc<AtomicBool>>) -> Result<(AutNFA<usize>,Duration,NfaTranslationCacheStats),TranslationError> {
     let now = Instant::now();
-    let nfa = get_nfa_from_interaction_via_composition_rec(gen_ctx,int,base_alphabet);
+    let mut cache = NfaTranslationCache::new(cancel);
+    let nfa = get_nfa_from_interaction_via_composition_rec(gen_ctx,int,&base_alphabet,&mut cache)?;
     let elapsed_get_nfa = now.elapsed();
-    (nfa,elapsed_get_nfa)
+    Ok( (nfa,elapsed_get_nfa,cache.stats) )
 }
 
 fn get_nfa_from_interaction_via_composition_rec(gen_ctx : &GeneralContext,
-                                            int : &Interaction,
-                                                alphabet : Vec<BTreeSet<TraceAction>>) -> AutNFA<usize> {
-    if interaction_has_only_strict_and_seq(int) {
-        let (nfa,_) = get_nfa_from_interaction_exploration(gen_ctx,int,alphabet);
-        nfa
+                                                int : &Interaction,
+                                                alphabet : &Vec<BTreeSet<TraceAction>>,
+                                                cache : &mut NfaTranslationCache) -> Result<AutNFA<usize>,TranslationError> {
+    if cache.is_cancelled() {
+        return Err( TranslationError::Cancelled );
+    }
+    let key = HashedInteraction::new(int.clone());
+    if let Some(nfa) = cache.memo.get(&key) {
+        cache.stats.hits += 1;
+        return Ok( nfa.clone() );
+    }
+    cache.stats.misses += 1;
+    let nfa = translate_via_composition(gen_ctx,int,alphabet,cache)?;
+    cache.memo.insert(key,nfa.clone());
+    Ok( nfa )
+}
+
+fn translate_via_composition(gen_ctx : &GeneralContext,
+                             int : &Interaction,
+                             alphabet : &Vec<BTreeSet<TraceAction>>,
+                             cache : &mut NfaTranslationCache) -> Result<AutNFA<usize>,TranslationError> {
+    // a forbidden action constrains the actions of its context, which composing the NFAs of the operands
+    // cannot account for, hence sub-interactions with forbidden actions are translated via exploration
+    if interaction_has_only_strict_and_seq(int) || int.contains_forbidden_actions() {
+        let (nfa,_) = get_nfa_from_interaction_exploration_with_budget(gen_ctx,int,alphabet.clone(),None,cache.cancel.clone())?;
+        Ok( nfa )
     } else {
         match int {
             Interaction::Alt(i1,i2) => {
-                let nfa1 = get_nfa_from_interaction_via_composition_rec(
-                    gen_ctx,
-                    i1,
-                    alphabet.clone());
-                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,
-                                                                        i2,
-                                                                        alphabet.clone());
-                nfa1.unite(nfa2).unwrap().trim()
+                let nfa1 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i1,alphabet,cache)?;
+                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i2,alphabet,cache)?;
+                Ok( nfa1.unite(nfa2).unwrap().trim() )
             },
             Interaction::Par(i1,i2) => {
-                let nfa1 = get_nfa_from_interaction_via_composition_rec(
-                    gen_ctx,
-                    i1,
-                    alphabet.clone());
-                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,
-                                                                        i2,
-                                                                        alphabet.clone());
-                nfa1.interleave(nfa2).unwrap().trim()
+                let nfa1 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i1,alphabet,cache)?;
+                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i2,alphabet,cache)?;
+                Ok( nfa1.interleave(nfa2).unwrap().trim() )
             },
             Interaction::Seq(i1,i2) => {
-                let nfa1 = get_nfa_from_interaction_via_composition_rec(
-                    gen_ctx,
-                    i1,
-                    alphabet.clone());
-                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,
-                                                                        i2,
-                                                                        alphabet.clone());
-                nfa1.concatenate(nfa2).unwrap().trim()
+                let nfa1 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i1,alphabet,cache)?;
+                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i2,alphabet,cache)?;
+                Ok( nfa1.concatenate(nfa2).unwrap().trim() )
             },
             Interaction::Strict(i1,i2) => {
-                let nfa1 = get_nfa_from_interaction_via_composition_rec(
-                    gen_ctx,
-                    i1,
-                    alphabet.clone());
-                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,
-                                                                        i2,
-                                                                        alphabet.clone());
-                nfa1.concatenate(nfa2).unwrap().trim()
+                let nfa1 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i1,alphabet,cache)?;
+                let nfa2 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i2,alphabet,cache)?;
+                Ok( nfa1.concatenate(nfa2).unwrap().trim() )
             },
             Interaction::Loop(lk,i1) => {
-                let nfa1 = get_nfa_from_interaction_via_composition_rec(
-                    gen_ctx,
-                    i1,
-                    alphabet.clone());
+                let nfa1 = get_nfa_from_interaction_via_composition_rec(gen_ctx,i1,alphabet,cache)?;
                 match lk {
                     LoopKind::SStrictSeq => {
-                        nfa1.kleene().trim()
+                        Ok( nfa1.kleene().trim() )
                     },
                     _ => {panic!()}
                 }
@@ -116,4 +164,38 @@ fn get_nfa_from_interaction_via_composition_rec(gen_ctx : &GeneralContext,
             _ => {panic!()}
         }
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::equiv::get_language_difference_witness;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    fn emission(lf_id : usize, ms_id : usize) -> Interaction {
+        Interaction::Emission(
+            EmissionAction::new(lf_id,ms_id,CommunicationSynchronicity::Asynchronous,vec![])
+        )
+    }
+
+    #[test]
+    fn composition_cache_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        // par(alt(l1!m1,l2!m1), alt(l1!m1,l2!m1))
+        let alt = Interaction::Alt(Box::new(emission(0,0)),Box::new(emission(1,0)));
+        let i = Interaction::Par(Box::new(alt.clone()),Box::new(alt));
+        let (nfa,_,stats) = get_nfa_from_interaction_via_composition_with_cache_stats(&gen_ctx,&i,alphabet.clone());
+        // the second alt is found in the cache, hence its actions are not visited
+        assert_eq!(stats, NfaTranslationCacheStats{hits:1,misses:4});
+        let (explored,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet);
+        assert!(get_language_difference_witness(&nfa,&explored).is_none());
+    }
+
+}
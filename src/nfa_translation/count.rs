@@ -0,0 +1,162 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::collections::{HashMap, HashSet};
+use autour_core::dfa::dfa::AutDFA;
+use autour_core::nfa::nfa::AutNFA;
+use autour_core::traits::translate::AutTranslatable;
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TraceLanguageCardinality {
+    Finite(u128),
+    Infinite
+}
+
+impl std::fmt::Display for TraceLanguageCardinality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceLanguageCardinality::Finite(num) => {
+                write!(f,"finite: {:}", num)
+            },
+            TraceLanguageCardinality::Infinite => {
+                write!(f,"infinite")
+            }
+        }
+    }
+}
+
+/// Returns the number of distinct words accepted by the NFA, or infinite
+/// if a cycle lies on a path from the initial state to a final state.
+/// The NFA is first determinized so that each accepted word is counted once.
+/// The count saturates at u128::MAX.
+pub fn count_nfa_traces(nfa : &AutNFA<usize>) -> TraceLanguageCardinality {
+    let dfa = nfa.to_dfa();
+    let useful = get_useful_states(&dfa);
+    if !useful.contains(&dfa.initial) {
+        return TraceLanguageCardinality::Finite(0);
+    }
+    // iterative depth first search from the initial state which detects back edges
+    // and yields the useful states in post-order
+    let mut on_stack : HashSet<usize> = HashSet::new();
+    let mut visited : HashSet<usize> = hashset!{dfa.initial};
+    let mut post_order : Vec<usize> = vec![];
+    let mut stack : Vec<(usize,Vec<usize>)> = vec![(dfa.initial,get_useful_successors(&dfa,&useful,dfa.initial))];
+    on_stack.insert(dfa.initial);
+    while let Some((state,mut successors)) = stack.pop() {
+        match successors.pop() {
+            None => {
+                on_stack.remove(&state);
+                post_order.push(state);
+            },
+            Some(next) => {
+                stack.push((state,successors));
+                if on_stack.contains(&next) {
+                    return TraceLanguageCardinality::Infinite;
+                }
+                if visited.insert(next) {
+                    on_stack.insert(next);
+                    stack.push((next,get_useful_successors(&dfa,&useful,next)));
+                }
+            }
+        }
+    }
+    // in an acyclic DFA the accepted words are in bijection with the paths to final states
+    let mut counts : HashMap<usize,u128> = HashMap::new();
+    for state in post_order {
+        let mut count : u128 = if dfa.finals.contains(&state) {1} else {0};
+        for next in get_useful_successors(&dfa,&useful,state) {
+            count = count.saturating_add(*counts.get(&next).unwrap());
+        }
+        counts.insert(state,count);
+    }
+    TraceLanguageCardinality::Finite(*counts.get(&dfa.initial).unwrap())
+}
+
+fn get_useful_successors(dfa : &AutDFA<usize>, useful : &HashSet<usize>, state : usize) -> Vec<usize> {
+    dfa.transitions[state].values().filter(|t| useful.contains(t)).cloned().collect()
+}
+
+/// States that are both reachable from the initial state and co-reachable from a final state.
+fn get_useful_states(dfa : &AutDFA<usize>) -> HashSet<usize> {
+    let mut reachable : HashSet<usize> = hashset!{dfa.initial};
+    let mut to_visit = vec![dfa.initial];
+    while let Some(state) = to_visit.pop() {
+        for next in dfa.transitions[state].values() {
+            if reachable.insert(*next) {
+                to_visit.push(*next);
+            }
+        }
+    }
+    let mut predecessors : Vec<Vec<usize>> = vec![vec![];dfa.transitions.len()];
+    for (origin,transitions) in dfa.transitions.iter().enumerate() {
+        for target in transitions.values() {
+            predecessors[*target].push(origin);
+        }
+    }
+    let mut coreachable : HashSet<usize> = dfa.finals.clone();
+    let mut to_visit : Vec<usize> = dfa.finals.iter().cloned().collect();
+    while let Some(state) = to_visit.pop() {
+        for prev in &predecessors[state] {
+            if coreachable.insert(*prev) {
+                to_visit.push(*prev);
+            }
+        }
+    }
+    reachable.intersection(&coreachable).cloned().collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::general_context::GeneralContext;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, ReceptionAction};
+    use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn count_traces_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let count = |int : &Interaction| {
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,int,alphabet.clone());
+            count_nfa_traces(&nfa)
+        };
+        // ***
+        assert_eq!(count(&Interaction::Empty), TraceLanguageCardinality::Finite(1));
+        let emission = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let reception = Interaction::Reception(ReceptionAction::new(None,0,CommunicationSynchronicity::Asynchronous,vec![1]));
+        // the two interleavings of actions on distinct lifelines
+        let par = Interaction::Par(Box::new(emission.clone()),Box::new(reception.clone()));
+        assert_eq!(count(&par), TraceLanguageCardinality::Finite(2));
+        // both branches yield the same word, which is counted once
+        let alt = Interaction::Alt(Box::new(emission.clone()),Box::new(emission.clone()));
+        assert_eq!(count(&alt), TraceLanguageCardinality::Finite(1));
+        let alt = Interaction::Alt(Box::new(par),Box::new(Interaction::Empty));
+        assert_eq!(count(&alt), TraceLanguageCardinality::Finite(3));
+        // ***
+        let looped = Interaction::Loop(LoopKind::SStrictSeq,Box::new(emission));
+        assert_eq!(count(&looped), TraceLanguageCardinality::Infinite);
+        assert_eq!(count(&looped).to_string(), "infinite");
+    }
+
+}
@@ -0,0 +1,292 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use autour_core::dfa::dfa::AutDFA;
+use autour_core::nfa::nfa::AutNFA;
+use autour_core::traits::transform::AutTransformable;
+use autour_core::traits::translate::AutTranslatable;
+use crate::core::language::hash::canonical::StableHasher;
+
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum LanguageDifferenceWitness {
+    AcceptedByLeftOnly(Vec<usize>),
+    AcceptedByRightOnly(Vec<usize>)
+}
+
+impl LanguageDifferenceWitness {
+    pub fn get_word(&self) -> &Vec<usize> {
+        match self {
+            LanguageDifferenceWitness::AcceptedByLeftOnly(word) => word,
+            LanguageDifferenceWitness::AcceptedByRightOnly(word) => word
+        }
+    }
+}
+
+/// Checks whether the two NFAs accept the same language.
+/// Both are determinized over the union of their alphabets and each is intersected with the complement of the other.
+/// Returns None if the languages are equal and otherwise a shortest word accepted by only one of the two.
+pub fn get_language_difference_witness(left : &AutNFA<usize>,
+                                       right : &AutNFA<usize>) -> Option<LanguageDifferenceWitness> {
+    let alphabet : HashSet<usize> = left.alphabet.union(&right.alphabet).cloned().collect();
+    let mut left = left.clone();
+    left.alphabet = alphabet.clone();
+    let mut right = right.clone();
+    right.alphabet = alphabet;
+    let left = left.to_dfa();
+    let right = right.to_dfa();
+    // both automata have the same alphabet so that the intersections cannot fail
+    let left_only = left.clone().intersect(right.clone().negate()).unwrap();
+    let right_only = right.intersect(left.negate()).unwrap();
+    match (get_shortest_accepted_word(&left_only),get_shortest_accepted_word(&right_only)) {
+        (None,None) => {
+            None
+        },
+        (Some(word),None) => {
+            Some(LanguageDifferenceWitness::AcceptedByLeftOnly(word))
+        },
+        (None,Some(word)) => {
+            Some(LanguageDifferenceWitness::AcceptedByRightOnly(word))
+        },
+        (Some(left_word),Some(right_word)) => {
+            if right_word.len() < left_word.len() {
+                Some(LanguageDifferenceWitness::AcceptedByRightOnly(right_word))
+            } else {
+                Some(LanguageDifferenceWitness::AcceptedByLeftOnly(left_word))
+            }
+        }
+    }
+}
+
+/// Minimal DFA of the language of a NFA, trimmed from its dead states and with its states numbered
+/// in breadth first order (letters in increasing order), so that two NFAs accept the same language
+/// if and only if they have the same canonical DFA.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CanonicalDfa {
+    /// whether each state is final, the initial state being the first one (there is none if the language is empty)
+    pub finals : Vec<bool>,
+    /// transitions (letter,target state) of each state, by increasing letters
+    pub transitions : Vec<Vec<(usize,usize)>>
+}
+
+impl CanonicalDfa {
+
+    pub fn from_nfa(nfa : &AutNFA<usize>) -> CanonicalDfa {
+        let dfa = nfa.to_dfa().minimize();
+        // states from which a final state is reachable
+        let mut live : HashSet<usize> = dfa.finals.clone();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (state,transitions) in dfa.transitions.iter().enumerate() {
+                if !live.contains(&state) && transitions.values().any(|next| live.contains(next)) {
+                    live.insert(state);
+                    changed = true;
+                }
+            }
+        }
+        let mut canonical = CanonicalDfa{finals:vec![],transitions:vec![]};
+        if !live.contains(&dfa.initial) {
+            // empty language
+            return canonical;
+        }
+        let mut numbering : HashMap<usize,usize> = hashmap!{dfa.initial => 0};
+        let mut queue : VecDeque<usize> = VecDeque::new();
+        queue.push_back(dfa.initial);
+        while let Some(state) = queue.pop_front() {
+            canonical.finals.push(dfa.finals.contains(&state));
+            let mut transitions : Vec<(&usize,&usize)> = dfa.transitions[state].iter()
+                .filter(|(_,next)| live.contains(next))
+                .collect();
+            transitions.sort();
+            let mut state_transitions = vec![];
+            for (letter,next) in transitions {
+                if !numbering.contains_key(next) {
+                    numbering.insert(*next,numbering.len());
+                    queue.push_back(*next);
+                }
+                state_transitions.push((*letter,*numbering.get(next).unwrap()));
+            }
+            canonical.transitions.push(state_transitions);
+        }
+        canonical
+    }
+
+    pub fn get_num_states(&self) -> usize {
+        self.finals.len()
+    }
+
+    /// Hash of the canonical DFA, equal for two NFAs accepting the same language
+    /// (and distinct for NFAs accepting distinct languages up to hash collisions).
+    pub fn get_signature(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        if self.finals.is_empty() {
+            0_usize.hash(&mut hasher);
+            return hasher.finish();
+        }
+        for (is_final,transitions) in self.finals.iter().zip(self.transitions.iter()) {
+            is_final.hash(&mut hasher);
+            for transition in transitions {
+                transition.hash(&mut hasher);
+            }
+            // separates the transitions of successive states
+            usize::MAX.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "finals" : self.finals,
+            "transitions" : self.transitions
+        })
+    }
+
+    /// Returns None if the value is not the encoding of a DFA (see 'to_json'),
+    /// which does not check that the DFA is a canonical one.
+    pub fn from_json(value : &serde_json::Value) -> Option<CanonicalDfa> {
+        let finals : Vec<bool> = value["finals"].as_array()?.iter()
+            .map(|is_final| is_final.as_bool())
+            .collect::<Option<Vec<bool>>>()?;
+        let mut transitions = vec![];
+        for state_transitions in value["transitions"].as_array()? {
+            let mut got = vec![];
+            for transition in state_transitions.as_array()? {
+                let letter = transition[0].as_u64()? as usize;
+                let next = transition[1].as_u64()? as usize;
+                if next >= finals.len() {
+                    return None;
+                }
+                got.push((letter,next));
+            }
+            transitions.push(got);
+        }
+        if transitions.len() != finals.len() {
+            return None;
+        }
+        Some(CanonicalDfa{finals,transitions})
+    }
+
+}
+
+/// Signature of the language of the NFA (see 'CanonicalDfa::get_signature'),
+/// together with the number of states of its minimal DFA (without the dead states).
+pub fn get_language_signature(nfa : &AutNFA<usize>) -> (u64,usize) {
+    let dfa = CanonicalDfa::from_nfa(nfa);
+    (dfa.get_signature(),dfa.get_num_states())
+}
+
+/// Breadth first search from the initial state, letters being explored in increasing order
+/// so that the returned word does not depend on the iteration order of the transitions.
+fn get_shortest_accepted_word(dfa : &AutDFA<usize>) -> Option<Vec<usize>> {
+    let mut parents : HashMap<usize,Option<(usize,usize)>> = hashmap!{dfa.initial => None};
+    let mut queue : VecDeque<usize> = VecDeque::new();
+    queue.push_back(dfa.initial);
+    while let Some(state) = queue.pop_front() {
+        if dfa.finals.contains(&state) {
+            let mut word = vec![];
+            let mut current = state;
+            while let Some((prev,letter)) = parents.get(&current).unwrap() {
+                word.push(*letter);
+                current = *prev;
+            }
+            word.reverse();
+            return Some(word);
+        }
+        let mut transitions : Vec<(&usize,&usize)> = dfa.transitions[state].iter().collect();
+        transitions.sort();
+        for (letter,next) in transitions {
+            if !parents.contains_key(next) {
+                parents.insert(*next,Some((state,*letter)));
+                queue.push_back(*next);
+            }
+        }
+    }
+    None
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::general_context::GeneralContext;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn lang_equiv_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let nfa = |int : &Interaction| {
+            get_nfa_from_interaction_exploration(&gen_ctx,int,alphabet.clone()).0
+        };
+        // ***
+        let em1 = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let em2 = Interaction::Emission(EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let seq = Interaction::Seq(Box::new(em1.clone()),Box::new(em2.clone()));
+        let par = Interaction::Par(Box::new(em1.clone()),Box::new(em2.clone()));
+        // actions on distinct lifelines are not ordered by weak sequencing
+        assert_eq!(get_language_difference_witness(&nfa(&seq),&nfa(&par)), None);
+        let strict = Interaction::Strict(Box::new(em2.clone()),Box::new(em1.clone()));
+        let got = get_language_difference_witness(&nfa(&par),&nfa(&strict)).unwrap();
+        assert!(matches!(got, LanguageDifferenceWitness::AcceptedByLeftOnly(_)));
+        assert_eq!(got.get_word().len(), 2);
+        // the shortest witness of the difference between a loop and its unrolling is the empty word
+        let looped = Interaction::Loop(LoopKind::SStrictSeq,Box::new(em1.clone()));
+        let got = get_language_difference_witness(&nfa(&em1),&nfa(&looped)).unwrap();
+        assert_eq!(got, LanguageDifferenceWitness::AcceptedByRightOnly(vec![]));
+    }
+
+    #[test]
+    fn language_signature_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let signature = |int : &Interaction| {
+            get_language_signature(&get_nfa_from_interaction_exploration(&gen_ctx,int,alphabet.clone()).0)
+        };
+        let em1 = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let em2 = Interaction::Emission(EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let seq = Interaction::Seq(Box::new(em1.clone()),Box::new(em2.clone()));
+        let par = Interaction::Par(Box::new(em2.clone()),Box::new(em1.clone()));
+        let strict = Interaction::Strict(Box::new(em1.clone()),Box::new(em2.clone()));
+        assert_eq!(signature(&seq), signature(&par));
+        assert_eq!(signature(&seq).1, 4);
+        assert_ne!(signature(&seq), signature(&strict));
+        assert_eq!(signature(&strict).1, 3);
+        let looped = Interaction::Loop(LoopKind::SStrictSeq,Box::new(em1.clone()));
+        let looped_twice = Interaction::Loop(LoopKind::SStrictSeq,Box::new(looped.clone()));
+        assert_eq!(signature(&looped), signature(&looped_twice));
+        assert_ne!(signature(&looped), signature(&Interaction::Empty));
+        assert_eq!(signature(&Interaction::Empty).1, 1);
+        // ***
+        let canonical = CanonicalDfa::from_nfa(&get_nfa_from_interaction_exploration(&gen_ctx,&seq,alphabet.clone()).0);
+        assert_eq!(canonical, CanonicalDfa::from_nfa(&get_nfa_from_interaction_exploration(&gen_ctx,&par,alphabet.clone()).0));
+        assert_eq!(CanonicalDfa::from_json(&canonical.to_json()), Some(canonical.clone()));
+        assert_eq!(CanonicalDfa::from_json(&serde_json::json!({"finals":[true],"transitions":[[[0,1]]]})), None);
+    }
+
+}
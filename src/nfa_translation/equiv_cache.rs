@@ -0,0 +1,181 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::hash::canonical::{canonical_hash, StableHasher};
+use crate::core::language::syntax::interaction::Interaction;
+use crate::nfa_translation::equiv::CanonicalDfa;
+
+
+/// Version of the encoding of the entries of a 'LanguageSignatureCache'
+/// (the canonical hashes of interactions, the hashes of alphabets and the language signatures and canonical DFAs),
+/// which is part of the keys so that entries written with another encoding are never read.
+pub const LANGUAGE_SIGNATURE_CACHE_VERSION : u32 = 1;
+
+/// Language of an interaction as stored in a 'LanguageSignatureCache'.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CachedLanguage {
+    pub signature : u64,
+    pub dfa : CanonicalDfa
+}
+
+/// Result of looking up the language of an interaction in a 'LanguageSignatureCache'.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LanguageSignatureLookup {
+    Hit(CachedLanguage),
+    /// there is an entry for the source file but it was computed for another interaction
+    /// (the file having been modified since) or it is unreadable
+    Stale,
+    Miss
+}
+
+/// On-disk cache of the languages (see 'CanonicalDfa') of the interactions of .hif files,
+/// with one '<key>.json' file per source file where the key combines the version of the encoding,
+/// the path of the source file and a hash of the alphabet.
+/// The canonical hash of the interaction is stored in the entry and checked against that of the
+/// interaction currently in the source file so that the entries of modified files are recomputed.
+pub struct LanguageSignatureCache {
+    pub folder : PathBuf
+}
+
+impl LanguageSignatureCache {
+
+    pub fn new(folder : PathBuf) -> std::io::Result<LanguageSignatureCache> {
+        std::fs::create_dir_all(&folder)?;
+        Ok(LanguageSignatureCache{folder})
+    }
+
+    pub fn get_alphabet_hash(alphabet : &Vec<BTreeSet<TraceAction>>) -> u64 {
+        let mut hasher = StableHasher::default();
+        alphabet.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn get_source_name(source : &Path) -> String {
+        std::fs::canonicalize(source).unwrap_or(source.to_path_buf()).to_string_lossy().to_string()
+    }
+
+    fn get_entry_path(&self, source_name : &str, alphabet_hash : u64) -> PathBuf {
+        let mut hasher = StableHasher::default();
+        (LANGUAGE_SIGNATURE_CACHE_VERSION,source_name,alphabet_hash).hash(&mut hasher);
+        self.folder.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    pub fn lookup(&self, source : &Path, interaction : &Interaction, alphabet_hash : u64) -> LanguageSignatureLookup {
+        let source_name = LanguageSignatureCache::get_source_name(source);
+        let content = match std::fs::read_to_string(self.get_entry_path(&source_name,alphabet_hash)) {
+            Err(_) => {
+                return LanguageSignatureLookup::Miss;
+            },
+            Ok( got ) => {
+                got
+            }
+        };
+        let entry = match serde_json::from_str::<serde_json::Value>(&content) {
+            Err(_) => {
+                return LanguageSignatureLookup::Stale;
+            },
+            Ok( got ) => {
+                got
+            }
+        };
+        let matches_source = entry["version"].as_u64() == Some(LANGUAGE_SIGNATURE_CACHE_VERSION as u64)
+            && entry["source"].as_str() == Some(&source_name)
+            && entry["alphabet_hash"].as_u64() == Some(alphabet_hash)
+            && entry["interaction_hash"].as_u64() == Some(canonical_hash(interaction));
+        if !matches_source {
+            return LanguageSignatureLookup::Stale;
+        }
+        match (entry["language_signature"].as_u64(),CanonicalDfa::from_json(&entry["dfa"])) {
+            (Some(signature),Some(dfa)) => {
+                LanguageSignatureLookup::Hit(CachedLanguage{signature,dfa})
+            },
+            _ => {
+                LanguageSignatureLookup::Stale
+            }
+        }
+    }
+
+    /// Writes (or overwrites) the entry of the source file.
+    pub fn store(&self,
+                 source : &Path,
+                 interaction : &Interaction,
+                 alphabet_hash : u64,
+                 language : &CachedLanguage) -> std::io::Result<()> {
+        let source_name = LanguageSignatureCache::get_source_name(source);
+        let entry = json!({
+            "version" : LANGUAGE_SIGNATURE_CACHE_VERSION,
+            "source" : source_name,
+            "alphabet_hash" : alphabet_hash,
+            "interaction_hash" : canonical_hash(interaction),
+            "language_signature" : language.signature,
+            "num_states" : language.dfa.get_num_states(),
+            "dfa" : language.dfa.to_json()
+        });
+        std::fs::write(self.get_entry_path(&source_name,alphabet_hash), serde_json::to_string_pretty(&entry).unwrap())
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn cache_test() {
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b}".to_string()).unwrap();
+        let i1 = parse_hif_string(&gen_ctx,"seq(a -- m -> b, loopS(b -- m ->|))".to_string()).unwrap();
+        let i2 = parse_hif_string(&gen_ctx,"par(a -- m -> b, b -- m ->|)".to_string()).unwrap();
+        let folder = std::env::temp_dir().join(format!("hibou_equiv_cache_test_{:}", std::process::id()));
+        let cache = LanguageSignatureCache::new(folder.join("cache")).unwrap();
+        let (source1,source2) = (folder.join("i1.hif"),folder.join("i2.hif"));
+        std::fs::write(&source1,"seq(a -- m -> b, loopS(b -- m ->|))").unwrap();
+        std::fs::write(&source2,"par(a -- m -> b, b -- m ->|)").unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let alphabet_hash = LanguageSignatureCache::get_alphabet_hash(&alphabet);
+        let dfa = CanonicalDfa::from_nfa(&get_nfa_from_interaction_exploration(&gen_ctx,&i1,alphabet.clone()).0);
+        let language = CachedLanguage{signature:dfa.get_signature(),dfa};
+        assert_eq!(cache.lookup(&source1,&i1,alphabet_hash), LanguageSignatureLookup::Miss);
+        cache.store(&source1,&i1,alphabet_hash,&language).unwrap();
+        assert_eq!(cache.lookup(&source1,&i1,alphabet_hash), LanguageSignatureLookup::Hit(language.clone()));
+        assert_eq!(cache.lookup(&source2,&i2,alphabet_hash), LanguageSignatureLookup::Miss);
+        // the entries depend on the alphabet
+        assert_eq!(cache.lookup(&source1,&i1,alphabet_hash+1), LanguageSignatureLookup::Miss);
+        // the entry of a source file whose interaction has changed is stale
+        assert_eq!(cache.lookup(&source1,&i2,alphabet_hash), LanguageSignatureLookup::Stale);
+        let path = cache.get_entry_path(&LanguageSignatureCache::get_source_name(&source1),alphabet_hash);
+        std::fs::write(&path,"{").unwrap();
+        assert_eq!(cache.lookup(&source1,&i1,alphabet_hash), LanguageSignatureLookup::Stale);
+        cache.store(&source1,&i2,alphabet_hash,&language).unwrap();
+        assert_eq!(cache.lookup(&source1,&i2,alphabet_hash), LanguageSignatureLookup::Hit(language));
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+}
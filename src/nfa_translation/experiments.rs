@@ -15,16 +15,26 @@ limitations under the License.
 */
 
 
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 use autour_core::dfa::dfa::AutDFA;
 use autour_core::nfa::nfa::AutNFA;
 use autour_core::traits::transform::AutTransformable;
 use autour_core::traits::translate::AutTranslatable;
+use rayon::prelude::*;
 use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::metrics::InteractionMetrics;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::error::HibouParsingError;
+use crate::io::input::hif::include::HifIncludeContext;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
 use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::timing_csv::NfaTimingCsvRow;
 
 pub struct NfaGenerationExperimentResults {
     pub int : Interaction,
@@ -46,47 +56,144 @@ pub struct NfaGenerationExperimentResults {
 
     pub dfa_minimized_from_compo : AutDFA<usize>,
     pub mindfa_med_time_from_compo : u128,
+
+    pub timing_stats : NfaGenerationTimingStats,
+
+    /// steps which produced automata of different sizes across their repetitions (see 'check_same_size')
+    pub size_inconsistencies : Vec<String>,
 }
 
 impl NfaGenerationExperimentResults {
-    pub fn new(int: Interaction, nfa_operational: AutNFA<usize>, nfa_operational_median_time: u128, nfa_minimized_kw_from_opera: AutNFA<usize>, nfa_kw_med_time_from_opera: u128, dfa_minimized_from_opera: AutDFA<usize>, mindfa_med_time_from_opera: u128, nfa_compositional: AutNFA<usize>, nfa_compositional_median_time: u128, nfa_minimized_kw_from_compo: AutNFA<usize>, nfa_kw_med_time_from_compo: u128, dfa_minimized_from_compo: AutDFA<usize>, mindfa_med_time_from_compo: u128) -> Self {
-        Self { int, nfa_operational, nfa_operational_median_time, nfa_minimized_kw_from_opera, nfa_kw_med_time_from_opera, dfa_minimized_from_opera, mindfa_med_time_from_opera, nfa_compositional, nfa_compositional_median_time, nfa_minimized_kw_from_compo, nfa_kw_med_time_from_compo, dfa_minimized_from_compo, mindfa_med_time_from_compo }
+    pub fn new(int: Interaction, nfa_operational: AutNFA<usize>, nfa_operational_median_time: u128, nfa_minimized_kw_from_opera: AutNFA<usize>, nfa_kw_med_time_from_opera: u128, dfa_minimized_from_opera: AutDFA<usize>, mindfa_med_time_from_opera: u128, nfa_compositional: AutNFA<usize>, nfa_compositional_median_time: u128, nfa_minimized_kw_from_compo: AutNFA<usize>, nfa_kw_med_time_from_compo: u128, dfa_minimized_from_compo: AutDFA<usize>, mindfa_med_time_from_compo: u128, timing_stats: NfaGenerationTimingStats, size_inconsistencies: Vec<String>) -> Self {
+        Self { int, nfa_operational, nfa_operational_median_time, nfa_minimized_kw_from_opera, nfa_kw_med_time_from_opera, dfa_minimized_from_opera, mindfa_med_time_from_opera, nfa_compositional, nfa_compositional_median_time, nfa_minimized_kw_from_compo, nfa_kw_med_time_from_compo, dfa_minimized_from_compo, mindfa_med_time_from_compo, timing_stats, size_inconsistencies }
+    }
+
+    /// Row recording the translation via the operational method.
+    pub fn get_timing_row(&self, name : String) -> NfaTimingCsvRow {
+        let (num_states,num_transitions) = nfa_size(&self.nfa_operational);
+        let mut row = NfaTimingCsvRow::new(name,
+                                           InteractionMetrics::extract_from_interaction(&self.int).get_num_symbols(),
+                                           self.nfa_operational_median_time,
+                                           num_states as u32,
+                                           num_transitions as u32,
+                                           self.nfa_operational.alphabet.len());
+        row.nondeterministic = !self.size_inconsistencies.is_empty();
+        row
+    }
+
+    /// Number of states of the NFA obtained via the operational method per symbol of the interaction
+    /// (the symbols being counted as in 'InteractionMetrics').
+    pub fn get_blowup(&self) -> f64 {
+        self.get_timing_row(String::new()).get_blowup()
+    }
+}
+
+/// Aggregation of the wall-clock times (in μs) measured over the repetitions of a step.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimingStats {
+    pub min : u128,
+    pub median : u128,
+    pub mean : u128,
+    pub p95 : u128,
+}
+
+impl TimingStats {
+
+    pub fn from_samples(samples : Vec<u128>) -> Self {
+        let mut samples = samples;
+        samples.sort();
+        let num = samples.len();
+        // nearest-rank percentile
+        let p95_rank = ((95 * num + 99) / 100).max(1);
+        Self {
+            min : samples[0],
+            median : samples[num / 2],
+            mean : samples.iter().sum::<u128>() / (num as u128),
+            p95 : samples[p95_rank - 1]
+        }
+    }
+
+}
+
+impl std::fmt::Display for TimingStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "min {:}μs, median {:}μs, mean {:}μs, p95 {:}μs", self.min, self.median, self.mean, self.p95)
     }
 }
 
-fn median(numbers: Vec<u128>) -> u128 {
-    let mut numbers = numbers;
-    numbers.sort();
-    let mid = numbers.len() / 2;
-    numbers[mid]
+pub struct NfaGenerationTimingStats {
+    pub nfa_operational : TimingStats,
+    pub nfa_kw_from_opera : TimingStats,
+    pub mindfa_from_opera : TimingStats,
+    pub nfa_compositional : TimingStats,
+    pub nfa_kw_from_compo : TimingStats,
+    pub mindfa_from_compo : TimingStats,
+}
+
+fn nfa_size(nfa : &AutNFA<usize>) -> (usize,usize) {
+    let num_transitions = nfa.transitions.iter()
+        .fold(0, |acc, x| acc + x.values().fold(0, |acc2, targets| acc2 + targets.len()));
+    (nfa.transitions.len(), num_transitions)
+}
+
+fn dfa_size(dfa : &AutDFA<usize>) -> (usize,usize) {
+    let num_transitions = dfa.transitions.iter().fold(0, |acc, x| acc + x.len());
+    (dfa.transitions.len(), num_transitions)
+}
+
+/// Checks that a repeated step always produced an automaton of the same size
+/// so that nondeterminism in the translation does not go unnoticed.
+/// A difference does not abort the experiment but is recorded in 'inconsistencies'
+/// (and flagged in the CSV row of the interaction).
+fn check_same_size(step : &str,
+                   previous : &mut Option<(usize,usize)>,
+                   got : (usize,usize),
+                   inconsistencies : &mut Vec<String>) {
+    match previous {
+        None => {
+            *previous = Some(got);
+        },
+        Some(prev) => {
+            if *prev != got {
+                let inconsistency = format!("{:} produced automata of different sizes (states,transitions) across repetitions : {:?} then {:?}", step, prev, got);
+                println!("{:}", inconsistency);
+                inconsistencies.push(inconsistency);
+            }
+        }
+    }
 }
 
 pub fn run_nfa_generation_experiment(int : Interaction,
                                      gen_ctx : GeneralContext,
-                                     num_tries_for_median : u32,
+                                     repetitions : usize,
                                      state_lim : usize) -> NfaGenerationExperimentResults {
 
     let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+    let mut size_inconsistencies = vec![];
 
+    let mut nfa_operational_size = None;
     let mut nfa_operational = AutNFA::new_void_object(hashset!{0});
     let mut nfa_operational_times = vec![];
-    for _ in 0..num_tries_for_median {
+    for _ in 0..repetitions {
         let (nfa,duration) = get_nfa_from_interaction_exploration(&gen_ctx,
                                                                   &int,
                                                                   alphabet.clone());
         let in_micros = duration.as_micros();
         println!("via exploration translated interaction into nfa of {:} states in {:}μs", nfa.transitions.len(), in_micros);
+        check_same_size("NFA via operational method", &mut nfa_operational_size, nfa_size(&nfa), &mut size_inconsistencies);
         nfa_operational = nfa;
         nfa_operational_times.push(in_micros);
     }
 
+    let mut nfa_kw_opera_size = None;
     let mut nfa_kw_opera = AutNFA::new_void_object(hashset!{0});
     let mut nfa_kw_opera_times = vec![];
     if nfa_operational.transitions.len() < state_lim {
-        for _ in 0..num_tries_for_median {
+        for _ in 0..repetitions {
             let now = Instant::now();
             nfa_kw_opera = nfa_operational.clone().minimize();
             let elapsed = now.elapsed();
+            check_same_size("minimized NFA after operational method", &mut nfa_kw_opera_size, nfa_size(&nfa_kw_opera), &mut size_inconsistencies);
             let in_micros = elapsed.as_micros();
             println!("minimized it into nfa of {:} states in {:}μs", nfa_kw_opera.transitions.len(), in_micros);
             nfa_kw_opera_times.push(in_micros);
@@ -96,37 +203,43 @@ pub fn run_nfa_generation_experiment(int : Interaction,
         nfa_kw_opera_times.push(0);
     }
 
+    let mut dfa_opera_size = None;
     let mut dfa_opera = AutDFA::new_void_object(hashset!{0});
     let mut mindfa_opera_times = vec![];
-    for _ in 0..num_tries_for_median {
+    for _ in 0..repetitions {
         let now = Instant::now();
         dfa_opera = nfa_operational.to_dfa().minimize();
         let elapsed = now.elapsed();
+        check_same_size("minimized DFA after operational method", &mut dfa_opera_size, dfa_size(&dfa_opera), &mut size_inconsistencies);
         let in_micros = elapsed.as_micros();
         println!("minimized it into dfa of {:} states in {:}μs", dfa_opera.transitions.len(), in_micros);
         mindfa_opera_times.push(in_micros);
     }
 
 
+    let mut nfa_compositional_size = None;
     let mut nfa_compositional = AutNFA::new_void_object(hashset!{0});
     let mut nfa_compositional_times = vec![];
-    for _ in 0..num_tries_for_median {
+    for _ in 0..repetitions {
         let (nfa,duration) = get_nfa_from_interaction_via_composition(&gen_ctx,
                                                                   &int,
                                                                   alphabet.clone());
         let in_micros = duration.as_micros();
         println!("via composition translated interaction into nfa of {:} states in {:}μs", nfa.transitions.len(), in_micros);
+        check_same_size("NFA via compositional method", &mut nfa_compositional_size, nfa_size(&nfa), &mut size_inconsistencies);
         nfa_compositional = nfa;
         nfa_compositional_times.push(in_micros);
     }
 
+    let mut nfa_kw_compo_size = None;
     let mut nfa_kw_compo = AutNFA::new_void_object(hashset!{0});
     let mut nfa_kw_compo_times = vec![];
     if nfa_compositional.transitions.len() < state_lim {
-        for _ in 0..num_tries_for_median {
+        for _ in 0..repetitions {
             let now = Instant::now();
             nfa_kw_compo = nfa_compositional.clone().minimize();
             let elapsed = now.elapsed();
+            check_same_size("minimized NFA after compositional method", &mut nfa_kw_compo_size, nfa_size(&nfa_kw_compo), &mut size_inconsistencies);
             let in_micros = elapsed.as_micros();
             println!("minimized it into nfa of {:} states in {:}μs", nfa_kw_compo.transitions.len(), in_micros);
             nfa_kw_compo_times.push(in_micros);
@@ -136,28 +249,199 @@ pub fn run_nfa_generation_experiment(int : Interaction,
         nfa_kw_compo_times.push(0);
     }
 
+    let mut dfa_compo_size = None;
     let mut dfa_compo = AutDFA::new_void_object(hashset!{0});
     let mut mindfa_compo_times = vec![];
-    for _ in 0..num_tries_for_median {
+    for _ in 0..repetitions {
         let now = Instant::now();
         dfa_compo = nfa_compositional.to_dfa().minimize();
         let elapsed = now.elapsed();
+        check_same_size("minimized DFA after compositional method", &mut dfa_compo_size, dfa_size(&dfa_compo), &mut size_inconsistencies);
         let in_micros = elapsed.as_micros();
         println!("minimized it into dfa of {:} states in {:}μs", dfa_compo.transitions.len(), in_micros);
         mindfa_compo_times.push(in_micros);
     }
 
+    let timing_stats = NfaGenerationTimingStats {
+        nfa_operational : TimingStats::from_samples(nfa_operational_times),
+        nfa_kw_from_opera : TimingStats::from_samples(nfa_kw_opera_times),
+        mindfa_from_opera : TimingStats::from_samples(mindfa_opera_times),
+        nfa_compositional : TimingStats::from_samples(nfa_compositional_times),
+        nfa_kw_from_compo : TimingStats::from_samples(nfa_kw_compo_times),
+        mindfa_from_compo : TimingStats::from_samples(mindfa_compo_times),
+    };
+
     NfaGenerationExperimentResults::new(int,
                                         nfa_operational,
-                                        median(nfa_operational_times),
+                                        timing_stats.nfa_operational.median,
                                         nfa_kw_opera,
-                                        median(nfa_kw_opera_times),
+                                        timing_stats.nfa_kw_from_opera.median,
                                         dfa_opera,
-                                        median(mindfa_opera_times),
+                                        timing_stats.mindfa_from_opera.median,
                                         nfa_compositional,
-                                        median(nfa_compositional_times),
+                                        timing_stats.nfa_compositional.median,
                                         nfa_kw_compo,
-                                        median(nfa_kw_compo_times),
+                                        timing_stats.nfa_kw_from_compo.median,
                                         dfa_compo,
-                                        median(mindfa_compo_times))
+                                        timing_stats.mindfa_from_compo.median,
+                                        timing_stats,
+                                        size_inconsistencies)
+}
+
+/// Results of the experiment on one of the files of a folder (see 'run_nfa_experiment_over_folder').
+pub struct NfaFolderExperimentResult {
+    /// name of the .hif file without its extension
+    pub file_name : String,
+    pub results : NfaGenerationExperimentResults,
+}
+
+/// Runs the experiment on each of the .hif files of 'folder'.
+/// The folder is read lazily, one file being parsed and translated only when the next result is required,
+/// so the files are visited in the order in which the file system lists them.
+/// Files which cannot be parsed are skipped (and logged) instead of aborting the whole run.
+pub fn run_nfa_experiment_over_folder<'a>(folder : &Path,
+                                          gen_ctx : &'a GeneralContext,
+                                          include_ctx : &'a HifIncludeContext,
+                                          repetitions : usize,
+                                          state_lim : usize) -> Result<impl Iterator<Item = NfaFolderExperimentResult> + 'a, String> {
+    let entries = match fs::read_dir(folder) {
+        Err(e) => {
+            return Err( format!("could not read folder '{:}' : {:}", folder.display(), e) );
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let iter = entries.filter_map(move |entry| {
+        let path = match entry {
+            Err(e) => {
+                println!("skipping unreadable folder entry : {:}", e);
+                return None;
+            },
+            Ok( got ) => {
+                got.path()
+            }
+        };
+        if !path.is_file() || path.extension().map_or(true, |ext| ext != HIBOU_INTERACTION_FILE_EXTENSION) {
+            return None;
+        }
+        let file_name = path.file_stem().unwrap().to_string_lossy().to_string();
+        match parse_hif_file_with(gen_ctx, &path.to_string_lossy(), include_ctx) {
+            Err(e) => {
+                println!("skipping file '{:}' : {:}", path.display(), e);
+                None
+            },
+            Ok( int ) => {
+                let results = run_nfa_generation_experiment(int, gen_ctx.clone(), repetitions, state_lim);
+                Some( NfaFolderExperimentResult{ file_name, results } )
+            }
+        }
+    });
+    Ok( iter )
+}
+
+/// Runs the experiment on each of the given .hif files, spread over 'jobs' worker threads.
+/// Each file is parsed and translated independently, with its own automata, and its result
+/// (or parsing error) is handed to 'on_result' together with the index of the file in 'paths'
+/// as soon as it is complete, hence not necessarily in the order of 'paths'.
+/// As in the sequential experiment, only the translation and minimization calls are timed.
+pub fn run_nfa_experiment_over_files_in_parallel<F>(paths : &[PathBuf],
+                                                    gen_ctx : &GeneralContext,
+                                                    include_ctx : &HifIncludeContext,
+                                                    repetitions : usize,
+                                                    state_lim : usize,
+                                                    jobs : usize,
+                                                    on_result : F)
+        where F : Fn(usize,Result<NfaFolderExperimentResult,HibouParsingError>) + Sync {
+    let num_done = AtomicUsize::new(0);
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build().unwrap();
+    pool.install(|| {
+        paths.par_iter().enumerate().for_each(|(idx,path)| {
+            let file_name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let got = parse_hif_file_with(gen_ctx, &path.to_string_lossy(), include_ctx).map(|int| {
+                let results = run_nfa_generation_experiment(int, gen_ctx.clone(), repetitions, state_lim);
+                NfaFolderExperimentResult{ file_name : file_name.clone(), results }
+            });
+            on_result(idx,got);
+            let done = num_done.fetch_add(1, Ordering::SeqCst) + 1;
+            println!("processed file '{:}' ({:} out of {:})", file_name, done, paths.len());
+        });
+    });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing_stats_test() {
+        let stats = TimingStats::from_samples(vec![5,1,3,2,4]);
+        assert_eq!(stats, TimingStats{min:1,median:3,mean:3,p95:5});
+        let single = TimingStats::from_samples(vec![7]);
+        assert_eq!(single, TimingStats{min:7,median:7,mean:7,p95:7});
+        let stats = TimingStats::from_samples((1..=100).collect());
+        assert_eq!(stats.p95, 95);
+    }
+
+    #[test]
+    fn check_same_size_test() {
+        let mut previous = None;
+        let mut inconsistencies = vec![];
+        check_same_size("step", &mut previous, (3,4), &mut inconsistencies);
+        check_same_size("step", &mut previous, (3,4), &mut inconsistencies);
+        assert!(inconsistencies.is_empty());
+        // a difference is recorded instead of aborting the experiment
+        check_same_size("step", &mut previous, (3,5), &mut inconsistencies);
+        assert_eq!(inconsistencies, vec!["step produced automata of different sizes (states,transitions) across repetitions : (3, 4) then (3, 5)".to_string()]);
+    }
+
+    #[test]
+    fn run_over_folder_test() {
+        let folder = std::env::temp_dir().join(format!("hibou_nfa_folder_test_{:}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("i1.hif"), "seq(a -- m1 -> b, b -- m1 ->|)").unwrap();
+        fs::write(folder.join("i2.hif"), "seq(a -- m1 ->").unwrap();
+        fs::write(folder.join("notes.txt"), "not an interaction").unwrap();
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("a".to_string());
+        gen_ctx.add_lf("b".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let results : Vec<NfaFolderExperimentResult> = run_nfa_experiment_over_folder(&folder, &gen_ctx, &HifIncludeContext::default(), 1, 20).unwrap().collect();
+        // the unparsable file is skipped and the other files are ignored
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_name, "i1");
+        assert_eq!(results[0].results.nfa_operational.transitions.len(), 4);
+        assert!(!results[0].results.get_timing_row("i1".to_string()).nondeterministic);
+        fs::remove_dir_all(&folder).unwrap();
+        assert!(run_nfa_experiment_over_folder(&folder, &gen_ctx, &HifIncludeContext::default(), 1, 20).is_err());
+    }
+
+    #[test]
+    fn run_over_files_in_parallel_test() {
+        let folder = std::env::temp_dir().join(format!("hibou_nfa_parallel_test_{:}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        let hifs = ["seq(a -- m1 -> b, b -- m1 ->|)", "a -- m1 ->|", "seq(a -- m1 ->", "par(a -- m1 ->|, b -- m1 ->|)"];
+        let mut paths = vec![];
+        for (x,hif) in hifs.iter().enumerate() {
+            let path = folder.join(format!("i{:}.hif",x));
+            fs::write(&path, hif).unwrap();
+            paths.push(path);
+        }
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("a".to_string());
+        gen_ctx.add_lf("b".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let collected = std::sync::Mutex::new(vec![]);
+        run_nfa_experiment_over_files_in_parallel(&paths, &gen_ctx, &HifIncludeContext::default(), 1, 20, 3, |idx,got| {
+            collected.lock().unwrap().push((idx,got.map(|result| (result.file_name,result.results.nfa_operational.transitions.len()))));
+        });
+        fs::remove_dir_all(&folder).unwrap();
+        let mut collected = collected.into_inner().unwrap();
+        collected.sort_by_key(|(idx,_)| *idx);
+        let sizes : Vec<Option<(String,usize)>> = collected.into_iter().map(|(_,got)| got.ok()).collect();
+        // each file gets the same result as in the sequential experiment
+        assert_eq!(sizes, vec![Some(("i0".to_string(),4)), Some(("i1".to_string(),2)), None, Some(("i3".to_string(),4))]);
+    }
+
 }
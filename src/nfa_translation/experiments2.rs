@@ -18,7 +18,10 @@ limitations under the License.
 use std::collections::{BTreeSet, HashSet};
 use std::fmt::{format, Formatter, write};
 use std::fs;
-use std::time::Instant;
+use std::sync::{mpsc, Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 use autour_core::dfa::dfa::AutDFA;
 use autour_core::nfa::nfa::AutNFA;
 use autour_core::traits::transform::AutTransformable;
@@ -39,33 +42,148 @@ use crate::experiments::parstrict_interaction_generation::generate_par_strict_in
 use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::canonize::canonize_interaction;
-use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
-use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::compositional::{get_nfa_from_interaction_via_composition_cancellable, NfaTranslationCacheStats};
+use crate::nfa_translation::get_nfa_from_logger::{get_nfa_from_interaction_exploration, get_nfa_from_interaction_exploration_with_budget, TranslationError};
+use crate::nfa_translation::timing_csv::NfaTimingCsvRow;
 use crate::process::canon::param::default::DefaultCanonizationProcess;
 
 
+/// Size of the DFA obtained by determinizing the NFA (without minimizing it).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DfaMeasure {
+    NotMeasured,
+    Measured(u32),
+    // the determinization was abandoned because it exceeded the timeout
+    TimedOut
+}
+
 pub struct NfaMetrics {
     pub median_time : u128,
     pub num_states : u32,
-    pub num_edges : u32
+    pub num_edges : u32,
+    // number of states and edges of the equivalent minimal DFA, if it was computed
+    pub minimized : Option<(u32,u32)>,
+    // number of states of the determinized NFA, if it was measured
+    pub dfa : DfaMeasure,
+    // whether the translation was abandoned because it exceeded the timeout
+    pub timed_out : bool,
+    // uses of the cache of sub-interactions, for the translations that have one
+    pub cache_stats : Option<NfaTranslationCacheStats>
 }
 
 impl NfaMetrics {
-    pub fn new(median_time: u128, num_states: u32, num_edges: u32) -> Self {
-        Self { median_time, num_states, num_edges }
+    pub fn new(median_time: u128, num_states: u32, num_edges: u32, minimized : Option<(u32,u32)>) -> Self {
+        Self { median_time, num_states, num_edges, minimized, dfa : DfaMeasure::NotMeasured, timed_out : false, cache_stats : None }
+    }
+    pub fn timed_out(minimize : bool, measure_dfa : bool) -> Self {
+        Self { median_time : 0,
+            num_states : 0,
+            num_edges : 0,
+            minimized : if minimize {Some((0,0))} else {None},
+            dfa : if measure_dfa {DfaMeasure::TimedOut} else {DfaMeasure::NotMeasured},
+            timed_out : true,
+            cache_stats : None }
     }
-    pub fn add_csv_title_line(nfa_name : &str, results : &mut String) {
+    /// Number of states of the DFA per state of the NFA.
+    pub fn get_dfa_blowup(&self) -> Option<f64> {
+        match self.dfa {
+            DfaMeasure::Measured(dfa_states) => {
+                Some((dfa_states as f64) / (self.num_states.max(1) as f64))
+            },
+            _ => {
+                None
+            }
+        }
+    }
+    pub fn add_csv_title_line(nfa_name : &str, with_minimized : bool, with_dfa : bool, results : &mut String) {
         results.push_str(&format!("{:}medtime,",nfa_name));
         results.push_str(&format!("{:}numstates,",nfa_name));
         results.push_str(&format!("{:}numedges,",nfa_name));
+        if with_minimized {
+            results.push_str(&format!("{:}minnumstates,",nfa_name));
+            results.push_str(&format!("{:}minnumedges,",nfa_name));
+        }
+        if with_dfa {
+            results.push_str(&format!("{:}dfanumstates,",nfa_name));
+            results.push_str(&format!("{:}dfablowup,",nfa_name));
+        }
     }
     pub fn add_csv_line(&self, results : &mut String) {
+        if self.timed_out {
+            let mut num_columns = if self.minimized.is_some() {5} else {3};
+            if self.dfa != DfaMeasure::NotMeasured {
+                num_columns += 2;
+            }
+            for _ in 0..num_columns {
+                results.push_str("TIMEOUT,");
+            }
+            return;
+        }
         results.push_str(&self.median_time.to_string());
         results.push_str(",");
         results.push_str(&self.num_states.to_string());
         results.push_str(",");
         results.push_str(&self.num_edges.to_string());
         results.push_str(",");
+        if let Some((min_states,min_edges)) = self.minimized {
+            results.push_str(&min_states.to_string());
+            results.push_str(",");
+            results.push_str(&min_edges.to_string());
+            results.push_str(",");
+        }
+        match self.dfa {
+            DfaMeasure::NotMeasured => {},
+            DfaMeasure::Measured(dfa_states) => {
+                results.push_str(&format!("{:},{:.3},", dfa_states, self.get_dfa_blowup().unwrap()));
+            },
+            DfaMeasure::TimedOut => {
+                results.push_str("TIMEOUT,TIMEOUT,");
+            }
+        }
+    }
+}
+
+
+/// Determinizes and minimizes the NFA, the resulting DFA accepts the same traces.
+pub fn minimize_nfa(nfa : &AutNFA<usize>) -> AutDFA<usize> {
+    nfa.to_dfa().minimize()
+}
+
+fn get_minimized_size(nfa : &AutNFA<usize>) -> (u32,u32) {
+    let dfa = minimize_nfa(nfa);
+    let num_edges = dfa.transitions.iter().fold(0, |x,t| x + (t.len() as u32));
+    println!("minimized it into dfa of {:} states", dfa.transitions.len());
+    (dfa.transitions.len() as u32, num_edges)
+}
+
+/// Determinizes the NFA via the subset construction on a worker thread (as 'translate_with_timeout')
+/// and measures the number of states of the resulting DFA, giving up if it takes longer than 'timeout'.
+/// The subset construction (from autour_core) cannot be cancelled hence an abandoned worker is detached
+/// and only releases its copy of the NFA once it terminates.
+fn get_determinized_size(nfa : &AutNFA<usize>, timeout : Option<Duration>) -> DfaMeasure {
+    let measure = match timeout {
+        None => {
+            Some(nfa.to_dfa().transitions.len() as u32)
+        },
+        Some( timeout ) => {
+            let (sender,receiver) = mpsc::channel();
+            let nfa = nfa.clone();
+            thread::spawn(move || {
+                // the receiver is gone if the determinization timed out
+                let _ = sender.send(nfa.to_dfa().transitions.len() as u32);
+            });
+            receiver.recv_timeout(timeout).ok()
+        }
+    };
+    match measure {
+        None => {
+            println!("determinization of nfa timed out");
+            DfaMeasure::TimedOut
+        },
+        Some( dfa_states ) => {
+            println!("determinized it into dfa of {:} states", dfa_states);
+            DfaMeasure::Measured(dfa_states)
+        }
     }
 }
 
@@ -121,12 +239,13 @@ impl NfaGenerationExperiment2ResultMetrics {
         Self { name, kind, interaction_metrics, nfa_operational, nfa_compositional }
     }
 
-    pub fn add_csv_title_line(results : &mut String) {
+    pub fn add_csv_title_line(with_minimized : bool, with_dfa : bool, results : &mut String) {
         results.push_str("name,");
         results.push_str("kind,");
         InteractionMetrics::add_csv_title_line(results);
-        NfaMetrics::add_csv_title_line("operat",results);
-        NfaMetrics::add_csv_title_line("compo",results);
+        NfaMetrics::add_csv_title_line("operat",with_minimized,with_dfa,results);
+        NfaMetrics::add_csv_title_line("compo",with_minimized,with_dfa,results);
+        results.push_str("compocachehits,compocachemisses,");
     }
 
     pub fn add_csv_line(&self, results : &mut String) {
@@ -136,6 +255,29 @@ impl NfaGenerationExperiment2ResultMetrics {
         self.interaction_metrics.add_csv_line(results);
         self.nfa_operational.add_csv_line(results);
         self.nfa_compositional.add_csv_line(results);
+        match self.nfa_compositional.cache_stats {
+            None => {
+                results.push_str("TIMEOUT,TIMEOUT,");
+            },
+            Some( stats ) => {
+                results.push_str(&format!("{:},{:},", stats.hits, stats.misses));
+            }
+        }
+    }
+
+    /// Row recording the translation via the operational method.
+    pub fn get_timing_row(&self, alphabet_size : usize) -> NfaTimingCsvRow {
+        if self.nfa_operational.timed_out {
+            return NfaTimingCsvRow::timed_out(self.name.clone(),
+                                              self.interaction_metrics.get_num_symbols(),
+                                              alphabet_size);
+        }
+        NfaTimingCsvRow::new(self.name.clone(),
+                             self.interaction_metrics.get_num_symbols(),
+                             self.nfa_operational.median_time,
+                             self.nfa_operational.num_states,
+                             self.nfa_operational.num_edges,
+                             alphabet_size)
     }
 }
 
@@ -149,34 +291,136 @@ fn median(numbers: Vec<u128>) -> u128 {
 
 
 
+/// Signature shared by the translations of interactions into NFAs, which abort once the flag is set.
+type NfaTranslation = fn(&GeneralContext,&Interaction,Vec<BTreeSet<TraceAction>>,Arc<AtomicBool>) -> Result<(AutNFA<usize>,Duration,Option<NfaTranslationCacheStats>),TranslationError>;
+
+fn translate_via_exploration(gen_ctx : &GeneralContext,
+                             i : &Interaction,
+                             alphabet : Vec<BTreeSet<TraceAction>>,
+                             cancel : Arc<AtomicBool>) -> Result<(AutNFA<usize>,Duration,Option<NfaTranslationCacheStats>),TranslationError> {
+    let (nfa,duration) = get_nfa_from_interaction_exploration_with_budget(gen_ctx,i,alphabet,None,Some(cancel))?;
+    Ok( (nfa,duration,None) )
+}
+
+fn translate_via_composition(gen_ctx : &GeneralContext,
+                             i : &Interaction,
+                             alphabet : Vec<BTreeSet<TraceAction>>,
+                             cancel : Arc<AtomicBool>) -> Result<(AutNFA<usize>,Duration,Option<NfaTranslationCacheStats>),TranslationError> {
+    let (nfa,duration,stats) = get_nfa_from_interaction_via_composition_cancellable(gen_ctx,i,alphabet,Some(cancel))?;
+    Ok( (nfa,duration,Some(stats)) )
+}
+
+/// Runs the translation on a worker thread and gives up on it if it takes longer than 'timeout'.
+/// The worker only owns copies of its inputs so that an abandoned translation holds no reference
+/// to the caller's data, and, on a timeout, its cancellation flag is set so that it stops
+/// exploring (resp. composing) and releases everything it allocated.
+fn translate_with_timeout(translation : NfaTranslation,
+                          gen_ctx : &GeneralContext,
+                          i : &Interaction,
+                          alphabet : &Vec<BTreeSet<TraceAction>>,
+                          timeout : Option<Duration>) -> Option<(AutNFA<usize>,Duration,Option<NfaTranslationCacheStats>)> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    match timeout {
+        None => {
+            translation(gen_ctx,i,alphabet.clone(),cancel).ok()
+        },
+        Some( timeout ) => {
+            let (sender,receiver) = mpsc::channel();
+            let gen_ctx = gen_ctx.clone();
+            let i = i.clone();
+            let alphabet = alphabet.clone();
+            let worker_cancel = cancel.clone();
+            thread::spawn(move || {
+                // the receiver is gone if the translation timed out
+                if let Ok(got) = translation(&gen_ctx,&i,alphabet,worker_cancel) {
+                    let _ = sender.send(got);
+                }
+            });
+            let got = receiver.recv_timeout(timeout).ok();
+            if got.is_none() {
+                cancel.store(true, Ordering::Relaxed);
+            }
+            got
+        }
+    }
+}
+
+fn get_nfa_size(nfa : &AutNFA<usize>) -> (u32,u32) {
+    let num_edges = nfa.transitions.iter()
+        .fold(0,|x,t|
+            x + t.iter().fold(0,|y,(_,c)| y + (c.len() as u32))
+        );
+    (nfa.transitions.len() as u32, num_edges)
+}
+
+/// Translates the interaction 'num_tries_for_median' times and measures the median time.
+/// A translation exceeding the timeout yields 'NfaMetrics::timed_out'
+/// while a determinization exceeding it only yields 'DfaMeasure::TimedOut'.
+/// The minimization is excluded from the timeout : as it cannot be cancelled, it is always carried out to the end.
+fn get_translation_metrics(translation : NfaTranslation,
+                           method_name : &str,
+                           gen_ctx : &GeneralContext,
+                           i : &Interaction,
+                           alphabet : &Vec<BTreeSet<TraceAction>>,
+                           num_tries_for_median : u32,
+                           minimize : bool,
+                           measure_dfa : bool,
+                           timeout : Option<Duration>) -> NfaMetrics {
+    let mut got_nfa = AutNFA::new_void_object(hashset!{0});
+    let mut times = vec![];
+    let mut cache_stats = None;
+    for _ in 0..num_tries_for_median {
+        match translate_with_timeout(translation,gen_ctx,i,alphabet,timeout) {
+            None => {
+                println!("via {:} translation of interaction timed out", method_name);
+                return NfaMetrics::timed_out(minimize,measure_dfa);
+            },
+            Some( (nfa,duration,stats) ) => {
+                let in_micros = duration.as_micros();
+                println!("via {:} translated interaction into nfa of {:} states in {:}μs", method_name, nfa.transitions.len(), in_micros);
+                if let Some(stats) = &stats {
+                    println!("with {:} cache hits and {:} cache misses", stats.hits, stats.misses);
+                }
+                got_nfa = nfa;
+                cache_stats = stats;
+                times.push(in_micros);
+            }
+        }
+    }
+    let (num_states,num_edges) = get_nfa_size(&got_nfa);
+    let mut metrics = NfaMetrics::new(
+        median(times),
+        num_states,
+        num_edges,
+        if minimize {Some(get_minimized_size(&got_nfa))} else {None}
+    );
+    if measure_dfa {
+        metrics.dfa = get_determinized_size(&got_nfa,timeout);
+    }
+    metrics.cache_stats = cache_stats;
+    metrics
+}
+
 pub fn get_nfa_metrics(gen_ctx : &GeneralContext,
                        i : &Interaction,
                        alphabet : &Vec<BTreeSet<TraceAction>>,
                        num_tries_for_median : u32,
-                       stop_if_opnfa_more_than : Option<u32>) -> Option<(NfaMetrics,NfaMetrics)> {
-    let mut nfa_operational = AutNFA::new_void_object(hashset!{0});
-    let mut nfa_operational_times = vec![];
-    for _ in 0..num_tries_for_median {
-        let (nfa,duration) = get_nfa_from_interaction_exploration(&gen_ctx,
-                                                                  &i,
-                                                                  alphabet.clone());
-        let in_micros = duration.as_micros();
-        println!("via exploration translated interaction into nfa of {:} states in {:}μs", nfa.transitions.len(), in_micros);
-        nfa_operational = nfa;
-        nfa_operational_times.push(in_micros);
-    }
-
-    let opmetrics = NfaMetrics::new(
-        median(nfa_operational_times),
-        nfa_operational.transitions.len() as u32,
-        nfa_operational.transitions.iter()
-            .fold(0,|x,t|
-                x + t.iter().fold(0,|y,(_,c)| y + (c.len() as u32))
-            )
-    );
+                       stop_if_opnfa_more_than : Option<u32>,
+                       minimize : bool,
+                       measure_dfa : bool,
+                       timeout : Option<Duration>) -> Option<(NfaMetrics,NfaMetrics)> {
+    let opmetrics = get_translation_metrics(translate_via_exploration,
+                                            "exploration",
+                                            gen_ctx,
+                                            i,
+                                            alphabet,
+                                            num_tries_for_median,
+                                            minimize,
+                                            measure_dfa,
+                                            timeout);
 
     if let Some(opnfa_limit) = stop_if_opnfa_more_than {
-        if opmetrics.num_states > opnfa_limit {
+        if !opmetrics.timed_out && opmetrics.num_states > opnfa_limit {
             println!("explo NFA has {:} states, more than {:} states, cancel costly computation of compo nfa",
                      opmetrics.num_states,
                      opnfa_limit);
@@ -184,26 +428,15 @@ pub fn get_nfa_metrics(gen_ctx : &GeneralContext,
         }
     }
 
-    let mut nfa_compositional = AutNFA::new_void_object(hashset!{0});
-    let mut nfa_compositional_times = vec![];
-    for _ in 0..num_tries_for_median {
-        let (nfa,duration) = get_nfa_from_interaction_via_composition(&gen_ctx,
-                                                                      &i,
-                                                                      alphabet.clone());
-        let in_micros = duration.as_micros();
-        println!("via composition translated interaction into nfa of {:} states in {:}μs", nfa.transitions.len(), in_micros);
-        nfa_compositional = nfa;
-        nfa_compositional_times.push(in_micros);
-    }
-
-    let cmpmetrics = NfaMetrics::new(
-        median(nfa_compositional_times),
-        nfa_compositional.transitions.len() as u32,
-        nfa_compositional.transitions.iter()
-            .fold(0,|x,t|
-                x + t.iter().fold(0,|y,(_,c)| y + (c.len() as u32))
-            )
-    );
+    let cmpmetrics = get_translation_metrics(translate_via_composition,
+                                             "composition",
+                                             gen_ctx,
+                                             i,
+                                             alphabet,
+                                             num_tries_for_median,
+                                             minimize,
+                                             measure_dfa,
+                                             timeout);
 
     return Some((opmetrics,cmpmetrics));
 }
@@ -231,11 +464,15 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                                       gen_depth : u32,
                                       max_symbols : u32,
                                       max_par : u32,
-                                      seed : u64) -> String {
+                                      seed : u64,
+                                      minimize : bool,
+                                      measure_dfa : bool,
+                                      timeout : Option<Duration>,
+                                      timing_rows : &mut Vec<NfaTimingCsvRow>) -> String {
 
 
     let mut csv_results = String::new();
-    NfaGenerationExperiment2ResultMetrics::add_csv_title_line(&mut csv_results);
+    NfaGenerationExperiment2ResultMetrics::add_csv_title_line(minimize, measure_dfa, &mut csv_results);
     csv_results.push_str("\n");
 
 
@@ -267,7 +504,10 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                         &i,
                         &default_alphabet,
                         num_tries_for_median,
-                        None
+                        None,
+                        minimize,
+                        measure_dfa,
+                        timeout
                     ).unwrap();
                     let metrics = NfaGenerationExperiment2ResultMetrics::new(
                         format!("par{:}act{:}",p,a),
@@ -278,6 +518,7 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                     );
                     metrics.add_csv_line(&mut csv_results);
                     csv_results.push_str("\n");
+                    timing_rows.push(metrics.get_timing_row(default_alphabet.len()));
                 }
             }
         }
@@ -293,7 +534,10 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                     &i,
                     &default_alphabet,
                     num_tries_for_median,
-                    None
+                    None,
+                    minimize,
+                    measure_dfa,
+                    timeout
                 ).unwrap();
                 let kind = match *imetrics.symbols.get(&SymbolKind::Par).unwrap() {
                     0 => GeneratedInteractionKind::LoopAltNoPar,
@@ -308,6 +552,7 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                 );
                 metrics.add_csv_line(&mut csv_results);
                 csv_results.push_str("\n");
+                timing_rows.push(metrics.get_timing_row(default_alphabet.len()));
             }
         }
 
@@ -343,10 +588,14 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                     max_symbols,
                     max_par,
                     num_tries_for_median,
+                    minimize,
+                    measure_dfa,
+                    timeout,
                     x) {
 
                 metrics.add_csv_line(&mut csv_results);
                 csv_results.push_str("\n");
+                timing_rows.push(metrics.get_timing_row(alphabet.len()));
                 x += 1;
             }
         }
@@ -396,7 +645,10 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                                     &i,
                                     &alphabet,
                                     num_tries_for_median,
-                                    Some(5000)) {
+                                    Some(5000),
+                                    minimize,
+                                    measure_dfa,
+                                    timeout) {
                                     let kind = match *imetrics.symbols.get(&SymbolKind::Par).unwrap() {
                                         0 => GeneratedInteractionKind::DoorsNoPar,
                                         _ => GeneratedInteractionKind::Doors
@@ -410,6 +662,7 @@ pub fn run_nfa_generation_experiment2(number_of_interactions : u32,
                                     );
                                     metrics.add_csv_line(&mut csv_results);
                                     csv_results.push_str("\n");
+                                    timing_rows.push(metrics.get_timing_row(alphabet.len()));
                                 }
                             } else {
                                 if !cond1 {
@@ -467,6 +720,9 @@ fn get_metrics_from_random_interaction(gen_ctx : &GeneralContext,
                                        max_symbols : u32,
                                        max_par : u32,
                                        num_tries_for_median : u32,
+                                       minimize : bool,
+                                       measure_dfa : bool,
+                                       timeout : Option<Duration>,
                                        x : u32) -> Option<NfaGenerationExperiment2ResultMetrics> {
 
     let imetrics = InteractionMetrics::extract_from_interaction(&i);
@@ -478,12 +734,13 @@ fn get_metrics_from_random_interaction(gen_ctx : &GeneralContext,
     draw_interaction(&gen_ctx,
                      &i,
                      &InteractionGraphicalRepresentation::AsSequenceDiagram,
+                     &None,
                      &"temp".to_string(),
                      &"random_gen".to_string(),
                      &format!("nfagenexp_i{:}",x));
 
 
-    match get_nfa_metrics(gen_ctx,&i,&alphabet,num_tries_for_median,Some(10000)) {
+    match get_nfa_metrics(gen_ctx,&i,&alphabet,num_tries_for_median,Some(10000),minimize,measure_dfa,timeout) {
         None => {
             return None;
         },
@@ -505,3 +762,112 @@ fn get_metrics_from_random_interaction(gen_ctx : &GeneralContext,
 
 
 }
+
+
+#[cfg(test)]
+mod tests {
+    use autour_core::traits::characterize::AutCharacterizable;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction, EmissionTargetRef};
+    use crate::core::language::syntax::interaction::LoopKind;
+    use super::*;
+
+    #[test]
+    fn minimize_nfa_preserves_traces_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_lf("l2".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        let m1 = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+        );
+        let m2 = Interaction::Emission(
+            EmissionAction::new(1,1,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(0)])
+        );
+        let i = Interaction::Seq(
+            Box::new(m1.clone()),
+            Box::new(Interaction::Loop(LoopKind::SStrictSeq,Box::new(Interaction::Alt(Box::new(m1),Box::new(m2)))))
+        );
+        // the full alphabet includes letters which do not occur in the interaction
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet);
+        let dfa = minimize_nfa(&nfa);
+        assert!(dfa.to_nfa().equals(&nfa));
+        assert!(dfa.transitions.len() <= nfa.transitions.len());
+    }
+
+    /// number of slow translations which stopped on their cancellation flag
+    static NUM_CANCELLED_TRANSLATIONS : std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+    fn slow_translation(gen_ctx : &GeneralContext,
+                        i : &Interaction,
+                        alphabet : Vec<BTreeSet<TraceAction>>,
+                        cancel : Arc<AtomicBool>) -> Result<(AutNFA<usize>,Duration,Option<NfaTranslationCacheStats>),TranslationError> {
+        for _ in 0..200 {
+            if cancel.load(Ordering::Relaxed) {
+                NUM_CANCELLED_TRANSLATIONS.fetch_add(1, Ordering::Relaxed);
+                return Err( TranslationError::Cancelled );
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        translate_via_exploration(gen_ctx,i,alphabet,cancel)
+    }
+
+    #[test]
+    fn translation_timeout_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let i = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let timeout = Some(Duration::from_millis(100));
+        assert!(translate_with_timeout(slow_translation,&gen_ctx,&i,&alphabet,timeout).is_none());
+        // the abandoned translation stops on its own instead of running to completion
+        let mut num_waits = 0;
+        while NUM_CANCELLED_TRANSLATIONS.load(Ordering::Relaxed) == 0 && num_waits < 100 {
+            thread::sleep(Duration::from_millis(10));
+            num_waits += 1;
+        }
+        assert_eq!(NUM_CANCELLED_TRANSLATIONS.load(Ordering::Relaxed), 1);
+        assert!(translate_with_timeout(translate_via_composition,&gen_ctx,&i,&alphabet,Some(Duration::from_secs(60))).is_some());
+        // both translations abort once their flag is set
+        let cancelled = Arc::new(AtomicBool::new(true));
+        assert_eq!(translate_via_exploration(&gen_ctx,&i,alphabet.clone(),cancelled.clone()).err(), Some(TranslationError::Cancelled));
+        assert_eq!(translate_via_composition(&gen_ctx,&i,alphabet.clone(),cancelled).err(), Some(TranslationError::Cancelled));
+        let metrics = get_translation_metrics(slow_translation,"slow",&gen_ctx,&i,&alphabet,1,true,false,timeout);
+        let mut line = String::new();
+        metrics.add_csv_line(&mut line);
+        assert_eq!(line, "TIMEOUT,TIMEOUT,TIMEOUT,TIMEOUT,TIMEOUT,");
+    }
+
+    #[test]
+    fn dfa_measure_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        gen_ctx.add_msg("m2".to_string());
+        let m1 = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let m2 = Interaction::Emission(
+            EmissionAction::new(0,1,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let i = Interaction::Alt(Box::new(m1.clone()),Box::new(Interaction::Strict(Box::new(m1),Box::new(m2))));
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+        let metrics = get_translation_metrics(translate_via_exploration,"exploration",&gen_ctx,&i,&alphabet,1,false,true,Some(Duration::from_secs(60)));
+        assert_eq!(metrics.dfa, DfaMeasure::Measured(nfa.to_dfa().transitions.len() as u32));
+        // the determinization columns follow the size of the NFA
+        let mut metrics = NfaMetrics::new(10,4,5,None);
+        metrics.dfa = DfaMeasure::Measured(6);
+        let mut line = String::new();
+        metrics.add_csv_line(&mut line);
+        assert_eq!(line, "10,4,5,6,1.500,");
+        let mut line = String::new();
+        NfaMetrics::timed_out(false,true).add_csv_line(&mut line);
+        assert_eq!(line, "TIMEOUT,TIMEOUT,TIMEOUT,TIMEOUT,TIMEOUT,");
+    }
+
+}
@@ -18,11 +18,14 @@ limitations under the License.
 
 
 use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use autour_core::nfa::nfa::AutNFA;
 use autour_core::traits::translate::AutTranslatable;
 use graph_process_manager_core::delegate::delegate::GenericProcessDelegate;
 use graph_process_manager_core::delegate::priorities::GenericProcessPriorities;
+use graph_process_manager_core::handler::filter::AbstractFilter;
 use graph_process_manager_core::manager::manager::GenericProcessManager;
 use graph_process_manager_core::queued_steps::queue::strategy::QueueSearchStrategy;
 use graph_process_manager_loggers::nfait::logger::GenericNFAITLogger;
@@ -31,17 +34,52 @@ use crate::core::general_context::GeneralContext;
 use crate::core::language::syntax::interaction::Interaction;
 use crate::process::explo::conf::ExplorationConfig;
 use crate::process::explo::context::{ExplorationContext, ExplorationParameterization};
-use crate::process::explo::filter::filter::ExplorationFilter;
+use crate::process::explo::filter::elim::ExplorationFilterEliminationKind;
+use crate::process::explo::filter::filter::{ExplorationFilter, ExplorationFilterCriterion};
 use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
 use crate::process::explo::node::ExplorationNodeKind;
 use crate::process::explo::priorities::ExplorationPriorities;
 use crate::process::explo::step::ExplorationStepKind;
 
 
+#[derive(Clone, PartialEq, Debug)]
+pub enum TranslationError {
+    /// the number of states built before aborting, which exceeds the budget
+    BudgetExceeded(usize),
+    /// the cancellation flag was set during the translation
+    Cancelled
+}
+
+impl std::fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranslationError::BudgetExceeded(num_states) => {
+                write!(f, "translation aborted after building {:} states, exceeding the state budget", num_states)
+            },
+            TranslationError::Cancelled => {
+                write!(f, "translation cancelled")
+            }
+        }
+    }
+}
+
 pub fn get_nfa_from_interaction_exploration(gen_ctx : &GeneralContext,
                                             int : &Interaction,
                                             base_alphabet : Vec<BTreeSet<TraceAction>>)
             -> (AutNFA<usize>,Duration) {
+    get_nfa_from_interaction_exploration_with_budget(gen_ctx,int,base_alphabet,None,None).unwrap()
+}
+
+/// Each node of the exploration is a state of the NFA (every step executes actions and nodes are memoized)
+/// hence, with a 'state_budget', the exploration is stopped as soon as it reaches one more node than the budget
+/// and the translation aborts instead of building the whole NFA.
+/// Likewise, once the 'cancel' flag is set (e.g. from another thread), no new node is explored and the translation aborts.
+pub fn get_nfa_from_interaction_exploration_with_budget(gen_ctx : &GeneralContext,
+                                                        int : &Interaction,
+                                                        base_alphabet : Vec<BTreeSet<TraceAction>>,
+                                                        state_budget : Option<usize>,
+                                                        cancel : Option<Arc<AtomicBool>>)
+            -> Result<(AutNFA<usize>,Duration),TranslationError> {
 
     let usize_alphabet : HashSet<usize> = (0..base_alphabet.len()).collect();
     let max_loop_depth = int.total_loop_num()*2;
@@ -51,6 +89,14 @@ pub fn get_nfa_from_interaction_exploration(gen_ctx : &GeneralContext,
                                              "nfa".to_string(),
                                              None,
                                              ".".to_string());
+    let mut filters : Vec<Box<dyn AbstractFilter<ExplorationFilterCriterion,ExplorationFilterEliminationKind>>> =
+        vec![Box::new(ExplorationFilter::MaxLoopInstanciation(max_loop_depth))];
+    if let Some(budget) = state_budget {
+        filters.push(Box::new(ExplorationFilter::MaxNodeNumber((budget + 1) as u32)));
+    }
+    if let Some(cancelled) = &cancel {
+        filters.push(Box::new(ExplorationFilter::Cancellation(cancelled.clone())));
+    }
     let explo_ctx = ExplorationContext::new(gen_ctx.clone());
     let delegate : GenericProcessDelegate<ExplorationStepKind,ExplorationNodeKind,ExplorationPriorities> =
         GenericProcessDelegate::new(QueueSearchStrategy::BFS,
@@ -60,7 +106,7 @@ pub fn get_nfa_from_interaction_exploration(gen_ctx : &GeneralContext,
         GenericProcessManager::new(explo_ctx,
                                    ExplorationParameterization{},
                                    delegate,
-                                   vec![Box::new(ExplorationFilter::MaxLoopInstanciation(max_loop_depth))],
+                                   filters,
                                    vec![Box::new(nfa_logger)],
                                    None,
                                    true);
@@ -73,6 +119,15 @@ pub fn get_nfa_from_interaction_exploration(gen_ctx : &GeneralContext,
     let (node_count,_) = exploration_manager.start_process(init_node);
     let elapsed_get_nfa = now.elapsed();
     // ***
+    if let Some(budget) = state_budget {
+        if node_count as usize > budget {
+            return Err( TranslationError::BudgetExceeded(node_count as usize) );
+        }
+    }
+    if cancel.map_or(false, |cancelled| cancelled.load(Ordering::Relaxed)) {
+        return Err( TranslationError::Cancelled );
+    }
+    // ***
     let raw_logger = exploration_manager.get_logger(0).unwrap();
     let nfa_logger : &GenericNFAITLogger<ExplorationConfig,usize,ActionNFAITPrinter> =
         raw_logger.as_any().downcast_ref::<GenericNFAITLogger<ExplorationConfig,usize,ActionNFAITPrinter>>().unwrap();
@@ -82,5 +137,42 @@ pub fn get_nfa_from_interaction_exploration(gen_ctx : &GeneralContext,
     // ***
     nfa.alphabet = usize_alphabet;
     // ***
-    return (nfa, elapsed_get_nfa);
-}
\ No newline at end of file
+    return Ok( (nfa, elapsed_get_nfa) );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use super::*;
+
+    #[test]
+    fn state_budget_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        for as_str in ["o", "a -- m -> b", "par(a -- m ->|, b -- n ->|, a -- n ->|)",
+                       "loopS(seq(a -- m -> b, b -- n -> a))", "alt(par(a -- m -> b, b -- n ->|), loopW(a -- n ->|))"] {
+            let i = parse_hif_string(&gen_ctx,as_str.to_string()).unwrap();
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+            let num_states = nfa.transitions.len();
+            // within the budget, the NFA is the same as without one
+            for budget in [num_states, num_states + 1] {
+                let (budgeted,_) = get_nfa_from_interaction_exploration_with_budget(&gen_ctx,&i,alphabet.clone(),Some(budget),None).unwrap();
+                assert_eq!(budgeted.transitions, nfa.transitions);
+                assert_eq!(budgeted.finals, nfa.finals);
+            }
+            // otherwise the translation aborts just after exceeding it
+            for budget in 0..num_states {
+                match get_nfa_from_interaction_exploration_with_budget(&gen_ctx,&i,alphabet.clone(),Some(budget),None) {
+                    Err(TranslationError::BudgetExceeded(built)) => {
+                        assert_eq!(built, budget + 1);
+                    },
+                    Ok(_) | Err(TranslationError::Cancelled) => {
+                        panic!("'{}' has {} states, exceeding budget {}", as_str, num_states, budget);
+                    }
+                }
+            }
+        }
+    }
+}
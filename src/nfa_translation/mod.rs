@@ -20,4 +20,11 @@ pub mod compositional;
 pub mod experiments;
 pub mod canonize;
 pub mod experiments2;
-
+pub mod regex;
+pub mod timing_csv;
+pub mod count;
+pub mod equiv;
+pub mod equiv_cache;
+pub mod shortest;
+pub mod tagged_union;
+pub mod prefix;
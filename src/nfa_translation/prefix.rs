@@ -0,0 +1,103 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::HashSet;
+use autour_core::nfa::nfa::AutNFA;
+
+
+/// Returns the NFA accepting the prefixes of the words accepted by 'nfa' :
+/// every state from which a final state can be reached becomes final.
+/// Hence the initial states are final (the empty word being a prefix of any accepted word)
+/// unless the language of 'nfa' is empty, in which case so is its prefix-closure.
+pub fn get_prefix_closed_nfa(nfa : &AutNFA<usize>) -> AutNFA<usize> {
+    let mut predecessors : Vec<Vec<usize>> = vec![vec![];nfa.transitions.len()];
+    for (origin,transitions) in nfa.transitions.iter().enumerate() {
+        for targets in transitions.values() {
+            for target in targets {
+                predecessors[*target].push(origin);
+            }
+        }
+    }
+    let mut coreachable : HashSet<usize> = nfa.finals.clone();
+    let mut to_visit : Vec<usize> = nfa.finals.iter().cloned().collect();
+    while let Some(state) = to_visit.pop() {
+        for prev in &predecessors[state] {
+            if coreachable.insert(*prev) {
+                to_visit.push(*prev);
+            }
+        }
+    }
+    let mut prefix_nfa = nfa.clone();
+    prefix_nfa.finals = coreachable;
+    prefix_nfa
+}
+
+
+#[cfg(test)]
+mod tests {
+    use autour_core::traits::run::AutRunnable;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::count::{count_nfa_traces, TraceLanguageCardinality};
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    /// Words of length at most 'max_length' over the letters of the NFA.
+    fn get_words(letters : &[usize], max_length : usize) -> Vec<Vec<usize>> {
+        let mut words = vec![vec![]];
+        let mut last = vec![vec![]];
+        for _ in 0..max_length {
+            last = last.iter().flat_map(|word : &Vec<usize>| letters.iter().map(move |letter| {
+                let mut longer = word.clone();
+                longer.push(*letter);
+                longer
+            })).collect();
+            words.extend(last.iter().cloned());
+        }
+        words
+    }
+
+    #[test]
+    fn prefix_closure_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        for as_str in ["o", "seq(a -- m -> b, b -- n -> a)", "alt(a -- m ->|, par(b -- n ->|, b -- m ->|))", "strict(loopS(a -- m ->|), b -- n ->|)"] {
+            let i = parse_hif_string(&gen_ctx,as_str.to_string()).unwrap();
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+            let prefix_nfa = get_prefix_closed_nfa(&nfa);
+            assert_eq!(prefix_nfa.transitions, nfa.transitions);
+            // the empty trace is always a valid prefix
+            assert!(prefix_nfa.initials.is_subset(&prefix_nfa.finals));
+            assert!(prefix_nfa.runs_trace(&[]).unwrap());
+            let letters : Vec<usize> = nfa.transitions.iter().flat_map(|x| x.keys().cloned()).collect::<HashSet<usize>>().into_iter().collect();
+            let words = get_words(&letters, 4);
+            // a word is accepted by the prefix-closure iff it can be extended into an accepted word
+            // (all the accepted words of these interactions being extensible within a single action)
+            for word in words.iter().filter(|word| word.len() < 4) {
+                let is_prefix = words.iter().any(|longer| longer.starts_with(word) && nfa.runs_trace(longer).unwrap());
+                assert_eq!(prefix_nfa.runs_trace(word).unwrap(), is_prefix, "{} : {:?}", as_str, word);
+            }
+        }
+        // 'a!m.b!n' has the prefixes '', 'a!m' and 'a!m.b!n'
+        let i = parse_hif_string(&gen_ctx,"strict(a -- m ->|, b -- n ->|)".to_string()).unwrap();
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&i,alphabet.clone());
+        assert_eq!(count_nfa_traces(&nfa), TraceLanguageCardinality::Finite(1));
+        assert_eq!(count_nfa_traces(&get_prefix_closed_nfa(&nfa)), TraceLanguageCardinality::Finite(3));
+    }
+}
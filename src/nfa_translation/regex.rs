@@ -0,0 +1,159 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::collections::HashMap;
+use autour_core::bre::bre::ExpBRE;
+use autour_core::bre::term::TermBRE;
+use autour_core::nfa::nfa::AutNFA;
+use autour_core::traits::access::AutAccessible;
+use autour_core::traits::characterize::AutCharacterizable;
+use autour_core::traits::repr::{AbstractLanguagePrinter, ExpBREPrintable};
+
+use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+
+
+/// Returns a regular expression, obtained via state elimination, for the language of the NFA.
+/// The empty language and the language containing only the empty word are handled
+/// before the elimination and respectively printed as '∅' and '𝜀'.
+pub fn get_regex_from_nfa(nfa : &AutNFA<usize>, printer : &ActionNFAITPrinter) -> String {
+    if nfa.is_empty() {
+        return printer.get_empty_symbol(false).to_string();
+    }
+    let trimmed = nfa.clone().trim();
+    if trimmed.transitions.iter().all(|t| t.is_empty()) {
+        return printer.get_epsilon_symbol(false).to_string();
+    }
+    let term = eliminate_states(&trimmed);
+    ExpBRE::from_raw(trimmed.alphabet.clone(), term).unwrap().regexp_to_string(false, printer)
+}
+
+
+fn kleene(term : TermBRE<usize>) -> TermBRE<usize> {
+    match term {
+        TermBRE::Empty | TermBRE::Epsilon => {
+            TermBRE::Epsilon
+        },
+        TermBRE::Kleene(sub_term) => {
+            TermBRE::Kleene(sub_term)
+        },
+        TermBRE::Union(mut sub_terms) if sub_terms.contains(&TermBRE::Epsilon) => {
+            // (𝜀|r)* = r*
+            sub_terms.remove(&TermBRE::Epsilon);
+            if sub_terms.len() == 1 {
+                kleene(sub_terms.into_iter().next().unwrap())
+            } else {
+                kleene(TermBRE::Union(sub_terms))
+            }
+        },
+        other => {
+            TermBRE::Kleene(Box::new(other))
+        }
+    }
+}
+
+fn add_edge(edges : &mut HashMap<(usize,usize),TermBRE<usize>>,
+            orig : usize,
+            targ : usize,
+            term : TermBRE<usize>) {
+    let new_term = match edges.remove(&(orig,targ)) {
+        None => term,
+        Some(old_term) => old_term.unite(term)
+    };
+    edges.insert((orig,targ), new_term);
+}
+
+/// Classical state elimination : a fresh start (resp. accept) state is linked to the
+/// initial (resp. final) states via 𝜀 and the original states are then removed one by one,
+/// each path 'i -> q -> j' being replaced by an edge 'i -> j' labelled 'R(i,q).R(q,q)*.R(q,j)'.
+fn eliminate_states(nfa : &AutNFA<usize>) -> TermBRE<usize> {
+    let num_states = nfa.transitions.len();
+    let start = num_states;
+    let accept = num_states + 1;
+    let mut edges : HashMap<(usize,usize),TermBRE<usize>> = HashMap::new();
+    for (orig, outgoing) in nfa.transitions.iter().enumerate() {
+        for (letter, targets) in outgoing {
+            for targ in targets {
+                add_edge(&mut edges, orig, *targ, TermBRE::Literal(*letter));
+            }
+        }
+    }
+    for init in &nfa.initials {
+        add_edge(&mut edges, start, *init, TermBRE::Epsilon);
+    }
+    for fin in &nfa.finals {
+        add_edge(&mut edges, *fin, accept, TermBRE::Epsilon);
+    }
+    // ***
+    for to_rip in 0..num_states {
+        let on_self = match edges.remove(&(to_rip,to_rip)) {
+            None => TermBRE::Epsilon,
+            Some(term) => kleene(term)
+        };
+        let incoming : Vec<(usize,TermBRE<usize>)> = edges.keys()
+            .filter(|(_,targ)| *targ == to_rip).map(|(orig,_)| *orig).collect::<Vec<usize>>()
+            .into_iter().map(|orig| (orig, edges.remove(&(orig,to_rip)).unwrap())).collect();
+        let outgoing : Vec<(usize,TermBRE<usize>)> = edges.keys()
+            .filter(|(orig,_)| *orig == to_rip).map(|(_,targ)| *targ).collect::<Vec<usize>>()
+            .into_iter().map(|targ| (targ, edges.remove(&(to_rip,targ)).unwrap())).collect();
+        for (orig, in_term) in &incoming {
+            for (targ, out_term) in &outgoing {
+                let through = in_term.clone()
+                    .concatenate(on_self.clone())
+                    .concatenate(out_term.clone());
+                add_edge(&mut edges, *orig, *targ, through);
+            }
+        }
+    }
+    // ***
+    edges.remove(&(start,accept)).unwrap_or(TermBRE::Empty)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::general_context::GeneralContext;
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use crate::core::language::syntax::interaction::{Interaction, LoopKind};
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn regex_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let printer = ActionNFAITPrinter::new(alphabet.clone(),gen_ctx.clone());
+        // ***
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&Interaction::Empty,alphabet.clone());
+        assert_eq!(get_regex_from_nfa(&nfa,&printer), "𝜀");
+        // ***
+        let mut empty_nfa = nfa.clone();
+        empty_nfa.finals.clear();
+        assert_eq!(get_regex_from_nfa(&empty_nfa,&printer), "∅");
+        // ***
+        let act = Interaction::Emission(EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![]));
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&act,alphabet.clone());
+        assert_eq!(get_regex_from_nfa(&nfa,&printer), "l!m");
+        // ***
+        let looped = Interaction::Loop(LoopKind::SStrictSeq,Box::new(act));
+        let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&looped,alphabet);
+        assert_eq!(get_regex_from_nfa(&nfa,&printer), "(l!m)*");
+    }
+
+}
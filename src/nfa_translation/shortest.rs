@@ -0,0 +1,110 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use autour_core::nfa::nfa::AutNFA;
+
+use crate::core::execution::trace::multitrace::Trace;
+use crate::core::execution::trace::trace::TraceAction;
+
+
+/// Breadth first search from the initial states of the NFA to any of its final states.
+/// States and letters are explored in increasing order, so that ties between shortest words
+/// are broken by the order of the letters in the alphabet.
+/// Returns None if the NFA accepts no word and the empty word if it accepts it.
+pub fn get_nfa_shortest_accepted_word(nfa : &AutNFA<usize>) -> Option<Vec<usize>> {
+    let mut parents : HashMap<usize,Option<(usize,usize)>> = HashMap::new();
+    let mut queue : VecDeque<usize> = VecDeque::new();
+    let mut initials : Vec<&usize> = nfa.initials.iter().collect();
+    initials.sort();
+    for initial in initials {
+        parents.insert(*initial,None);
+        queue.push_back(*initial);
+    }
+    while let Some(state) = queue.pop_front() {
+        if nfa.finals.contains(&state) {
+            let mut word = vec![];
+            let mut current = state;
+            while let Some((prev,letter)) = parents.get(&current).unwrap() {
+                word.push(*letter);
+                current = *prev;
+            }
+            word.reverse();
+            return Some(word);
+        }
+        let mut transitions : Vec<(&usize,&_)> = nfa.transitions[state].iter().collect();
+        transitions.sort_by_key(|(letter,_)| **letter);
+        for (letter,targets) in transitions {
+            let mut targets : Vec<&usize> = targets.iter().collect();
+            targets.sort();
+            for next in targets {
+                if !parents.contains_key(next) {
+                    parents.insert(*next,Some((state,*letter)));
+                    queue.push_back(*next);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Shortest trace accepted by an NFA whose letters are the indices of the actions in 'alphabet'.
+pub fn get_nfa_shortest_accepted_trace(nfa : &AutNFA<usize>,
+                                       alphabet : &Vec<BTreeSet<TraceAction>>) -> Option<Trace> {
+    return get_nfa_shortest_accepted_word(nfa).map(|word| {
+        word.into_iter().map(|letter| alphabet[letter].clone()).collect()
+    });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::execution::trace::trace::TraceActionKind;
+    use crate::core::language::syntax::builder::InteractionBuilder;
+    use crate::core::language::syntax::interaction::Interaction;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+    use super::*;
+
+    #[test]
+    fn shortest_trace_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+        let builder = InteractionBuilder::new(&gen_ctx);
+        let shortest = |int : &Interaction| {
+            let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,int,alphabet.clone());
+            get_nfa_shortest_accepted_trace(&nfa,&alphabet)
+        };
+        let emission = |lf_id,ms_id| btreeset!{TraceAction::new(lf_id,TraceActionKind::Emission,ms_id)};
+        let reception = |lf_id,ms_id| btreeset!{TraceAction::new(lf_id,TraceActionKind::Reception,ms_id)};
+        // the empty trace is explicitly returned when accepted
+        let looped = builder.loop_s(builder.transmission("a","m","b").unwrap());
+        assert_eq!(shortest(&looped), Some(vec![]));
+        let strict = builder.strict(builder.transmission("a","m","b").unwrap(),builder.transmission("b","n","a").unwrap());
+        assert_eq!(shortest(&strict), Some(vec![emission(0,0),reception(1,0),emission(1,1),reception(0,1)]));
+        // the shortest branch is chosen and ties are broken by the declaration order of the actions
+        let alt = builder.alt(vec![
+            builder.transmission("a","m","b").unwrap(),
+            builder.emit("b","n").unwrap(),
+            builder.emit("a","n").unwrap()
+        ]);
+        assert_eq!(shortest(&alt), Some(vec![emission(0,1)]));
+    }
+
+}
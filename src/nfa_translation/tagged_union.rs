@@ -0,0 +1,173 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::{BTreeSet, HashSet};
+use autour_core::nfa::nfa::AutNFA;
+use autour_core::traits::build::AutBuildable;
+use autour_core::traits::error::AutError;
+use autour_core::traits::repr::{AbstractLanguagePrinter, AutGraphvizDrawable};
+use graphviz_dot_builder::colors::GraphvizColor;
+use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
+use graphviz_dot_builder::item::cluster::GraphVizCluster;
+use graphviz_dot_builder::item::item::GraphVizGraphItem;
+use graphviz_dot_builder::item::node::style::GraphvizNodeStyleItem;
+use graphviz_dot_builder::traits::DotBuildable;
+
+
+/// Fill colors of the regions of the states coming from the successive sources (cycled through if there are more sources).
+const SOURCE_COLORS : [GraphvizColor;8] = [
+    GraphvizColor::lightblue,
+    GraphvizColor::palegreen,
+    GraphvizColor::lightpink,
+    GraphvizColor::lightyellow,
+    GraphvizColor::lavender,
+    GraphvizColor::peachpuff,
+    GraphvizColor::lightcyan,
+    GraphvizColor::thistle
+];
+
+
+/// Union of the NFAs of several interactions (their sources) which keeps track of the source of each state,
+/// so that the sources accepting a word can be told apart.
+pub struct TaggedNfaUnion {
+    pub nfa : AutNFA<usize>,
+    /// index of the source of each state of 'nfa'
+    pub state_sources : Vec<usize>,
+    pub num_sources : usize
+}
+
+impl TaggedNfaUnion {
+
+    /// The i-th NFA (there must be at least one) is the source with index i.
+    /// All the NFAs must be over the same alphabet.
+    pub fn from_nfas(nfas : Vec<AutNFA<usize>>) -> Result<Self,AutError<usize>> {
+        let num_sources = nfas.len();
+        let mut state_sources = vec![];
+        let mut united : Option<AutNFA<usize>> = None;
+        for (source,nfa) in nfas.into_iter().enumerate() {
+            // 'unite' appends the states of its argument after those of the NFA it is called on
+            state_sources.extend(std::iter::repeat(source).take(nfa.transitions.len()));
+            united = Some(match united {
+                None => nfa,
+                Some(got) => got.unite(nfa)?
+            });
+        }
+        Ok(Self { nfa : united.expect("at least one NFA is required"), state_sources, num_sources })
+    }
+
+    /// Indices of the sources which accept the word.
+    pub fn get_accepting_sources(&self, word : &[usize]) -> BTreeSet<usize> {
+        let mut current : HashSet<usize> = self.nfa.initials.clone();
+        for letter in word {
+            let mut next = HashSet::new();
+            for state in &current {
+                if let Some(targets) = self.nfa.transitions[*state].get(letter) {
+                    next.extend(targets.iter().cloned());
+                }
+            }
+            current = next;
+        }
+        current.iter()
+            .filter(|state| self.nfa.finals.contains(state))
+            .map(|state| self.state_sources[*state])
+            .collect()
+    }
+
+    /// Number of states coming from each source.
+    pub fn get_num_states_per_source(&self) -> Vec<usize> {
+        let mut counts = vec![0;self.num_sources];
+        for source in &self.state_sources {
+            counts[*source] += 1;
+        }
+        counts
+    }
+
+    /// Draws the NFA with the states of each source in a colored region labelled with 'source_labels'.
+    pub fn to_dot<Printer : AbstractLanguagePrinter<usize>>(&self,
+                                                             printer : &Printer,
+                                                             source_labels : &[String]) -> GraphVizDiGraph {
+        let nfa_as_dot = self.nfa.to_dot(false, &hashset!{}, printer);
+        let mut regions : Vec<GraphVizCluster> = (0..self.num_sources).map(|source| {
+            GraphVizCluster::new(format!("source{:}", source),
+                                 vec![GraphvizNodeStyleItem::Label(source_labels[source].clone()),
+                                      GraphvizNodeStyleItem::FillColor(SOURCE_COLORS[source % SOURCE_COLORS.len()].clone())],
+                                 vec![],
+                                 vec![])
+        }).collect();
+        for item in nfa_as_dot.items {
+            if let GraphVizGraphItem::Node(node) = item {
+                // states are drawn as 'Sk' and the points leading to initial states as 'Ik'
+                let state : usize = node.id[1..].parse().unwrap();
+                regions[self.state_sources[state]].add_node(node);
+            }
+        }
+        let mut digraph = GraphVizDiGraph::new(nfa_as_dot.style);
+        for region in regions {
+            digraph.add_cluster(region);
+        }
+        for edge in nfa_as_dot.edges {
+            digraph.add_edge(edge);
+        }
+        digraph
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use graphviz_dot_builder::traits::DotTranslatable;
+
+    use crate::core::general_context::GeneralContext;
+    use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+    use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+
+    use super::*;
+
+    #[test]
+    fn tagged_union_test() {
+        let alphabet : HashSet<usize> = hashset!{0,1};
+        let union = TaggedNfaUnion::from_nfas(vec![
+            AutNFA::new_matching(alphabet.clone(), &[0]),
+            AutNFA::new_matching(alphabet.clone(), &[0,1]),
+            AutNFA::new_matching(alphabet.clone(), &[0])
+        ]).unwrap();
+        assert_eq!(union.get_num_states_per_source(), vec![2,3,2]);
+        assert_eq!(union.get_accepting_sources(&[0]), BTreeSet::from([0,2]));
+        assert_eq!(union.get_accepting_sources(&[0,1]), BTreeSet::from([1]));
+        assert!(union.get_accepting_sources(&[1]).is_empty());
+        // ***
+        let mismatched = TaggedNfaUnion::from_nfas(vec![
+            AutNFA::new_matching(alphabet.clone(), &[0]),
+            AutNFA::new_matching(hashset!{0}, &[0])
+        ]);
+        assert!(mismatched.is_err());
+        // ***
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("l1".to_string());
+        gen_ctx.add_msg("m1".to_string());
+        let printer = ActionNFAITPrinter::new(get_alphabet_from_gen_ctx(&gen_ctx), gen_ctx);
+        let labels : Vec<String> = vec!["a".to_string(),"b".to_string(),"c".to_string()];
+        let dot = union.to_dot(&printer, &labels).to_dot_string();
+        assert!(dot.contains("subgraph cluster_source0"));
+        assert!(dot.contains("subgraph cluster_source2"));
+        assert!(dot.contains("fillcolor=lightpink"));
+    }
+
+}
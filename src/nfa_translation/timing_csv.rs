@@ -0,0 +1,232 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+
+/// One row of the CSV file in which the NFA generation experiments record
+/// the translation of each input interaction.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NfaTimingCsvRow {
+    pub name : String,
+    pub num_symbols : u32,
+    // median time of the translation into a NFA, in milliseconds
+    pub translation_ms : f64,
+    pub num_states : u32,
+    pub num_transitions : u32,
+    pub alphabet_size : usize,
+    // whether the translation was abandoned because it exceeded the timeout
+    pub timed_out : bool,
+    // whether a repeated step produced automata of different sizes across its repetitions
+    pub nondeterministic : bool
+}
+
+impl NfaTimingCsvRow {
+    pub fn new(name: String, num_symbols: u32, translation_micros: u128, num_states: u32, num_transitions: u32, alphabet_size: usize) -> Self {
+        Self { name, num_symbols, translation_ms : (translation_micros as f64) / 1000.0, num_states, num_transitions, alphabet_size, timed_out : false, nondeterministic : false }
+    }
+    pub fn timed_out(name: String, num_symbols: u32, alphabet_size: usize) -> Self {
+        Self { name, num_symbols, translation_ms : 0.0, num_states : 0, num_transitions : 0, alphabet_size, timed_out : true, nondeterministic : false }
+    }
+    /// Number of states of the NFA per symbol of the interaction.
+    pub fn get_blowup(&self) -> f64 {
+        (self.num_states as f64) / (self.num_symbols.max(1) as f64)
+    }
+    pub fn add_csv_title_line(results : &mut String) {
+        results.push_str("name,numsymbols,translationms,numstates,numtransitions,alphabetsize,blowup,nondeterministic");
+    }
+    pub fn add_csv_line(&self, results : &mut String) {
+        if self.timed_out {
+            results.push_str(&format!("{:},{:},TIMEOUT,TIMEOUT,TIMEOUT,{:},TIMEOUT,TIMEOUT",
+                                      self.name,
+                                      self.num_symbols,
+                                      self.alphabet_size));
+            return;
+        }
+        results.push_str(&format!("{:},{:},{:.3},{:},{:},{:},{:.3},{:}",
+                                  self.name,
+                                  self.num_symbols,
+                                  self.translation_ms,
+                                  self.num_states,
+                                  self.num_transitions,
+                                  self.alphabet_size,
+                                  self.get_blowup(),
+                                  self.nondeterministic));
+    }
+}
+
+
+/// Appends the rows at the end of the CSV file, creating it if it does not exist.
+/// The title line is only written when the file is new (or empty) so that
+/// successive runs can accumulate their results in the same file.
+pub fn append_nfa_timing_csv(path : &Path, rows : &[NfaTimingCsvRow]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut content = String::new();
+    if file.metadata()?.len() == 0 {
+        NfaTimingCsvRow::add_csv_title_line(&mut content);
+        content.push('\n');
+    }
+    for row in rows {
+        row.add_csv_line(&mut content);
+        content.push('\n');
+    }
+    file.write_all(content.as_bytes())
+}
+
+
+/// Appends rows at the end of a CSV file (as 'append_nfa_timing_csv') from several threads,
+/// each row being written as soon as it is added.
+pub struct NfaTimingCsvAppender {
+    file : Mutex<File>
+}
+
+impl NfaTimingCsvAppender {
+    pub fn open(path : &Path) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            let mut title = String::new();
+            NfaTimingCsvRow::add_csv_title_line(&mut title);
+            title.push('\n');
+            file.write_all(title.as_bytes())?;
+        }
+        Ok( Self{ file : Mutex::new(file) } )
+    }
+
+    pub fn append(&self, row : &NfaTimingCsvRow) -> std::io::Result<()> {
+        let mut line = String::new();
+        row.add_csv_line(&mut line);
+        line.push('\n');
+        // the whole line is written at once so that rows from distinct threads are not interleaved
+        self.file.lock().unwrap().write_all(line.as_bytes())
+    }
+}
+
+
+/// Aggregate statistics over the rows of a corpus, for when the per-file rows are not needed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NfaTimingSummary {
+    // number of interactions that were parsed, whether their translation completed or timed out
+    pub num_interactions : usize,
+    pub num_parse_failures : usize,
+    pub num_timeouts : usize,
+    // the following only account for the translations that completed
+    pub total_states : u64,
+    pub avg_blowup : f64,
+    pub median_blowup : f64,
+    pub min_translation_ms : f64,
+    pub max_translation_ms : f64
+}
+
+impl NfaTimingSummary {
+    pub fn from_rows(rows : &[NfaTimingCsvRow], num_parse_failures : usize) -> Self {
+        let completed : Vec<&NfaTimingCsvRow> = rows.iter().filter(|r| !r.timed_out).collect();
+        let mut blowups : Vec<f64> = completed.iter().map(|r| r.get_blowup()).collect();
+        blowups.sort_by(|x,y| x.partial_cmp(y).unwrap());
+        let median_blowup = if blowups.is_empty() {
+            0.0
+        } else if blowups.len() % 2 == 1 {
+            blowups[blowups.len() / 2]
+        } else {
+            (blowups[blowups.len() / 2 - 1] + blowups[blowups.len() / 2]) / 2.0
+        };
+        Self {
+            num_interactions : rows.len(),
+            num_parse_failures,
+            num_timeouts : rows.len() - completed.len(),
+            total_states : completed.iter().map(|r| r.num_states as u64).sum(),
+            avg_blowup : blowups.iter().sum::<f64>() / (blowups.len().max(1) as f64),
+            median_blowup,
+            min_translation_ms : completed.iter().map(|r| r.translation_ms).fold(None, |m : Option<f64>,x| Some(m.map_or(x, |m| m.min(x)))).unwrap_or(0.0),
+            max_translation_ms : completed.iter().map(|r| r.translation_ms).fold(0.0, f64::max)
+        }
+    }
+    pub fn to_lines(&self) -> Vec<String> {
+        vec![
+            format!("interactions      : {:}", self.num_interactions),
+            format!("parse failures    : {:}", self.num_parse_failures),
+            format!("timeouts          : {:}", self.num_timeouts),
+            format!("total NFA states  : {:}", self.total_states),
+            format!("blow-up           : {:.3} average, {:.3} median (states per symbol)", self.avg_blowup, self.median_blowup),
+            format!("translation time  : {:.3}ms min, {:.3}ms max", self.min_translation_ms, self.max_translation_ms)
+        ]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_nfa_timing_csv_test() {
+        let path = std::env::temp_dir().join(format!("hibou_timing_csv_test_{:}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let row = NfaTimingCsvRow::new("i0".to_string(), 7, 1500, 4, 6, 3);
+        append_nfa_timing_csv(&path, std::slice::from_ref(&row)).unwrap();
+        append_nfa_timing_csv(&path, &[row, NfaTimingCsvRow::timed_out("i1".to_string(), 9, 3)]).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content,
+                   "name,numsymbols,translationms,numstates,numtransitions,alphabetsize,blowup,nondeterministic\ni0,7,1.500,4,6,3,0.571,false\ni0,7,1.500,4,6,3,0.571,false\ni1,9,TIMEOUT,TIMEOUT,TIMEOUT,3,TIMEOUT,TIMEOUT\n");
+    }
+
+    #[test]
+    fn summary_test() {
+        let rows = vec![NfaTimingCsvRow::new("i0".to_string(), 4, 1500, 4, 6, 3),
+                        NfaTimingCsvRow::new("i1".to_string(), 2, 500, 6, 8, 3),
+                        NfaTimingCsvRow::new("i2".to_string(), 10, 2500, 5, 8, 3),
+                        NfaTimingCsvRow::timed_out("i3".to_string(), 9, 3)];
+        let summary = NfaTimingSummary::from_rows(&rows, 2);
+        assert_eq!(summary.num_interactions, 4);
+        assert_eq!(summary.num_parse_failures, 2);
+        assert_eq!(summary.num_timeouts, 1);
+        assert_eq!(summary.total_states, 15);
+        assert_eq!(summary.median_blowup, 1.0);
+        assert!((summary.avg_blowup - 1.5).abs() < 1e-9);
+        assert_eq!(summary.min_translation_ms, 0.5);
+        assert_eq!(summary.max_translation_ms, 2.5);
+        let empty = NfaTimingSummary::from_rows(&[], 1);
+        assert_eq!(empty.to_lines()[1], "parse failures    : 1");
+        assert_eq!(empty.min_translation_ms, 0.0);
+    }
+
+    #[test]
+    fn csv_appender_test() {
+        let path = std::env::temp_dir().join(format!("hibou_timing_csv_appender_test_{:}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let appender = NfaTimingCsvAppender::open(&path).unwrap();
+        std::thread::scope(|scope| {
+            for x in 0..4 {
+                let appender = &appender;
+                scope.spawn(move || {
+                    appender.append(&NfaTimingCsvRow::new(format!("i{:}",x), 7, 1500, 4, 6, 3)).unwrap();
+                });
+            }
+        });
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut lines : Vec<&str> = content.lines().collect();
+        assert_eq!(lines.remove(0), "name,numsymbols,translationms,numstates,numtransitions,alphabetsize,blowup,nondeterministic");
+        lines.sort();
+        assert_eq!(lines, vec!["i0,7,1.500,4,6,3,0.571,false","i1,7,1.500,4,6,3,0.571,false","i2,7,1.500,4,6,3,0.571,false","i3,7,1.500,4,6,3,0.571,false"]);
+    }
+
+}
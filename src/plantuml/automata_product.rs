@@ -55,7 +55,7 @@ fn to_plant_uml_ap_rec(output_file : &mut File,
             return (last_state,state_count);
         },
         &Interaction::Emission(ref em_act) => {
-            let ms_name = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
+            let ms_name = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
             match em_act.targets.len() {
                 0 => {
                     let transition_label = format!("!{}",ms_name);
@@ -70,7 +70,7 @@ fn to_plant_uml_ap_rec(output_file : &mut File,
             }
         },
         &Interaction::Reception(ref rc_act) => {
-            let ms_name = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
+            let ms_name = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
             let transition_label = format!("?{}",ms_name);
             let new_state_label = format!("lf{}_s{}",lf_id,state_count);
             let last_state_label = get_last_label(lf_id, last_state);
@@ -174,6 +174,9 @@ fn to_plant_uml_ap_rec(output_file : &mut File,
         },
         &Interaction::And(_,_) => {
             panic!("translation towards puml-ap does not implement ands");
+        },
+        &Interaction::Not(_) => {
+            panic!("translation towards puml-ap does not implement forbidden actions");
         }
     }
 }
@@ -14,145 +14,206 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::fs::File;
-use std::io::Write;
+use std::fs;
 
 use crate::core::general_context::GeneralContext;
-use crate::core::language::syntax::action::*;
+use crate::core::language::syntax::action::EmissionTargetRef;
 use crate::core::language::syntax::interaction::{Interaction, LoopKind};
 use crate::core::language::syntax::util::get_recursive_frag::*;
+use crate::io::output::to_hfiles::trace::trace_action::trace_action_as_htf_encoding;
 
-pub fn to_plant_uml_sd(output_path : &String,
-                       title : &str,
-                       interaction : &Interaction,
-                       gen_ctx : &GeneralContext) {
-    let mut output_file = File::create(output_path).unwrap();
-    output_file.write( "@startuml\n".as_bytes() );
-    //output_file.write( "title-".as_bytes() );
-    //output_file.write( title.as_bytes() );
-    //output_file.write( "\n".as_bytes() );
-    to_plant_uml_sd_rec(&mut output_file, interaction, gen_ctx);
-    output_file.write( "@enduml\n".as_bytes() );
+
+/// Translates an interaction into the text of a PlantUML sequence diagram.
+/// Transmissions are translated into arrows (one per target for broadcasts),
+/// 'alt' into 'alt' blocks and loops into 'loop' blocks labelled with the kind of the loop.
+/// PlantUML has no weakly sequential or co-region constructs :
+/// 'seq' is a mere succession of messages, 'par' and 'coreg' are translated into 'par' blocks
+/// (with a note giving the concurrent lifelines of co-regions) and 'strict', 'sync' and 'and' into groups.
+/// Forbidden actions are translated into notes over their lifeline.
+pub fn interaction_to_plantuml(interaction : &Interaction, gen_ctx : &GeneralContext) -> String {
+    let mut puml = "@startuml\n".to_string();
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        puml.push_str(&format!("participant {}\n", gen_ctx.get_lf_name(lf_id).unwrap()));
+    }
+    interaction_to_plantuml_rec(&mut puml, interaction, gen_ctx, 0);
+    puml.push_str("@enduml\n");
+    puml
 }
 
-fn to_plant_uml_sd_rec(output_file : &mut File,
-                       interaction : &Interaction,
-                       gen_ctx : &GeneralContext) {
+fn push_line(puml : &mut String, depth : usize, line : &str) {
+    puml.push_str(&"  ".repeat(depth));
+    puml.push_str(line);
+    puml.push('\n');
+}
+
+/// Translates each fragment within a block separated by 'else'.
+fn fragments_to_plantuml_block(puml : &mut String,
+                               header : &str,
+                               note : Option<String>,
+                               frags : Vec<&Interaction>,
+                               gen_ctx : &GeneralContext,
+                               depth : usize) {
+    push_line(puml, depth, header);
+    if let Some(note) = note {
+        push_line(puml, depth + 1, &note);
+    }
+    let num_frags = frags.len();
+    for (x,frag) in frags.into_iter().enumerate() {
+        interaction_to_plantuml_rec(puml, frag, gen_ctx, depth + 1);
+        if x + 1 < num_frags {
+            push_line(puml, depth, "else");
+        }
+    }
+    push_line(puml, depth, "end");
+}
+
+fn interaction_to_plantuml_rec(puml : &mut String,
+                               interaction : &Interaction,
+                               gen_ctx : &GeneralContext,
+                               depth : usize) {
     match interaction {
-        &Interaction::Empty => {},
-        &Interaction::Reception(ref rc_act) => {
-            let ms_name = gen_ctx.get_ms_name(rc_act.ms_id).unwrap();
-            match rc_act.recipients.len() {
-                1 => {
-                    let lf_id = rc_act.recipients.get(0).unwrap();
-                    let lf_name = gen_ctx.get_lf_name(*lf_id).unwrap();
-                    output_file.write( format!("->{} : {}\n", &lf_name, &ms_name).as_bytes() );
-                },
-                _ => {
-                    panic!("translation towards puml-sd does not implement broadcasts");
-                }
+        Interaction::Empty => {},
+        Interaction::Reception(rc_act) => {
+            let ms_name = gen_ctx.get_ms_label(rc_act.ms_id,rc_act.ms_param).unwrap();
+            for lf_id in &rc_act.recipients {
+                let lf_name = gen_ctx.get_lf_name(*lf_id).unwrap();
+                push_line(puml, depth, &format!("[-> {} : {}", lf_name, ms_name));
             }
         },
-        &Interaction::Emission(ref em_act) => {
-            let ms_name = gen_ctx.get_ms_name(em_act.ms_id).unwrap();
+        Interaction::Emission(em_act) => {
+            let mut ms_name = gen_ctx.get_ms_label(em_act.ms_id,em_act.ms_param).unwrap();
+            if let Some(group_id) = em_act.target_group {
+                ms_name = format!("{} @{}", ms_name, gen_ctx.get_group_name(group_id).unwrap());
+            }
             let lf_name = gen_ctx.get_lf_name(em_act.origin_lf_id).unwrap();
-            match em_act.targets.len() {
-                0 => {
-                    output_file.write( format!("{}-> : {}\n", &lf_name, &ms_name).as_bytes() );
-                },
-                1 => {
-                    let target_ref = em_act.targets.get(0).unwrap();
-                    match target_ref {
-                        EmissionTargetRef::Gate(_) => {
-                            output_file.write( format!("{}->] : {}\n", &lf_name, &ms_name).as_bytes() );
-                        },
-                        EmissionTargetRef::Lifeline(tar_lf_id) => {
-                            let tar_lf_name = gen_ctx.get_lf_name(*tar_lf_id).unwrap();
-                            output_file.write( format!("{}->{} : {}\n", &lf_name, &tar_lf_name, &ms_name).as_bytes() );
-                        }
+            if em_act.targets.is_empty() {
+                push_line(puml, depth, &format!("{} ->] : {}", lf_name, ms_name));
+            }
+            for target_ref in &em_act.targets {
+                match target_ref {
+                    EmissionTargetRef::Gate(_) => {
+                        push_line(puml, depth, &format!("{} ->] : {}", lf_name, ms_name));
+                    },
+                    EmissionTargetRef::Lifeline(tar_lf_id) => {
+                        let tar_lf_name = gen_ctx.get_lf_name(*tar_lf_id).unwrap();
+                        push_line(puml, depth, &format!("{} -> {} : {}", lf_name, tar_lf_name, ms_name));
                     }
-                },
-                _ => {
-                    panic!("translation towards puml-sd does not implement broadcasts");
                 }
             }
         },
-        &Interaction::Seq(ref i1, ref i2) => {
-            to_plant_uml_sd_rec(output_file, i1, gen_ctx);
-            to_plant_uml_sd_rec(output_file, i2, gen_ctx);
+        Interaction::Seq(i1, i2) => {
+            interaction_to_plantuml_rec(puml, i1, gen_ctx, depth);
+            interaction_to_plantuml_rec(puml, i2, gen_ctx, depth);
         },
-        &Interaction::Strict(ref i1, ref i2) => {
-            let mut strict_frags = get_recursive_strict_frags(i1);
-            strict_frags.extend_from_slice(&mut get_recursive_strict_frags(i2));
-            // ***
-            output_file.write( "group strict\n".as_bytes() );
-            let mut rem = strict_frags.len();
-            for frag in strict_frags {
-                to_plant_uml_sd_rec(output_file, frag, gen_ctx);
-                rem = rem - 1;
-                if rem > 0 {
-                    output_file.write( "else\n".as_bytes() );
-                }
-            }
-            output_file.write( "end\n".as_bytes() );
+        Interaction::Strict(i1, i2) => {
+            let mut frags = get_recursive_strict_frags(i1);
+            frags.extend(get_recursive_strict_frags(i2));
+            fragments_to_plantuml_block(puml, "group strict", None, frags, gen_ctx, depth);
         },
-        &Interaction::Par(ref i1, ref i2) => {
-            let mut par_frags = get_recursive_par_frags(i1);
-            par_frags.extend_from_slice(&mut get_recursive_par_frags(i2));
-            // ***
-            output_file.write( "group par\n".as_bytes() );
-            let mut rem = par_frags.len();
-            for frag in par_frags {
-                to_plant_uml_sd_rec(output_file, frag, gen_ctx);
-                rem = rem - 1;
-                if rem > 0 {
-                    output_file.write( "else\n".as_bytes() );
-                }
-            }
-            output_file.write( "end\n".as_bytes() );
+        Interaction::Par(i1, i2) => {
+            let mut frags = get_recursive_par_frags(i1);
+            frags.extend(get_recursive_par_frags(i2));
+            fragments_to_plantuml_block(puml, "par", None, frags, gen_ctx, depth);
         },
-        &Interaction::Alt(ref i1, ref i2) => {
-            let mut alt_frags = get_recursive_alt_frags(i1);
-            alt_frags.extend_from_slice(&mut get_recursive_alt_frags(i2));
-            // ***
-            output_file.write( "alt\n".as_bytes() );
-            let mut rem = alt_frags.len();
-            for frag in alt_frags {
-                to_plant_uml_sd_rec(output_file, frag, gen_ctx);
-                rem = rem - 1;
-                if rem > 0 {
-                    output_file.write( "else\n".as_bytes() );
-                }
-            }
-            output_file.write( "end\n".as_bytes() );
+        Interaction::CoReg(cr, i1, i2) => {
+            let mut frags = get_recursive_coreg_frags(cr, i1);
+            frags.extend(get_recursive_coreg_frags(cr, i2));
+            let note = if cr.is_empty() {
+                "note across : co-region on no lifeline (weakly sequential)".to_string()
+            } else {
+                let lf_names : Vec<String> = cr.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+                format!("note over {} : co-region", lf_names.join(","))
+            };
+            fragments_to_plantuml_block(puml, "par", Some(note), frags, gen_ctx, depth);
         },
-        &Interaction::Loop(ref kind, ref i1) => {
-            // ***
-            match kind {
-                &LoopKind::SStrictSeq => {
-                    output_file.write( "group loopX\n".as_bytes() );
-                },
-                &LoopKind::HHeadFirstWS => {
-                    output_file.write( "group loopH\n".as_bytes() );
-                },
-                &LoopKind::WWeakSeq => {
-                    output_file.write( "group loopS\n".as_bytes() );
-                },
-                &LoopKind::PInterleaving => {
-                    output_file.write( "group loopP\n".as_bytes() );
-                }
-            }
-            to_plant_uml_sd_rec(output_file, i1, gen_ctx);
-            output_file.write( "end\n".as_bytes() );
+        Interaction::Alt(i1, i2) => {
+            let mut frags = get_recursive_alt_frags(i1);
+            frags.extend(get_recursive_alt_frags(i2));
+            fragments_to_plantuml_block(puml, "alt", None, frags, gen_ctx, depth);
         },
-        &Interaction::CoReg(_,_,_) => {
-            panic!("translation towards puml-sd does not implement co-regions");
+        Interaction::Loop(kind, i1) => {
+            let header = match kind {
+                LoopKind::SStrictSeq => "loop loopS",
+                LoopKind::HHeadFirstWS => "loop loopH",
+                LoopKind::WWeakSeq => "loop loopW",
+                LoopKind::PInterleaving => "loop loopP"
+            };
+            fragments_to_plantuml_block(puml, header, None, vec![i1], gen_ctx, depth);
         },
-        &Interaction::Sync(_,_,_) => {
-            panic!("translation towards puml-sd does not implement sync");
+        Interaction::Sync(sync_acts, i1, i2) => {
+            let mut frags = get_recursive_sync_frags(sync_acts, i1);
+            frags.extend(get_recursive_sync_frags(sync_acts, i2));
+            fragments_to_plantuml_block(puml, "group sync", None, frags, gen_ctx, depth);
         },
-        &Interaction::And(_,_) => {
-            panic!("translation towards puml-sd does not implement ands");
+        Interaction::And(i1, i2) => {
+            fragments_to_plantuml_block(puml, "group and", None, vec![i1, i2], gen_ctx, depth);
+        },
+        Interaction::Not(act) => {
+            let lf_name = gen_ctx.get_lf_name(act.lf_id).unwrap();
+            push_line(puml, depth, &format!("note over {} : not({})", lf_name, trace_action_as_htf_encoding(gen_ctx,act)));
         }
     }
-}
\ No newline at end of file
+}
+
+
+/// Writes the PlantUML sequence diagram of an interaction (see interaction_to_plantuml) into a file.
+pub fn to_plant_uml_sd(output_path : &String,
+                       interaction : &Interaction,
+                       gen_ctx : &GeneralContext) -> std::io::Result<()> {
+    fs::write(output_path, interaction_to_plantuml(interaction, gen_ctx))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::language::syntax::action::{CommunicationSynchronicity, EmissionAction};
+    use super::*;
+
+    #[test]
+    fn plantuml_test() {
+        let mut gen_ctx = GeneralContext::new();
+        gen_ctx.add_lf("a".to_string());
+        gen_ctx.add_lf("b".to_string());
+        gen_ctx.add_msg("m".to_string());
+        let a_to_b = Interaction::Emission(
+            EmissionAction::new(0,0,CommunicationSynchronicity::Asynchronous,vec![EmissionTargetRef::Lifeline(1)])
+        );
+        let b_out = Interaction::Emission(
+            EmissionAction::new(1,0,CommunicationSynchronicity::Asynchronous,vec![])
+        );
+        let i = Interaction::Loop(
+            LoopKind::SStrictSeq,
+            Box::new(Interaction::Alt(
+                Box::new(a_to_b.clone()),
+                Box::new(Interaction::CoReg(vec![1],Box::new(a_to_b),Box::new(b_out)))
+            ))
+        );
+        let expected = [
+            "@startuml",
+            "participant a",
+            "participant b",
+            "loop loopS",
+            "  alt",
+            "    a -> b : m",
+            "  else",
+            "    par",
+            "      note over b : co-region",
+            "      a -> b : m",
+            "    else",
+            "      b ->] : m",
+            "    end",
+            "  end",
+            "end",
+            "@enduml",
+            ""
+        ].join("\n");
+        assert_eq!(interaction_to_plantuml(&i,&gen_ctx), expected);
+        // ***
+        let output_path = std::env::temp_dir().join(format!("hibou_puml_sd_test_{:}.puml", std::process::id()));
+        let output_path = output_path.to_str().unwrap().to_string();
+        to_plant_uml_sd(&output_path,&i,&gen_ctx).unwrap();
+        assert_eq!(std::fs::read_to_string(&output_path).unwrap(), expected);
+        std::fs::remove_file(&output_path).unwrap();
+    }
+
+}
@@ -16,4 +16,4 @@ limitations under the License.
 
 
 pub mod glog;
-pub mod slog;
\ No newline at end of file
+pub mod slog;pub mod prefix;
@@ -0,0 +1,120 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::any::Any;
+
+use graph_process_manager_core::delegate::priorities::GenericProcessPriorities;
+use graph_process_manager_core::handler::filter::AbstractFilter;
+use graph_process_manager_core::manager::logger::AbstractProcessLogger;
+use graph_process_manager_core::queued_steps::queue::strategy::QueueSearchStrategy;
+
+use crate::process::ana::conf::{AnalysisConfig, AnalysisStaticLocalVerdictAnalysisProof};
+use crate::process::ana::context::AnalysisContext;
+use crate::process::ana::filter::elim::AnalysisFilterEliminationKind;
+use crate::process::ana::filter::filter::AnalysisFilterCriterion;
+use crate::process::ana::node::node::AnalysisNodeKind;
+use crate::process::ana::param::param::AnalysisParameterization;
+use crate::process::ana::priorities::AnalysisPriorities;
+use crate::process::ana::step::AnalysisStepKind;
+use crate::process::ana::verdict::global::AnalysisGlobalVerdict;
+use crate::process::ana::verdict::local::AnalysisLocalVerdict;
+
+
+/// Keeps track, while the analysis runs, of the node which consumed the most actions of the multi-trace
+/// so that, on a Fail verdict, the actions which could not be matched can be reported
+/// without another search (it only accounts for the nodes the analysis has actually explored).
+pub struct AnalysisFurthestPrefixLogger {
+    /// number of multi-actions consumed on each component of the multi-trace in the furthest node
+    pub furthest : Vec<usize>
+}
+
+impl AnalysisFurthestPrefixLogger {
+    pub fn new() -> AnalysisFurthestPrefixLogger {
+        AnalysisFurthestPrefixLogger{furthest:vec![]}
+    }
+}
+
+impl AbstractProcessLogger<AnalysisConfig> for AnalysisFurthestPrefixLogger {
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn log_initialize(&mut self) {
+        self.furthest = vec![];
+    }
+
+    fn log_parameterization(&mut self,
+                            _strategy : &QueueSearchStrategy,
+                            _priorities : &GenericProcessPriorities<AnalysisPriorities>,
+                            _filters : &[Box<dyn AbstractFilter<AnalysisFilterCriterion,AnalysisFilterEliminationKind>>],
+                            _goal : &Option<AnalysisGlobalVerdict>,
+                            _use_memoization : bool,
+                            _parameterization : &AnalysisParameterization) {}
+
+    fn log_filtered(&mut self,
+                    _context : &AnalysisContext,
+                    _parent_node_id : u32,
+                    _new_node_id : u32,
+                    _elim_kind : &AnalysisFilterEliminationKind) {}
+
+    fn log_new_node(&mut self,
+                    _context : &AnalysisContext,
+                    _param : &AnalysisParameterization,
+                    _new_node_id : u32,
+                    new_node : &AnalysisNodeKind) {
+        let consumed : Vec<usize> = new_node.flags.canals.iter().map(|canal| canal.consumed).collect();
+        if self.furthest.is_empty() || consumed.iter().sum::<usize>() > self.furthest.iter().sum::<usize>() {
+            self.furthest = consumed;
+        }
+    }
+
+    fn log_new_step(&mut self,
+                    _context : &AnalysisContext,
+                    _param : &AnalysisParameterization,
+                    _origin_node_id : u32,
+                    _target_node_id : u32,
+                    _step : &AnalysisStepKind,
+                    _target_node : &AnalysisNodeKind,
+                    _target_depth : u32) {}
+
+    fn log_verdict_on_no_child(&mut self,
+                               _context : &AnalysisContext,
+                               _param : &AnalysisParameterization,
+                               _parent_node_id : u32,
+                               _verdict : &AnalysisLocalVerdict) {}
+
+    fn log_verdict_on_static_analysis(&mut self,
+                                      _context : &AnalysisContext,
+                                      _param : &AnalysisParameterization,
+                                      _parent_node_id : u32,
+                                      _verdict : &AnalysisLocalVerdict,
+                                      _proof : &AnalysisStaticLocalVerdictAnalysisProof) {}
+
+    fn log_terminate(&mut self,
+                     _global_verdict : &AnalysisGlobalVerdict) {}
+
+    fn log_notify_terminal_node_reached(&mut self,
+                                        _context : &AnalysisContext,
+                                        _node_id : u32) {}
+
+    fn log_notify_last_child_of_node_processed(&mut self,
+                                               _context : &AnalysisContext,
+                                               _parent_node_id : u32) {}
+
+}
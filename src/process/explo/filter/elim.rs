@@ -19,7 +19,8 @@ use std::fmt;
 pub enum ExplorationFilterEliminationKind {
     MaxLoopInstanciation,
     MaxProcessDepth,
-    MaxNodeNumber
+    MaxNodeNumber,
+    Cancelled
 }
 
 impl fmt::Display for ExplorationFilterEliminationKind {
@@ -33,6 +34,9 @@ impl fmt::Display for ExplorationFilterEliminationKind {
             },
             ExplorationFilterEliminationKind::MaxNodeNumber => {
                 write!(f,"MaxNum")
+            },
+            ExplorationFilterEliminationKind::Cancelled => {
+                write!(f,"Cancelled")
             }
         }
     }
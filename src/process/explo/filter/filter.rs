@@ -17,6 +17,8 @@ limitations under the License.
 
 
 use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use graph_process_manager_core::handler::filter::AbstractFilter;
 
@@ -36,7 +38,9 @@ impl fmt::Display for ExplorationFilterCriterion {
 pub enum ExplorationFilter {
     MaxLoopInstanciation(u32),
     MaxProcessDepth(u32),
-    MaxNodeNumber(u32)
+    MaxNodeNumber(u32),
+    /// eliminates every new node once the flag is set, so that the exploration stops (see 'translate_with_timeout')
+    Cancellation(Arc<AtomicBool>)
 }
 
 impl fmt::Display for ExplorationFilter {
@@ -50,6 +54,9 @@ impl fmt::Display for ExplorationFilter {
             },
             ExplorationFilter::MaxNodeNumber(num) => {
                 write!(f,"MaxNum={}",num)
+            },
+            ExplorationFilter::Cancellation(_) => {
+                write!(f,"Cancellation")
             }
         }
     }
@@ -76,6 +83,11 @@ impl AbstractFilter<ExplorationFilterCriterion,ExplorationFilterEliminationKind>
                 if node_counter >= *max_node_number {
                     return Some( ExplorationFilterEliminationKind::MaxNodeNumber );
                 }
+            },
+            ExplorationFilter::Cancellation( cancelled ) => {
+                if cancelled.load(Ordering::Relaxed) {
+                    return Some( ExplorationFilterEliminationKind::Cancelled );
+                }
             }
         }
         return None;
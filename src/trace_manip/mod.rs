@@ -15,4 +15,5 @@ limitations under the License.
 */
 
 pub mod slice;
-pub mod mutate;
\ No newline at end of file
+pub mod mutate;
+pub mod project;
\ No newline at end of file
@@ -0,0 +1,115 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::trace::multitrace::{MultiTrace, Trace};
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::general_context::GeneralContext;
+use crate::io::file_extensions::HIBOU_TRACE_FILE_EXTENSION;
+use crate::io::output::to_hfiles::trace::to_htf::write_multi_trace_into_file;
+
+
+/// Local trace of the lifeline 'lf_id' in the multi-trace, i.e. its actions in their order of occurrence.
+/// Simultaneous actions of the lifeline are kept together.
+pub fn project_multi_trace(multi_trace : &MultiTrace,
+                           lf_id : usize) -> Trace {
+    let mut local_trace : Trace = vec![];
+    for canal_trace in multi_trace {
+        for actions in canal_trace {
+            let local_actions : BTreeSet<TraceAction> = actions.iter()
+                .filter(|act| act.lf_id == lf_id)
+                .cloned()
+                .collect();
+            if !local_actions.is_empty() {
+                local_trace.push(local_actions);
+            }
+        }
+    }
+    local_trace
+}
+
+/// Writes the local trace of each lifeline of the signature into a '<trace_name>_<lifeline>.htf' file,
+/// lifelines without any action getting an empty trace.
+/// Returns the paths of the written files together with the lengths of the local traces.
+pub fn generate_lifeline_projections(gen_ctx : &GeneralContext,
+                                     multi_trace : &MultiTrace,
+                                     parent_folder : Option<&str>,
+                                     trace_name : &str) -> Vec<(String,usize)> {
+    let mut written = vec![];
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        let file_name = format!("{:}_{:}.{:}", trace_name, gen_ctx.get_lf_name(lf_id).unwrap(), HIBOU_TRACE_FILE_EXTENSION);
+        let path : PathBuf = match parent_folder {
+            None => {
+                [&file_name].iter().collect()
+            },
+            Some( parent ) => {
+                [parent, &file_name].iter().collect()
+            }
+        };
+        let local_trace = project_multi_trace(multi_trace,lf_id);
+        let local_length = local_trace.len();
+        write_multi_trace_into_file(path.as_path(),
+                                    gen_ctx,
+                                    &CoLocalizations::new(vec![btreeset!{lf_id}]),
+                                    &vec![local_trace]);
+        written.push((path.into_os_string().to_str().unwrap().to_string(),local_length));
+    }
+    written
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use crate::io::input::htf::implem::multitrace_from_text;
+    use crate::io::input::htf::interface::parse_htf_file;
+    use super::*;
+
+    #[test]
+    fn project_test() {
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b;c}".to_string()).unwrap();
+        let parse = |htf : &str| multitrace_from_text(&gen_ctx,&htf.to_string()).unwrap().1;
+        // simultaneous actions of different lifelines are split
+        let multi_trace = parse("{\n[a,b] a!m.{a!n,b?m}.b!n;\n[c] c?n.c?m\n}");
+        assert_eq!(project_multi_trace(&multi_trace,0), parse("a!m.a!n")[0]);
+        assert_eq!(project_multi_trace(&multi_trace,1), parse("b?m.b!n")[0]);
+        assert_eq!(project_multi_trace(&multi_trace,2), parse("c?n.c?m")[0]);
+        // the same local traces are obtained from a single trace
+        let single_trace = parse("a!m.b?m.c?n.a!n.b!n.c?m");
+        for lf_id in 0..3 {
+            assert_eq!(project_multi_trace(&single_trace,lf_id), project_multi_trace(&multi_trace,lf_id));
+        }
+        // every lifeline gets a file, which can be parsed back
+        let folder = std::env::temp_dir().join(format!("hibou_project_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        let written = generate_lifeline_projections(&gen_ctx,&parse("a!m.b?m"),folder.to_str(),"t");
+        assert_eq!(written.iter().map(|(_,len)| *len).collect::<Vec<usize>>(), vec![1,1,0]);
+        assert!(written[2].0.ends_with("t_c.htf"));
+        for (lf_id,(path,_)) in written.iter().enumerate() {
+            let (colocs,reparsed) = parse_htf_file(&gen_ctx,path).unwrap();
+            assert_eq!(colocs.locs_lf_ids[0], btreeset!{lf_id});
+            assert_eq!(reparsed[0], project_multi_trace(&parse("a!m.b?m"),lf_id));
+        }
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+}
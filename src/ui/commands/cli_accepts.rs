@@ -0,0 +1,82 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use clap::ArgMatches;
+
+use crate::core::execution::semantics::membership::{is_multi_trace_accepted, MembershipVerdict};
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::input::htf::interface::parse_htf_file;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_accepts(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int ) => {
+                    let htf_file_path = matches.value_of("htf").unwrap();
+                    match parse_htf_file(&gen_ctx,htf_file_path) {
+                        Err(e) => {
+                            return (vec![e.to_string()],1);
+                        },
+                        Ok( (co_localizations,multi_trace) ) => {
+                            let mut ret_print = vec![];
+                            ret_print.push( "".to_string());
+                            ret_print.push( format!("checking trace from file '{}'",htf_file_path) );
+                            ret_print.push( format!("w.r.t. interaction from file '{}'",hif_file_path) );
+                            ret_print.push( "".to_string());
+                            match is_multi_trace_accepted(&int,&co_localizations,&multi_trace) {
+                                MembershipVerdict::Accepted => {
+                                    ret_print.push( "ACCEPTED".to_string());
+                                },
+                                MembershipVerdict::Rejected(consumed) => {
+                                    ret_print.push( "REJECTED".to_string());
+                                    for (coloc_id,trace) in multi_trace.iter().enumerate() {
+                                        let coloc_consumed = *consumed.get(coloc_id).unwrap();
+                                        let on_component = if multi_trace.len() > 1 {
+                                            format!(" on component {:}", coloc_id)
+                                        } else {
+                                            "".to_string()
+                                        };
+                                        if coloc_consumed < trace.len() {
+                                            ret_print.push( format!("first action that could not be fired{:} is at index {:}", on_component, coloc_consumed));
+                                        } else if multi_trace.len() == 1 {
+                                            ret_print.push( "all actions could be fired but the interaction cannot terminate after them".to_string());
+                                        }
+                                    }
+                                }
+                            }
+                            ret_print.push( "".to_string());
+                            return (ret_print,0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -14,35 +14,45 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::path::Path;
 use std::time::Instant;
 
 use clap::ArgMatches;
 use graph_process_manager_core::delegate::delegate::GenericProcessDelegate;
 use graph_process_manager_core::manager::manager::GenericProcessManager;
+use graph_process_manager_core::queued_steps::queue::strategy::QueueSearchStrategy;
+use graphviz_dot_builder::traits::GraphVizOutputFormat;
 
+use crate::core::execution::semantics::coverage::get_multi_trace_coverage;
 use crate::core::execution::trace::multitrace::multi_trace_length;
 use crate::io::input::hcf::ana::interface::parse_hcf_file_for_ana;
 use crate::io::input::hcf::ana::options::HibouAnalyzeOptions;
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::io::input::htf::interface::parse_htf_file;
+use crate::io::output::draw_interactions::interface::draw_interaction_coverage;
+use crate::io::output::to_hfiles::trace::trace_action::trace_actions_as_htf_encoding;
 use crate::process::ana::conf::AnalysisConfig;
 use crate::process::ana::context::AnalysisContext;
+use crate::process::ana::loggers::prefix::AnalysisFurthestPrefixLogger;
 use crate::process::ana::node::flags::MultiTraceAnalysisFlags;
 use crate::process::ana::node::node::AnalysisNodeKind;
 use crate::process::ana::priorities::AnalysisPriorities;
 use crate::process::ana::step::AnalysisStepKind;
+use crate::process::ana::verdict::global::AnalysisGlobalVerdict;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
@@ -67,6 +77,20 @@ pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
                             } else {
                                 ana_opts = HibouAnalyzeOptions::default();
                             }
+                            let mut ana_opts = ana_opts;
+                            if matches.is_present("search") {
+                                match matches.value_of("search").unwrap() {
+                                    "bfs" => {
+                                        ana_opts.strategy = QueueSearchStrategy::BFS;
+                                    },
+                                    "dfs" => {
+                                        ana_opts.strategy = QueueSearchStrategy::DFS;
+                                    },
+                                    other => {
+                                        return (vec![format!("unknown search strategy : '{:}' (expected 'bfs' or 'dfs')", other)],1);
+                                    }
+                                }
+                            }
                             // ***
                             let multi_trace_length = multi_trace_length(&multi_trace);
                             let mut ret_print = vec![];
@@ -79,6 +103,8 @@ pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
                                 ret_print.push( "WARNING : uses experimental Partial Order Reduction that returns FALSE NEGATIVES when using synchronous operator".to_string() );
                             }
                             ret_print.push( "".to_string());
+                            // kept to locate the failure or to compute the coverage once the analysis is over
+                            let kept_data = (gen_ctx.clone(),int.clone(),co_localizations.clone(),multi_trace.clone());
                             // ***
                             let ana_ctx = AnalysisContext::new(gen_ctx,co_localizations,multi_trace,multi_trace_length);
                             let delegate : GenericProcessDelegate<AnalysisStepKind,AnalysisNodeKind,AnalysisPriorities> = GenericProcessDelegate::new(ana_opts.strategy,ana_opts.priorities);
@@ -96,11 +122,15 @@ pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
                                                                                    sim_config.get_reset_rem_act(multi_trace_length,&int));
                                 }
                             }
+                            // records the furthest prefix consumed by the analysis to locate a failure
+                            let mut loggers = ana_opts.loggers;
+                            let prefix_logger_id = loggers.len();
+                            loggers.push(Box::new(AnalysisFurthestPrefixLogger::new()));
                             let mut analysis_manager : GenericProcessManager<AnalysisConfig> = GenericProcessManager::new(ana_ctx,
                                                                                                                           ana_opts.ana_param,
-                                                                                                                                delegate,
+                                                                                                                          delegate,
                                                                                                                           ana_opts.filters,
-                                                                                                                          ana_opts.loggers,
+                                                                                                                          loggers,
                                                                                                                           ana_opts.goal,
                                                                                                                           ana_opts.use_memoization);
 
@@ -113,6 +143,46 @@ pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
                             ret_print.push( format!("verdict    : '{}'", verdict.to_string() ) );
                             ret_print.push( format!("node count : {:?}", node_count ) );
                             ret_print.push( format!("elapsed    : {:?}", elapsed_time.as_secs_f64() ) );
+                            let (gen_ctx,int,co_localizations,multi_trace) = kept_data;
+                            if verdict == AnalysisGlobalVerdict::Fail {
+                                let prefix_logger = analysis_manager.get_logger(prefix_logger_id).unwrap()
+                                    .as_any().downcast_ref::<AnalysisFurthestPrefixLogger>().unwrap();
+                                for (coloc_id,trace) in multi_trace.iter().enumerate() {
+                                    let coloc_consumed = *prefix_logger.furthest.get(coloc_id).unwrap();
+                                    if let Some(actions) = trace.get(coloc_consumed) {
+                                        ret_print.push( format!("no matching frontier action for '{}' at index {:} on component {:}",
+                                                                trace_actions_as_htf_encoding(&gen_ctx,actions),
+                                                                coloc_consumed,
+                                                                coloc_id) );
+                                    }
+                                }
+                            }
+                            if matches.is_present("coverage") && verdict == AnalysisGlobalVerdict::Pass {
+                                match get_multi_trace_coverage(&int,&co_localizations,&multi_trace) {
+                                    Err(e) => {
+                                        ret_print.push( format!("coverage   : {}", e) );
+                                    },
+                                    Ok( None ) => {
+                                        ret_print.push( "coverage   : no accepting path was found".to_string() );
+                                    },
+                                    Ok( Some(coverage) ) => {
+                                        let file_name = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+                                        let output_file_name = format!("{}_coverage", file_name);
+                                        if let Err(e) = draw_interaction_coverage(&gen_ctx,
+                                                                                  &int,
+                                                                                  &coverage.covered_positions,
+                                                                                  &GraphVizOutputFormat::png,
+                                                                                  &"temp".to_string(),
+                                                                                  &"".to_string(),
+                                                                                  &output_file_name) {
+                                            ret_print.push( format!("coverage   : {}", e) );
+                                        } else {
+                                            ret_print.push( format!("coverage   : {}/{} actions", coverage.num_covered_actions, coverage.num_actions) );
+                                            ret_print.push( format!("drawn on   : {}.png", output_file_name) );
+                                        }
+                                    }
+                                }
+                            }
                             return (ret_print,0);
                         }
                     }
@@ -120,4 +190,50 @@ pub fn cli_analyze(matches : &ArgMatches) -> (Vec<String>,u32) {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::App;
+    use super::*;
+
+    #[test]
+    fn analyze_failure_test() {
+        let root = std::env::temp_dir().join(format!("hibou_analyze_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let (hsf_path,hif_path) = (root.join("s.hsf"),root.join("i.hif"));
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b}").unwrap();
+        std::fs::write(&hif_path, "seq(a -- m -> b, b -- n -> a)").unwrap();
+        let analyze = |htf : &str, search : &str| {
+            let htf_path = root.join("t.htf");
+            std::fs::write(&htf_path, htf).unwrap();
+            let yaml = load_yaml!("../hibou_cli.yml");
+            let matches = App::from_yaml(yaml).get_matches_from(vec!["hibou_label", "analyze",
+                                                                      hsf_path.to_str().unwrap(),
+                                                                      hif_path.to_str().unwrap(),
+                                                                      htf_path.to_str().unwrap(),
+                                                                      "--search", search]);
+            cli_analyze(matches.subcommand_matches("analyze").unwrap())
+        };
+        for search in ["bfs","dfs"] {
+            let (got_print,got_code) = analyze("{[a] a!m.a?n ; [b] b?m.b!n}", search);
+            assert_eq!(got_code, 0);
+            assert!(got_print.contains(&"verdict    : 'Pass'".to_string()));
+            assert!(!got_print.iter().any(|line| line.starts_with("no matching frontier action")));
+            let failures = |got_print : &Vec<String>| -> Vec<String> {
+                got_print.iter().filter(|line| line.starts_with("no matching frontier action")).cloned().collect()
+            };
+            // 'm' is only emitted once
+            let (got_print,_) = analyze("a!m.a!m", search);
+            assert!(got_print.contains(&"verdict    : 'Fail'".to_string()));
+            assert_eq!(failures(&got_print), vec!["no matching frontier action for 'a!m' at index 1 on component 0".to_string()]);
+            // the failure is located on the furthest node the analysis explored
+            let (got_print,_) = analyze("{[a] a!m.a!m ; [b] b?m}", search);
+            assert!(got_print.contains(&"verdict    : 'Fail'".to_string()));
+            assert!(failures(&got_print).contains(&"no matching frontier action for 'a!m' at index 1 on component 0".to_string()));
+        }
+        assert_eq!(analyze("{[a] a!m ; [b] b?m}", "hcs").1, 1);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+}
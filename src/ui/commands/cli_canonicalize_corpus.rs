@@ -0,0 +1,203 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use serde_json::json;
+
+use crate::core::language::hash::canonical::HashedInteraction;
+use crate::core::language::syntax::metrics::InteractionMetrics;
+use crate::experiments::interaction_random_gen::interface::normalize_interaction;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::{get_hif_files_in_folder, parse_hif_file_with};
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+/// Groups the interactions of one or several folders into classes of interactions
+/// having the same normal form (see 'normalize_interaction').
+/// In each class, the interaction with the fewest symbols (the first one in case of a tie)
+/// is kept as representative and the others are reported as duplicates (and deleted if required).
+pub fn cli_canonicalize_corpus(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let folders : Vec<&str> = matches.values_of("folders").unwrap().collect();
+            let mut hif_paths : Vec<PathBuf> = vec![];
+            for folder in &folders {
+                match get_hif_files_in_folder(folder) {
+                    Err(e) => {
+                        return (vec![e],1);
+                    },
+                    Ok( got ) => {
+                        hif_paths.extend(got);
+                    }
+                }
+            }
+            if hif_paths.is_empty() {
+                return (vec![format!("no .{:} file in folders {:?}", HIBOU_INTERACTION_FILE_EXTENSION, folders)],1);
+            }
+            // ***
+            // all the files are parsed before any of them is deleted
+            let mut classes : Vec<Vec<usize>> = vec![];
+            let mut class_of_form : HashMap<HashedInteraction,usize> = HashMap::new();
+            let mut sizes : Vec<u32> = vec![];
+            for (x,path) in hif_paths.iter().enumerate() {
+                match parse_hif_file_with(&gen_ctx,path.to_str().unwrap(),&include_ctx) {
+                    Err(e) => {
+                        return (vec![format!("in file '{:}' : {:}", path.display(), e)],1);
+                    },
+                    Ok( int ) => {
+                        sizes.push( InteractionMetrics::extract_from_interaction(&int).get_num_symbols() );
+                        let form = HashedInteraction::new(normalize_interaction(&gen_ctx,&int));
+                        match class_of_form.get(&form) {
+                            None => {
+                                class_of_form.insert(form, classes.len());
+                                classes.push(vec![x]);
+                            },
+                            Some( class_id ) => {
+                                classes[*class_id].push(x);
+                            }
+                        }
+                    }
+                }
+            }
+            // ***
+            let delete = matches.is_present("delete");
+            let mut mapping = vec![];
+            let mut num_duplicates = 0;
+            for class in &classes {
+                if class.len() < 2 {
+                    continue;
+                }
+                // min_by_key returns the first of the minimal elements
+                let kept = *class.iter().min_by_key(|x| sizes[**x]).unwrap();
+                let duplicates : Vec<&PathBuf> = class.iter().filter(|x| **x != kept).map(|x| &hif_paths[*x]).collect();
+                num_duplicates += duplicates.len();
+                if delete {
+                    for path in &duplicates {
+                        if let Err(e) = fs::remove_file(path) {
+                            return (vec![format!("could not delete file '{:}' : {:}", path.display(), e)],1);
+                        }
+                    }
+                }
+                mapping.push(json!({
+                    "kept" : hif_paths[kept].to_string_lossy(),
+                    "duplicates" : duplicates.iter().map(|path| path.to_string_lossy()).collect::<Vec<_>>()
+                }));
+            }
+            // ***
+            let output_file_name : String;
+            if matches.is_present("output") {
+                let extracted = matches.value_of("output").unwrap();
+                output_file_name = extracted.to_string();
+            } else {
+                let folder_name = Path::new(folders[0]).file_name().map_or("interactions".to_string(), |x| x.to_str().unwrap().to_string());
+                output_file_name = format!("{}_classes", folder_name);
+            }
+            let output_file_path = format!("{}.json", output_file_name);
+            let mapping_json = json!({
+                "signature" : hsf_file_path,
+                "folders" : folders,
+                "num_files" : hif_paths.len(),
+                "num_classes" : classes.len(),
+                "deleted" : delete,
+                "classes" : mapping
+            });
+            if let Err(e) = fs::write(&output_file_path, serde_json::to_string_pretty(&mapping_json).unwrap()) {
+                return (vec![format!("could not write mapping file '{:}' : {:}", output_file_path, e)],1);
+            }
+            // ***
+            let mut ret_print = vec![];
+            ret_print.push( "".to_string());
+            ret_print.push( "CANONICALIZING CORPUS".to_string());
+            ret_print.push( format!("from folders {:?}",folders) );
+            ret_print.push( format!("{} interactions in {} equivalence classes",hif_paths.len(),classes.len()) );
+            if delete {
+                ret_print.push( format!("deleted {} duplicates",num_duplicates) );
+            } else {
+                ret_print.push( format!("found {} duplicates (use --delete to remove them)",num_duplicates) );
+            }
+            ret_print.push( format!("mapping on file : {}",output_file_path) );
+            ret_print.push( "".to_string());
+            return (ret_print,0);
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use clap::App;
+    use super::*;
+
+    #[test]
+    fn canonicalize_corpus_test() {
+        let root = std::env::temp_dir().join(format!("hibou_canonicalize_corpus_test_{:}", std::process::id()));
+        let (folder1,folder2) = (root.join("f1"),root.join("f2"));
+        fs::create_dir_all(&folder1).unwrap();
+        fs::create_dir_all(&folder2).unwrap();
+        let hsf_path = root.join("s.hsf");
+        fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b}").unwrap();
+        fs::write(folder1.join("x1.hif"), "par(a -- m -> b, b -- n -> a)").unwrap();
+        fs::write(folder1.join("x2.hif"), "seq(a -- m -> b, b -- n -> a)").unwrap();
+        // equal to 'x1' up to commutativity and neutrality of 'empty', with more symbols
+        fs::write(folder2.join("y1.hif"), "par(b -- n -> a, seq(o, a -- m -> b))").unwrap();
+        fs::write(folder2.join("y2.hif"), "alt(b -- n -> a, b -- n -> a)").unwrap();
+        let output = root.join("classes");
+        let canonicalize = |options : &[&str]| {
+            let mut args = vec!["hibou_label", "canonicalize_corpus", hsf_path.to_str().unwrap(),
+                                folder1.to_str().unwrap(), folder2.to_str().unwrap(), "-o", output.to_str().unwrap()];
+            args.extend_from_slice(options);
+            let yaml = load_yaml!("../hibou_cli.yml");
+            let matches = App::from_yaml(yaml).get_matches_from(args);
+            cli_canonicalize_corpus(matches.subcommand_matches("canonicalize_corpus").unwrap())
+        };
+        let read_mapping = || -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(root.join("classes.json")).unwrap()).unwrap()
+        };
+        let (got_print,got_code) = canonicalize(&[]);
+        assert_eq!(got_code, 0);
+        assert!(got_print.contains(&"4 interactions in 3 equivalence classes".to_string()));
+        let mapping = read_mapping();
+        assert_eq!(mapping["num_classes"], 3);
+        assert_eq!(mapping["deleted"], false);
+        let classes = mapping["classes"].as_array().unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0]["kept"], folder1.join("x1.hif").to_string_lossy().to_string());
+        assert_eq!(classes[0]["duplicates"], json!([folder2.join("y1.hif").to_string_lossy()]));
+        assert!(folder2.join("y1.hif").exists());
+        // ***
+        let (_,got_code) = canonicalize(&["--delete"]);
+        assert_eq!(got_code, 0);
+        assert_eq!(read_mapping()["deleted"], true);
+        assert!(!folder2.join("y1.hif").exists());
+        assert!(folder1.join("x1.hif").exists() && folder1.join("x2.hif").exists() && folder2.join("y2.hif").exists());
+        let (got_print,_) = canonicalize(&[]);
+        assert!(got_print.contains(&"3 interactions in 3 equivalence classes".to_string()));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -23,7 +23,7 @@ use graph_process_manager_core::manager::manager::GenericProcessManager;
 use graph_process_manager_core::queued_steps::queue::strategy::QueueSearchStrategy;
 use crate::io::input::hcf::canon::interface::parse_hcf_file_for_canonize;
 use crate::io::input::hcf::canon::options::HibouCanonizeOptions;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::io::input::hsf::interface::parse_hsf_file;
 use crate::process::canon::conf::CanonizationConfig;
 use crate::process::canon::context::CanonizationContext;
@@ -32,17 +32,19 @@ use crate::process::canon::param::default::DefaultCanonizationProcess;
 use crate::process::canon::param::phase::CanonizationParameterization;
 use crate::process::canon::priorities::CanonizationPriorities;
 use crate::process::canon::step::CanonizationStepKind;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_canonize(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
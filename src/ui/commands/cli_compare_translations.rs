@@ -0,0 +1,174 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use autour_core::nfa::nfa::AutNFA;
+use clap::ArgMatches;
+
+use crate::core::execution::trace::trace::TraceAction;
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::interaction::Interaction;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::trace::trace_action::trace_actions_as_htf_encoding;
+use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
+use crate::nfa_translation::equiv::{get_language_difference_witness, LanguageDifferenceWitness};
+use crate::nfa_translation::experiments::TimingStats;
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+type NfaTranslation = fn(&GeneralContext,&Interaction,Vec<BTreeSet<TraceAction>>) -> (AutNFA<usize>,Duration);
+
+/// Translates the interaction 'repetitions' times, returning the last NFA together with its size and the measured times.
+fn run_translation(translation : NfaTranslation,
+                   gen_ctx : &GeneralContext,
+                   int : &Interaction,
+                   alphabet : &Vec<BTreeSet<TraceAction>>,
+                   repetitions : usize) -> (AutNFA<usize>,usize,usize,TimingStats) {
+    let mut got_nfa = AutNFA::new_void_object(hashset!{0});
+    let mut times = vec![];
+    for _ in 0..repetitions {
+        let (nfa,duration) = translation(gen_ctx,int,alphabet.clone());
+        times.push(duration.as_micros());
+        got_nfa = nfa;
+    }
+    let num_states = got_nfa.transitions.len();
+    let num_transitions = got_nfa.transitions.iter()
+        .fold(0, |acc, x| acc + x.values().fold(0, |acc2, targets| acc2 + targets.len()));
+    (got_nfa,num_states,num_transitions,TimingStats::from_samples(times))
+}
+
+/// Runs both the operational (exploration) and compositional translations on the same interaction,
+/// which are the two translations measured by the nfa experiments, checks that they yield NFAs
+/// accepting the same language and compares their sizes and timings.
+pub fn cli_compare_translations(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int ) => {
+                    let repetitions : usize = match matches.value_of("tries") {
+                        None => {
+                            5
+                        },
+                        Some( as_str ) => {
+                            match as_str.trim().parse::<usize>() {
+                                Ok( got ) if got > 0 => {
+                                    got
+                                },
+                                _ => {
+                                    return (vec![format!("number of tries must be a positive integer : '{:}'", as_str)],1);
+                                }
+                            }
+                        }
+                    };
+                    let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+                    let (op_nfa,op_states,op_transitions,op_stats) = run_translation(get_nfa_from_interaction_exploration,
+                                                                                      &gen_ctx, &int, &alphabet, repetitions);
+                    let (cmp_nfa,cmp_states,cmp_transitions,cmp_stats) = run_translation(get_nfa_from_interaction_via_composition,
+                                                                                          &gen_ctx, &int, &alphabet, repetitions);
+                    // ***
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "COMPARING NFA TRANSLATIONS".to_string());
+                    ret_print.push( format!("of interaction from file '{}'",hif_file_path) );
+                    ret_print.push( format!("timings over {:} repetitions :", repetitions) );
+                    ret_print.push( format!("operational   : {:} states, {:} transitions, {:}", op_states, op_transitions, op_stats) );
+                    ret_print.push( format!("compositional : {:} states, {:} transitions, {:}", cmp_states, cmp_transitions, cmp_stats) );
+                    if op_stats.median <= cmp_stats.median {
+                        ret_print.push( "operational translation is the fastest (by median time)".to_string() );
+                    } else {
+                        ret_print.push( "compositional translation is the fastest (by median time)".to_string() );
+                    }
+                    // ***
+                    let ret_code = match get_language_difference_witness(&op_nfa,&cmp_nfa) {
+                        None => {
+                            ret_print.push( "both NFAs accept the same language".to_string() );
+                            0
+                        },
+                        Some( witness ) => {
+                            let trace : Vec<String> = witness.get_word().iter()
+                                .map(|letter| trace_actions_as_htf_encoding(&gen_ctx, &alphabet[*letter]))
+                                .collect();
+                            let accepting = match witness {
+                                LanguageDifferenceWitness::AcceptedByLeftOnly(_) => "operational",
+                                LanguageDifferenceWitness::AcceptedByRightOnly(_) => "compositional"
+                            };
+                            ret_print.push( "the NFAs accept different languages".to_string() );
+                            ret_print.push( format!("witness trace accepted only by the {:} NFA : '{:}'", accepting, trace.join(".")) );
+                            1
+                        }
+                    };
+                    ret_print.push( "".to_string());
+                    return (ret_print,ret_code);
+                }
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use clap::App;
+    use super::*;
+
+    #[test]
+    fn compare_translations_test() {
+        let root = std::env::temp_dir().join(format!("hibou_compare_translations_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let (hsf_path,hif_path) = (root.join("s.hsf"),root.join("i.hif"));
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b;c}").unwrap();
+        let compare = |hif : &str, tries : &str| {
+            std::fs::write(&hif_path, hif).unwrap();
+            let yaml = load_yaml!("../hibou_cli.yml");
+            let matches = App::from_yaml(yaml).get_matches_from(vec!["hibou_label", "compare_translations",
+                                                                      hsf_path.to_str().unwrap(),
+                                                                      hif_path.to_str().unwrap(),
+                                                                      "--tries", tries]);
+            cli_compare_translations(matches.subcommand_matches("compare_translations").unwrap())
+        };
+        // interactions within the fragment supported by the compositional translation
+        for hif in ["seq(a -- m -> b, b -- n -> c)",
+                    "par(loopS(a -- m -> b), alt(c -- n -> a, o))",
+                    "strict(loopS(alt(a -- m -> b, b -- n ->|)), par(b -- m -> c, c -- n -> b))"] {
+            let (got_print,got_code) = compare(hif, "2");
+            assert_eq!(got_code, 0);
+            assert!(got_print.contains(&"timings over 2 repetitions :".to_string()));
+            assert!(got_print.contains(&"both NFAs accept the same language".to_string()));
+            assert_eq!(got_print.iter().filter(|line| line.ends_with("translation is the fastest (by median time)")).count(), 1);
+        }
+        let (got_print,got_code) = compare("a -- m -> b", "0");
+        assert_eq!(got_code, 1);
+        assert_eq!(got_print, vec!["number of tries must be a positive integer : '0'".to_string()]);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -18,22 +18,25 @@ limitations under the License.
 use std::path::Path;
 
 use clap::ArgMatches;
+use graphviz_dot_builder::traits::GraphVizOutputFormat;
 
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
-use crate::io::output::draw_interactions::interface::{InteractionGraphicalRepresentation,draw_interaction};
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::draw_interactions::interface::{InteractionGraphicalRepresentation,draw_interaction_with_format};
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 
 pub fn cli_draw(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
@@ -65,13 +68,55 @@ pub fn cli_draw(matches : &ArgMatches) -> (Vec<String>,u32) {
                         output_file_name = format!("{}_repr", file_name);
                     }
                     // ***
-                    draw_interaction(&gen_ctx, &int, &rep_kind, &"temp".to_string(), &"".to_string(), &output_file_name);
+                    let output_format = match matches.value_of("format") {
+                        None => {
+                            GraphVizOutputFormat::png
+                        },
+                        Some( as_str ) => {
+                            match as_str.trim() {
+                                "png" => GraphVizOutputFormat::png,
+                                "svg" => GraphVizOutputFormat::svg,
+                                _ => {
+                                    return (vec![format!("unknown output format : '{:}'", as_str)],1);
+                                }
+                            }
+                        }
+                    };
+                    let mut lifeline_order : Option<Vec<usize>> = None;
+                    if let Some(extracted) = matches.value_of("lifeline_order") {
+                        let mut order = vec![];
+                        for lf_name in extracted.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                            match gen_ctx.get_lf_id(lf_name) {
+                                None => {
+                                    return (vec![format!("unknown lifeline : '{:}'", lf_name)],1);
+                                },
+                                Some( lf_id ) => {
+                                    if order.contains(&lf_id) {
+                                        return (vec![format!("lifeline '{:}' appears more than once in the lifeline order", lf_name)],1);
+                                    }
+                                    order.push(lf_id);
+                                }
+                            }
+                        }
+                        lifeline_order = Some(order);
+                    }
+                    // ***
+                    if let Err(e) = draw_interaction_with_format(&gen_ctx,
+                                                                 &int,
+                                                                 &rep_kind,
+                                                                 &lifeline_order,
+                                                                 &output_format,
+                                                                 &"temp".to_string(),
+                                                                 &"".to_string(),
+                                                                 &output_file_name) {
+                        return (vec![e],1);
+                    }
                     // ***
                     let mut ret_print = vec![];
                     ret_print.push( "".to_string());
                     ret_print.push( "DRAWING INTERACTION".to_string());
                     ret_print.push( format!("from file '{}'",hif_file_path) );
-                    ret_print.push( format!("on file : {}",output_file_name) );
+                    ret_print.push( format!("on file : {}.{}",output_file_name,output_format) );
                     ret_print.push( "".to_string());
                     return (ret_print,0);
                 }
@@ -0,0 +1,132 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use clap::ArgMatches;
+use serde_json::json;
+
+use crate::experiments::exec_tree::explore_execution_tree;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::interaction::to_sexpr::interaction_to_sexpr;
+use crate::io::output::to_hfiles::trace::trace_action::trace_actions_as_htf_encoding;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+fn parse_bound(matches : &ArgMatches, name : &str, default : usize) -> Result<usize,String> {
+    match matches.value_of(name) {
+        None => {
+            Ok( default )
+        },
+        Some( as_str ) => {
+            match as_str.trim().parse::<usize>() {
+                Ok( got ) => {
+                    Ok( got )
+                },
+                Err(_) => {
+                    Err( format!("{:} must be a non-negative integer : '{:}'", name, as_str) )
+                }
+            }
+        }
+    }
+}
+
+/// Writes the execution tree of an interaction (see 'explore_execution_tree') as JSON Lines,
+/// one line per node, residual interactions being given as S-expressions.
+pub fn cli_exec_tree(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int ) => {
+                    let max_depth = match parse_bound(matches,"max_depth",10) {
+                        Err(e) => {
+                            return (vec![e],1);
+                        },
+                        Ok( got ) => {
+                            got as u32
+                        }
+                    };
+                    let max_nodes = match parse_bound(matches,"max_nodes",10000) {
+                        Err(e) => {
+                            return (vec![e],1);
+                        },
+                        Ok( got ) => {
+                            got.max(1)
+                        }
+                    };
+                    let tree = explore_execution_tree(&int,max_depth,max_nodes);
+                    // ***
+                    let output_file_path : String;
+                    if matches.is_present("output") {
+                        output_file_path = format!("{}.jsonl", matches.value_of("output").unwrap());
+                    } else {
+                        let file_name = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+                        output_file_path = format!("{}_exec_tree.jsonl", file_name);
+                    }
+                    let mut writer = match File::create(&output_file_path) {
+                        Err(e) => {
+                            return (vec![format!("could not create file '{:}' : {:}", output_file_path, e)],1);
+                        },
+                        Ok( file ) => {
+                            BufWriter::new(file)
+                        }
+                    };
+                    for step in &tree.steps {
+                        let line = json!({
+                            "parent_id" : step.parent_id,
+                            "action" : step.action.as_ref().map(|actions| trace_actions_as_htf_encoding(&gen_ctx,actions)),
+                            "child_id" : step.child_id,
+                            "is_accepting" : step.is_accepting,
+                            "interaction" : interaction_to_sexpr(&gen_ctx,&step.interaction)
+                        });
+                        if let Err(e) = writeln!(writer, "{}", line) {
+                            return (vec![format!("could not write to file '{:}' : {:}", output_file_path, e)],1);
+                        }
+                    }
+                    if let Err(e) = writer.flush() {
+                        return (vec![format!("could not write to file '{:}' : {:}", output_file_path, e)],1);
+                    }
+                    // ***
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "EXPLORING EXECUTION TREE".to_string());
+                    ret_print.push( format!("of interaction from file '{}'",hif_file_path) );
+                    ret_print.push( format!("{} nodes of which {} accepting", tree.steps.len(), tree.steps.iter().filter(|step| step.is_accepting).count()) );
+                    if tree.truncated {
+                        ret_print.push( format!("truncated by the bounds (max depth {}, max nodes {})", max_depth, max_nodes) );
+                    }
+                    ret_print.push( format!("on file : {}",output_file_path) );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -26,23 +26,25 @@ use crate::io::input::hcf::explo::interface::parse_hcf_file_for_explore;
 use crate::io::input::hcf::explo::options::HibouExploreOptions;
 
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::process::explo::conf::ExplorationConfig;
 use crate::process::explo::context::{ExplorationContext, ExplorationParameterization};
 use crate::process::explo::node::ExplorationNodeKind;
 use crate::process::explo::priorities::ExplorationPriorities;
 use crate::process::explo::step::ExplorationStepKind;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_explore(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
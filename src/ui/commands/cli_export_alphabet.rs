@@ -0,0 +1,56 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::path::Path;
+use clap::ArgMatches;
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::nfa_translation::alphabet::{get_alphabet_from_gen_ctx, write_alphabet_file, ALPHABET_ENCODING_VERSION};
+
+
+pub fn cli_export_alphabet(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let output_name = match matches.value_of("output") {
+                None => {
+                    Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap().to_string()
+                },
+                Some( got ) => {
+                    got.to_string()
+                }
+            };
+            let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+            match write_alphabet_file(&gen_ctx,&alphabet,&output_name) {
+                Err(e) => {
+                    return (vec![format!("could not write alphabet : {:}", e)],1);
+                },
+                Ok( file_name ) => {
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("alphabet of the NFAs translated from interactions over signature '{:}' : {:} letters", hsf_file_path, alphabet.len()) );
+                    ret_print.push( format!("written in '{:}' (encoding version {:})", file_name, ALPHABET_ENCODING_VERSION) );
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -15,27 +15,33 @@ limitations under the License.
 */
 
 
+use std::fs::File;
+use std::io::Write;
+
 use autour_core::nfa::nfa::AutNFA;
 use autour_core::traits::translate::AutTranslatable;
 use clap::ArgMatches;
 use crate::core::language::syntax::metrics::InteractionMetrics;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::io::input::hsf::interface::parse_hsf_file;
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::canonize::canonize_interaction;
 use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
 use crate::process::canon::param::default::DefaultCanonizationProcess;
+use crate::ui::commands::cli_rng_gen_raw_interactions::get_basic_blocks_summary;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_get_metrics(matches : &ArgMatches) -> (Vec<String>, u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
@@ -59,6 +65,18 @@ pub fn cli_get_metrics(matches : &ArgMatches) -> (Vec<String>, u32) {
                             ret_print.push( "GETTING INTERACTION METRICS".to_string());
                             let imetrics = InteractionMetrics::extract_from_interaction(&int);
                             ret_print.append(&mut imetrics.string_summary());
+                            if let Some(json_file_path) = matches.value_of("json") {
+                                let written = File::create(json_file_path)
+                                    .and_then(|mut file| file.write_all(serde_json::to_string_pretty(&imetrics.to_json()).unwrap().as_bytes()));
+                                if let Err(e) = written {
+                                    return (vec![format!("could not write json file '{:}' : {:}", json_file_path, e)],1);
+                                }
+                                ret_print.push( format!("written on file : {}",json_file_path) );
+                            }
+                            // the counts are those of the generated interaction, before canonization
+                            if let Some(summary) = get_basic_blocks_summary(hif_file_path) {
+                                ret_print.push( summary );
+                            }
                         },
                         "operatNFA" => {
                             let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
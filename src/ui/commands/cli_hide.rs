@@ -0,0 +1,85 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::core::transformation::hiding::hide;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+
+pub fn cli_hide(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let mut hidden : HashSet<usize> = HashSet::new();
+                    let mut hidden_names : Vec<&str> = vec![];
+                    for lf_name in matches.value_of("lifelines").unwrap().split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                        match gen_ctx.get_lf_id(lf_name) {
+                            None => {
+                                return (vec![format!("unknown lifeline : '{:}'", lf_name)],1);
+                            },
+                            Some( lf_id ) => {
+                                hidden.insert(lf_id);
+                                hidden_names.push(lf_name);
+                            }
+                        }
+                    }
+                    let projected = hide(&int,&hidden,&gen_ctx);
+                    // ***
+                    let output_file_name : String;
+                    if matches.is_present("output") {
+                        let extracted = matches.value_of("output").unwrap();
+                        output_file_name = extracted.to_string();
+                    } else {
+                        let file_name = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+                        output_file_name = format!("{}_hidden", file_name);
+                    }
+                    let output_file_path = format!("{}.{}", output_file_name, HIBOU_INTERACTION_FILE_EXTENSION);
+                    interaction_to_hif(Path::new(&output_file_path), &gen_ctx, &projected);
+                    // ***
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "HIDING LIFELINES".to_string());
+                    ret_print.push( format!("from file '{}'",hif_file_path) );
+                    ret_print.push( format!("hidden lifelines : {}",hidden_names.join(",")) );
+                    ret_print.push( format!("on file : {}",output_file_path) );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::path::{Path, PathBuf};
+
+use autour_core::traits::repr::AbstractLanguagePrinter;
+use clap::ArgMatches;
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
+use crate::nfa_translation::equiv::{get_language_difference_witness, CanonicalDfa, LanguageDifferenceWitness};
+use crate::nfa_translation::equiv_cache::{CachedLanguage, LanguageSignatureCache, LanguageSignatureLookup};
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_lang_equiv(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let left_file_path = matches.value_of("hif1").unwrap();
+            let right_file_path = matches.value_of("hif2").unwrap();
+            let left_int = match parse_hif_file_with(&gen_ctx,left_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {int}
+            };
+            let right_int = match parse_hif_file_with(&gen_ctx,right_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {int}
+            };
+            let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+            let translate = |int| {
+                if matches.is_present("compositional") {
+                    get_nfa_from_interaction_via_composition(&gen_ctx,int,alphabet.clone()).0
+                } else {
+                    get_nfa_from_interaction_exploration(&gen_ctx,int,alphabet.clone()).0
+                }
+            };
+
+            let mut ret_print = vec![];
+            ret_print.push( "".to_string());
+            ret_print.push( format!("comparing the trace languages of interactions from files '{}' and '{}'",left_file_path,right_file_path) );
+
+            // with the cache, equivalent interactions are recognized from their canonical DFAs
+            // and the NFAs are only needed to find a distinguishing trace
+            let mut nfas = [None,None];
+            if let Some(cache_folder) = matches.value_of("cache_dir") {
+                let cache = match LanguageSignatureCache::new(PathBuf::from(cache_folder)) {
+                    Err(e) => {
+                        return (vec![format!("could not create cache folder : {:}", e)],1);
+                    },
+                    Ok( got ) => {
+                        got
+                    }
+                };
+                let alphabet_hash = LanguageSignatureCache::get_alphabet_hash(&alphabet);
+                let mut languages = vec![];
+                let (mut num_hits, mut num_stale) = (0,0);
+                for (idx,(file_path,int)) in [(left_file_path,&left_int),(right_file_path,&right_int)].into_iter().enumerate() {
+                    match cache.lookup(Path::new(file_path),int,alphabet_hash) {
+                        LanguageSignatureLookup::Hit(language) => {
+                            languages.push(language);
+                            num_hits += 1;
+                            continue;
+                        },
+                        LanguageSignatureLookup::Stale => {
+                            num_stale += 1;
+                        },
+                        LanguageSignatureLookup::Miss => {}
+                    }
+                    let nfa = translate(int);
+                    let dfa = CanonicalDfa::from_nfa(&nfa);
+                    let language = CachedLanguage{signature:dfa.get_signature(),dfa};
+                    if let Err(e) = cache.store(Path::new(file_path),int,alphabet_hash,&language) {
+                        return (vec![format!("could not write into cache folder '{:}' : {:}", cache.folder.display(), e)],1);
+                    }
+                    languages.push(language);
+                    nfas[idx] = Some(nfa);
+                }
+                ret_print.push( format!("languages read from cache '{}' for {} out of 2 interactions ({} stale entries recomputed)",
+                                        cache.folder.display(), num_hits, num_stale) );
+                // the signatures are only compared first, the canonical DFAs being equal if and only if the languages are
+                if languages[0].signature == languages[1].signature && languages[0].dfa == languages[1].dfa {
+                    ret_print.push( "".to_string());
+                    ret_print.push( "EQUIVALENT".to_string() );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+            let [left_nfa,right_nfa] = nfas;
+            let left_nfa = left_nfa.unwrap_or_else(|| translate(&left_int));
+            let right_nfa = right_nfa.unwrap_or_else(|| translate(&right_int));
+
+            ret_print.push( "".to_string());
+            match get_language_difference_witness(&left_nfa,&right_nfa) {
+                None => {
+                    ret_print.push( "EQUIVALENT".to_string() );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                },
+                Some( witness ) => {
+                    let (accepted_by,rejected_by) = match witness {
+                        LanguageDifferenceWitness::AcceptedByLeftOnly(_) => (left_file_path,right_file_path),
+                        LanguageDifferenceWitness::AcceptedByRightOnly(_) => (right_file_path,left_file_path)
+                    };
+                    let printer = ActionNFAITPrinter::new(alphabet,gen_ctx);
+                    let trace = if witness.get_word().is_empty() {
+                        printer.get_epsilon_symbol(false).to_string()
+                    } else {
+                        witness.get_word().iter()
+                            .map(|letter| printer.get_letter_string_repr(letter))
+                            .collect::<Vec<String>>()
+                            .join(printer.get_concatenation_separator(false))
+                    };
+                    ret_print.push( "NOT EQUIVALENT".to_string() );
+                    ret_print.push( format!("shortest trace accepted by '{}' but not by '{}' :", accepted_by, rejected_by) );
+                    ret_print.push( trace );
+                    ret_print.push( "".to_string());
+                    return (ret_print,1);
+                }
+            }
+        }
+    }
+}
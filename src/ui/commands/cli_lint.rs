@@ -0,0 +1,62 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use clap::ArgMatches;
+
+use crate::core::language::lint::lint_interaction;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_lint(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let warnings = lint_interaction(&gen_ctx,&int);
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "LINTING INTERACTION".to_string());
+                    ret_print.push( format!("from file '{}'",hif_file_path) );
+                    ret_print.push( format!("on file : {}",hsf_file_path) );
+                    ret_print.push( "".to_string());
+                    if warnings.is_empty() {
+                        ret_print.push( "no warning".to_string());
+                    } else {
+                        for warning in &warnings {
+                            ret_print.push( format!("warning {:}", warning.describe(&gen_ctx)) );
+                        }
+                        ret_print.push( "".to_string());
+                        ret_print.push( format!("{:} warnings", warnings.len()) );
+                    }
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -15,43 +15,137 @@ limitations under the License.
 */
 
 
-use std::collections::HashSet;
-use std::fs::write;
+
 use std::path::Path;
-use std::time::Instant;
-use clap::App;
+
 use clap::ArgMatches;
 
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::syntax::util::fold_recursive_frags::{fold_recursive_alt_frags, fold_recursive_par_frags, fold_recursive_seq_frags, fold_recursive_strict_frags};
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::{get_hif_files_in_folder, parse_hif_file_with};
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::ui::util::include_context::get_hif_include_context;
 
-pub fn cli_merge(matches : &ArgMatches) -> (Vec<String>,u32) {
 
+pub fn cli_merge(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
-    match parse_hsf_file(hsf_file_path,&ProcessKind::None) {
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
         Err(e) => {
-            ret_print.push( e.to_string() );
-            print_retval(ret_print);
-            return -1;
+            return (vec![e.to_string()],1);
         },
-        Ok( (gen_ctx,my_int,hoptions) ) => {
-            let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
-            //
-            let opt_returns : bool = matches.is_present("returns");
-            let opt_complete : bool = matches.is_present("complete");
-            let opt_graphic : bool = matches.is_present("graphic");
+        Ok( gen_ctx ) => {
+            let folder = matches.value_of("folder").unwrap();
+            let hif_paths = match get_hif_files_in_folder(folder) {
+                Err(e) => {
+                    return (vec![e],1);
+                },
+                Ok( got ) => {
+                    got
+                }
+            };
+            if hif_paths.is_empty() {
+                return (vec![format!("no .{:} file in folder '{:}'", HIBOU_INTERACTION_FILE_EXTENSION, folder)],1);
+            }
             // ***
+            let op_name = matches.value_of("op").unwrap_or("alt");
+            let fold : fn(&mut Vec<&Interaction>) -> Interaction = match op_name {
+                "alt" => fold_recursive_alt_frags,
+                "par" => fold_recursive_par_frags,
+                "strict" => fold_recursive_strict_frags,
+                "seq" => fold_recursive_seq_frags,
+                _ => {
+                    return (vec![format!("unknown combining operator : '{:}' (expected alt, par, strict or seq)", op_name)],1);
+                }
+            };
             // ***
-            if my_int.has_coregions() {
-                ret_print.push( "Interaction term has coregions -> Not Implemented".to_string() );
-                print_retval(ret_print);
-                return -1;
+            let mut ints : Vec<Interaction> = vec![];
+            for path in &hif_paths {
+                // references to lifelines or messages outside of the shared signature are parsing errors
+                match parse_hif_file_with(&gen_ctx,path.to_str().unwrap(),&include_ctx) {
+                    Err(e) => {
+                        return (vec![format!("in file '{:}' : {:}", path.display(), e)],1);
+                    },
+                    Ok( int ) => {
+                        ints.push(int);
+                    }
+                }
             }
-            merge_process_interaction_term(&my_int,&gen_ctx,opt_returns,opt_complete,opt_graphic,&file_name);
+            let merged = fold(&mut ints.iter().collect());
             // ***
+            let output_file_name : String;
+            if matches.is_present("output") {
+                let extracted = matches.value_of("output").unwrap();
+                output_file_name = extracted.to_string();
+            } else {
+                let folder_name = Path::new(folder).file_name().map_or("interactions".to_string(), |x| x.to_str().unwrap().to_string());
+                output_file_name = format!("{}_merged", folder_name);
+            }
+            let output_file_path = format!("{}.{}", output_file_name, HIBOU_INTERACTION_FILE_EXTENSION);
+            interaction_to_hif(Path::new(&output_file_path), &gen_ctx, &merged);
+            // ***
+            let mut ret_print = vec![];
             ret_print.push( "".to_string());
-            ret_print.push( "MERGING process for INTERACTION".to_string());
-            ret_print.push( format!("from file '{}'",hsf_file_path) );
-            ret_print.push( "".to_string());
+            ret_print.push( "MERGING INTERACTIONS".to_string());
+            ret_print.push( format!("from folder '{}'",folder) );
+            ret_print.push( format!("{} interactions combined with {}",ints.len(),op_name) );
+            ret_print.push( format!("on file : {}",output_file_path) );
             ret_print.push( "".to_string());
+            return (ret_print,0);
         }
     }
-}
\ No newline at end of file
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use clap::App;
+    use crate::experiments::interaction_random_gen::interface::normalize_interaction;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hif::interface::parse_hif_file;
+    use super::*;
+
+    #[test]
+    fn merge_test() {
+        let root = std::env::temp_dir().join(format!("hibou_merge_test_{:}", std::process::id()));
+        let folder = root.join("ints");
+        std::fs::create_dir_all(&folder).unwrap();
+        let hsf_path = root.join("s.hsf");
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b}").unwrap();
+        std::fs::write(folder.join("i10.hif"), "b -- n -> a").unwrap();
+        std::fs::write(folder.join("i2.hif"), "seq(a -- m -> b, b -- m -> a)").unwrap();
+        std::fs::write(folder.join("i1.hif"), "a -- m -> b").unwrap();
+        let output = root.join("merged");
+        let merge = |op : &str| {
+            let yaml = load_yaml!("../hibou_cli.yml");
+            let matches = App::from_yaml(yaml).get_matches_from(vec!["hibou_label", "merge", hsf_path.to_str().unwrap(),
+                                                                      folder.to_str().unwrap(), "--op", op, "-o", output.to_str().unwrap()]);
+            cli_merge(matches.subcommand_matches("merge").unwrap())
+        };
+        let gen_ctx = parse_hsf_file(hsf_path.to_str().unwrap()).unwrap();
+        let output_path = root.join("merged.hif");
+        // the interactions are combined in the order of the numbers ending their names
+        let (got_print,got_code) = merge("strict");
+        assert_eq!(got_code, 0);
+        assert!(got_print.contains(&"3 interactions combined with strict".to_string()));
+        let merged = parse_hif_file(&gen_ctx, output_path.to_str().unwrap()).unwrap();
+        assert_eq!(merged, parse_hif_string(&gen_ctx, "strict(a -- m -> b, seq(a -- m -> b, b -- m -> a), b -- n -> a)".to_string()).unwrap());
+        // the order of the branches of alt is not significant
+        let (_,got_code) = merge("alt");
+        assert_eq!(got_code, 0);
+        let merged = parse_hif_file(&gen_ctx, output_path.to_str().unwrap()).unwrap();
+        let expected = parse_hif_string(&gen_ctx, "alt(a -- m -> b, seq(a -- m -> b, b -- m -> a), b -- n -> a)".to_string()).unwrap();
+        assert_eq!(normalize_interaction(&gen_ctx,&merged), normalize_interaction(&gen_ctx,&expected));
+        // all the interactions must be defined over the shared signature
+        std::fs::write(folder.join("i3.hif"), "a -- z -> b").unwrap();
+        std::fs::remove_file(&output_path).unwrap();
+        let (got_print,got_code) = merge("alt");
+        assert_eq!(got_code, 1);
+        assert!(got_print[0].contains("i3.hif"));
+        assert!(!output_path.exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
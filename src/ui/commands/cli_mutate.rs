@@ -0,0 +1,131 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::json;
+
+use crate::core::language::mutate::generate_mutants;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_mutate(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int ) => {
+                    let num_mutants : usize = match matches.value_of("num") {
+                        None => {
+                            1
+                        },
+                        Some( as_str ) => {
+                            match as_str.trim().parse::<usize>() {
+                                Ok( got ) => {
+                                    got
+                                },
+                                Err(_) => {
+                                    return (vec![format!("number of mutants must be a non-negative integer : '{:}'", as_str)],1);
+                                }
+                            }
+                        }
+                    };
+                    let seed : u64 = match matches.value_of("seed") {
+                        None => {
+                            0
+                        },
+                        Some( as_str ) => {
+                            match as_str.trim().parse::<u64>() {
+                                Ok( got ) => {
+                                    got
+                                },
+                                Err(_) => {
+                                    return (vec![format!("seed must be a non-negative integer : '{:}'", as_str)],1);
+                                }
+                            }
+                        }
+                    };
+                    let parent_folder : &str = matches.value_of("parent_folder").unwrap_or(".");
+                    if let Err(e) = std::fs::create_dir_all(parent_folder) {
+                        return (vec![format!("could not create folder '{:}' : {:}", parent_folder, e)],1);
+                    }
+                    let mutant_name : String = match matches.value_of("name") {
+                        None => {
+                            Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap().to_string()
+                        },
+                        Some( extracted ) => {
+                            extracted.chars().filter(|c| !c.is_whitespace()).collect()
+                        }
+                    };
+                    // ***
+                    let mutants = generate_mutants(&gen_ctx,&int,num_mutants,&mut StdRng::seed_from_u64(seed));
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "GENERATED SINGLE EDIT MUTANTS".to_string());
+                    ret_print.push( format!("of interaction from file '{}'",hif_file_path) );
+                    if mutants.len() < num_mutants {
+                        ret_print.push( format!("only {} distinct mutants out of the {} requested", mutants.len(), num_mutants) );
+                    }
+                    let mut entries = vec![];
+                    for (x,(mutation,mutant)) in mutants.iter().enumerate() {
+                        let file_name = format!("{:}_mutant{:}.{:}", mutant_name, x, HIBOU_INTERACTION_FILE_EXTENSION);
+                        let path : PathBuf = [parent_folder, &file_name].iter().collect();
+                        interaction_to_hif(path.as_path(),&gen_ctx,mutant);
+                        let description = mutation.describe(&gen_ctx);
+                        ret_print.push( format!("into file '{}' : {}", path.display(), description) );
+                        entries.push(json!({
+                            "file" : file_name,
+                            "mutation" : description
+                        }));
+                    }
+                    // the mutation applied to each mutant is recorded along with them
+                    let record_path : PathBuf = [parent_folder, &format!("{:}_mutants.json", mutant_name)].iter().collect();
+                    let record = json!({
+                        "original" : hif_file_path,
+                        "seed" : seed,
+                        "mutants" : entries
+                    });
+                    let written = File::create(&record_path).and_then(|mut file| file.write_all(serde_json::to_string_pretty(&record).unwrap().as_bytes()));
+                    if let Err(e) = written {
+                        return (vec![format!("could not write '{:}' : {:}", record_path.display(), e)],1);
+                    }
+                    ret_print.push( format!("mutations recorded in '{}'", record_path.display()) );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
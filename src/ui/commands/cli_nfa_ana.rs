@@ -45,17 +45,19 @@ use graphviz_dot_builder::traits::{DotBuildable, DotPrintable, GraphVizOutputFor
 
 use crate::core::execution::trace::multitrace::multi_trace_length;
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 use crate::io::input::htf::interface::parse_htf_file;
 use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
 use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 
 pub fn cli_nfa_ana(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
@@ -64,7 +66,7 @@ pub fn cli_nfa_ana(matches : &ArgMatches) -> (Vec<String>,u32) {
             let hif_file_path = matches.value_of("hif").unwrap();
             let path_object = Path::new(hif_file_path);
             let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
@@ -96,6 +98,7 @@ pub fn cli_nfa_ana(matches : &ArgMatches) -> (Vec<String>,u32) {
                                 draw_interaction(&printer.gen_ctx,
                                                  &int,
                                                  &InteractionGraphicalRepresentation::AsSequenceDiagram,
+                                                 &None,
                                                  &".".to_string(),
                                                  &".".to_string(),
                                                  &int_name);
@@ -0,0 +1,107 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::path::Path;
+use autour_core::traits::repr::AutGraphvizDrawable;
+
+use clap::ArgMatches;
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::nfa_translation::alphabet::{get_alphabet_from_gen_ctx, write_alphabet_file};
+use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_nfa_draw(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            let path_object = Path::new(hif_file_path);
+            let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let output_format = match matches.value_of("format") {
+                        None => {
+                            GraphVizOutputFormat::png
+                        },
+                        Some( as_str ) => {
+                            match as_str.trim() {
+                                "png" => GraphVizOutputFormat::png,
+                                "svg" => GraphVizOutputFormat::svg,
+                                _ => {
+                                    return (vec![format!("unknown output format : '{:}'", as_str)],1);
+                                }
+                            }
+                        }
+                    };
+
+                    let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+                    let (method,(nfa,elapsed)) = if matches.is_present("compositional") {
+                        ("compositional", get_nfa_from_interaction_via_composition(&gen_ctx,&int,alphabet.clone()))
+                    } else {
+                        ("incremental", get_nfa_from_interaction_exploration(&gen_ctx,&int,alphabet.clone()))
+                    };
+
+                    // states are labelled with their index and transitions with the corresponding actions
+                    let printer = ActionNFAITPrinter::new(alphabet,gen_ctx);
+                    let nfa_name = format!("{}_nfa",file_name);
+                    let nfa_as_dot = nfa.to_dot(false,&hashset!{},&printer);
+
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("translated interaction from file '{}'",hif_file_path) );
+                    ret_print.push( format!("into NFA via {:} method : time : {:?} , num states {:?}", method, elapsed, nfa.transitions.len() ) );
+                    // the dot file is written before graphviz is called to render the image
+                    match nfa_as_dot.print_dot(&[".".to_string()],
+                                               &nfa_name,
+                                               &output_format) {
+                        Ok(_) => {
+                            ret_print.push( format!("drawn in '{}.dot' and '{}.{}'", nfa_name, nfa_name, output_format) );
+                        },
+                        Err(e) => {
+                            ret_print.push( format!("written in '{}.dot'", nfa_name) );
+                            ret_print.push( format!("could not render image (is graphviz installed ?) : {:}", e) );
+                        }
+                    }
+                    if matches.is_present("alphabet") {
+                        match write_alphabet_file(&printer.gen_ctx,&printer.index_to_action_map,&nfa_name) {
+                            Ok( alphabet_file_name ) => {
+                                ret_print.push( format!("letters of the NFA mapped to actions in '{:}'", alphabet_file_name) );
+                            },
+                            Err(e) => {
+                                return (vec![format!("could not write alphabet : {:}", e)],1);
+                            }
+                        }
+                    }
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -16,6 +16,7 @@ limitations under the License.
 
 
 use std::path::Path;
+use std::sync::Mutex;
 use std::time::Instant;
 use autour_core::traits::letter::AutAlphabetSubstitutable;
 use autour_core::traits::repr::AutGraphvizDrawable;
@@ -33,47 +34,54 @@ use graphviz_dot_builder::traits::{DotBuildable, DotPrintable, GraphVizOutputFor
 use autour_core::traits::characterize::AutCharacterizable;
 
 use crate::core::execution::trace::trace::TraceAction;
+use crate::core::general_context::GeneralContext;
 
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::{get_hif_files_in_folder, parse_hif_file_with};
 use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
-use crate::nfa_translation::experiments::run_nfa_generation_experiment;
+use crate::nfa_translation::experiments::{run_nfa_experiment_over_files_in_parallel, run_nfa_generation_experiment};
+use crate::nfa_translation::timing_csv::{append_nfa_timing_csv, NfaTimingCsvAppender, NfaTimingCsvRow, NfaTimingSummary};
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_nfa_experiment(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
+            let num_tries : usize = match matches.value_of("reps").or(matches.value_of("num_tries")) {
+                None => {
+                    3
+                },
+                Some( as_str ) => {
+                    as_str.trim().parse::<usize>().unwrap().max(1)
+                }
+            };
+
+            let states_lim : usize = match matches.value_of("states_lim") {
+                None => {
+                    10
+                },
+                Some( as_str ) => {
+                    as_str.trim().parse::<usize>().unwrap()
+                }
+            };
+
             let hif_file_path = matches.value_of("hif").unwrap();
             let path_object = Path::new(hif_file_path);
+            if path_object.is_dir() {
+                return cli_nfa_experiment_on_folder(matches,gen_ctx,hif_file_path,num_tries,states_lim);
+            }
             let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
                 Ok( int) => {
 
-                    let num_tries : u32 = match matches.value_of("num_tries") {
-                        None => {
-                            3
-                        },
-                        Some( as_str ) => {
-                            as_str.trim().parse::<u32>().unwrap()
-                        }
-                    };
-
-                    let states_lim : usize = match matches.value_of("states_lim") {
-                        None => {
-                            10
-                        },
-                        Some( as_str ) => {
-                            as_str.trim().parse::<usize>().unwrap()
-                        }
-                    };
-
                     let result = run_nfa_generation_experiment(int,gen_ctx,num_tries,states_lim);
 
                     let mut ret_print = vec![];
@@ -105,6 +113,26 @@ pub fn cli_nfa_experiment(matches : &ArgMatches) -> (Vec<String>,u32) {
                                             result.mindfa_med_time_from_compo,
                                             result.dfa_minimized_from_compo.transitions.len() ) );
 
+                    ret_print.push( format!("blow-up of the NFA via operational method : {:.3} states per symbol", result.get_blowup()) );
+
+                    ret_print.push( format!("timings over {:} repetitions :", num_tries) );
+                    let stats = &result.timing_stats;
+                    ret_print.push( format!("NFA via operational method : {:}", stats.nfa_operational) );
+                    ret_print.push( format!("minimized NFA after operational method : {:}", stats.nfa_kw_from_opera) );
+                    ret_print.push( format!("minimized DFA after operational method : {:}", stats.mindfa_from_opera) );
+                    ret_print.push( format!("NFA via compositional method : {:}", stats.nfa_compositional) );
+                    ret_print.push( format!("minimized NFA after compositional method : {:}", stats.nfa_kw_from_compo) );
+                    ret_print.push( format!("minimized DFA after compositional method : {:}", stats.mindfa_from_compo) );
+                    ret_print.extend( result.size_inconsistencies.iter().cloned() );
+
+                    if let Some(csv_path) = matches.value_of("csv") {
+                        let row = result.get_timing_row(file_name.to_string());
+                        if let Err(e) = append_nfa_timing_csv(Path::new(csv_path), &[row]) {
+                            return (vec![format!("could not write to csv file '{:}' : {:}", csv_path, e)],1);
+                        }
+                        ret_print.push( format!("appended timings to '{:}'", csv_path) );
+                    }
+
                     // ***
 
                     if matches.is_present("draw") {
@@ -118,4 +146,179 @@ pub fn cli_nfa_experiment(matches : &ArgMatches) -> (Vec<String>,u32) {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+
+/// Runs the experiment on each of the .hif files of a folder, possibly ranking them
+/// by the blow-up of their translation (see 'NfaGenerationExperimentResults::get_blowup').
+fn cli_nfa_experiment_on_folder(matches : &ArgMatches,
+                                gen_ctx : GeneralContext,
+                                folder : &str,
+                                num_tries : usize,
+                                states_lim : usize) -> (Vec<String>,u32) {
+    let include_ctx = get_hif_include_context(matches);
+    let hif_paths = match get_hif_files_in_folder(folder) {
+        Err(e) => {
+            return (vec![e],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let top_k : usize = match matches.value_of("top") {
+        None => {
+            10
+        },
+        Some( as_str ) => {
+            match as_str.trim().parse::<usize>() {
+                Ok( got ) => {
+                    got
+                },
+                Err(_) => {
+                    return (vec![format!("number of interactions to rank must be a non-negative integer : '{:}'", as_str)],1);
+                }
+            }
+        }
+    };
+    let mut ret_print = vec![];
+    ret_print.push( "".to_string());
+    ret_print.push( format!("Generating FAs from the {} interactions of folder '{}'", hif_paths.len(), folder) );
+    let jobs : Option<usize> = match matches.value_of("jobs") {
+        None => {
+            None
+        },
+        Some( as_str ) => {
+            match as_str.trim().parse::<usize>() {
+                Ok( got ) if got > 0 => {
+                    Some(got)
+                },
+                _ => {
+                    return (vec![format!("number of jobs must be a positive integer : '{:}'", as_str)],1);
+                }
+            }
+        }
+    };
+    // with '--summary-only', interactions that cannot be parsed are counted instead of stopping the experiment
+    let summary_only = matches.is_present("summary_only");
+    let mut num_parse_failures : usize = 0;
+    let mut rows = vec![];
+    match jobs {
+        None => {
+            for path in &hif_paths {
+                let file_name = path.file_stem().unwrap().to_str().unwrap().to_string();
+                match parse_hif_file_with(&gen_ctx,path.to_str().unwrap(),&include_ctx) {
+                    Err(_) if summary_only => {
+                        num_parse_failures += 1;
+                    },
+                    Err(e) => {
+                        return (vec![format!("in file '{:}' : {:}", path.display(), e)],1);
+                    },
+                    Ok( int ) => {
+                        let result = run_nfa_generation_experiment(int,gen_ctx.clone(),num_tries,states_lim);
+                        let row = result.get_timing_row(file_name.clone());
+                        if !summary_only {
+                            ret_print.push( folder_row_summary(&row) );
+                        }
+                        rows.push(row);
+                    }
+                }
+            }
+            if let Some(csv_path) = matches.value_of("csv") {
+                if let Err(e) = append_nfa_timing_csv(Path::new(csv_path), &rows) {
+                    return (vec![format!("could not write to csv file '{:}' : {:}", csv_path, e)],1);
+                }
+                ret_print.push( format!("appended timings to '{:}'", csv_path) );
+            }
+        },
+        Some( jobs ) => {
+            ret_print.push( format!("using {} worker threads", jobs) );
+            // rows are appended to the csv file as soon as their file is processed
+            let csv_appender = match matches.value_of("csv") {
+                None => {
+                    None
+                },
+                Some( csv_path ) => {
+                    match NfaTimingCsvAppender::open(Path::new(csv_path)) {
+                        Err(e) => {
+                            return (vec![format!("could not write to csv file '{:}' : {:}", csv_path, e)],1);
+                        },
+                        Ok( got ) => {
+                            Some(got)
+                        }
+                    }
+                }
+            };
+            let csv_error : Mutex<Option<String>> = Mutex::new(None);
+            let collected : Mutex<Vec<(usize,Result<NfaTimingCsvRow,String>)>> = Mutex::new(vec![]);
+            run_nfa_experiment_over_files_in_parallel(&hif_paths, &gen_ctx, &include_ctx, num_tries, states_lim, jobs, |idx,got| {
+                let got = got.map(|result| result.results.get_timing_row(result.file_name))
+                    .map_err(|e| format!("in file '{:}' : {:}", hif_paths[idx].display(), e));
+                if let (Some(appender),Ok(row)) = (&csv_appender,&got) {
+                    if let Err(e) = appender.append(row) {
+                        csv_error.lock().unwrap().get_or_insert(format!("could not write to csv file '{:}' : {:}", matches.value_of("csv").unwrap(), e));
+                    }
+                }
+                collected.lock().unwrap().push((idx,got));
+            });
+            if let Some(e) = csv_error.into_inner().unwrap() {
+                return (vec![e],1);
+            }
+            // the rows are listed in the order of the files
+            let mut collected = collected.into_inner().unwrap();
+            collected.sort_by_key(|(idx,_)| *idx);
+            for (_,got) in collected {
+                match got {
+                    Err(_) if summary_only => {
+                        num_parse_failures += 1;
+                    },
+                    Err(e) => {
+                        return (vec![e],1);
+                    },
+                    Ok( row ) => {
+                        if !summary_only {
+                            ret_print.push( folder_row_summary(&row) );
+                        }
+                        rows.push(row);
+                    }
+                }
+            }
+            if let Some(csv_path) = matches.value_of("csv") {
+                ret_print.push( format!("appended timings to '{:}'", csv_path) );
+            }
+        }
+    }
+    if matches.is_present("sort_by_blowup") {
+        let mut ranked : Vec<&NfaTimingCsvRow> = rows.iter().collect();
+        ranked.sort_by(|x,y| y.get_blowup().partial_cmp(&x.get_blowup()).unwrap());
+        ranked.truncate(top_k);
+        ret_print.push( format!("{} worst blow-ups :", ranked.len()) );
+        for (rank,row) in ranked.iter().enumerate() {
+            ret_print.push( format!("{}. {} : {:.3} ({} states for {} symbols)",
+                                    rank + 1,
+                                    row.name,
+                                    row.get_blowup(),
+                                    row.num_states,
+                                    row.num_symbols) );
+        }
+    }
+    if summary_only {
+        ret_print.push( "summary :".to_string() );
+        ret_print.extend( NfaTimingSummary::from_rows(&rows, num_parse_failures).to_lines() );
+    }
+    ret_print.push( "".to_string());
+    return (ret_print,0);
+}
+
+fn folder_row_summary(row : &NfaTimingCsvRow) -> String {
+    let summary = format!("{} : {} symbols, NFA via operational method of {} states in {:.3}ms, blow-up {:.3}",
+                          row.name,
+                          row.num_symbols,
+                          row.num_states,
+                          row.translation_ms,
+                          row.get_blowup());
+    if row.nondeterministic {
+        format!("{}, automata of different sizes across repetitions", summary)
+    } else {
+        summary
+    }
+}
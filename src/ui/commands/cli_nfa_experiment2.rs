@@ -18,7 +18,7 @@ limitations under the License.
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use autour_core::traits::letter::AutAlphabetSubstitutable;
 use autour_core::traits::repr::AutGraphvizDrawable;
 use autour_core::traits::transform::AutTransformable;
@@ -41,6 +41,7 @@ use crate::io::input::hif::interface::parse_hif_file;
 use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
 use crate::nfa_translation::experiments2::run_nfa_generation_experiment2;
 use crate::nfa_translation::experiments::run_nfa_generation_experiment;
+use crate::nfa_translation::timing_csv::append_nfa_timing_csv;
 
 
 pub fn cli_nfa_experiment2(matches : &ArgMatches) -> (Vec<String>,u32) {
@@ -105,6 +106,15 @@ pub fn cli_nfa_experiment2(matches : &ArgMatches) -> (Vec<String>,u32) {
                 }
             };
 
+            let timeout : Option<Duration> = match matches.value_of("timeout_secs") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    Some(Duration::from_secs(as_str.trim().parse::<u64>().unwrap()))
+                }
+            };
+
             println!("num_ints : {:}, gen_depth : {:}, max_symbols : {:}, max_par : {:}, seed : {:}",
                      number_of_interactions,
                      gen_depth,
@@ -121,13 +131,18 @@ pub fn cli_nfa_experiment2(matches : &ArgMatches) -> (Vec<String>,u32) {
             };*/
             let output_file_name = format!("{}_exp.csv", Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap());
 
+            let mut timing_rows = vec![];
             let csv_results = run_nfa_generation_experiment2(number_of_interactions,
                                                              &gen_ctx,
                                                              num_tries,
                                                              gen_depth,
                                                              max_symbols,
                                                              max_par,
-                                                             seed);
+                                                             seed,
+                                                             matches.is_present("minimize"),
+                                                             matches.is_present("measure_dfa"),
+                                                             timeout,
+                                                             &mut timing_rows);
 
             let mut file = File::create(output_file_name.clone()).unwrap();
             file.write(csv_results.as_bytes() );
@@ -139,6 +154,13 @@ pub fn cli_nfa_experiment2(matches : &ArgMatches) -> (Vec<String>,u32) {
                 output_file_name)
             );
 
+            if let Some(csv_path) = matches.value_of("csv") {
+                if let Err(e) = append_nfa_timing_csv(Path::new(csv_path), &timing_rows) {
+                    return (vec![format!("could not write to csv file '{:}' : {:}", csv_path, e)],1);
+                }
+                ret_print.push( format!("appended timings of {:} interactions to '{:}'", timing_rows.len(), csv_path) );
+            }
+
             return (ret_print,0);
         }
     }
@@ -0,0 +1,63 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use clap::ArgMatches;
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::regex::get_regex_from_nfa;
+use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_nfa_to_regex(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+                    let (nfa,_) = if matches.is_present("compositional") {
+                        get_nfa_from_interaction_via_composition(&gen_ctx,&int,alphabet.clone())
+                    } else {
+                        get_nfa_from_interaction_exploration(&gen_ctx,&int,alphabet.clone())
+                    };
+                    let printer = ActionNFAITPrinter::new(alphabet,gen_ctx);
+                    let regex = get_regex_from_nfa(&nfa,&printer);
+
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("regular expression of the traces of interaction from file '{}'",hif_file_path) );
+                    ret_print.push( "".to_string());
+                    ret_print.push( regex );
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
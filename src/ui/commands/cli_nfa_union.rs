@@ -0,0 +1,148 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+use graphviz_dot_builder::traits::{DotPrintable, GraphVizOutputFormat};
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::input::htf::interface::parse_htf_file;
+use crate::nfa_translation::alphabet::{get_alphabet_from_gen_ctx, write_alphabet_file};
+use crate::nfa_translation::compositional::get_nfa_from_interaction_via_composition;
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::tagged_union::TaggedNfaUnion;
+use crate::process::explo::loggers::nfait::printer::ActionNFAITPrinter;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_nfa_union(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let output_format = match matches.value_of("format") {
+                None => {
+                    GraphVizOutputFormat::png
+                },
+                Some( as_str ) => {
+                    match as_str.trim() {
+                        "png" => GraphVizOutputFormat::png,
+                        "svg" => GraphVizOutputFormat::svg,
+                        _ => {
+                            return (vec![format!("unknown output format : '{:}'", as_str)],1);
+                        }
+                    }
+                }
+            };
+            let output_name = match matches.value_of("output") {
+                None => "nfa_union".to_string(),
+                Some(name) => name.to_string()
+            };
+            let method = if matches.is_present("compositional") { "compositional" } else { "incremental" };
+            let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+
+            let hif_file_paths : Vec<&str> = matches.values_of("hifs").unwrap().collect();
+            let mut nfas = vec![];
+            let mut labels = vec![];
+            for hif_file_path in &hif_file_paths {
+                match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                    Err(e) => {
+                        return (vec![format!("in file '{:}' : {:}", hif_file_path, e)],1);
+                    },
+                    Ok( int ) => {
+                        let (nfa,_) = if matches.is_present("compositional") {
+                            get_nfa_from_interaction_via_composition(&gen_ctx,&int,alphabet.clone())
+                        } else {
+                            get_nfa_from_interaction_exploration(&gen_ctx,&int,alphabet.clone())
+                        };
+                        nfas.push(nfa);
+                        let file_name = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+                        labels.push(format!("{:} : {:}", labels.len(), file_name));
+                    }
+                }
+            }
+            // all the NFAs are over the alphabet of the signature
+            let union = TaggedNfaUnion::from_nfas(nfas).unwrap();
+
+            let mut ret_print = vec![];
+            ret_print.push( "".to_string());
+            ret_print.push( "UNITING NFAS OF INTERACTIONS".to_string());
+            ret_print.push( format!("on file : {}",hsf_file_path) );
+            ret_print.push( format!("translated via {:} method into NFAs with :", method) );
+            for (source,num_states) in union.get_num_states_per_source().iter().enumerate() {
+                ret_print.push( format!("  {:} states from '{:}' (source {:})", num_states, hif_file_paths[source], source) );
+            }
+            ret_print.push( format!("united into a NFA with {:} states", union.nfa.transitions.len()) );
+
+            let printer = ActionNFAITPrinter::new(alphabet,gen_ctx);
+            if let Some(htf_file_path) = matches.value_of("htf") {
+                match parse_htf_file(&printer.gen_ctx,htf_file_path) {
+                    Err(e) => {
+                        return (vec![e.to_string()],1);
+                    },
+                    Ok( (co_localizations,mut multi_trace) ) => {
+                        if co_localizations.locs_lf_ids.len() != 1 {
+                            return (vec!["trace should be a global trace".to_string()],1);
+                        }
+                        let trace = multi_trace.remove(0);
+                        // actions outside of the alphabet are mapped to a letter which no source accepts
+                        let outside_letter = printer.index_to_action_map.len();
+                        let word : Vec<usize> = trace.iter().map(|x| {
+                            printer.index_to_action_map.iter().position(|y| y == x).unwrap_or(outside_letter)
+                        }).collect();
+                        let sources = union.get_accepting_sources(&word);
+                        if sources.is_empty() {
+                            ret_print.push( format!("trace from file '{:}' accepted by no source", htf_file_path) );
+                        } else {
+                            let names : Vec<String> = sources.iter().map(|source| format!("'{:}' (source {:})", hif_file_paths[*source], source)).collect();
+                            ret_print.push( format!("trace from file '{:}' accepted by {:}", htf_file_path, names.join(", ")) );
+                        }
+                    }
+                }
+            }
+
+            match union.to_dot(&printer,&labels).print_dot(&[".".to_string()],
+                                                          &output_name,
+                                                          &output_format) {
+                Ok(_) => {
+                    ret_print.push( format!("drawn in '{}.dot' and '{}.{}'", output_name, output_name, output_format) );
+                },
+                Err(e) => {
+                    ret_print.push( format!("written in '{}.dot'", output_name) );
+                    ret_print.push( format!("could not render image (is graphviz installed ?) : {:}", e) );
+                }
+            }
+            if matches.is_present("alphabet") {
+                match write_alphabet_file(&printer.gen_ctx,&printer.index_to_action_map,&output_name) {
+                    Ok( alphabet_file_name ) => {
+                        ret_print.push( format!("letters of the NFA mapped to actions in '{:}'", alphabet_file_name) );
+                    },
+                    Err(e) => {
+                        return (vec![format!("could not write alphabet : {:}", e)],1);
+                    }
+                }
+            }
+            return (ret_print,0);
+        }
+    }
+}
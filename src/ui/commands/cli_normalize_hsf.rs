@@ -0,0 +1,98 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::core::language::signature::get_normalized_signature;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::{get_files_matching_pattern, parse_hif_file_with};
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::io::output::to_hfiles::signature::to_hsf::signature_to_hsf;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_normalize_hsf(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let normalized = get_normalized_signature(&gen_ctx);
+            // all the interactions are parsed before any file is written
+            // so that nothing is rewritten if one of them is not well-defined over the signature
+            let mut to_rewrite = vec![];
+            let mut skipped = vec![];
+            if let Some(pattern) = matches.value_of("rewrite_hif") {
+                let hif_paths = match get_files_matching_pattern(pattern) {
+                    Err(e) => {
+                        return (vec![e],1);
+                    },
+                    Ok( got ) => {
+                        got
+                    }
+                };
+                for path in hif_paths {
+                    let hif_file_path = path.to_str().unwrap().to_string();
+                    // rewriting a file would inline its includes, hence files with includes are left untouched
+                    let has_includes = std::fs::read_to_string(&path).map_or(false, |content| content.contains("@include"));
+                    if has_includes {
+                        skipped.push(hif_file_path);
+                        continue;
+                    }
+                    match parse_hif_file_with(&gen_ctx,&hif_file_path,&include_ctx) {
+                        Err(e) => {
+                            return (vec![format!("in file '{:}' : {:}", hif_file_path, e)],1);
+                        },
+                        Ok( int ) => {
+                            to_rewrite.push((hif_file_path,int));
+                        }
+                    }
+                }
+            }
+            if let Err(e) = signature_to_hsf(Path::new(hsf_file_path), &normalized) {
+                return (vec![format!("could not write file '{:}' : {:}", hsf_file_path, e)],1);
+            }
+            for (hif_file_path,int) in &to_rewrite {
+                interaction_to_hif(Path::new(hif_file_path), &gen_ctx, int);
+                if let Err(e) = parse_hif_file_with(&normalized,hif_file_path,&include_ctx) {
+                    return (vec![format!("rewritten file '{:}' is not well-defined over the normalized signature : {:}", hif_file_path, e)],1);
+                }
+            }
+            // ***
+            let mut ret_print = vec![];
+            ret_print.push( "".to_string());
+            ret_print.push( "NORMALIZED SIGNATURE".to_string());
+            ret_print.push( format!("rewritten file '{}'",hsf_file_path) );
+            if gen_ctx == normalized {
+                ret_print.push( "the declarations were already in canonical order".to_string() );
+            }
+            if matches.is_present("rewrite_hif") {
+                ret_print.push( format!("rewritten {} interaction files", to_rewrite.len()) );
+                for hif_file_path in &skipped {
+                    ret_print.push( format!("left untouched '{}' which includes other files", hif_file_path) );
+                }
+            }
+            ret_print.push( "".to_string());
+            return (ret_print,0);
+        }
+    }
+}
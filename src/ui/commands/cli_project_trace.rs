@@ -0,0 +1,65 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::htf::interface::parse_htf_file;
+use crate::trace_manip::project::generate_lifeline_projections;
+
+
+pub fn cli_project_trace(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let htf_file_path = matches.value_of("htf").unwrap();
+            match parse_htf_file(&gen_ctx, htf_file_path) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( (_,multi_trace) ) => {
+                    let parent_folder : Option<&str> = matches.value_of("parent_folder");
+                    if let Some(folder_name) = parent_folder {
+                        if let Err(e) = std::fs::create_dir_all(folder_name) {
+                            return (vec![format!("could not create folder '{:}' : {:}", folder_name, e)],1);
+                        }
+                    }
+                    let trace_name : &str = Path::new(htf_file_path).file_stem().unwrap().to_str().unwrap();
+                    let written = generate_lifeline_projections(&gen_ctx,
+                                                                &multi_trace,
+                                                                parent_folder,
+                                                                trace_name);
+                    // ***
+                    let mut ret_print = vec![];
+                    ret_print.push( "PROJECTED MULTITRACE ON EACH LIFELINE".to_string());
+                    ret_print.push( format!("from file '{}'",htf_file_path) );
+                    for (file_path,local_length) in written {
+                        ret_print.push( format!("into file '{}' ({} actions)",file_path,local_length) );
+                    }
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
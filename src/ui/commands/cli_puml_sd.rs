@@ -20,34 +20,43 @@ use std::path::Path;
 use clap::ArgMatches;
 
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
 
 use crate::plantuml::sequence::to_plant_uml_sd;
+use crate::ui::util::include_context::get_hif_include_context;
 
 pub fn cli_puml_sd(matches : &ArgMatches) -> (Vec<String>,u32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
             return (vec![e.to_string()],1);
         },
         Ok( gen_ctx ) => {
             let hif_file_path = matches.value_of("hif").unwrap();
-            match parse_hif_file(&gen_ctx,hif_file_path) {
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
                 Err(e) => {
                     return (vec![e.to_string()],1);
                 },
                 Ok( int) => {
-                    let mut ret_print = vec![];
-                    let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
-                    let spec_output_file = format!("{}_sd.puml", file_name);
+                    let spec_output_file : String;
+                    if matches.is_present("output") {
+                        let extracted = matches.value_of("output").unwrap();
+                        spec_output_file = format!("{}.puml", extracted);
+                    } else {
+                        let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
+                        spec_output_file = format!("{}_sd.puml", file_name);
+                    }
+                    if let Err(e) = to_plant_uml_sd(&spec_output_file, &int, &gen_ctx) {
+                        return (vec![format!("could not write '{}' : {}", spec_output_file, e)],1);
+                    }
                     // ***
+                    let mut ret_print = vec![];
                     ret_print.push( "".to_string());
                     ret_print.push( "TRANSLATING INTERACTION to puml-sd".to_string());
-                    ret_print.push( format!("from file '{}'",hsf_file_path) );
+                    ret_print.push( format!("from file '{}'",hif_file_path) );
                     ret_print.push( format!("on file : {}",spec_output_file) );
                     ret_print.push( "".to_string());
-                    to_plant_uml_sd(&spec_output_file,file_name, &int, &gen_ctx);
-                    // ***
                     return (ret_print,0);
                 }
             }
@@ -0,0 +1,107 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::path::PathBuf;
+use clap::{App, ArgMatches};
+
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::ui::commands::cli_rng_gen_raw_interactions::{cli_rng_gen_raw_interactions_with_mode, RawGenerationMode};
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_replay(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let manifest_path = matches.value_of("manifest").unwrap();
+    let manifest : serde_json::Value = match std::fs::read_to_string(manifest_path) {
+        Err(e) => {
+            return (vec![format!("could not read manifest '{:}' : {:}", manifest_path, e)],1);
+        },
+        Ok( content ) => {
+            match serde_json::from_str(&content) {
+                Err(e) => {
+                    return (vec![format!("could not parse manifest '{:}' : {:}", manifest_path, e)],1);
+                },
+                Ok( got ) => {
+                    got
+                }
+            }
+        }
+    };
+    // the interaction may be given as 'i137.hif', 'i137' or '137'
+    let as_str = matches.value_of("interaction").unwrap().trim();
+    let index = match as_str.trim_end_matches(&format!(".{:}", HIBOU_INTERACTION_FILE_EXTENSION))
+        .trim_start_matches('i')
+        .parse::<usize>() {
+        Err(_) => {
+            return (vec![format!("interactions are designated by their index, e.g. 'i137' : '{:}'", as_str)],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let file_name = format!("i{:}.{:}", index, HIBOU_INTERACTION_FILE_EXTENSION);
+    let entry = match manifest.get("files").and_then(|x| x.as_array())
+        .and_then(|files| files.iter().find(|entry| entry.get("file").and_then(|x| x.as_str()) == Some(&file_name))) {
+        None => {
+            return (vec![format!("manifest '{:}' has no entry for '{:}'", manifest_path, file_name)],1);
+        },
+        Some( got ) => {
+            got
+        }
+    };
+    let (rng_words,hash) = match (entry.get("rng_words").and_then(|x| x.as_u64()), entry.get("hash").and_then(|x| x.as_str())) {
+        (Some(rng_words),Some(hash)) => {
+            (rng_words,hash.to_string())
+        },
+        _ => {
+            return (vec![format!("the entry of '{:}' records no rng position or hash : it was generated among worker threads or before they were recorded", file_name)],1);
+        }
+    };
+    // the entries carried over from a previous generation keep the arguments of this generation
+    let arguments : Vec<String> = match entry.get("arguments").or_else(|| manifest.get("arguments")).and_then(|x| x.as_array()) {
+        None => {
+            return (vec![format!("manifest '{:}' records no generation arguments", manifest_path)],1);
+        },
+        Some( got ) => {
+            got.iter().filter_map(|x| x.as_str().map(|y| y.to_string())).collect()
+        }
+    };
+    let mut args : Vec<String> = vec!["hibou_label".to_string(), "rng_gen_raw_interactions".to_string()];
+    args.extend(arguments);
+    let yaml = load_yaml!("../hibou_cli.yml");
+    let gen_matches = match App::from_yaml(yaml).try_get_matches_from(&args) {
+        Err(e) => {
+            return (vec![format!("invalid generation arguments in manifest : {:}", e)],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let output : PathBuf = match matches.value_of("output") {
+        None => {
+            PathBuf::from(format!("i{:}_replay.{:}", index, HIBOU_INTERACTION_FILE_EXTENSION))
+        },
+        Some( got ) => {
+            PathBuf::from(format!("{:}.{:}", got, HIBOU_INTERACTION_FILE_EXTENSION))
+        }
+    };
+    let mode = RawGenerationMode::Replay{ index, rng_words, hash, output };
+    let (mut got_print,got_code,_) = cli_rng_gen_raw_interactions_with_mode(gen_matches.subcommand_matches("rng_gen_raw_interactions").unwrap(), &get_hif_include_context(matches), &mode);
+    let mut ret_print = vec![format!("replaying '{:}' from manifest '{:}' with arguments : {:}", file_name, manifest_path, args[2..].join(" "))];
+    ret_print.append(&mut got_print);
+    (ret_print,got_code)
+}
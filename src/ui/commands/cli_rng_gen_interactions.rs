@@ -38,8 +38,9 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 
 use crate::core::execution::trace::trace::TraceAction;
+use crate::core::language::hash::canonical::HashedInteraction;
 use crate::experiments::interaction_random_gen::interface::generate_canonical_random_interaction;
-use crate::experiments::interaction_random_gen::probas::InteractionSymbolsProbabilities;
+use crate::experiments::interaction_random_gen::probas::{InteractionGenerationSymbol, InteractionSymbolsProbabilities};
 use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
 
 use crate::io::input::hsf::interface::parse_hsf_file;
@@ -212,7 +213,34 @@ pub fn cli_rng_gen_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32)
                 }
             };
             
-            if (pempty + paction + pstrict + pseq + pcoreg + ppar + ploops + ploopw + ploopp + palt + pbasic + ptr + pbc - 1.0).abs() > f32::EPSILON {
+            let pgate: f32 = match matches.value_of("pgate") {
+                None => {
+                    0.0
+                }
+                Some(as_str) => {
+                    as_str.trim().parse::<f32>().unwrap()
+                }
+            };
+            
+            let pemission: f32 = match matches.value_of("pemission") {
+                None => {
+                    0.0
+                }
+                Some(as_str) => {
+                    as_str.trim().parse::<f32>().unwrap()
+                }
+            };
+            
+            let preception: f32 = match matches.value_of("preception") {
+                None => {
+                    0.0
+                }
+                Some(as_str) => {
+                    as_str.trim().parse::<f32>().unwrap()
+                }
+            };
+            
+            if (pempty + paction + pstrict + pseq + pcoreg + ppar + ploops + ploopw + ploopp + palt + pbasic + ptr + pbc + pgate + pemission + preception - 1.0).abs() > f32::EPSILON {
                 panic!("Probabilities do not sum to 1.0");
             }
             
@@ -249,7 +277,7 @@ pub fn cli_rng_gen_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32)
                     "custom" => {
                         probas_name = "custom";
                         InteractionSymbolsProbabilities::custom(
-                            pempty, paction, pstrict, pseq, pcoreg, ppar, ploops, ploopw, ploopp, palt, pbasic, ptr, pbc
+                            pempty, paction, pstrict, pseq, pcoreg, ppar, ploops, ploopw, ploopp, palt, pbasic, ptr, pbc, pgate, pemission, preception
                         )
                     },
                     "default" => {
@@ -265,6 +293,10 @@ pub fn cli_rng_gen_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32)
 
 
 
+            if probas.get_probability(&InteractionGenerationSymbol::Gate) > 0.0 && gen_ctx.get_gt_num() == 0 {
+                return (vec!["gate actions can be generated but the signature declares no gate".to_string()], 1, 0.0);
+            }
+
             let mut ret_print = vec![];
             ret_print.push( "generated random interactions interactions".to_string());
             ret_print.push(
@@ -289,7 +321,7 @@ pub fn cli_rng_gen_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32)
             let mut x = 0;
             let mut max_tries = num_tries;
             'myloop : while x < number_of_interactions {
-                println!("trying to generate interaction {} out of {}", x, number_of_interactions);
+                trace!("trying to generate interaction {} out of {}", x, number_of_interactions);
                 let mut got_one = false;
                 if let Some(i) = generate_canonical_random_interaction(&gen_ctx,
                                                               &mut rng,
@@ -297,21 +329,23 @@ pub fn cli_rng_gen_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32)
                                                               min_symbols,
                                                               &probas
                 ) {
-                    if !memoized_ints.contains(&i) {
+                    let hashed = HashedInteraction::new(i);
+                    if !memoized_ints.contains(&hashed) {
                         got_one = true;
+                        let i = hashed.get_interaction();
                         let file_name = format!("i{:}.{:}", x, HIBOU_INTERACTION_FILE_EXTENSION);
                         let path : PathBuf = [&output_folder, &file_name].iter().collect();
-                        interaction_to_hif(path.as_path(),&gen_ctx,&i);
-                        memoized_ints.insert(i);
+                        interaction_to_hif(path.as_path(),&gen_ctx,i);
+                        memoized_ints.insert(hashed);
                         x += 1;
-                        println!("wrote to file '{:?}'", path.as_path())
+                        info!("wrote to file '{:}'", path.display())
                     }
                 }
                 if !got_one {
-                    println!("retrying...");
+                    trace!("retrying...");
                     max_tries -= 1;
                     if max_tries <= 0 {
-                        println!("... max retries exceeded");
+                        warn!("max retries exceeded after generating {} interactions out of {}", x, number_of_interactions);
                         break 'myloop;
                     }
                 }
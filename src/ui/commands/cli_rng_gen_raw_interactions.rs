@@ -15,18 +15,17 @@ limitations under the License.
 */
 
 
-use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use autour_core::traits::letter::AutAlphabetSubstitutable;
 use autour_core::traits::repr::AutGraphvizDrawable;
 use autour_core::traits::transform::AutTransformable;
 use autour_core::traits::translate::AutTranslatable;
 
 
-use clap::ArgMatches;
+use clap::{App, ArgMatches, ValueSource};
 use graphviz_dot_builder::edge::edge::GraphVizEdge;
 use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
 use graphviz_dot_builder::item::node::node::GraphVizNode;
@@ -36,22 +35,118 @@ use graphviz_dot_builder::traits::{DotBuildable, DotPrintable, GraphVizOutputFor
 use autour_core::traits::characterize::AutCharacterizable;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde_json::json;
 
+use crate::core::colocalizations::CoLocalizations;
+use crate::core::execution::trace::multitrace::{MultiTrace, Trace};
 use crate::core::execution::trace::trace::TraceAction;
-use crate::experiments::interaction_random_gen::interface::generate_raw_random_interaction;
-use crate::experiments::interaction_random_gen::probas::InteractionSymbolsProbabilities;
-use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::experiments::trace_sampling::{derive_rejected_traces, sample_accepted_traces};
+use crate::core::general_context::GeneralContext;
+use crate::core::language::syntax::interaction::Interaction;
+use crate::core::language::hash::canonical::{canonical_hash, HashedInteraction};
+use crate::core::language::syntax::metrics::{InteractionMetrics, SymbolCounts};
+use crate::experiments::interaction_random_gen::histogram::SizeHistogram;
+use crate::experiments::interaction_random_gen::stats::CumulativeGenerationStats;
+use crate::experiments::interaction_random_gen::predicate::MetricPredicate;
+use crate::experiments::interaction_random_gen::interface::{generate_random_interactions, generate_random_interactions_memoized, generate_random_interactions_streamed, regenerate_interaction, GenerationParams, GenerationState, InteractionDeduplicationKind, InteractionMemo, DepthDistribution};
+use crate::experiments::interaction_random_gen::resume::{CountingRng, GenerationCheckpoint};
+use crate::experiments::interaction_random_gen::probas::{InteractionGenerationSymbol, InteractionSymbolsProbabilities, InteractionSymbolsProbabilitiesError, LoopKindDistribution};
+use crate::experiments::interaction_random_gen::weights::GenerationWeights;
+use crate::io::file_extensions::{HIBOU_INTERACTION_FILE_EXTENSION, HIBOU_TRACE_FILE_EXTENSION};
 
 use crate::io::input::hsf::interface::parse_hsf_file;
-use crate::io::input::hif::interface::parse_hif_file;
-use crate::io::output::draw_interactions::interface::{draw_interaction, InteractionGraphicalRepresentation};
-use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::io::input::hif::include::HifIncludeContext;
+use crate::io::input::hif::interface::{get_files_matching_pattern, parse_hif_file_with};
+use crate::io::input::htf::interface::parse_htf_file;
+use crate::io::output::draw_interactions::interface::{draw_interaction, draw_interaction_with_format, InteractionGraphicalRepresentation};
+use crate::io::output::to_hfiles::trace::to_htf::write_multi_trace_into_file;
+use crate::io::output::to_hfiles::interaction::to_hif::{interaction_to_hif_with_provenance, HifProvenance};
 use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
 use crate::nfa_translation::experiments2::run_nfa_generation_experiment2;
 use crate::nfa_translation::experiments::run_nfa_generation_experiment;
+use crate::ui::util::include_context::get_hif_include_context;
 
 
 pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f32) {
+    cli_rng_gen_raw_interactions_or_validate(matches, &get_hif_include_context(matches), false)
+}
+
+/// With 'validate_only', all the arguments are checked (and the files they refer to read)
+/// but the process stops right before the generation, without writing anything.
+/// The prefix and suffix files are parsed with 'include_ctx' given that the matches may only
+/// contain the arguments of 'rng_gen_raw_interactions' (e.g. when they come from 'run_spec').
+pub fn cli_rng_gen_raw_interactions_or_validate(matches : &ArgMatches,
+                                                include_ctx : &HifIncludeContext,
+                                                validate_only : bool) -> (Vec<String>,u32,f32) {
+    if validate_only {
+        cli_rng_gen_raw_interactions_with_mode(matches, include_ctx, &RawGenerationMode::Validate)
+    } else {
+        cli_rng_gen_raw_interactions_with_mode(matches, include_ctx, &RawGenerationMode::Generate)
+    }
+}
+
+/// Value of an optional argument, an error message being returned (instead of panicking)
+/// if it is given but cannot be parsed.
+fn parse_optional_argument<T : std::str::FromStr>(matches : &ArgMatches, id : &str) -> Result<Option<T>,String> {
+    match matches.value_of(id) {
+        None => {
+            Ok(None)
+        },
+        Some( as_str ) => {
+            match as_str.trim().parse::<T>() {
+                Ok( got ) => {
+                    Ok(Some(got))
+                },
+                Err(_) => {
+                    Err(format!("invalid value for '{:}' : '{:}'", id, as_str))
+                }
+            }
+        }
+    }
+}
+
+/// Values taken by the positional arguments which are not given, with which the positional arguments
+/// preceding a given one can be filled (e.g. by 'run_spec' which always gives the output folder).
+const RAW_GENERATION_POSITIONAL_DEFAULTS : [(&str,&str);18] = [
+    ("num_ints","350"), ("probas","default"), ("max_depth","10"), ("min_symbols","100"), ("seed","0"),
+    ("pempty","0.5"), ("paction","0.5"), ("pstrict","0.0"), ("pseq","0.0"), ("pcoreg","0.0"), ("ppar","0.0"),
+    ("ploopS","0.0"), ("ploopW","0.0"), ("ploopP","0.0"), ("palt","0.0"), ("pbasic","0.0"),
+    ("ptransmission","0.0"), ("pbroadcast","0.0")
+];
+
+pub fn get_positional_default(id : &str) -> Option<&'static str> {
+    RAW_GENERATION_POSITIONAL_DEFAULTS.iter().find(|(arg,_)| *arg == id).map(|(_,default)| *default)
+}
+
+/// Value of a positional argument, its default value (see 'get_positional_default') being taken if it is not given.
+fn parse_positional_argument<T : std::str::FromStr>(matches : &ArgMatches, id : &str) -> Result<T,String> {
+    match parse_optional_argument::<T>(matches, id)? {
+        Some( got ) => {
+            Ok(got)
+        },
+        None => {
+            get_positional_default(id).and_then(|default| default.parse::<T>().ok())
+                .ok_or_else(|| format!("no default value for '{:}'", id))
+        }
+    }
+}
+
+/// What is done once all the arguments of 'rng_gen_raw_interactions' are checked.
+pub enum RawGenerationMode {
+    Generate,
+    /// stops right before the generation, without writing anything
+    Validate,
+    /// only draws again the interaction 'ix' (with x the index) of the attempt which started once
+    /// the seeded rng had drawn 'rng_words' words (see 'regenerate_interaction')
+    /// and writes it into 'output' if its canonical hash is 'hash'
+    Replay{ index : usize, rng_words : u64, hash : String, output : PathBuf }
+}
+
+pub fn cli_rng_gen_raw_interactions_with_mode(matches : &ArgMatches,
+                                              include_ctx : &HifIncludeContext,
+                                              mode : &RawGenerationMode) -> (Vec<String>,u32,f32) {
     let hsf_file_path = matches.value_of("hsf").unwrap();
     match parse_hsf_file(hsf_file_path) {
         Err(e) => {
@@ -59,171 +154,277 @@ pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f
         }
         Ok( gen_ctx ) => {
 
-            let number_of_interactions : u32 = match matches.value_of("num_ints") {
-                None => {
-                    350
+            let number_of_interactions : u32 = match parse_positional_argument::<u32>(matches, "num_ints") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
                 },
-                Some( as_str ) => {
-                    as_str.trim().parse::<u32>().unwrap()
+                Ok( got ) => {
+                    got
                 }
             };
 
 
 
-            let max_depth : u32 = match matches.value_of("max_depth") {
-                None => {
-                    10
+            let max_depth : u32 = match parse_positional_argument::<u32>(matches, "max_depth") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
                 },
-                Some( as_str ) => {
-                    as_str.trim().parse::<u32>().unwrap()
+                Ok( got ) => {
+                    got
                 }
             };
 
-            let min_symbols : u32 = match matches.value_of("min_symbols") {
-                None => {
-                    100
+            let min_depth : u32 = match parse_optional_argument::<u32>(matches, "min_depth") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
                 },
-                Some( as_str ) => {
-                    as_str.trim().parse::<u32>().unwrap()
+                Ok( got ) => {
+                    got.unwrap_or(0)
                 }
             };
-            let num_tries : u32 = match matches.value_of("num_tries") {
-                None => {
-                    number_of_interactions*100*min_symbols
+            if min_depth > max_depth {
+                return (vec![format!("min_depth {:} exceeds max_depth {:}", min_depth, max_depth)], 1, 0.0);
+            }
+
+            let min_symbols : u32 = match parse_positional_argument::<u32>(matches, "min_symbols") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
                 },
-                Some( as_str ) => {
-                    as_str.trim().parse::<u32>().unwrap()
+                Ok( got ) => {
+                    got
                 }
             };
-            let pempty: f32 = match matches.value_of("pempty") {
-                None => {
-                    0.5
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let max_width : u32 = match parse_optional_argument::<u32>(matches, "max_width") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(u32::MAX)
                 }
             };
-            
-            let paction: f32 = match matches.value_of("paction") {
-                None => {
-                    0.5
+            let num_tries : u32 = match parse_optional_argument::<u32>(matches, "num_tries") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or_else(|| number_of_interactions*100*min_symbols)
                 }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            };
+            let pempty : f32 = match parse_positional_argument::<f32>(matches, "pempty") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let pstrict: f32 = match matches.value_of("pstrict") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let paction : f32 = match parse_positional_argument::<f32>(matches, "paction") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let pseq: f32 = match matches.value_of("pseq") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let pstrict : f32 = match parse_positional_argument::<f32>(matches, "pstrict") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let pcoreg: f32 = match matches.value_of("pcoreg") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let pseq : f32 = match parse_positional_argument::<f32>(matches, "pseq") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let ppar: f32 = match matches.value_of("ppar") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let pcoreg : f32 = match parse_positional_argument::<f32>(matches, "pcoreg") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let ploops: f32 = match matches.value_of("ploopS") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let ppar : f32 = match parse_positional_argument::<f32>(matches, "ppar") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let ploopw: f32 = match matches.value_of("ploopW") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let ploops : f32 = match parse_positional_argument::<f32>(matches, "ploopS") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let ploopp: f32 = match matches.value_of("ploopP") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let ploopw : f32 = match parse_positional_argument::<f32>(matches, "ploopW") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let palt: f32 = match matches.value_of("palt") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let ploopp : f32 = match parse_positional_argument::<f32>(matches, "ploopP") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let pbasic: f32 = match matches.value_of("pbasic") {
-                None => {
-                    0.0
-                }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            let palt : f32 = match parse_positional_argument::<f32>(matches, "palt") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let ptr: f32 = match matches.value_of("ptransmission") {
-                None => {
-                    0.0
+            let pbasic : f32 = match parse_positional_argument::<f32>(matches, "pbasic") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            };
+            
+            let ptr : f32 = match parse_positional_argument::<f32>(matches, "ptransmission") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
             
-            let pbc: f32 = match matches.value_of("pbroadcast") {
-                None => {
-                    0.0
+            let pbc : f32 = match parse_positional_argument::<f32>(matches, "pbroadcast") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
-                Some(as_str) => {
-                    as_str.trim().parse::<f32>().unwrap()
+            };
+            
+            let pgate : f32 = match parse_optional_argument::<f32>(matches, "pgate") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(0.0)
                 }
             };
             
-            if (pempty + paction + pstrict + pseq + pcoreg + ppar + ploops + ploopw + ploopp + palt + pbasic + ptr + pbc - 1.0).abs() > f32::EPSILON {
-                panic!("Probabilities do not sum to 1.0");
-            }
+            let pemission : f32 = match parse_optional_argument::<f32>(matches, "pemission") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(0.0)
+                }
+            };
             
+            let preception : f32 = match parse_optional_argument::<f32>(matches, "preception") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(0.0)
+                }
+            };
             
+            let tolerance : f32 = match parse_optional_argument::<f32>(matches, "tolerance") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(f32::EPSILON)
+                }
+            };
 
-            let seed : u64 = match matches.value_of("seed") {
+            // an overall loop probability may be given instead of the probabilities of each kind of loop
+            let (ploops, ploopw, ploopp) = match matches.value_of("ploop") {
                 None => {
-                    0
+                    (ploops, ploopw, ploopp)
                 },
                 Some( as_str ) => {
-                    as_str.trim().parse::<u64>().unwrap()
+                    if matches.value_of("probas") != Some("custom") {
+                        return (vec!["--ploop only applies to the custom probabilities".to_string()], 1, 0.0);
+                    }
+                    if ploops + ploopw + ploopp > 0.0 {
+                        return (vec!["--ploop replaces ploopS, ploopW and ploopP, which must then be 0".to_string()], 1, 0.0);
+                    }
+                    let ploop = match as_str.trim().parse::<f32>() {
+                        Ok( got ) if (0.0..=1.0).contains(&got) => {
+                            got
+                        },
+                        _ => {
+                            return (vec![format!("loop probability must be between 0 and 1 : '{:}'", as_str)], 1, 0.0);
+                        }
+                    };
+                    let loop_kinds = match matches.value_of("loop_kinds") {
+                        None => {
+                            LoopKindDistribution::default()
+                        },
+                        Some( spec ) => {
+                            match LoopKindDistribution::from_spec(spec) {
+                                Err(e) => {
+                                    return (vec![e.to_string()], 1, 0.0);
+                                },
+                                Ok( got ) => {
+                                    got
+                                }
+                            }
+                        }
+                    };
+                    loop_kinds.expand(ploop)
+                }
+            };
+
+            let probas_sum = pempty + paction + pstrict + pseq + pcoreg + ppar + ploops + ploopw + ploopp + palt + pbasic + ptr + pbc + pgate + pemission + preception;
+            if (probas_sum - 1.0).abs() > tolerance {
+                let msgs = vec![
+                    format!("probabilities sum to {:}, expected 1.0 (tolerance {:})", probas_sum, tolerance)
+                ];
+                return (msgs, 1, 0.0);
+            }
+            // within tolerance, rescale so that the custom distribution sums exactly to 1.0
+            let (pempty, paction, pstrict, pseq, pcoreg, ppar, ploops, ploopw, ploopp, palt, pbasic, ptr, pbc, pgate, pemission, preception) = (
+                pempty / probas_sum, paction / probas_sum, pstrict / probas_sum, pseq / probas_sum,
+                pcoreg / probas_sum, ppar / probas_sum, ploops / probas_sum, ploopw / probas_sum,
+                ploopp / probas_sum, palt / probas_sum, pbasic / probas_sum, ptr / probas_sum, pbc / probas_sum,
+                pgate / probas_sum, pemission / probas_sum, preception / probas_sum
+            );
+
+
+
+            let seed : u64 = match parse_positional_argument::<u64>(matches, "seed") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got
                 }
             };
 
@@ -235,7 +436,18 @@ pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f
             };
 
             let mut probas_name = "default";
-            let probas = if matches.is_present("probas") {
+            let probas = if matches.is_present("probas_file") {
+                let extracted = matches.value_of("probas_file").unwrap();
+                match InteractionSymbolsProbabilities::from_toml_file(extracted) {
+                    Err(e) => {
+                        return (vec![e.to_string()], 1, 0.0);
+                    },
+                    Ok(got_probas) => {
+                        probas_name = extracted;
+                        got_probas
+                    }
+                }
+            } else if matches.is_present("probas") {
                 let extracted = matches.value_of("probas").unwrap();
                 match extracted {
                     "conservative" => {
@@ -243,20 +455,20 @@ pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f
                         InteractionSymbolsProbabilities::conservative()
                     },
                     "protocols_with_coreg" => {
-                        probas_name = "conservative";
+                        probas_name = "protocols_with_coreg";
                         InteractionSymbolsProbabilities::protocols_with_coreg()
                     },
                     "custom" => {
                         probas_name = "custom";
                         InteractionSymbolsProbabilities::custom(
-                            pempty, paction, pstrict, pseq, pcoreg, ppar, ploops, ploopw, ploopp, palt, pbasic, ptr, pbc
+                            pempty, paction, pstrict, pseq, pcoreg, ppar, ploops, ploopw, ploopp, palt, pbasic, ptr, pbc, pgate, pemission, preception
                         )
                     },
                     "default" => {
                         InteractionSymbolsProbabilities::default_non_regular()
                     },
                     _ => {
-                        panic!("unknown probas for interactions generations : '{:}'", extracted)
+                        return (vec![format!("unknown probas for interactions generations : '{:}'", extracted)], 1, 0.0);
                     }
                 }
             } else {
@@ -265,6 +477,205 @@ pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f
 
 
 
+            let probas = match matches.value_of("exclude") {
+                None => {
+                    probas
+                },
+                Some( as_str ) => {
+                    let mut excluded = vec![];
+                    for key in as_str.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                        match InteractionGenerationSymbol::from_profile_key(key) {
+                            None => {
+                                return (vec![InteractionSymbolsProbabilitiesError::UnknownSymbol(key.to_string()).to_string()], 1, 0.0);
+                            },
+                            Some( symbol ) => {
+                                excluded.push(symbol);
+                            }
+                        }
+                    }
+                    match probas.without_symbols(&excluded) {
+                        Err(e) => {
+                            return (vec![e.to_string()], 1, 0.0);
+                        },
+                        Ok( got_probas ) => {
+                            got_probas
+                        }
+                    }
+                }
+            };
+
+            if probas.get_probability(&InteractionGenerationSymbol::Gate) > 0.0 && gen_ctx.get_gt_num() == 0 {
+                return (vec!["gate actions can be generated but the signature declares no gate".to_string()], 1, 0.0);
+            }
+
+            // the probability profile may also give weights to messages and lifelines
+            let mut weights = match matches.value_of("probas_file") {
+                None => {
+                    GenerationWeights::default()
+                },
+                Some( extracted ) => {
+                    match GenerationWeights::from_toml_file(&gen_ctx, extracted) {
+                        Err(e) => {
+                            return (vec![e.to_string()], 1, 0.0);
+                        },
+                        Ok( got_weights ) => {
+                            got_weights
+                        }
+                    }
+                }
+            };
+            if let Some( as_str ) = matches.value_of("handshake_bias") {
+                match as_str.trim().parse::<f64>() {
+                    Ok( got ) if (0.0..=1.0).contains(&got) => {
+                        weights.handshake_bias = got;
+                    },
+                    _ => {
+                        return (vec![format!("handshake bias must be a probability between 0 and 1 : '{:}'", as_str)], 1, 0.0);
+                    }
+                }
+            }
+            // the budget also covers the setup of the generation which follows the parsing of the arguments
+            let time_budget_secs : Option<u64> = match matches.value_of("time_budget_secs") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    match as_str.trim().parse::<u64>() {
+                        Ok( got ) => {
+                            Some(got)
+                        },
+                        Err(_) => {
+                            return (vec![format!("time budget must be a whole number of seconds : '{:}'", as_str)], 1, 0.0);
+                        }
+                    }
+                }
+            };
+            let deadline = time_budget_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+            let adaptive_tries : Option<u32> = match matches.value_of("adaptive_tries") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    match as_str.trim().parse::<u32>() {
+                        Ok( got ) if got > 0 => {
+                            Some(got)
+                        },
+                        _ => {
+                            return (vec![format!("adaptive tries must be a positive number of interactions : '{:}'", as_str)], 1, 0.0);
+                        }
+                    }
+                }
+            };
+
+            let max_nfa_states : Option<usize> = match matches.value_of("max_nfa_states") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    match as_str.trim().parse::<usize>() {
+                        Ok( got ) if got > 0 => {
+                            Some(got)
+                        },
+                        _ => {
+                            return (vec![format!("maximum number of NFA states must be a positive integer : '{:}'", as_str)], 1, 0.0);
+                        }
+                    }
+                }
+            };
+            let nfa_check_min_symbols : u32 = match matches.value_of("nfa_check_min_symbols") {
+                None => {
+                    0
+                },
+                Some( as_str ) => {
+                    match as_str.trim().parse::<u32>() {
+                        Ok( got ) => {
+                            got
+                        },
+                        Err(_) => {
+                            return (vec![format!("minimum number of symbols for the NFA check must be a whole number : '{:}'", as_str)], 1, 0.0);
+                        }
+                    }
+                }
+            };
+
+            let accept_if : Option<MetricPredicate> = match matches.value_of("accept_if") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    match MetricPredicate::parse(as_str) {
+                        Err(e) => {
+                            return (vec![format!("invalid --accept-if '{:}' : {:}", as_str, e)], 1, 0.0);
+                        },
+                        Ok( got ) => {
+                            Some(got)
+                        }
+                    }
+                }
+            };
+
+            // the prefix and suffix of the scaffold are parsed against the same signature as the generated interactions
+            let mut scaffold : (Option<HashedInteraction>,Option<HashedInteraction>) = (None,None);
+            for (arg_name,part) in [("prefix",&mut scaffold.0),("suffix",&mut scaffold.1)] {
+                if let Some(hif_file_path) = matches.value_of(arg_name) {
+                    match parse_hif_file_with(&gen_ctx,hif_file_path,include_ctx) {
+                        Err(e) => {
+                            return (vec![format!("could not parse {:} '{:}' : {:}", arg_name, hif_file_path, e)], 1, 0.0);
+                        },
+                        Ok( got ) => {
+                            *part = Some(HashedInteraction::new(got));
+                        }
+                    }
+                }
+            }
+
+            let mut must_accept : Vec<(CoLocalizations,MultiTrace)> = vec![];
+            if let Some(folder) = matches.value_of("must_accept") {
+                let htf_paths = match get_files_matching_pattern(&format!("{:}/*.{:}", folder.trim_end_matches('/'), HIBOU_TRACE_FILE_EXTENSION)) {
+                    Err(e) => {
+                        return (vec![e], 1, 0.0);
+                    },
+                    Ok( got ) => {
+                        got
+                    }
+                };
+                if htf_paths.is_empty() {
+                    return (vec![format!("no .{:} file in folder '{:}'", HIBOU_TRACE_FILE_EXTENSION, folder)], 1, 0.0);
+                }
+                for path in htf_paths {
+                    match parse_htf_file(&gen_ctx,path.to_str().unwrap()) {
+                        Err(e) => {
+                            return (vec![format!("could not parse reference trace '{:}' : {:}", path.display(), e)], 1, 0.0);
+                        },
+                        Ok( got ) => {
+                            must_accept.push(got);
+                        }
+                    }
+                }
+            }
+
+            let depth_dist : Option<DepthDistribution> = match matches.value_of("depth_dist") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    match DepthDistribution::from_spec(as_str.trim()) {
+                        None => {
+                            return (vec![format!("unknown depth distribution : '{:}' (expected 'uniform:MIN:MAX')", as_str)], 1, 0.0);
+                        },
+                        Some( got ) => {
+                            if got.get_max_depth() > max_depth {
+                                return (vec![format!("depth distribution '{:}' exceeds max_depth {:}", got, max_depth)], 1, 0.0);
+                            }
+                            if got.get_max_depth() < min_depth {
+                                return (vec![format!("depth distribution '{:}' never reaches min_depth {:}", got, min_depth)], 1, 0.0);
+                            }
+                            Some(got)
+                        }
+                    }
+                }
+            };
+
             let mut ret_print = vec![];
             ret_print.push( "generated random interactions interactions".to_string());
             ret_print.push(
@@ -272,55 +683,868 @@ pub fn cli_rng_gen_raw_interactions(matches : &ArgMatches) -> (Vec<String>,u32,f
                         probas_name)
             );
             ret_print.push(
-                format!("num_ints : {:}, max_depth : {:}, min_symbols : {:}, seed : {:}",
+                format!("num_ints : {:}, max_depth : {:}, min_depth : {:}, min_symbols : {:}, max_width : {:}, seed : {:}",
                         number_of_interactions,
                         max_depth,
+                        min_depth,
                         min_symbols,
+                        max_width,
                         seed)
             );
             ret_print.push( format!(
                 "in folder '{:}'",
                 output_folder)
             );
+            if let Some(dist) = &depth_dist {
+                ret_print.push( format!("with depths sampled from {:}", dist) );
+            }
+            if matches.is_present("allow_vacuous_loops") {
+                ret_print.push( "keeping loops with a possibly empty body".to_string() );
+            }
+            if matches.is_present("finite_only") {
+                ret_print.push( "keeping only interactions with finitely many traces".to_string() );
+            }
+            if let Some( as_str ) = matches.value_of("handshake_bias") {
+                ret_print.push( format!("emissions directly received with probability {:}", as_str.trim()) );
+            }
+            if matches.is_present("ploop") {
+                ret_print.push( format!("loops drawn with probability {:} as loopS {:.4}, loopW {:.4}, loopP {:.4}",
+                                        matches.value_of("ploop").unwrap().trim(), ploops, ploopw, ploopp) );
+            }
+            if let Some(secs) = time_budget_secs {
+                ret_print.push( format!("stopping after at most {:} seconds", secs) );
+            }
+            if let Some(num_accepted) = adaptive_tries {
+                ret_print.push( format!("re-estimating the number of tries from the acceptance rate of the first {:} interactions", num_accepted) );
+            }
+            if matches.is_present("diverse") {
+                ret_print.push( "favoring interactions introducing unseen operator pairs until the pair coverage saturates".to_string() );
+            }
+            if probas.get_probability(&InteractionGenerationSymbol::Basic) > 0.0 {
+                ret_print.push( format!("'basic' draws (with probability {:.4}) a sequential block in expanded form, with symbols drawn as {:}",
+                                        probas.get_probability(&InteractionGenerationSymbol::Basic),
+                                        InteractionSymbolsProbabilities::describe_basic()) );
+            }
+            if matches.is_present("prefix") || matches.is_present("suffix") {
+                ret_print.push( format!("embedding each generated interaction as strict({:}, <generated>, {:})",
+                                        matches.value_of("prefix").unwrap_or("o"),
+                                        matches.value_of("suffix").unwrap_or("o")) );
+            }
+            if let Some(folder) = matches.value_of("must_accept") {
+                ret_print.push( format!("keeping only interactions accepting the {:} reference multi-traces of folder '{:}'", must_accept.len(), folder) );
+            }
+            if let Some( as_str ) = matches.value_of("accept_if") {
+                ret_print.push( format!("keeping only interactions satisfying '{:}'", as_str.trim()) );
+            }
+            if let Some(max_states) = max_nfa_states {
+                ret_print.push( format!("keeping only interactions whose NFA has at most {:} states (checked from {:} symbols)", max_states, nfa_check_min_symbols) );
+            }
+
+            let dedup : InteractionDeduplicationKind = match matches.value_of("dedup") {
+                None => {
+                    InteractionDeduplicationKind::Structural
+                },
+                Some( as_str ) => {
+                    match InteractionDeduplicationKind::from_name(as_str.trim()) {
+                        None => {
+                            return (vec![format!("unknown deduplication mode : '{:}'", as_str)], 1, 0.0);
+                        },
+                        Some( got ) => {
+                            got
+                        }
+                    }
+                }
+            };
+            if dedup == InteractionDeduplicationKind::Semantic {
+                ret_print.push( "deduplicating interactions up to normalization".to_string());
+            }
+
+            let jobs : Option<u32> = match parse_optional_argument::<u32>(matches, "jobs") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.map(|x| x.max(1))
+                }
+            };
+
+            let lifelines : Option<Vec<usize>> = match matches.value_of("lifelines") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    let mut lf_ids = vec![];
+                    for lf_name in as_str.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                        match gen_ctx.get_lf_id(lf_name) {
+                            None => {
+                                return (vec![format!("unknown lifeline : '{:}'", lf_name)], 1, 0.0);
+                            },
+                            Some( lf_id ) => {
+                                if !lf_ids.contains(&lf_id) {
+                                    lf_ids.push(lf_id);
+                                }
+                            }
+                        }
+                    }
+                    if lf_ids.is_empty() {
+                        return (vec!["no lifeline selected".to_string()], 1, 0.0);
+                    }
+                    Some(lf_ids)
+                }
+            };
+            if let Some(lf_ids) = &lifelines {
+                if lf_ids.len() < 2 {
+                    // transmissions and broadcasts require a target lifeline and are redrawn
+                    let only_patterns = probas.get_probability(&InteractionGenerationSymbol::Transmission)
+                        + probas.get_probability(&InteractionGenerationSymbol::Broadcast) > 1.0 - 1e-6;
+                    if only_patterns {
+                        return (vec!["a single lifeline is selected but only transmissions and broadcasts can be generated".to_string()], 1, 0.0);
+                    }
+                }
+                let lf_names : Vec<String> = lf_ids.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+                ret_print.push( format!("restricted to lifelines {:}", lf_names.join(",")) );
+            }
+
+            let dedup_window : Option<usize> = match parse_optional_argument::<usize>(matches, "dedup_window") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    if let Some(window) = got {
+                        ret_print.push( format!("detecting duplicates among the last {:} interactions only", window) );
+                    }
+                    got
+                }
+            };
+
+            let mut params = GenerationParams::new(number_of_interactions,
+                                                   max_depth,
+                                                   min_symbols,
+                                                   num_tries,
+                                                   probas);
+            params.max_width = max_width;
+            params.min_depth = min_depth;
+            params.dedup = dedup;
+            params.lifelines = lifelines;
+            params.dedup_window = dedup_window;
+            params.depth_dist = depth_dist.clone();
+            params.weights = weights;
+            params.loop_requires_content = !matches.is_present("allow_vacuous_loops");
+            params.finite_only = matches.is_present("finite_only");
+            params.deadline = deadline;
+            params.adaptive_tries = adaptive_tries;
+            params.diverse = matches.is_present("diverse");
+            params.max_nfa_states = max_nfa_states;
+            params.nfa_check_min_symbols = nfa_check_min_symbols;
+            params.accept_if = accept_if;
+            params.scaffold = scaffold;
+            params.must_accept = must_accept;
+            if params.diverse && jobs.is_some() {
+                return (vec!["--diverse cannot be combined with --jobs as the pair coverage is shared by all the interactions".to_string()], 1, 0.0);
+            }
+
+            let checkpoint_every : u32 = match parse_optional_argument::<u32>(matches, "checkpoint_every") {
+                Err(e) => {
+                    return (vec![e], 1, 0.0);
+                },
+                Ok( got ) => {
+                    got.unwrap_or(100).max(1)
+                }
+            };
+            // numbers of accepted and rejected traces written along with each interaction
+            let with_traces : Option<(u32,u32)> = match matches.value_of("with_traces") {
+                None => {
+                    None
+                },
+                Some( as_str ) => {
+                    let parsed = as_str.trim().split_once(':')
+                        .and_then(|(p,n)| Some((p.trim().parse::<u32>().ok()?, n.trim().parse::<u32>().ok()?)));
+                    match parsed {
+                        None => {
+                            return (vec![format!("numbers of traces must be given as 'p:n' with p and n non-negative integers : '{:}'", as_str)], 1, 0.0);
+                        },
+                        Some( got ) => {
+                            Some(got)
+                        }
+                    }
+                }
+            };
+            if let Some((num_positives,num_negatives)) = with_traces {
+                ret_print.push( format!("writing with each interaction up to {:} of its traces and {:} rejected traces derived from them", num_positives, num_negatives) );
+            }
+
+            // in a dry run, the generation is carried on as usual but nothing is written
+            let dry_run = matches.is_present("dry_run");
+            if dry_run && (matches.is_present("resume") || matches.is_present("draw") || matches.is_present("plot_sizes")
+                || matches.is_present("overwrite") || matches.is_present("append") || matches.is_present("append_stats")) {
+                return (vec!["--dry-run cannot be combined with --resume, --draw, --plot-sizes, --overwrite, --append or --append-stats".to_string()], 1, 0.0);
+            }
+            if matches.is_present("resume") && (matches.is_present("overwrite") || matches.is_present("append")) {
+                return (vec!["--resume cannot be combined with --overwrite or --append".to_string()], 1, 0.0);
+            }
+            let num_buckets : u32 = match matches.value_of("buckets") {
+                None => {
+                    10
+                },
+                Some( as_str ) => {
+                    match as_str.trim().parse::<u32>() {
+                        Ok( got ) if got > 0 => {
+                            got
+                        },
+                        _ => {
+                            return (vec![format!("number of buckets must be a positive integer : '{:}'", as_str)], 1, 0.0);
+                        }
+                    }
+                }
+            };
+            match mode {
+                RawGenerationMode::Generate => {},
+                RawGenerationMode::Validate => {
+                    return (ret_print, 0, 0.0);
+                },
+                RawGenerationMode::Replay{ index, rng_words, hash, output } => {
+                    let mut rng = CountingRng::resume(seed, *rng_words);
+                    match regenerate_interaction(&gen_ctx, &params, &mut rng) {
+                        Err(rejection) => {
+                            return (vec![format!("the replayed attempt was rejected ({:?}) : the generation arguments or the signature must have changed", rejection)], 1, 0.0);
+                        },
+                        Ok( i ) => {
+                            let got_hash = format!("{:016x}", canonical_hash(&i));
+                            if got_hash != *hash {
+                                return (vec![format!("the replayed interaction has hash {:} instead of {:} : the generation arguments or the signature must have changed", got_hash, hash)], 1, 0.0);
+                            }
+                            let provenance = if matches.is_present("no_provenance") {
+                                None
+                            } else {
+                                Some(HifProvenance::new(&gen_ctx, seed, *index, &i))
+                            };
+                            interaction_to_hif_with_provenance(output.as_path(),&gen_ctx,&i,provenance.as_ref());
+                            ret_print.push( format!("replayed interaction i{:} after {:} rng words, with matching hash {:}", index, rng_words, hash) );
+                            ret_print.push( format!("wrote it to file '{:}'", output.display()) );
+                            return (ret_print, 0, 0.0);
+                        }
+                    }
+                }
+            }
+            let state_path : PathBuf = [&output_folder, "generation.state"].iter().collect();
+            let mut state = GenerationState::new(&params, CountingRng::new(seed));
+            let mut manifest_files = vec![];
+            let mut first_index : usize = 0;
+            if matches.is_present("resume") {
+                if jobs.is_some() {
+                    return (vec!["resuming a generation split among worker threads is not supported".to_string()], 1, 0.0);
+                }
+                match GenerationCheckpoint::load(&gen_ctx,&state_path) {
+                    Err(e) => {
+                        return (vec![format!("could not resume from '{:}' : {:}", state_path.display(), e)], 1, 0.0);
+                    },
+                    Ok( checkpoint ) => {
+                        if checkpoint.state.rng.get_seed() != seed {
+                            return (vec![format!("the generation to resume was seeded with {:}", checkpoint.state.rng.get_seed())], 1, 0.0);
+                        }
+                        ret_print.push( format!("resuming after {:} interactions", checkpoint.state.num_generated) );
+                        state = checkpoint.state;
+                        manifest_files = checkpoint.files;
+                        first_index = checkpoint.first_index;
+                    }
+                }
+            } else if !dry_run {
+                // files left by a previous generation would otherwise be mixed with the new ones
+                if let Err(e) = std::fs::create_dir_all(&output_folder) {
+                    return (vec![format!("could not create output folder '{:}' : {:}", output_folder, e)], 1, 0.0);
+                }
+                let existing = match get_interaction_file_indices(&output_folder) {
+                    Err(e) => {
+                        return (vec![format!("could not read output folder '{:}' : {:}", output_folder, e)], 1, 0.0);
+                    },
+                    Ok( got ) => {
+                        got
+                    }
+                };
+                if !existing.is_empty() {
+                    if matches.is_present("overwrite") {
+                        for x in &existing {
+                            for extension in [HIBOU_INTERACTION_FILE_EXTENSION, "svg"] {
+                                let path : PathBuf = [&output_folder, &format!("i{:}.{:}", x, extension)].iter().collect();
+                                if path.exists() {
+                                    if let Err(e) = std::fs::remove_file(&path) {
+                                        return (vec![format!("could not remove '{:}' : {:}", path.display(), e)], 1, 0.0);
+                                    }
+                                }
+                            }
+                        }
+                        ret_print.push( format!("removed {:} interaction files of a previous generation", existing.len()) );
+                    } else if matches.is_present("append") {
+                        first_index = existing.iter().max().unwrap() + 1;
+                        ret_print.push( format!("appending to {:} interaction files of a previous generation, from i{:}", existing.len(), first_index) );
+                    } else {
+                        return (vec![format!("output folder '{:}' already contains {:} interaction files : pass --overwrite to remove them or --append to number the new ones after them",
+                                             output_folder, existing.len())], 1, 0.0);
+                    }
+                }
+            }
+            // with --append, the manifest keeps listing the files of the previous generation
+            let previous_files : Vec<serde_json::Value> = if first_index > 0 {
+                read_manifest_files(&output_folder)
+            } else {
+                vec![]
+            };
+
+            // the first checkpointing error, after which nothing more is written
+            let mut write_error : Option<String> = None;
+            // drawing errors do not stop the generation, they are recorded in the manifest and reported at the end
+            let mut draw_errors : Vec<String> = vec![];
+            let mut write_interaction = |i : Interaction, got_state : Option<&GenerationState<CountingRng>>| {
+                if write_error.is_some() {
+                    return;
+                }
+                let x = first_index + manifest_files.len();
+                let file_name = format!("i{:}.{:}", x, HIBOU_INTERACTION_FILE_EXTENSION);
+                let mut entry = manifest_file_entry(&file_name,&i);
+                // from which the interaction can be drawn again by 'replay' (unknown when split among worker threads)
+                if let Some(rng) = got_state.and_then(|got_state| got_state.last_attempt_rng.as_ref()) {
+                    entry["rng_words"] = json!(rng.get_words());
+                }
+                // the NFA size is only known for the interactions checked against --max-nfa-states
+                if let Some(num_states) = got_state.and_then(|got_state| got_state.last_nfa_states) {
+                    entry["nfa_states"] = json!(num_states);
+                }
+                if let Some(num_accepted) = got_state.and_then(|got_state| got_state.last_accepted_traces) {
+                    entry["accepted_traces"] = json!(num_accepted);
+                }
+                // the sub-interactions drawn for 'basic' cannot be told apart in the written file
+                if let Some(got_state) = got_state.filter(|got_state| !got_state.last_basic_blocks.is_empty()) {
+                    let mut basic_counts = SymbolCounts::default();
+                    for block in &got_state.last_basic_blocks {
+                        basic_counts.add_metrics(&InteractionMetrics::extract_from_interaction(block));
+                    }
+                    entry["basic_blocks"] = json!(got_state.last_basic_blocks.len());
+                    entry["basic_symbols"] = basic_counts.to_json();
+                }
+                if dry_run {
+                    manifest_files.push(entry);
+                    return;
+                }
+                let path : PathBuf = [&output_folder, &file_name].iter().collect();
+                // the provenance comment lets a file be traced back to its generation even once renamed or moved
+                let provenance = if matches.is_present("no_provenance") {
+                    None
+                } else {
+                    Some(HifProvenance::new(&gen_ctx, seed, x, &i))
+                };
+                interaction_to_hif_with_provenance(path.as_path(),&gen_ctx,&i,provenance.as_ref());
+                if let Some((num_positives,num_negatives)) = with_traces {
+                    // each interaction gets its own rng so that its traces do not depend on the other interactions
+                    let mut traces_rng = StdRng::seed_from_u64(seed.wrapping_add(x as u64));
+                    let positives : Vec<Trace> = sample_accepted_traces(&i,num_positives,1,&mut traces_rng).into_iter().unique().collect();
+                    let co_localizations = CoLocalizations::get_trivial_partition(gen_ctx.get_lf_num());
+                    let negatives = derive_rejected_traces(&i,&co_localizations,&positives,num_negatives,&mut traces_rng);
+                    for (label,traces) in [("pos",&positives),("neg",&negatives)] {
+                        for (k,trace) in traces.iter().enumerate() {
+                            let trace_file_name = format!("i{:}_{:}{:}.{:}", x, label, k, HIBOU_TRACE_FILE_EXTENSION);
+                            let trace_path : PathBuf = [&output_folder, &trace_file_name].iter().collect();
+                            write_multi_trace_into_file(trace_path.as_path(),&gen_ctx,&co_localizations,&vec![trace.clone()]);
+                        }
+                    }
+                    entry["positive_traces"] = json!(positives.len());
+                    entry["negative_traces"] = json!(negatives.len());
+                }
+                info!("wrote to file '{:}'", path.display());
+                if matches.is_present("draw") {
+                    if let Err(e) = draw_interaction_with_format(&gen_ctx,
+                                                                 &i,
+                                                                 &InteractionGraphicalRepresentation::AsTerm,
+                                                                 &None,
+                                                                 &GraphVizOutputFormat::svg,
+                                                                 &"temp".to_string(),
+                                                                 &output_folder,
+                                                                 &format!("i{:}", x)) {
+                        entry["drawing_error"] = json!(e.to_string());
+                        draw_errors.push(format!("could not draw interaction i{:} : {:}", x, e));
+                    }
+                }
+                manifest_files.push(entry);
+                if let Some(got_state) = got_state {
+                    if got_state.num_generated % checkpoint_every == 0 {
+                        let checkpoint = GenerationCheckpoint{ state : got_state.clone(), files : manifest_files.clone(), first_index };
+                        if let Err(e) = checkpoint.save(&gen_ctx,&state_path) {
+                            write_error = Some(format!("could not write checkpoint : {:}", e));
+                        }
+                    }
+                }
+            };
 
-            let mut memoized_ints = HashSet::new();
+            let mut pair_coverage : Option<f64> = None;
+            let mut run_stats = CumulativeGenerationStats::new_run();
+            if let Some(jobs) = jobs {
+                ret_print.push( format!("using {:} worker threads", jobs) );
+                for i in generate_in_parallel(&gen_ctx, params, jobs, seed) {
+                    write_interaction(i, None);
+                }
+                if dry_run {
+                    ret_print.push( "dry run : attempts and retries are not tracked across worker threads".to_string() );
+                }
+            } else {
+                // interactions are written as soon as they are generated
+                // and the generation state is regularly saved so that it may be resumed
+                generate_random_interactions_streamed(&gen_ctx, &params, &mut state,
+                                                      &mut |i,got_state| write_interaction(i,Some(got_state)));
+                if dry_run {
+                    // with adaptive tries, the budget is the one re-estimated during the generation
+                    let (retries,budget) = match params.adaptive_tries {
+                        None => (params.num_tries - state.remaining_tries, params.num_tries),
+                        Some(_) => (state.failed_tries(), state.failed_tries() + state.remaining_tries)
+                    };
+                    let attempts = state.num_generated + retries;
+                    ret_print.push( format!("dry run : {:} attempts, {:} accepted (acceptance rate {:.1}%), {:} retries consumed out of {:}",
+                                            attempts,
+                                            state.num_generated,
+                                            100.0 * state.num_generated as f64 / attempts.max(1) as f64,
+                                            retries,
+                                            budget) );
+                }
+                if min_depth > 0 {
+                    ret_print.push( format!("{:} attempts rejected for being shallower than min_depth {:}", state.shallow_rejections, min_depth) );
+                }
+                if params.finite_only {
+                    ret_print.push( format!("{:} attempts rejected for having infinitely many traces", state.infinite_rejections) );
+                }
+                ret_print.push( format!("{:} attempts rejected as duplicates, {:} for having fewer than min_symbols {:} symbols, {:} for failing the other constraints (max_width, loops with a possibly empty body)",
+                                        state.duplicate_rejections, state.few_symbols_rejections, min_symbols, state.other_rejections) );
+                if params.diverse {
+                    let reachable = params.probas.get_reachable_operator_pairs();
+                    ret_print.push( format!("{:} attempts rejected for introducing no unseen operator pair", state.redundant_rejections) );
+                    ret_print.push( format!("operator pair coverage : {:} out of {:} reachable pairs ({:.1}%), {:} pairs covered overall",
+                                            reachable.intersection(&state.covered_pairs).count(),
+                                            reachable.len(),
+                                            100.0 * state.pair_coverage(&params.probas),
+                                            state.covered_pairs.len()) );
+                    pair_coverage = Some(state.pair_coverage(&params.probas));
+                }
+                if let Some( as_str ) = matches.value_of("accept_if") {
+                    ret_print.push( format!("{:} attempts rejected for not satisfying '{:}'", state.predicate_rejections, as_str.trim()) );
+                }
+                if !params.must_accept.is_empty() {
+                    ret_print.push( format!("{:} attempts rejected for not accepting all the {:} reference multi-traces", state.trace_rejections, params.must_accept.len()) );
+                    for (num_accepted,count) in state.partial_acceptances.iter().enumerate().filter(|(_,count)| **count > 0) {
+                        ret_print.push( format!("    {:} of which accepted {:} of them", count, num_accepted) );
+                    }
+                }
+                if let Some(max_states) = params.max_nfa_states {
+                    ret_print.push( format!("{:} attempts rejected for translating into a NFA with more than {:} states", state.nfa_rejections, max_states) );
+                }
+                run_stats.add_state(&state);
+                if !dry_run && write_error.is_none() {
+                    let checkpoint = GenerationCheckpoint{ state, files : manifest_files.clone(), first_index };
+                    if let Err(e) = checkpoint.save(&gen_ctx,&state_path) {
+                        write_error = Some(format!("could not write checkpoint : {:}", e));
+                    }
+                }
+            }
+            if let Some(e) = write_error {
+                return (vec![e], 1, 0.0);
+            }
+            if let (Some(secs),Some(deadline)) = (time_budget_secs,deadline) {
+                if (manifest_files.len() as u32) < number_of_interactions && Instant::now() >= deadline {
+                    ret_print.push( format!("time budget of {:} seconds exceeded : reached {:} out of {:} interactions", secs, manifest_files.len(), number_of_interactions) );
+                }
+            }
 
-            let mut rng = StdRng::seed_from_u64(seed);
-            let mut x = 0;
-            let mut max_tries = num_tries;
-            'myloop : while x < number_of_interactions {
-                println!("trying to generate interaction {} out of {}", x, number_of_interactions);
-                let mut got_one = false;
-                if let Some(i) = generate_raw_random_interaction(&gen_ctx,
-                                                              &mut rng,
-                                                              max_depth,
-                                                              min_symbols,
-                                                              &probas
-                ) {
-                    if !memoized_ints.contains(&i) {
-                        got_one = true;
-                        let file_name = format!("i{:}.{:}", x, HIBOU_INTERACTION_FILE_EXTENSION);
-                        let path : PathBuf = [&output_folder, &file_name].iter().collect();
-                        interaction_to_hif(path.as_path(),&gen_ctx,&i);
-                        memoized_ints.insert(i);
-                        x += 1;
-                        println!("wrote to file '{:?}'", path.as_path())
+            // symbol occurrences are aggregated from the manifest so that files generated
+            // before a resumed run are also accounted for
+            let mut symbol_counts = SymbolCounts::default();
+            for entry in &manifest_files {
+                if let Some(symbols) = entry.get("symbols") {
+                    symbol_counts.add_json(symbols);
+                }
+            }
+            ret_print.push( format!("generated {:} interactions with symbol occurrences :", manifest_files.len()) );
+            ret_print.push( symbol_counts.to_summary() );
+            let num_basic_blocks : u64 = manifest_files.iter().filter_map(|entry| entry.get("basic_blocks").and_then(|x| x.as_u64())).sum();
+            if num_basic_blocks > 0 {
+                let mut basic_counts = SymbolCounts::default();
+                for entry in &manifest_files {
+                    if let Some(symbols) = entry.get("basic_symbols") {
+                        basic_counts.add_json(symbols);
                     }
                 }
-                if !got_one {
-                    println!("retrying...");
-                    max_tries -= 1;
-                    if max_tries <= 0 {
-                        println!("... max retries exceeded");
-                        break 'myloop;
+                ret_print.push( format!("of which drawn within {:} 'basic' blocks : {:}", num_basic_blocks, basic_counts.to_summary()) );
+            }
+            if max_nfa_states.is_some() {
+                let nfa_sizes : Vec<u64> = manifest_files.iter().filter_map(|entry| entry.get("nfa_states").and_then(|x| x.as_u64())).collect();
+                if nfa_sizes.is_empty() {
+                    ret_print.push( "no generated interaction was translated into a NFA".to_string() );
+                } else {
+                    ret_print.push( format!("NFA states of the {:} translated interactions : min {:}, average {:.1}, max {:}",
+                                            nfa_sizes.len(),
+                                            nfa_sizes.iter().min().unwrap(),
+                                            nfa_sizes.iter().sum::<u64>() as f64 / nfa_sizes.len() as f64,
+                                            nfa_sizes.iter().max().unwrap()) );
+                    let histogram = SizeHistogram::from_sizes(&nfa_sizes, num_buckets);
+                    let buckets : Vec<String> = histogram.buckets.iter()
+                        .map(|(lower,upper,count)| format!("{:}-{:} : {:}", lower, upper, count))
+                        .collect();
+                    ret_print.push( format!("NFA states distribution : {:}", buckets.join(", ")) );
+                }
+            }
+            if dry_run {
+                let sizes : Vec<u64> = manifest_files.iter().filter_map(|entry| entry["num_symbols"].as_u64()).collect();
+                let depths : Vec<u64> = manifest_files.iter().filter_map(|entry| entry["depth"].as_u64()).collect();
+                if !sizes.is_empty() {
+                    ret_print.push( format!("symbols per interaction : min {:}, average {:.1}, max {:}",
+                                            sizes.iter().min().unwrap(),
+                                            sizes.iter().sum::<u64>() as f64 / sizes.len() as f64,
+                                            sizes.iter().max().unwrap()) );
+                    ret_print.push( format!("depth : min {:}, max {:}", depths.iter().min().unwrap(), depths.iter().max().unwrap()) );
+                }
+                ret_print.push( "no file was written".to_string() );
+                return (ret_print, 0, 0.0);
+            }
+
+            let manifest = json!({
+                "signature" : hsf_file_path,
+                "probas" : probas_name,
+                "seed" : seed,
+                "num_ints" : number_of_interactions,
+                "max_depth" : max_depth,
+                "min_depth" : min_depth,
+                "finite_only" : matches.is_present("finite_only"),
+                "time_budget_secs" : time_budget_secs,
+                "adaptive_tries" : adaptive_tries,
+                "pair_coverage" : pair_coverage,
+                "max_nfa_states" : max_nfa_states,
+                "accept_if" : matches.value_of("accept_if").map(|x| x.trim()),
+                "must_accept" : matches.value_of("must_accept"),
+                "with_traces" : with_traces.map(|(p,n)| json!({"positives" : p, "negatives" : n})),
+                "prefix" : matches.value_of("prefix"),
+                "suffix" : matches.value_of("suffix"),
+                "nfa_check_min_symbols" : max_nfa_states.map(|_| nfa_check_min_symbols),
+                "depth_dist" : depth_dist.map(|dist| dist.to_string()),
+                "min_symbols" : min_symbols,
+                "first_index" : first_index,
+                "arguments" : get_generation_arguments(matches),
+                "files" : previous_files.iter().chain(manifest_files.iter()).collect::<Vec<&serde_json::Value>>()
+            });
+            if let Err(e) = write_manifest(&output_folder, &manifest) {
+                return (vec![format!("could not write manifest : {:}", e)], 1, 0.0);
+            }
+            if matches.is_present("plot_sizes") {
+                let sizes : Vec<u64> = manifest_files.iter().filter_map(|entry| entry["num_symbols"].as_u64()).collect();
+                let histogram = SizeHistogram::from_sizes(&sizes, num_buckets);
+                if let Err(e) = histogram.write_files(&output_folder) {
+                    return (vec![format!("could not write size histogram : {:}", e)], 1, 0.0);
+                }
+                ret_print.push( format!("size histogram over {:} buckets written to 'sizes.svg' and 'sizes.csv'", histogram.buckets.len()) );
+            }
+            let drawing_failed = !draw_errors.is_empty();
+            if drawing_failed {
+                // the interactions and the manifest are written nonetheless
+                ret_print.push( format!("{:} interactions out of {:} could not be drawn (see 'drawing_error' in the manifest) :", draw_errors.len(), manifest_files.len()) );
+                ret_print.extend(draw_errors);
+            }
+            if matches.is_present("append_stats") {
+                for entry in &manifest_files {
+                    run_stats.add_manifest_entry(entry);
+                }
+                match run_stats.append_to_folder(&output_folder) {
+                    Err(e) => {
+                        return (vec![format!("could not append statistics : {:}", e)], 1, 0.0);
+                    },
+                    Ok( merged ) => {
+                        ret_print.push( format!("statistics of {:} generations with {:} interactions accumulated in 'stats.json'", merged.runs, merged.num_interactions) );
+                        if let Some(rate) = merged.get_acceptance_rate() {
+                            ret_print.push( format!("cumulative acceptance rate {:.1}% over {:} tracked attempts", 100.0 * rate, merged.attempts) );
+                        }
+                        let buckets : Vec<String> = merged.get_size_histogram(num_buckets).buckets.iter()
+                            .map(|(lower,upper,count)| format!("{:}-{:} : {:}", lower, upper, count))
+                            .collect();
+                        ret_print.push( format!("cumulative symbols per interaction distribution : {:}", buckets.join(", ")) );
                     }
                 }
+            }
 
+            return (ret_print, if drawing_failed {1} else {0}, 0.0); // Add a default f32 value
+        }
+    }
+}
+
+
+/// Splits the generation of 'params.num_ints' interactions across 'jobs' worker threads.
+/// Worker 'w' uses its own rng seeded with 'seed + w' and its own memoization set so that
+/// the result does not depend on thread scheduling.
+/// Cross-worker duplicates are removed when merging (in worker order) and the missing
+/// interactions are then generated sequentially from an rng seeded with 'seed + jobs'.
+fn generate_in_parallel(gen_ctx : &GeneralContext,
+                        params : GenerationParams,
+                        jobs : u32,
+                        seed : u64) -> Vec<Interaction> {
+    let mut worker_params = params.clone();
+    worker_params.num_tries = params.num_tries.div_ceil(jobs);
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs as usize).build().unwrap();
+    let per_worker : Vec<Vec<Interaction>> = pool.install(|| {
+        (0..jobs).into_par_iter().map(|worker_idx| {
+            let mut worker_params = worker_params.clone();
+            worker_params.num_ints = params.num_ints / jobs + if worker_idx < params.num_ints % jobs {1} else {0};
+            let mut rng = StdRng::seed_from_u64(seed + worker_idx as u64);
+            generate_random_interactions(gen_ctx, worker_params, &mut rng)
+        }).collect()
+    });
+    // ***
+    let mut memo = InteractionMemo::new(params.dedup_window);
+    let mut generated = vec![];
+    for worker_ints in per_worker {
+        for i in worker_ints {
+            let key = HashedInteraction::new(params.dedup.get_key(gen_ctx,&i));
+            if memo.insert(key) {
+                generated.push(i);
             }
+        }
+    }
+    // ***
+    let missing = params.num_ints - generated.len() as u32;
+    if missing > 0 {
+        info!("{:} interactions missing after merging workers, generating replacements...", missing);
+        worker_params.num_ints = missing;
+        let mut rng = StdRng::seed_from_u64(seed + jobs as u64);
+        generated.extend(generate_random_interactions_memoized(gen_ctx,
+                                                               &worker_params,
+                                                               &mut rng,
+                                                               &mut memo));
+    }
+    generated
+}
 
 
+fn manifest_file_entry(file_name : &str, i : &Interaction) -> serde_json::Value {
+    let metrics = InteractionMetrics::extract_from_interaction(i);
+    json!({
+        "file" : file_name,
+        "num_symbols" : metrics.get_num_symbols(),
+        "depth" : metrics.depth,
+        "hash" : format!("{:016x}", canonical_hash(i)),
+        "symbols" : metrics.to_json()["symbols"]
+    })
+}
 
-            return (ret_print, 0, 0.0); // Add a default f32 value
+/// Arguments of 'rng_gen_raw_interactions' (after its name) which yield the same matches as 'matches'
+/// : the positional arguments in their order and then the other arguments given on the command line.
+pub fn get_generation_arguments(matches : &ArgMatches) -> Vec<String> {
+    let yaml = load_yaml!("../hibou_cli.yml");
+    let app = App::from_yaml(yaml);
+    let command = app.find_subcommand("rng_gen_raw_interactions").unwrap();
+    let mut positionals = vec![];
+    let mut options = vec![];
+    for arg in command.get_arguments() {
+        let id = arg.get_id();
+        if matches.value_source(id) != Some(ValueSource::CommandLine) {
+            continue;
         }
+        let value = if arg.is_takes_value_set() || arg.is_positional() {
+            matches.value_of(id)
+        } else {
+            None
+        };
+        match (arg.is_positional(),value) {
+            (true,Some(got)) => {
+                positionals.push(got.to_string());
+            },
+            (_,Some(got)) => {
+                options.push(format!("--{:}={:}", arg.get_long().unwrap(), got));
+            },
+            (_,None) => {
+                options.push(format!("--{:}", arg.get_long().unwrap()));
+            }
+        }
+    }
+    positionals.extend(options);
+    positionals
+}
+
+/// Indices 'x' of the 'ix.hif' files in the output folder.
+pub fn get_interaction_file_indices(output_folder : &str) -> std::io::Result<Vec<usize>> {
+    let mut indices = vec![];
+    for entry in std::fs::read_dir(output_folder)? {
+        let path = entry?.path();
+        if path.extension().and_then(|x| x.to_str()) != Some(HIBOU_INTERACTION_FILE_EXTENSION) {
+            continue;
+        }
+        let index = path.file_stem()
+            .and_then(|x| x.to_str())
+            .and_then(|x| x.strip_prefix('i'))
+            .and_then(|x| x.parse::<usize>().ok());
+        if let Some(index) = index {
+            indices.push(index);
+        }
+    }
+    Ok(indices)
+}
+
+/// Number of sub-interactions drawn for 'basic' and their symbol occurrences in the interaction
+/// of the given .hif file, as recorded in the 'manifest.json' next to it (if any).
+pub fn get_basic_blocks_summary(hif_file_path : &str) -> Option<String> {
+    let path = Path::new(hif_file_path);
+    let file_name = path.file_name()?.to_str()?;
+    let output_folder = path.parent().map(|x| x.to_str().unwrap_or(".")).filter(|x| !x.is_empty()).unwrap_or(".");
+    let entry = read_manifest_files(output_folder).into_iter()
+        .find(|entry| entry.get("file").and_then(|x| x.as_str()) == Some(file_name))?;
+    let num_blocks = entry.get("basic_blocks").and_then(|x| x.as_u64()).unwrap_or(0);
+    let mut basic_counts = SymbolCounts::default();
+    if let Some(symbols) = entry.get("basic_symbols") {
+        basic_counts.add_json(symbols);
     }
-}
\ No newline at end of file
+    if num_blocks == 0 {
+        Some("no sub-interaction was drawn for 'basic' during the generation".to_string())
+    } else {
+        Some(format!("number of sub-interactions drawn for 'basic' during the generation : {:}, with symbol occurrences : {:}", num_blocks, basic_counts.to_summary()))
+    }
+}
+
+/// Entries of the 'files' field of the 'manifest.json' of the output folder (none if there is no such manifest).
+/// Entries without 'arguments' get those of the manifest so that, once appended to by another generation,
+/// each entry keeps the arguments of the generation which wrote it.
+fn read_manifest_files(output_folder : &str) -> Vec<serde_json::Value> {
+    let path : PathBuf = [output_folder, "manifest.json"].iter().collect();
+    let manifest = match std::fs::read_to_string(&path).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok()) {
+        None => {
+            return vec![];
+        },
+        Some( got ) => {
+            got
+        }
+    };
+    let mut files = manifest.get("files").and_then(|x| x.as_array()).cloned().unwrap_or_default();
+    if let Some(arguments) = manifest.get("arguments") {
+        for entry in files.iter_mut().filter(|entry| entry.get("arguments").is_none()) {
+            if let Some(as_object) = entry.as_object_mut() {
+                as_object.insert("arguments".to_string(), arguments.clone());
+            }
+        }
+    }
+    files
+}
+
+/// Writes 'manifest.json' into the output folder.
+/// The manifest is first written to a temporary file which is then renamed
+/// so that an interrupted run never leaves a partial manifest behind.
+pub fn write_manifest(output_folder : &str, manifest : &serde_json::Value) -> std::io::Result<()> {
+    let tmp_path : PathBuf = [output_folder, "manifest.json.tmp"].iter().collect();
+    let final_path : PathBuf = [output_folder, "manifest.json"].iter().collect();
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(serde_json::to_string_pretty(manifest).unwrap().as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, &final_path)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::io::input::hif::interface::parse_hif_file;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    /// Runs the generation with the positional arguments up to the output folder, the
+    /// probabilities of the custom distribution being left to their defaults.
+    fn generate_in(hsf_path : &Path, num_ints : &str, probas : &str, seed : &str, folder : &Path, options : &[&str]) -> (Vec<String>,u32,f32) {
+        let mut args = vec!["hibou_label".to_string(), "rng_gen_raw_interactions".to_string(),
+                            hsf_path.to_str().unwrap().to_string(), num_ints.to_string(), probas.to_string(),
+                            "4".to_string(), "1".to_string(), seed.to_string()];
+        for (id,default) in RAW_GENERATION_POSITIONAL_DEFAULTS.iter().skip(5) {
+            assert!(id.starts_with('p'));
+            args.push(default.to_string());
+        }
+        args.push(folder.to_str().unwrap().to_string());
+        args.extend(options.iter().map(|x| x.to_string()));
+        let yaml = load_yaml!("../hibou_cli.yml");
+        let matches = App::from_yaml(yaml).get_matches_from(args);
+        cli_rng_gen_raw_interactions(matches.subcommand_matches("rng_gen_raw_interactions").unwrap())
+    }
+
+    #[test]
+    fn manifest_test() {
+        let root = std::env::temp_dir().join(format!("hibou_gen_manifest_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let hsf_path = root.join("s.hsf");
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b;c}").unwrap();
+        let gen_ctx = parse_hsf_file(hsf_path.to_str().unwrap()).unwrap();
+        let folder = root.join("ints");
+        let (_,got_code,_) = generate_in(&hsf_path, "5", "protocols_with_coreg", "17", &folder, &[]);
+        assert_eq!(got_code, 0);
+        let manifest : serde_json::Value = serde_json::from_str(&std::fs::read_to_string(folder.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(manifest["seed"], 17);
+        assert_eq!(manifest["probas"], "protocols_with_coreg");
+        assert_eq!(manifest["num_ints"], 5);
+        let files = manifest["files"].as_array().unwrap();
+        assert_eq!(files.len(), 5);
+        for entry in files {
+            let file_path = folder.join(entry["file"].as_str().unwrap());
+            let int = parse_hif_file(&gen_ctx, file_path.to_str().unwrap()).unwrap();
+            let metrics = InteractionMetrics::extract_from_interaction(&int);
+            assert_eq!(entry["num_symbols"], metrics.get_num_symbols());
+            // binary operators may be reassociated when written and parsed again hence only the symbols are compared
+            assert_eq!(entry["symbols"], metrics.to_json()["symbols"]);
+            assert_eq!(entry["hash"].as_str().unwrap().len(), 16);
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+
+    #[test]
+    fn generate_in_parallel_test() {
+        // a small signature and depth so that the workers are likely to draw the same interactions
+        let gen_ctx = parse_hsf_string("@message{m}\n@lifeline{a;b}".to_string()).unwrap();
+        let params = GenerationParams::new(12,2,1,200,InteractionSymbolsProbabilities::default_regular());
+        let first = generate_in_parallel(&gen_ctx, params.clone(), 3, 5);
+        assert!(!first.is_empty());
+        assert_eq!(generate_in_parallel(&gen_ctx, params.clone(), 3, 5), first);
+        // duplicates drawn by different workers are removed when merging
+        let distinct : HashSet<&Interaction> = first.iter().collect();
+        assert_eq!(distinct.len(), first.len());
+        assert!(first.len() <= 12);
+    }
+
+
+    #[test]
+    fn existing_output_folder_test() {
+        let root = std::env::temp_dir().join(format!("hibou_gen_existing_folder_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let hsf_path = root.join("s.hsf");
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b;c}").unwrap();
+        let folder = root.join("ints");
+        let get_indices = || {
+            let mut got = get_interaction_file_indices(folder.to_str().unwrap()).unwrap();
+            got.sort();
+            got
+        };
+        let read_manifest = || -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(folder.join("manifest.json")).unwrap()).unwrap()
+        };
+        assert_eq!(generate_in(&hsf_path, "3", "default", "1", &folder, &[]).1, 0);
+        assert_eq!(get_indices(), vec![0,1,2]);
+        // the files of the previous generation are neither removed nor mixed with new ones
+        let (got_print,got_code,_) = generate_in(&hsf_path, "3", "default", "2", &folder, &[]);
+        assert_eq!(got_code, 1);
+        assert!(got_print[0].contains("already contains 3 interaction files"));
+        assert_eq!(get_indices(), vec![0,1,2]);
+        // ***
+        assert_eq!(generate_in(&hsf_path, "3", "default", "2", &folder, &["--append"]).1, 0);
+        assert_eq!(get_indices(), vec![0,1,2,3,4,5]);
+        let manifest = read_manifest();
+        assert_eq!(manifest["first_index"], 3);
+        let files : Vec<&str> = manifest["files"].as_array().unwrap().iter().map(|entry| entry["file"].as_str().unwrap()).collect();
+        assert_eq!(files, vec!["i0.hif","i1.hif","i2.hif","i3.hif","i4.hif","i5.hif"]);
+        // ***
+        assert_eq!(generate_in(&hsf_path, "2", "default", "3", &folder, &["--overwrite"]).1, 0);
+        assert_eq!(get_indices(), vec![0,1]);
+        let manifest = read_manifest();
+        assert_eq!(manifest["first_index"], 0);
+        assert_eq!(manifest["files"].as_array().unwrap().len(), 2);
+        // ***
+        assert_eq!(generate_in(&hsf_path, "2", "default", "3", &folder, &["--dry-run", "--append"]).1, 1);
+        assert_eq!(generate_in(&hsf_path, "2", "default", "3", &folder, &["--resume", "--overwrite"]).1, 1);
+        assert_eq!(get_indices(), vec![0,1]);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+}
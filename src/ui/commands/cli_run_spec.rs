@@ -0,0 +1,240 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::{App, ArgMatches};
+use rayon::prelude::*;
+use serde_json::json;
+
+use crate::experiments::interaction_random_gen::spec::{GenerationSpec, GenerationSpecConfig, GenerationSpecValue};
+use crate::ui::commands::cli_rng_gen_raw_interactions::{cli_rng_gen_raw_interactions_or_validate, get_interaction_file_indices, get_positional_default};
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+/// Command line arguments of 'rng_gen_raw_interactions' described by a configuration,
+/// its output folder being the sub-folder of 'parent_folder' named after it.
+fn get_config_command_line(generator : &App, config : &GenerationSpecConfig, parent_folder : &str) -> Result<Vec<String>,String> {
+    let mut args = config.args.clone();
+    let output_folder : PathBuf = [parent_folder, &config.name].iter().collect();
+    args.insert("folder".to_string(), GenerationSpecValue::Value(output_folder.to_str().unwrap().to_string()));
+    for key in args.keys() {
+        if !generator.get_arguments().any(|arg| arg.get_id() == key) {
+            return Err(format!("configuration '{:}' : unknown argument '{:}'", config.name, key));
+        }
+    }
+    let mut command_line = vec![generator.get_name().to_string()];
+    // positional arguments are given in order, up to the last one in the configuration (i.e. the output folder),
+    // those which are not in the configuration taking their default values
+    let positionals : Vec<&str> = generator.get_positionals().map(|arg| arg.get_id()).collect();
+    let num_positionals = positionals.iter().rposition(|id| args.contains_key(*id)).map_or(0, |x| x + 1);
+    for id in &positionals[..num_positionals] {
+        match (args.get(*id), get_positional_default(id)) {
+            (Some(GenerationSpecValue::Value(value)),_) => {
+                command_line.push(value.clone());
+            },
+            (Some(GenerationSpecValue::Flag(_)),_) => {
+                return Err(format!("configuration '{:}' : '{:}' must be given a value", config.name, id));
+            },
+            (None,Some(default)) => {
+                command_line.push(default.to_string());
+            },
+            (None,None) => {
+                return Err(format!("configuration '{:}' : '{:}' must be given as some of the arguments following it are", config.name, id));
+            }
+        }
+    }
+    for arg in generator.get_arguments().filter(|arg| !arg.is_positional()) {
+        let option = match (arg.get_long(), arg.get_short()) {
+            (Some(long),_) => format!("--{:}", long),
+            (None,Some(short)) => format!("-{:}", short),
+            (None,None) => {
+                continue;
+            }
+        };
+        match (args.get(arg.get_id()), arg.is_takes_value_set()) {
+            (None,_) | (Some(GenerationSpecValue::Flag(false)),false) => {},
+            (Some(GenerationSpecValue::Value(value)),true) => {
+                command_line.push(option);
+                command_line.push(value.clone());
+            },
+            (Some(GenerationSpecValue::Flag(true)),false) => {
+                command_line.push(option);
+            },
+            (Some(_),true) => {
+                return Err(format!("configuration '{:}' : '{:}' must be given a value", config.name, arg.get_id()));
+            },
+            (Some(_),false) => {
+                return Err(format!("configuration '{:}' : '{:}' is a flag and must be given as true or false", config.name, arg.get_id()));
+            }
+        }
+    }
+    Ok(command_line)
+}
+
+pub fn cli_run_spec(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let spec_file_path = matches.value_of("spec").unwrap();
+    let spec = match GenerationSpec::from_toml_file(spec_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let parent_folder : &str = matches.value_of("parent_folder").unwrap_or(".");
+    // the configurations are parsed without the global options, which are hence taken from 'run_spec'
+    let include_ctx = get_hif_include_context(matches);
+    let jobs : usize = match matches.value_of("jobs") {
+        None => {
+            1
+        },
+        Some( as_str ) => {
+            match as_str.trim().parse::<usize>() {
+                Ok( got ) if got > 0 => {
+                    got
+                },
+                _ => {
+                    return (vec![format!("number of jobs must be a positive integer : '{:}'", as_str)],1);
+                }
+            }
+        }
+    };
+    let yaml = load_yaml!("../hibou_cli.yml");
+    let app = App::from_yaml(yaml);
+    let generator = app.find_subcommand("rng_gen_raw_interactions").unwrap();
+    // the whole spec is validated before any configuration is generated
+    let mut command_lines = vec![];
+    for config in &spec.configs {
+        let command_line = match get_config_command_line(generator, config, parent_folder) {
+            Err(e) => {
+                return (vec![e],1);
+            },
+            Ok( got ) => {
+                got
+            }
+        };
+        let config_matches = match generator.clone().try_get_matches_from(&command_line) {
+            Err(e) => {
+                return (vec![format!("configuration '{:}' : {:}", config.name, e)],1);
+            },
+            Ok( got ) => {
+                got
+            }
+        };
+        let (got_print,got_code,_) = cli_rng_gen_raw_interactions_or_validate(&config_matches, &include_ctx, true);
+        if got_code != 0 {
+            return (vec![format!("configuration '{:}' : {:}", config.name, got_print.join(" "))],1);
+        }
+        // a previous generation in the sub-folder would otherwise only be reported once the previous configurations are generated
+        let output_folder = config_matches.value_of("folder").unwrap();
+        let reuses_folder = ["resume", "overwrite", "append", "dry_run"].iter().any(|id| config_matches.is_present(id));
+        if !reuses_folder && get_interaction_file_indices(output_folder).map_or(false, |got| !got.is_empty()) {
+            return (vec![format!("configuration '{:}' : output folder '{:}' already contains interaction files (set 'overwrite' or 'append')", config.name, output_folder)],1);
+        }
+        command_lines.push(command_line);
+    }
+    // ***
+    let run_config = |command_line : &Vec<String>| {
+        let config_matches = generator.clone().try_get_matches_from(command_line).unwrap();
+        let now = Instant::now();
+        let (got_print,got_code,_) = cli_rng_gen_raw_interactions_or_validate(&config_matches, &include_ctx, false);
+        (got_print, got_code, now.elapsed().as_secs_f64())
+    };
+    let results : Vec<(Vec<String>,u32,f64)> = if jobs > 1 {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build().unwrap();
+        pool.install(|| command_lines.par_iter().map(run_config).collect())
+    } else {
+        command_lines.iter().map(run_config).collect()
+    };
+    // ***
+    let mut ret_print = vec![];
+    ret_print.push( "".to_string());
+    ret_print.push( format!("RAN {} GENERATION CONFIGURATIONS", spec.configs.len()) );
+    ret_print.push( format!("from spec '{}'", spec_file_path) );
+    let mut entries = vec![];
+    let mut num_failures = 0;
+    for ((config,command_line),(got_print,got_code,elapsed)) in spec.configs.iter().zip(command_lines.iter()).zip(results.iter()) {
+        let folder : PathBuf = [parent_folder, &config.name].iter().collect();
+        if *got_code == 0 {
+            ret_print.push( format!("configuration '{}' generated into '{}' in {:.2}s", config.name, folder.display(), elapsed) );
+        } else {
+            num_failures += 1;
+            ret_print.push( format!("configuration '{}' failed : {}", config.name, got_print.join(" ")) );
+        }
+        entries.push(json!({
+            "name" : config.name,
+            "folder" : config.name,
+            "command_line" : command_line,
+            "success" : *got_code == 0,
+            "elapsed_secs" : elapsed
+        }));
+    }
+    let index = json!({
+        "spec" : spec_file_path,
+        "jobs" : jobs,
+        "configs" : entries
+    });
+    let index_path : PathBuf = [parent_folder, "index.json"].iter().collect();
+    let written = std::fs::create_dir_all(parent_folder)
+        .and_then(|_| File::create(&index_path))
+        .and_then(|mut file| file.write_all(serde_json::to_string_pretty(&index).unwrap().as_bytes()));
+    if let Err(e) = written {
+        return (vec![format!("could not write '{:}' : {:}", index_path.display(), e)],1);
+    }
+    ret_print.push( format!("index written to '{}'", index_path.display()) );
+    ret_print.push( "".to_string());
+    if num_failures > 0 {
+        return (ret_print,1);
+    }
+    return (ret_print,0);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_spec_in(root : &PathBuf, spec_str : &str) -> (Vec<String>,u32) {
+        let spec_path = root.join("spec.toml");
+        std::fs::write(&spec_path, spec_str).unwrap();
+        let yaml = load_yaml!("../hibou_cli.yml");
+        let matches = App::from_yaml(yaml).get_matches_from(vec!["hibou_label", "run_spec", spec_path.to_str().unwrap(), "-p", root.to_str().unwrap()]);
+        cli_run_spec(matches.subcommand_matches("run_spec").unwrap())
+    }
+
+    #[test]
+    fn run_spec_test() {
+        let root = std::env::temp_dir().join(format!("hibou_run_spec_test_{:}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let hsf_path = root.join("s.hsf");
+        std::fs::write(&hsf_path, "@message{m;n}\n@lifeline{a;b}").unwrap();
+        let shared = format!("hsf = {:?}\nnum_ints = 2\nmax_depth = 3\nmin_symbols = 1\ndry_run = true\n", hsf_path.to_str().unwrap());
+        // a configuration only giving some of the positional arguments
+        let (_,got_code) = run_spec_in(&root, &format!("{:}[[config]]\nname = \"minimal\"\n", shared));
+        assert_eq!(got_code, 0);
+        // malformed values are reported instead of panicking
+        let (got_print,got_code) = run_spec_in(&root, &format!("{:}[[config]]\nname = \"malformed\"\nseed = \"1x\"\n", shared));
+        assert_eq!(got_code, 1);
+        assert!(got_print[0].contains("invalid value for 'seed' : '1x'"));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
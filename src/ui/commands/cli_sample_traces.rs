@@ -0,0 +1,108 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::experiments::trace_sampling::sample_accepted_traces;
+use crate::io::file_extensions::HIBOU_TRACE_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::trace::to_htf::write_multi_trace_into_file;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_sample_traces(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            let path_object = Path::new(hif_file_path);
+            let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let num_traces : u32 = match matches.value_of("num_traces") {
+                        None => {
+                            10
+                        },
+                        Some( as_str ) => {
+                            as_str.trim().parse::<u32>().unwrap()
+                        }
+                    };
+
+                    let max_loop_depth : u32 = match matches.value_of("max_loop_depth") {
+                        None => {
+                            1
+                        },
+                        Some( as_str ) => {
+                            as_str.trim().parse::<u32>().unwrap()
+                        }
+                    };
+
+                    let seed : u64 = match matches.value_of("seed") {
+                        None => {
+                            0
+                        },
+                        Some( as_str ) => {
+                            as_str.trim().parse::<u64>().unwrap()
+                        }
+                    };
+
+                    let output_folder : String = match matches.value_of("folder") {
+                        None => {
+                            format!("{}_traces", file_name)
+                        },
+                        Some( as_str ) => {
+                            as_str.to_string()
+                        }
+                    };
+                    fs::create_dir_all(&output_folder).unwrap();
+
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    let traces = sample_accepted_traces(&int,num_traces,max_loop_depth,&mut rng);
+
+                    // global traces, i.e. multi-traces over a single co-localization of all lifelines
+                    let co_localizations = CoLocalizations::get_trivial_partition(gen_ctx.get_lf_num());
+                    for (x,trace) in traces.into_iter().enumerate() {
+                        let trace_file_name = format!("{}_t{:}.{:}", file_name, x, HIBOU_TRACE_FILE_EXTENSION);
+                        let path : PathBuf = [&output_folder, &trace_file_name].iter().collect();
+                        write_multi_trace_into_file(path.as_path(),&gen_ctx,&co_localizations,&vec![trace]);
+                    }
+
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("sampled accepted traces of interaction from file '{}'",hif_file_path) );
+                    ret_print.push( format!("num_traces : {:}, max_loop_depth : {:}, seed : {:}", num_traces, max_loop_depth, seed) );
+                    ret_print.push( format!("in folder '{:}'", output_folder) );
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::fs;
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::io::file_extensions::{HIBOU_INTERACTION_FILE_EXTENSION, HIBOU_SEXPR_INTERACTION_FILE_EXTENSION};
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::input::sexpr::interface::parse_sexpr_file;
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+use crate::io::output::to_hfiles::interaction::to_sexpr::interaction_to_sexpr;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+
+pub fn cli_sexpr(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    let input_file_path = matches.value_of("input").unwrap();
+    // ***
+    let to_sexpr : bool;
+    if matches.is_present("to_sexpr") {
+        to_sexpr = true;
+    } else if matches.is_present("from_sexpr") {
+        to_sexpr = false;
+    } else {
+        // the direction of the conversion is given by the extension of the input file
+        let file_extension = Path::new(input_file_path).extension().map(|x| x.to_str().unwrap()).unwrap_or("");
+        to_sexpr = file_extension != HIBOU_SEXPR_INTERACTION_FILE_EXTENSION;
+    }
+    // ***
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let parsed;
+            if to_sexpr {
+                parsed = parse_hif_file_with(&gen_ctx,input_file_path,&include_ctx);
+            } else {
+                parsed = parse_sexpr_file(&gen_ctx,input_file_path);
+            }
+            match parsed {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let output_file_name : String;
+                    if matches.is_present("output") {
+                        let extracted = matches.value_of("output").unwrap();
+                        output_file_name = extracted.to_string();
+                    } else {
+                        let file_name = Path::new(input_file_path).file_stem().unwrap().to_str().unwrap();
+                        output_file_name = file_name.to_string();
+                    }
+                    let output_file_path : String;
+                    if to_sexpr {
+                        output_file_path = format!("{}.{}", output_file_name, HIBOU_SEXPR_INTERACTION_FILE_EXTENSION);
+                        if let Err(e) = fs::write(&output_file_path, interaction_to_sexpr(&gen_ctx, &int)) {
+                            return (vec![e.to_string()],1);
+                        }
+                    } else {
+                        output_file_path = format!("{}.{}", output_file_name, HIBOU_INTERACTION_FILE_EXTENSION);
+                        interaction_to_hif(Path::new(&output_file_path), &gen_ctx, &int);
+                    }
+                    // ***
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    if to_sexpr {
+                        ret_print.push( "CONVERTING INTERACTION TO S-EXPRESSION".to_string());
+                    } else {
+                        ret_print.push( "CONVERTING INTERACTION FROM S-EXPRESSION".to_string());
+                    }
+                    ret_print.push( format!("from file '{}'",input_file_path) );
+                    ret_print.push( format!("on file : {}",output_file_path) );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
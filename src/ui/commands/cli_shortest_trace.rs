@@ -0,0 +1,88 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::core::colocalizations::CoLocalizations;
+use crate::io::file_extensions::HIBOU_TRACE_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::trace::to_htf::write_multi_trace_into_file;
+use crate::nfa_translation::alphabet::get_alphabet_from_gen_ctx;
+use crate::nfa_translation::get_nfa_from_logger::get_nfa_from_interaction_exploration;
+use crate::nfa_translation::shortest::get_nfa_shortest_accepted_trace;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_shortest_trace(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            let file_name : &str = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let alphabet = get_alphabet_from_gen_ctx(&gen_ctx);
+                    let (nfa,_) = get_nfa_from_interaction_exploration(&gen_ctx,&int,alphabet.clone());
+
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("shortest trace accepted by interaction from file '{}'",hif_file_path) );
+                    ret_print.push( "".to_string());
+                    match get_nfa_shortest_accepted_trace(&nfa,&alphabet) {
+                        None => {
+                            ret_print.push( "the interaction accepts no trace".to_string() );
+                            ret_print.push( "".to_string());
+                            return (ret_print,1);
+                        },
+                        Some( trace ) => {
+                            let output_file : String = match matches.value_of("output") {
+                                None => {
+                                    format!("{:}_shortest.{:}", file_name, HIBOU_TRACE_FILE_EXTENSION)
+                                },
+                                Some( as_str ) => {
+                                    as_str.to_string()
+                                }
+                            };
+                            if trace.is_empty() {
+                                ret_print.push( "the empty trace".to_string() );
+                            } else {
+                                ret_print.push( format!("of length {:}", trace.len()) );
+                            }
+                            // global trace, i.e. multi-trace over a single co-localization of all lifelines
+                            let co_localizations = CoLocalizations::get_trivial_partition(gen_ctx.get_lf_num());
+                            write_multi_trace_into_file(Path::new(&output_file),&gen_ctx,&co_localizations,&vec![trace]);
+                            ret_print.push( format!("written in file '{:}'", output_file) );
+                            ret_print.push( "".to_string());
+                            return (ret_print,0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use clap::ArgMatches;
+
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::{get_hif_provenance, parse_hif_file_with};
+use crate::io::output::to_text::{interaction_as_indented_text, interaction_as_oneline_text};
+use crate::ui::commands::cli_rng_gen_raw_interactions::get_basic_blocks_summary;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+pub fn cli_show(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( format!("interaction from file '{}'",hif_file_path) );
+                    ret_print.push( "".to_string());
+                    if matches.is_present("oneline") {
+                        ret_print.push( interaction_as_oneline_text(&gen_ctx,&int) );
+                    } else {
+                        for line in interaction_as_indented_text(&gen_ctx,&int).lines() {
+                            ret_print.push( line.to_string() );
+                        }
+                    }
+                    if let Some(provenance) = std::fs::read_to_string(hif_file_path).ok().and_then(|content| get_hif_provenance(&content)) {
+                        ret_print.push( "".to_string());
+                        ret_print.push( format!("generated as interaction {:} of the generation with seed {:}", provenance.id, provenance.seed) );
+                        if !provenance.matches(&gen_ctx, &int) {
+                            ret_print.push( "the interaction was modified after its generation (its hash differs from the recorded one)".to_string() );
+                        }
+                    }
+                    // for generated interactions, actions, strict and seq may have been drawn for 'basic'
+                    if let Some(summary) = get_basic_blocks_summary(hif_file_path) {
+                        ret_print.push( "".to_string());
+                        ret_print.push( summary );
+                    }
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use crate::core::language::ord::corpus::CorpusInteraction;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::{get_hif_files_in_folder, parse_hif_file_with};
+use crate::ui::commands::cli_rng_gen_raw_interactions::write_manifest;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+/// Files of the folder which accompany the interaction file of the given stem
+/// (e.g. 'i3_pos0.htf' or 'i3.svg' for 'i3.hif'), other interaction files excepted.
+fn get_companion_files(folder : &str, stem : &str) -> Vec<String> {
+    let mut companions = vec![];
+    if let Ok(entries) = std::fs::read_dir(folder) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() || path.extension().map_or(false, |ext| ext == HIBOU_INTERACTION_FILE_EXTENSION) {
+                continue;
+            }
+            let name = path.file_name().unwrap().to_str().unwrap().to_string();
+            if name.starts_with(&format!("{:}_", stem)) || name.starts_with(&format!("{:}.", stem)) {
+                companions.push(name);
+            }
+        }
+    }
+    companions.sort();
+    companions
+}
+
+/// Replaces, in the manifest, every string which is the name of a renamed file by its new name.
+fn rename_in_manifest(value : &mut serde_json::Value, renamings : &HashMap<String,String>) {
+    match value {
+        serde_json::Value::String(name) => {
+            if let Some(new_name) = renamings.get(name) {
+                *name = new_name.clone();
+            }
+        },
+        serde_json::Value::Array(values) => {
+            for x in values.iter_mut() {
+                rename_in_manifest(x, renamings);
+            }
+        },
+        serde_json::Value::Object(fields) => {
+            for (_,x) in fields.iter_mut() {
+                rename_in_manifest(x, renamings);
+            }
+        },
+        _ => {}
+    }
+}
+
+pub fn cli_sort_corpus(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    let gen_ctx = match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    let folder = matches.value_of("folder").unwrap();
+    let prefix : String = matches.value_of("prefix").unwrap_or("i").chars().filter(|c| !c.is_whitespace()).collect();
+    let dry_run = matches.is_present("dry_run");
+    let hif_paths = match get_hif_files_in_folder(folder) {
+        Err(e) => {
+            return (vec![e],1);
+        },
+        Ok( got ) => {
+            got
+        }
+    };
+    // all files are parsed before any of them is renamed
+    let mut corpus = vec![];
+    for path in hif_paths {
+        let path_str = path.to_str().unwrap().to_string();
+        match parse_hif_file_with(&gen_ctx,&path_str,&include_ctx) {
+            Err(e) => {
+                return (vec![format!("could not parse '{:}' : {:}", path_str, e)],1);
+            },
+            Ok( int ) => {
+                let stem = path.file_stem().unwrap().to_str().unwrap().to_string();
+                corpus.push( (CorpusInteraction::new(int),stem) );
+            }
+        }
+    }
+    // equal interactions keep the order of their file names
+    corpus.sort();
+    // ***
+    let mut ret_print = vec![];
+    ret_print.push( "".to_string());
+    ret_print.push( format!("SORTING {} INTERACTIONS", corpus.len()) );
+    ret_print.push( format!("from folder '{}', from the smallest to the largest", folder) );
+    let mut renamings : HashMap<String,String> = HashMap::new();
+    for (k,(corpus_int,stem)) in corpus.iter().enumerate() {
+        let new_stem = format!("{:}{:}", prefix, k);
+        ret_print.push( format!("{}.{} -> {}.{} ({} symbols)", stem, HIBOU_INTERACTION_FILE_EXTENSION, new_stem, HIBOU_INTERACTION_FILE_EXTENSION, corpus_int.get_num_symbols()) );
+        renamings.insert( format!("{:}.{:}", stem, HIBOU_INTERACTION_FILE_EXTENSION), format!("{:}.{:}", new_stem, HIBOU_INTERACTION_FILE_EXTENSION) );
+        for companion in get_companion_files(folder, stem) {
+            let new_companion = format!("{:}{:}", new_stem, &companion[stem.len()..]);
+            ret_print.push( format!("    {} -> {}", companion, new_companion) );
+            renamings.insert( companion, new_companion );
+        }
+    }
+    if dry_run {
+        ret_print.push( "dry run : no file was renamed".to_string());
+        ret_print.push( "".to_string());
+        return (ret_print,0);
+    }
+    // renaming in two phases so that a new name never overwrites a file which is yet to be renamed
+    let renamings_list : Vec<(&String,&String)> = renamings.iter().filter(|(old,new)| old != new).collect();
+    for (x,(old,_)) in renamings_list.iter().enumerate() {
+        let old_path : PathBuf = [folder, old.as_str()].iter().collect();
+        let tmp_path : PathBuf = [folder, &format!(".sort_corpus_{:}.tmp", x)].iter().collect();
+        if let Err(e) = std::fs::rename(&old_path, &tmp_path) {
+            return (vec![format!("could not rename '{:}' : {:}", old_path.display(), e)],1);
+        }
+    }
+    for (x,(_,new)) in renamings_list.iter().enumerate() {
+        let tmp_path : PathBuf = [folder, &format!(".sort_corpus_{:}.tmp", x)].iter().collect();
+        let new_path : PathBuf = [folder, new.as_str()].iter().collect();
+        if let Err(e) = std::fs::rename(&tmp_path, &new_path) {
+            return (vec![format!("could not rename '{:}' into '{:}' : {:}", tmp_path.display(), new_path.display(), e)],1);
+        }
+    }
+    // the manifest of a generated corpus is updated along with the files
+    let manifest_path : PathBuf = [folder, "manifest.json"].iter().collect();
+    if let Ok(content) = std::fs::read_to_string(&manifest_path) {
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Err(e) => {
+                ret_print.push( format!("could not update '{}' : {}", manifest_path.display(), e) );
+            },
+            Ok( mut manifest ) => {
+                rename_in_manifest(&mut manifest, &renamings);
+                if let Some(files) = manifest.get_mut("files").and_then(|x| x.as_array_mut()) {
+                    files.sort_by_key(|entry| {
+                        let name = entry.get("file").and_then(|x| x.as_str()).unwrap_or("");
+                        let number = name.strip_prefix(prefix.as_str())
+                            .and_then(|x| x.strip_suffix(&format!(".{:}", HIBOU_INTERACTION_FILE_EXTENSION)))
+                            .and_then(|x| x.parse::<usize>().ok());
+                        (number.is_none(), number, name.to_string())
+                    });
+                }
+                if let Err(e) = write_manifest(folder, &manifest) {
+                    return (vec![format!("could not write '{:}' : {:}", manifest_path.display(), e)],1);
+                }
+                ret_print.push( format!("file names updated in '{}'", manifest_path.display()) );
+            }
+        }
+    }
+    ret_print.push( "".to_string());
+    return (ret_print,0);
+}
@@ -0,0 +1,80 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::core::execution::trace::to_interaction::lift_trace_to_interaction;
+use crate::io::file_extensions::HIBOU_INTERACTION_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::htf::interface::parse_htf_file;
+use crate::io::output::to_hfiles::interaction::to_hif::interaction_to_hif;
+
+
+pub fn cli_trace_to_interaction(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let htf_file_path = matches.value_of("htf").unwrap();
+            match parse_htf_file(&gen_ctx,htf_file_path) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( (_,multi_trace) ) => {
+                    // a multi-trace is only accepted if at most one of its components is non-empty
+                    let mut non_empty = multi_trace.iter().filter(|trace| !trace.is_empty());
+                    let trace = non_empty.next().cloned().unwrap_or(vec![]);
+                    if non_empty.next().is_some() {
+                        return (vec![format!("file '{}' does not contain a single totally ordered trace", htf_file_path)],1);
+                    }
+                    match lift_trace_to_interaction(&gen_ctx,&trace) {
+                        Err(e) => {
+                            return (vec![e],1);
+                        },
+                        Ok( int ) => {
+                            let output_file_name : String;
+                            if matches.is_present("output") {
+                                let extracted = matches.value_of("output").unwrap();
+                                output_file_name = extracted.to_string();
+                            } else {
+                                let file_name = Path::new(htf_file_path).file_stem().unwrap().to_str().unwrap();
+                                output_file_name = format!("{}_lifted", file_name);
+                            }
+                            let output_file_path = format!("{}.{}", output_file_name, HIBOU_INTERACTION_FILE_EXTENSION);
+                            interaction_to_hif(Path::new(&output_file_path), &gen_ctx, &int);
+                            // ***
+                            let mut ret_print = vec![];
+                            ret_print.push( "".to_string());
+                            ret_print.push( "LIFTING TRACE TO INTERACTION".to_string());
+                            ret_print.push( format!("from file '{}'",htf_file_path) );
+                            ret_print.push( format!("strict sequence of {} actions",trace.len()) );
+                            ret_print.push( format!("on file : {}",output_file_path) );
+                            ret_print.push( "".to_string());
+                            return (ret_print,0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::core::general_context::GeneralContext;
+use crate::core::language::signature::{get_signature_declarations_num, get_used_signature};
+use crate::io::file_extensions::HIBOU_SIGNATURE_FILE_EXTENSION;
+use crate::io::input::hsf::interface::parse_hsf_file;
+use crate::io::input::hif::interface::parse_hif_file_with;
+use crate::io::output::to_hfiles::signature::to_hsf::signature_to_hsf;
+use crate::ui::util::include_context::get_hif_include_context;
+
+
+fn get_directions_num(gen_ctx : &GeneralContext) -> usize {
+    return (0..gen_ctx.get_ms_num()).map(|ms_id| gen_ctx.get_ms_directions(ms_id).len()).sum();
+}
+
+pub fn cli_used_signature(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let hsf_file_path = matches.value_of("hsf").unwrap();
+    let include_ctx = get_hif_include_context(matches);
+    match parse_hsf_file(hsf_file_path) {
+        Err(e) => {
+            return (vec![e.to_string()],1);
+        },
+        Ok( gen_ctx ) => {
+            let hif_file_path = matches.value_of("hif").unwrap();
+            match parse_hif_file_with(&gen_ctx,hif_file_path,&include_ctx) {
+                Err(e) => {
+                    return (vec![e.to_string()],1);
+                },
+                Ok( int) => {
+                    let used_ctx = get_used_signature(&gen_ctx,&int);
+                    // ***
+                    let output_file_name : String;
+                    if matches.is_present("output") {
+                        let extracted = matches.value_of("output").unwrap();
+                        output_file_name = extracted.to_string();
+                    } else {
+                        let file_name = Path::new(hif_file_path).file_stem().unwrap().to_str().unwrap();
+                        output_file_name = format!("{}_signature", file_name);
+                    }
+                    let output_file_path = format!("{}.{}", output_file_name, HIBOU_SIGNATURE_FILE_EXTENSION);
+                    if let Err(e) = signature_to_hsf(Path::new(&output_file_path), &used_ctx) {
+                        return (vec![format!("could not write file '{:}' : {:}", output_file_path, e)],1);
+                    }
+                    // ***
+                    let dropped = get_signature_declarations_num(&gen_ctx) - get_signature_declarations_num(&used_ctx);
+                    let mut ret_print = vec![];
+                    ret_print.push( "".to_string());
+                    ret_print.push( "SIGNATURE USED BY INTERACTION".to_string());
+                    ret_print.push( format!("from file '{}'",hif_file_path) );
+                    ret_print.push( format!("lifelines  : {} out of {}", used_ctx.get_lf_num(), gen_ctx.get_lf_num()) );
+                    ret_print.push( format!("messages   : {} out of {}", used_ctx.get_ms_num(), gen_ctx.get_ms_num()) );
+                    ret_print.push( format!("gates      : {} out of {}", used_ctx.get_gt_num(), gen_ctx.get_gt_num()) );
+                    ret_print.push( format!("groups     : {} out of {}", used_ctx.get_group_num(), gen_ctx.get_group_num()) );
+                    ret_print.push( format!("directions : {} out of {}", get_directions_num(&used_ctx), get_directions_num(&gen_ctx)) );
+                    ret_print.push( format!("dropped {} declarations", dropped) );
+                    ret_print.push( format!("on file : {}",output_file_path) );
+                    ret_print.push( "".to_string());
+                    return (ret_print,0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use clap::{App, ArgMatches};
+
+use crate::experiments::interaction_random_gen::repro::get_first_folder_divergence;
+use crate::ui::commands::cli_rng_gen_raw_interactions::cli_rng_gen_raw_interactions;
+
+
+pub fn cli_verify_repro(matches : &ArgMatches) -> (Vec<String>,u32) {
+    let mut args : Vec<String> = vec!["hibou_label".to_string(), "rng_gen_raw_interactions".to_string()];
+    args.extend(matches.values_of("generation_args").unwrap().map(|x| x.to_string()));
+    // the generation arguments are parsed as those of 'rng_gen_raw_interactions' so as to find the output folder
+    let yaml = load_yaml!("../hibou_cli.yml");
+    let folder_index = match App::from_yaml(yaml).try_get_matches_from(&args) {
+        Err(e) => {
+            return (vec![format!("invalid generation arguments : {:}", e)],1);
+        },
+        Ok( got ) => {
+            let gen_matches = got.subcommand_matches("rng_gen_raw_interactions").unwrap();
+            if gen_matches.is_present("dry_run") || gen_matches.is_present("resume") {
+                return (vec!["the generation to check cannot be a dry run nor resume a previous generation".to_string()],1);
+            }
+            match gen_matches.index_of("folder") {
+                None => {
+                    return (vec!["the generation arguments must include the output folder (replaced by temporary folders)".to_string()],1);
+                },
+                Some( idx ) => {
+                    // indices of the subcommand arguments start from the subcommand name, which comes after the binary name
+                    idx + 1
+                }
+            }
+        }
+    };
+
+    let root = std::env::temp_dir().join(format!("hibou_verify_repro_{:}", std::process::id()));
+    let mut folders = vec![];
+    for run in 0..2 {
+        let folder = root.join(format!("run{:}", run));
+        if folder.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&folder) {
+                return (vec![format!("could not clear temporary folder '{:}' : {:}", folder.display(), e)],1);
+            }
+        }
+        if let Err(e) = std::fs::create_dir_all(&folder) {
+            return (vec![format!("could not create temporary folder '{:}' : {:}", folder.display(), e)],1);
+        }
+        let mut run_args = args.clone();
+        run_args[folder_index] = folder.to_str().unwrap().to_string();
+        let run_matches = App::from_yaml(yaml).get_matches_from(&run_args);
+        let (got_print,got_code,_) = cli_rng_gen_raw_interactions(run_matches.subcommand_matches("rng_gen_raw_interactions").unwrap());
+        if got_code != 0 {
+            let mut ret_print = vec![format!("generation {:} failed :", run + 1)];
+            ret_print.extend(got_print);
+            return (ret_print,1);
+        }
+        folders.push(folder);
+    }
+
+    let mut ret_print = vec![];
+    ret_print.push( "".to_string());
+    ret_print.push( "CHECKING REPRODUCIBILITY OF GENERATION".to_string());
+    ret_print.push( format!("with arguments : {:}", args[2..].join(" ")) );
+    match get_first_folder_divergence(&folders[0],&folders[1]) {
+        Err(e) => {
+            return (vec![format!("could not compare generated folders : {:}", e)],1);
+        },
+        Ok( None ) => {
+            let _ = std::fs::remove_dir_all(&root);
+            ret_print.push( "two generations with the same arguments produced byte-identical files".to_string() );
+            return (ret_print,0);
+        },
+        Ok( Some(divergence) ) => {
+            // the folders are kept for inspection
+            ret_print.push( format!("two generations with the same arguments diverge : {:}", divergence) );
+            ret_print.push( format!("generated files kept in '{:}' and '{:}'", folders[0].display(), folders[1].display()) );
+            return (ret_print,1);
+        }
+    }
+}
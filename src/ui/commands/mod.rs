@@ -15,9 +15,35 @@ limitations under the License.
 */
 
 pub mod cli_analyze;
+pub mod cli_accepts;
 pub mod cli_nfa_ana;
 pub mod cli_nfa_experiment;
 pub mod cli_canonize;
+pub mod cli_simplify;
+pub mod cli_hide;
+pub mod cli_sexpr;
+pub mod cli_merge;
+pub mod cli_canonicalize_corpus;
+pub mod cli_compare_translations;
+pub mod cli_exec_tree;
+pub mod cli_shortest_trace;
+pub mod cli_show;
+pub mod cli_unroll;
+pub mod cli_used_signature;
+pub mod cli_trace_to_interaction;
+pub mod cli_draw_both;
+pub mod cli_lint;
+pub mod cli_nfa_union;
+pub mod cli_verify_repro;
+pub mod cli_skeleton;
+pub mod cli_project_trace;
+pub mod cli_normalize_hsf;
+pub mod cli_mutate;
+pub mod cli_sort_corpus;
+pub mod cli_run_spec;
+pub mod cli_prefix_nfa;
+pub mod cli_replay;
+pub mod cli_export_alphabet;
 pub mod cli_draw;
 pub mod cli_explore;
 pub mod cli_puml_ap;
@@ -28,6 +54,11 @@ pub mod cli_mutate_swap_actions;
 pub mod cli_mutate_swap_components;
 pub mod cli_mutate_remove_actions;
 pub mod cli_glosem;
+pub mod cli_nfa_draw;
+pub mod cli_count_traces;
+pub mod cli_lang_equiv;
+pub mod cli_nfa_to_regex;
+pub mod cli_sample_traces;
 pub mod cli_nfa_experiment2;
 pub mod cli_get_metrics;
 pub mod cli_rng_gen_interactions;
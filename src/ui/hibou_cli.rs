@@ -17,6 +17,7 @@ limitations under the License.
 
 use clap::App;
 
+use crate::ui::commands::cli_accepts::cli_accepts;
 use crate::ui::commands::cli_analyze::cli_analyze;
 use crate::ui::commands::cli_canonize::cli_canonize;
 use crate::ui::commands::cli_draw::cli_draw;
@@ -28,6 +29,10 @@ use crate::ui::commands::cli_mutate_remove_actions::cli_mutate_remove_actions;
 use crate::ui::commands::cli_mutate_swap_actions::cli_mutate_swap_actions;
 use crate::ui::commands::cli_mutate_swap_components::cli_mutate_swap_components;
 use crate::ui::commands::cli_nfa_ana::cli_nfa_ana;
+use crate::ui::commands::cli_nfa_draw::cli_nfa_draw;
+use crate::ui::commands::cli_count_traces::cli_count_traces;
+use crate::ui::commands::cli_lang_equiv::cli_lang_equiv;
+use crate::ui::commands::cli_nfa_to_regex::cli_nfa_to_regex;
 use crate::ui::commands::cli_nfa_experiment2::cli_nfa_experiment2;
 use crate::ui::commands::cli_nfa_experiment::cli_nfa_experiment;
 use crate::ui::commands::cli_puml_ap::cli_puml_ap;
@@ -35,18 +40,50 @@ use crate::ui::commands::cli_puml_sd::cli_puml_sd;
 use crate::ui::commands::cli_rng_gen_interactions::cli_rng_gen_interactions;
 use crate::ui::commands::cli_rng_gen_raw_interactions::cli_rng_gen_raw_interactions;
 
+use crate::ui::commands::cli_sample_traces::cli_sample_traces;
+use crate::ui::commands::cli_simplify::cli_simplify;
+use crate::ui::commands::cli_hide::cli_hide;
+use crate::ui::commands::cli_sexpr::cli_sexpr;
+use crate::ui::commands::cli_merge::cli_merge;
+use crate::ui::commands::cli_canonicalize_corpus::cli_canonicalize_corpus;
+use crate::ui::commands::cli_compare_translations::cli_compare_translations;
+use crate::ui::commands::cli_exec_tree::cli_exec_tree;
+use crate::ui::commands::cli_shortest_trace::cli_shortest_trace;
+use crate::ui::commands::cli_show::cli_show;
+use crate::ui::commands::cli_unroll::cli_unroll;
+use crate::ui::commands::cli_used_signature::cli_used_signature;
+use crate::ui::commands::cli_trace_to_interaction::cli_trace_to_interaction;
+use crate::ui::commands::cli_draw_both::cli_draw_both;
+use crate::ui::commands::cli_lint::cli_lint;
+use crate::ui::commands::cli_nfa_union::cli_nfa_union;
+use crate::ui::commands::cli_verify_repro::cli_verify_repro;
+use crate::ui::commands::cli_skeleton::cli_skeleton;
+use crate::ui::commands::cli_project_trace::cli_project_trace;
+use crate::ui::commands::cli_normalize_hsf::cli_normalize_hsf;
+use crate::ui::commands::cli_mutate::cli_mutate;
+use crate::ui::commands::cli_sort_corpus::cli_sort_corpus;
+use crate::ui::commands::cli_run_spec::cli_run_spec;
+use crate::ui::commands::cli_prefix_nfa::cli_prefix_nfa;
+use crate::ui::commands::cli_replay::cli_replay;
+use crate::ui::commands::cli_export_alphabet::cli_export_alphabet;
 use crate::ui::commands::cli_slice::cli_slice;
 use crate::ui::util::printing::print_on_hibou_cli;
+use crate::io::file_extensions::{HIF_FORMAT_VERSION, HIF_VERSION_TAG};
+use crate::ui::util::terminal_logger::{get_log_level_filter, init_terminal_logger};
 
 pub fn hibou_cli() -> i32 {
 
     let yaml = load_yaml!("hibou_cli.yml");
     let matches = App::from_yaml(yaml).get_matches();
+    init_terminal_logger(get_log_level_filter(matches.is_present("verbose"), matches.is_present("quiet")));
 
     let mut ret_print : Vec<String> = vec![];
     let mut ret_code : u32 = 1;
 
-    if let Some(matches) = matches.subcommand_matches("draw") {
+    if matches.is_present("format_version") {
+        ret_print = vec![format!("{:} {:}", HIF_VERSION_TAG, HIF_FORMAT_VERSION)];
+        ret_code = 0;
+    } else if let Some(matches) = matches.subcommand_matches("draw") {
         let mut got = cli_draw(matches);
         ret_print = got.0;
         ret_code = got.1;
@@ -62,6 +99,22 @@ pub fn hibou_cli() -> i32 {
         let mut got = cli_glosem(matches);
         ret_print = got.0;
         ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("nfa_draw") {
+        let mut got = cli_nfa_draw(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("count_traces") {
+        let mut got = cli_count_traces(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("lang_equiv") {
+        let mut got = cli_lang_equiv(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("nfa_to_regex") {
+        let mut got = cli_nfa_to_regex(matches);
+        ret_print = got.0;
+        ret_code = got.1;
     } else if let Some(matches) = matches.subcommand_matches("nfa_experiment") {
         let mut got = cli_nfa_experiment(matches);
         ret_print = got.0;
@@ -100,10 +153,118 @@ pub fn hibou_cli() -> i32 {
         let mut got = cli_canonize(matches);
         ret_print = got.0;
         ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("simplify") {
+        let mut got = cli_simplify(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("hide") {
+        let mut got = cli_hide(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("sexpr") {
+        let mut got = cli_sexpr(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("merge") {
+        let mut got = cli_merge(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("canonicalize_corpus") {
+        let mut got = cli_canonicalize_corpus(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("compare_translations") {
+        let mut got = cli_compare_translations(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("exec_tree") {
+        let mut got = cli_exec_tree(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("shortest_trace") {
+        let mut got = cli_shortest_trace(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("show") {
+        let mut got = cli_show(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("unroll") {
+        let mut got = cli_unroll(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("used_signature") {
+        let mut got = cli_used_signature(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("trace_to_interaction") {
+        let mut got = cli_trace_to_interaction(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("draw_both") {
+        let mut got = cli_draw_both(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("lint") {
+        let mut got = cli_lint(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("nfa_union") {
+        let mut got = cli_nfa_union(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("verify_repro") {
+        let mut got = cli_verify_repro(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("skeleton") {
+        let mut got = cli_skeleton(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("project_trace") {
+        let mut got = cli_project_trace(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("normalize_hsf") {
+        let mut got = cli_normalize_hsf(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("mutate") {
+        let mut got = cli_mutate(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("sort_corpus") {
+        let mut got = cli_sort_corpus(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("run_spec") {
+        let mut got = cli_run_spec(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("prefix_nfa") {
+        let mut got = cli_prefix_nfa(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("replay") {
+        let mut got = cli_replay(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("export_alphabet") {
+        let mut got = cli_export_alphabet(matches);
+        ret_print = got.0;
+        ret_code = got.1;
     } else if let Some(matches) = matches.subcommand_matches("analyze") {
         let mut got = cli_analyze(matches);
         ret_print = got.0;
         ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("accepts") {
+        let mut got = cli_accepts(matches);
+        ret_print = got.0;
+        ret_code = got.1;
+    } else if let Some(matches) = matches.subcommand_matches("sample_traces") {
+        let mut got = cli_sample_traces(matches);
+        ret_print = got.0;
+        ret_code = got.1;
     } else if let Some(matches) = matches.subcommand_matches("slice") {
         let mut got = cli_slice(matches);
         ret_print = got.0;
@@ -130,6 +291,6 @@ pub fn hibou_cli() -> i32 {
     }
     // ***
     print_on_hibou_cli(ret_print);
-    return 0;
+    return ret_code as i32;
 }
 
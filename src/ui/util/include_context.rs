@@ -0,0 +1,68 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+
+use clap::ArgMatches;
+
+use crate::io::input::hif::include::HifIncludeContext;
+
+
+/// Context for parsing the .hif files of a command from its global '--include-path'
+/// and '--canonicalize-on-parse' options.
+pub fn get_hif_include_context(matches : &ArgMatches) -> HifIncludeContext {
+    let search_dirs = matches.value_of("include_path").map_or(vec![], |include_path| std::env::split_paths(include_path).collect());
+    return HifIncludeContext::new(search_dirs, matches.is_present("canonicalize_on_parse"));
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use clap::App;
+    use crate::io::input::hif::interaction::parse_hif_string;
+    use crate::io::input::hif::interface::parse_hif_file_with;
+    use crate::io::input::hsf::implem::parse_hsf_string;
+    use super::*;
+
+    #[test]
+    fn include_context_from_matches_test() {
+        let dir = std::env::temp_dir().join(format!("hibou_include_context_test_{:}", std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::write(dir.join("lib").join("part.hif"), "alt(b -- n -> a, a -- m -> b)").unwrap();
+        std::fs::write(dir.join("main.hif"), "@include \"part.hif\"").unwrap();
+        let main_path = dir.join("main.hif");
+        let gen_ctx = parse_hsf_string("@message{m;n}\n@lifeline{a;b}".to_string()).unwrap();
+        let parse_with = |options : &[&str]| {
+            let yaml = load_yaml!("../hibou_cli.yml");
+            let mut args = vec!["hibou_label"];
+            args.extend_from_slice(options);
+            args.extend_from_slice(&["show", "sig.hsf", main_path.to_str().unwrap()]);
+            let matches = App::from_yaml(yaml).get_matches_from(args);
+            let include_ctx = get_hif_include_context(matches.subcommand_matches("show").unwrap());
+            parse_hif_file_with(&gen_ctx, main_path.to_str().unwrap(), &include_ctx)
+        };
+        assert!(parse_with(&[]).is_err());
+        let lib_dir = dir.join("lib");
+        let got = parse_with(&["--include-path", lib_dir.to_str().unwrap()]).unwrap();
+        assert_eq!(got, parse_hif_string(&gen_ctx, "alt(b -- n -> a, a -- m -> b)".to_string()).unwrap());
+        // the canonicalization applies to the included interactions
+        let canonical = parse_with(&["--include-path", lib_dir.to_str().unwrap(), "--canonicalize-on-parse"]).unwrap();
+        assert_eq!(canonical, parse_hif_string(&gen_ctx, "alt(a -- m -> b, b -- n -> a)".to_string()).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
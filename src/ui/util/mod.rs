@@ -15,4 +15,6 @@ limitations under the License.
 */
 
 
-pub mod printing;
\ No newline at end of file
+pub mod include_context;
+pub mod printing;
+pub mod terminal_logger;
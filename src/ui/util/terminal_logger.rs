@@ -0,0 +1,79 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+
+
+use std::io::{IsTerminal, Write};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+
+/// Logger writing the records on the standard error,
+/// with the level colorized when the standard error is a terminal.
+struct TerminalLogger {
+    level : LevelFilter,
+    colorized : bool
+}
+
+fn get_level_ansi_color(level : Level) -> &'static str {
+    match level {
+        Level::Error => "\x1b[31m",
+        Level::Warn => "\x1b[33m",
+        Level::Info => "\x1b[32m",
+        Level::Debug => "\x1b[36m",
+        Level::Trace => "\x1b[90m"
+    }
+}
+
+impl Log for TerminalLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = record.level();
+        let mut stderr = std::io::stderr().lock();
+        let _ = if self.colorized {
+            writeln!(stderr, "{}{:<5}\x1b[0m {}", get_level_ansi_color(level), level, record.args())
+        } else {
+            writeln!(stderr, "{:<5} {}", level, record.args())
+        };
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+/// Level filter selected by the '--verbose' and '--quiet' flags, defaulting to the info level.
+pub fn get_log_level_filter(verbose : bool, quiet : bool) -> LevelFilter {
+    if verbose {
+        LevelFilter::Trace
+    } else if quiet {
+        LevelFilter::Warn
+    } else {
+        LevelFilter::Info
+    }
+}
+
+pub fn init_terminal_logger(level : LevelFilter) {
+    let logger = TerminalLogger{level, colorized : std::io::stderr().is_terminal()};
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(level);
+    }
+}